@@ -0,0 +1,152 @@
+use anyhow::Result;
+use colored::Colorize;
+use cortex_mem_config::Config;
+use cortex_mem_core::{
+    CacheConfig, EmbeddingConfig, EmbeddingDiskCache, LlmCacheConfig, LlmResultCache,
+};
+use std::path::PathBuf;
+
+fn open(data_dir: &str, config: &LlmCacheConfig) -> Option<LlmResultCache> {
+    let cache = LlmResultCache::new(CacheConfig::from_config(config, data_dir));
+    cache.is_persistent().then_some(cache)
+}
+
+/// Root of the persistent embedding cache (None when not persistent)
+fn embedding_root(data_dir: &str, config: &Config) -> Option<PathBuf> {
+    EmbeddingConfig::default()
+        .with_persistent_cache(&config.embedding_cache, data_dir)
+        .persist_dir
+}
+
+fn print_not_persistent(what: &str, section: &str) {
+    println!(
+        "  {} {} is not persistent; set {} under {} to keep it across restarts",
+        "ℹ".yellow().bold(),
+        what,
+        "persistent = true".bright_blue(),
+        section.bright_blue()
+    );
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / MIB)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Show the contents of the persistent LLM result and embedding caches
+pub async fn stats(data_dir: &str, config: &Config) -> Result<()> {
+    println!("{} LLM Result Cache", "💾".bold());
+    println!();
+
+    match open(data_dir, &config.llm_cache) {
+        None => print_not_persistent("The LLM result cache", "[llm_cache]"),
+        Some(cache) => {
+            let Some(stats) = cache.disk_stats().await else {
+                anyhow::bail!("Failed to read the persistent LLM cache");
+            };
+
+            println!("  {}: {}", "Directory".cyan(), stats.dir.display());
+            println!("  {}: {}", "Entries".cyan(), stats.entries);
+            println!(
+                "  {}: {} / {}",
+                "Size".cyan(),
+                format_bytes(stats.bytes),
+                format_bytes(stats.max_bytes)
+            );
+
+            if !stats.by_kind.is_empty() {
+                println!();
+                println!("{} By kind:", "📊".cyan().bold());
+                for (kind, (entries, bytes)) in &stats.by_kind {
+                    println!(
+                        "  {:<10} {:>6} entries  {}",
+                        kind.bright_blue(),
+                        entries,
+                        format_bytes(*bytes).dimmed()
+                    );
+                }
+            }
+
+            if stats.stale_entries > 0 {
+                println!(
+                    "\n{} {} entries belong to an older prompt version and will age out",
+                    "ℹ".yellow().bold(),
+                    stats.stale_entries
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("{} Embedding Cache", "💾".bold());
+    println!();
+
+    let Some(root) = embedding_root(data_dir, config) else {
+        print_not_persistent("The embedding cache", "[embedding_cache]");
+        return Ok(());
+    };
+    println!("  {}: {}", "Directory".cyan(), root.display());
+
+    let models = EmbeddingDiskCache::list(&root)?;
+    if models.is_empty() {
+        println!("  {}: 0", "Entries".cyan());
+        return Ok(());
+    }
+    println!();
+    for model in models {
+        let name = model.model.as_deref().unwrap_or("(unknown model)");
+        // Vectors of other models are never read; `cache clear` removes them
+        let note = if model.model.as_deref() == Some(config.embedding.model_name.as_str()) {
+            ""
+        } else {
+            " [not the configured model]"
+        };
+        let dimension = model
+            .dimension
+            .map_or_else(|| "?".to_string(), |d| d.to_string());
+        println!(
+            "  {} (dim {}){}  {} entries  {}",
+            name.bright_blue(),
+            dimension,
+            note.yellow(),
+            model.entries,
+            format_bytes(model.bytes).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove every entry of the persistent LLM result and embedding caches
+pub async fn clear(data_dir: &str, config: &Config) -> Result<()> {
+    match open(data_dir, &config.llm_cache) {
+        Some(cache) => {
+            let entries = cache.disk_stats().await.map_or(0, |s| s.entries);
+            cache.clear().await;
+            println!(
+                "{} Removed {} cached LLM results",
+                "✓".green().bold(),
+                entries
+            );
+        }
+        None => print_not_persistent("The LLM result cache", "[llm_cache]"),
+    }
+
+    match embedding_root(data_dir, config) {
+        Some(root) => {
+            let removed = EmbeddingDiskCache::clear_all(&root)?;
+            println!(
+                "{} Removed {} cached embeddings",
+                "✓".green().bold(),
+                removed
+            );
+        }
+        None => print_not_persistent("The embedding cache", "[embedding_cache]"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use cortex_mem_tools::{BatchMessage, MemoryOperations, StoreBatchArgs};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Import messages from a JSON array or JSONL file
+///
+/// Each entry: `{"thread_id": "...", "role": "user", "content": "...", "timestamp": "2024-05-01T10:00:00Z"}`.
+/// Entries without `thread_id` go to `default_thread`.
+pub async fn execute(
+    operations: Arc<MemoryOperations>,
+    file: &Path,
+    default_thread: Option<&str>,
+    extract: bool,
+) -> Result<()> {
+    let raw = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let mut messages = parse_messages(&raw)
+        .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+    if messages.is_empty() {
+        println!("{} No messages found in {}", "⚠".yellow().bold(), file.display());
+        return Ok(());
+    }
+
+    if let Some(thread) = default_thread {
+        for m in messages.iter_mut().filter(|m| m.thread_id.is_empty()) {
+            m.thread_id = thread.to_string();
+        }
+    }
+
+    println!(
+        "{} Importing {} messages from {}",
+        "📥".bold(),
+        messages.len(),
+        file.display().to_string().cyan()
+    );
+
+    let response = operations
+        .store_batch(StoreBatchArgs { messages, extract })
+        .await?;
+
+    println!(
+        "{} Imported {} messages into {} sessions",
+        "✓".green().bold(),
+        response.total_messages,
+        response.threads.len()
+    );
+    for thread in &response.threads {
+        println!(
+            "  • {} ({} messages)",
+            thread.thread_id.cyan(),
+            thread.message_count
+        );
+    }
+
+    if extract {
+        println!(
+            "{} Extraction triggered for {} sessions",
+            "🧠".bold(),
+            response.extraction_triggered.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Accept either a JSON array or one JSON object per line (blank lines ignored)
+fn parse_messages(raw: &str) -> Result<Vec<BatchMessage>> {
+    if raw.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(raw)?);
+    }
+
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("line {}", i + 1))
+        })
+        .collect()
+}
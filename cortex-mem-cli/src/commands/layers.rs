@@ -1,5 +1,7 @@
 use anyhow::Result;
+use cortex_mem_core::LayerMode;
 use cortex_mem_core::automation::{LayerGenerator, LayerGenerationConfig};
+use cortex_mem_core::layers::validation::LayerIssue;
 use cortex_mem_tools::MemoryOperations;
 use std::sync::Arc;
 
@@ -16,12 +18,15 @@ pub async fn ensure_all(operations: Arc<MemoryOperations>) -> Result<()> {
     };
     
     // Create LayerGenerator
-    let config = LayerGenerationConfig::default();
-    let generator = LayerGenerator::new(
+    let config = generation_config(&operations);
+    let mut generator = LayerGenerator::new(
         operations.filesystem().clone(),
         llm_client,
         config,
     );
+    if let Some(cache) = operations.llm_cache() {
+        generator = generator.with_cache(cache.clone());
+    }
     
     // Execute scan and generation
     let stats = generator.ensure_all_layers().await?;
@@ -53,7 +58,7 @@ pub async fn status(operations: Arc<MemoryOperations>) -> Result<()> {
             .clone()
     };
     
-    let config = LayerGenerationConfig::default();
+    let config = generation_config(&operations);
     let generator = LayerGenerator::new(
         operations.filesystem().clone(),
         llm_client,
@@ -66,6 +71,7 @@ pub async fn status(operations: Arc<MemoryOperations>) -> Result<()> {
     
     // Detect missing directories
     let missing = generator.filter_missing_layers(&directories).await?;
+    let extractive = generator.filter_extractive_layers(&directories).await?;
     
     let complete = directories.len() - missing.len();
     let complete_percent = if directories.len() > 0 {
@@ -77,7 +83,12 @@ pub async fn status(operations: Arc<MemoryOperations>) -> Result<()> {
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("✅ Complete (has L0/L1): {} ({:.0}%)", complete, complete_percent);
     println!("❌ Missing (no L0/L1):   {} ({:.0}%)", missing.len(), 100 - complete_percent);
+    println!("📝 Extractive (no LLM):  {}", extractive.len());
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if !extractive.is_empty() && operations.layer_mode() != LayerMode::Extractive {
+        println!("\n💡 Tip: Run `cortex-mem layers upgrade-extractive` to regenerate extractive layers with the LLM");
+    }
     
     if missing.len() > 0 {
         println!("\n💡 Tip: Run `cortex-mem layers ensure-all` to generate missing files");
@@ -110,12 +121,15 @@ pub async fn regenerate_oversized(operations: Arc<MemoryOperations>) -> Result<(
             .clone()
     };
     
-    let config = LayerGenerationConfig::default();
-    let generator = LayerGenerator::new(
+    let config = generation_config(&operations);
+    let mut generator = LayerGenerator::new(
         operations.filesystem().clone(),
         llm_client,
         config,
     );
+    if let Some(cache) = operations.llm_cache() {
+        generator = generator.with_cache(cache.clone());
+    }
     
     let stats = generator.regenerate_oversized_abstracts().await?;
     
@@ -133,3 +147,104 @@ pub async fn regenerate_oversized(operations: Arc<MemoryOperations>) -> Result<(
     
     Ok(())
 }
+
+/// Regenerate extractive (LLM-free) layers with the LLM
+pub async fn upgrade_extractive(operations: Arc<MemoryOperations>) -> Result<()> {
+    if operations.layer_mode() == LayerMode::Extractive {
+        anyhow::bail!("Layer mode is `extractive`; set `[layers] mode` to `auto` or `llm` to upgrade");
+    }
+
+    println!("🔍 Scanning for extractive .abstract.md / .overview.md files...\n");
+
+    let llm_client = {
+        let sm = operations.session_manager().read().await;
+        sm.llm_client()
+            .ok_or_else(|| anyhow::anyhow!("LLM client not available"))?
+            .clone()
+    };
+
+    let config = generation_config(&operations);
+    let mut generator = LayerGenerator::new(
+        operations.filesystem().clone(),
+        llm_client,
+        config,
+    );
+    if let Some(cache) = operations.llm_cache() {
+        generator = generator.with_cache(cache.clone());
+    }
+
+    let stats = generator.upgrade_extractive_layers().await?;
+
+    println!("\n✅ Upgrade complete!");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("📊 Statistics:");
+    println!("   • Extractive directories: {}", stats.total);
+    println!("   • Upgraded:               {}", stats.generated);
+    println!("   • Failed:                 {}", stats.failed);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if stats.failed > 0 {
+        println!("\n⚠️  Some directories are still extractive. Check that the LLM is reachable.");
+    }
+
+    Ok(())
+}
+
+/// Audit existing .abstract.md / .overview.md files
+pub async fn audit(operations: Arc<MemoryOperations>) -> Result<()> {
+    println!("🔍 Auditing L0/L1 layer files...\n");
+
+    let llm_client = {
+        let sm = operations.session_manager().read().await;
+        sm.llm_client()
+            .ok_or_else(|| anyhow::anyhow!("LLM client not available"))?
+            .clone()
+    };
+
+    let config = generation_config(&operations);
+    let generator = LayerGenerator::new(
+        operations.filesystem().clone(),
+        llm_client,
+        config,
+    );
+
+    let report = generator.audit_layers().await?;
+
+    let count = |is_issue: fn(&LayerIssue) -> bool| {
+        report
+            .entries
+            .iter()
+            .filter(|entry| entry.issues.iter().any(is_issue))
+            .count()
+    };
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("📊 Audit of {} layer files in {} directories:", report.layers_checked, report.directories);
+    println!("   • Stale:          {}", count(|i| matches!(i, LayerIssue::Stale)));
+    println!("   • Oversized:      {}", count(|i| matches!(i, LayerIssue::Oversized { .. })));
+    println!("   • Empty:          {}", count(|i| matches!(i, LayerIssue::Empty)));
+    println!("   • Wrong language: {}", count(|i| matches!(i, LayerIssue::LanguageMismatch { .. })));
+    println!("   • Extractive:     {}", report.extractive);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if report.entries.is_empty() {
+        println!("\n✨ All layer files look healthy!");
+        return Ok(());
+    }
+
+    println!("\nLayers with issues:");
+    for entry in &report.entries {
+        let issues: Vec<String> = entry.issues.iter().map(|i| i.to_string()).collect();
+        println!("  • {}: {}", entry.layer_uri, issues.join("; "));
+    }
+
+    Ok(())
+}
+
+/// Default generation config with the `[layers] mode` of this instance
+fn generation_config(operations: &MemoryOperations) -> LayerGenerationConfig {
+    LayerGenerationConfig {
+        mode: operations.layer_mode(),
+        ..LayerGenerationConfig::default()
+    }
+}
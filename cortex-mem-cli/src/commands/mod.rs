@@ -1,8 +1,11 @@
 pub mod add;
+pub mod cache;
 pub mod delete;
 pub mod get;
+pub mod import;
 pub mod layers;
 pub mod list;
+pub mod prompts;
 pub mod search;
 pub mod session;
 pub mod stats;
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use cortex_mem_core::llm::templates::TEMPLATES;
+use cortex_mem_core::{CortexFilesystem, PromptTemplates};
+use std::path::{Path, PathBuf};
+
+/// Prompt templates of a tenant
+fn templates(data_dir: &str, tenant: &str) -> PromptTemplates {
+    PromptTemplates::for_filesystem(&CortexFilesystem::with_tenant(data_dir, tenant))
+}
+
+/// Write the built-in templates as `{name}.md` files
+///
+/// Defaults to the tenant's override directory; existing files are kept
+/// unless `force` is set.
+pub async fn dump(data_dir: &str, tenant: &str, dir: Option<PathBuf>, force: bool) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => dir,
+        None => templates(data_dir, tenant)
+            .dir()
+            .map(Path::to_path_buf)
+            .context("Tenant has no prompt directory")?,
+    };
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    println!(
+        "{} Writing default prompt templates to {}",
+        "📝".bold(),
+        dir.display()
+    );
+    println!();
+
+    let (mut written, mut skipped) = (0, 0);
+    for template in TEMPLATES {
+        let path = dir.join(format!("{}.md", template.name));
+        if path.exists() && !force {
+            println!(
+                "  {} {} (exists, use --force to overwrite)",
+                "-".dimmed(),
+                template.name.dimmed()
+            );
+            skipped += 1;
+            continue;
+        }
+        std::fs::write(&path, template.default)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("  {} {}", "✓".green(), template.name.bright_blue());
+        written += 1;
+    }
+
+    println!();
+    println!(
+        "{} {} written, {} skipped",
+        "✓".green().bold(),
+        written,
+        skipped
+    );
+    println!(
+        "\n  {} Edit a file to override that prompt; delete it to restore the default",
+        "💡".dimmed()
+    );
+    Ok(())
+}
+
+/// List the templates, which of them the tenant overrides and their versions
+pub async fn list(data_dir: &str, tenant: &str) -> Result<()> {
+    let templates = templates(data_dir, tenant);
+    let dir = templates.dir().map(Path::to_path_buf).unwrap_or_default();
+
+    println!("{} Prompt templates (tenant: {})", "📋".bold(), tenant);
+    println!("  {}: {}", "Directory".cyan(), dir.display());
+    println!();
+
+    for template in TEMPLATES {
+        let overridden = templates.load_override(template.name).is_some();
+        let status = if overridden {
+            "overridden".yellow()
+        } else {
+            "default".dimmed()
+        };
+        let placeholders = template
+            .placeholders
+            .iter()
+            .map(|p| format!("{{{{{}}}}}", p))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!(
+            "  {:<20} {:<10} {:<15} {}",
+            template.name.bright_blue(),
+            status,
+            templates.version(&[template.name]),
+            template.description
+        );
+        if !placeholders.is_empty() {
+            println!("  {:<20} {}", "", placeholders.dimmed());
+        }
+    }
+
+    // Files that do not match any template are never used
+    let unknown: Vec<String> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| {
+            name.strip_suffix(".md")
+                .is_none_or(|stem| PromptTemplates::find(stem).is_none())
+        })
+        .collect();
+    if !unknown.is_empty() {
+        println!(
+            "\n{} Not a known template, ignored: {}",
+            "⚠".yellow().bold(),
+            unknown.join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -1,10 +1,10 @@
 use anyhow::Result;
 use colored::Colorize;
-use cortex_mem_core::FilesystemOperations;
+use cortex_mem_core::{FilesystemOperations, UsageTotals, UsageTracker};
 use cortex_mem_tools::MemoryOperations;
 use std::sync::Arc;
 
-pub async fn execute(operations: Arc<MemoryOperations>) -> Result<()> {
+pub async fn execute(operations: Arc<MemoryOperations>, days: u32) -> Result<()> {
     println!("{} Cortex-Mem Statistics", "📊".bold());
     println!();
 
@@ -46,5 +46,90 @@ pub async fn execute(operations: Arc<MemoryOperations>) -> Result<()> {
     println!("{} Storage:", "💾".cyan().bold());
     println!("  {}: {}", "Data directory".cyan(), fs.root_path().display());
 
+    println!();
+    match operations.usage() {
+        Some(tracker) => print_usage(tracker, days),
+        None => println!(
+            "{} Token usage: {}",
+            "🪙".cyan().bold(),
+            "disabled ([usage] enabled = false)".dimmed()
+        ),
+    }
+
     Ok(())
+}
+
+fn print_usage(tracker: &UsageTracker, days: u32) {
+    let to = chrono::Utc::now().date_naive();
+    let from = to - chrono::Duration::days(i64::from(days.max(1)) - 1);
+    let summary = tracker.summary(from, to);
+    let config = tracker.config();
+
+    println!("{} Token usage ({} to {}, UTC):", "🪙".cyan().bold(), from, to);
+    if summary.total.calls == 0 {
+        println!("  {}", "No LLM or embedding calls recorded".dimmed());
+    } else {
+        println!("  {}", "By day:".bold());
+        for (day, totals) in summary.by_day.iter().rev() {
+            println!("    {}  {}", day, format_totals(totals));
+        }
+        println!("  {}", "By operation:".bold());
+        for (operation, totals) in &summary.by_operation {
+            println!("    {:<11} {}", operation.as_str(), format_totals(totals));
+        }
+        println!("  {}", "By model:".bold());
+        for (model, totals) in &summary.by_model {
+            println!("    {}  {}", model.cyan(), format_totals(totals));
+        }
+        println!("  {}  {}", "Total:".bold(), format_totals(&summary.total));
+        if summary.total.estimated_calls > 0 {
+            println!(
+                "  {}",
+                "~ some token counts were estimated (provider reported no usage)".dimmed()
+            );
+        }
+    }
+
+    let today = tracker.today();
+    if let Some(budget) = config.daily_token_budget {
+        println!(
+            "  {}: {} / {} tokens today",
+            "Token budget".cyan(),
+            today.total_tokens(),
+            budget
+        );
+    }
+    if let Some(budget) = config.daily_cost_budget_usd {
+        println!(
+            "  {}: ${:.4} / ${:.2} today",
+            "Cost budget".cyan(),
+            today.cost_usd,
+            budget
+        );
+    }
+    if tracker.over_budget() {
+        let skipped: Vec<&str> = config
+            .skip_when_over_budget
+            .iter()
+            .map(|task| task.as_str())
+            .collect();
+        println!(
+            "  {} Over budget: skipping {}",
+            "⚠".yellow().bold(),
+            if skipped.is_empty() { "nothing".to_string() } else { skipped.join(", ") }
+        );
+    }
+}
+
+fn format_totals(totals: &UsageTotals) -> String {
+    let marker = if totals.estimated_calls > 0 { "~" } else { "" };
+    format!(
+        "{} calls, {}{} prompt + {}{} completion tokens, ${:.4}",
+        totals.calls,
+        marker,
+        totals.prompt_tokens,
+        marker,
+        totals.completion_tokens,
+        totals.cost_usd
+    )
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 use anyhow::Result;
 use colored::Colorize;
+use cortex_mem_config::{EmbeddingCacheConfig, EmbeddingConfig};
+use cortex_mem_core::{
+    EmbeddingClient, MigrationPhase, MigrationTarget, VectorMigration, VectorMigrator,
+};
 use cortex_mem_tools::MemoryOperations;
 use std::sync::Arc;
 
@@ -130,8 +134,213 @@ pub async fn status(operations: Arc<MemoryOperations>) -> Result<()> {
     Ok(())
 }
 
+// ── Embedding Model Migration ────────────────────────────────────────────────
+
+/// Start a migration and backfill the target collection
+pub async fn migrate_start(
+    operations: Arc<MemoryOperations>,
+    embedding: &EmbeddingConfig,
+    embedding_cache: &EmbeddingCacheConfig,
+    data_dir: &str,
+    target: MigrationTarget,
+    dim: Option<usize>,
+) -> Result<()> {
+    let client = target_client(&target, embedding, embedding_cache, data_dir)?;
+    let migrator = migrator(&operations);
+
+    let state = migrator.start(target, &client, dim).await?;
+    println!("{} Embedding model migration started", "🚚".bold());
+    println!("  From:   {} ({})", state.source_collection, embedding.model_name);
+    println!(
+        "  To:     {} ({}, dim {})",
+        state.target_collection, state.target.model_name, state.dimension
+    );
+    println!(
+        "  {}",
+        "Restart running servers so new memories are written to both collections".yellow()
+    );
+
+    backfill(&migrator, &client).await
+}
+
+/// Resume an interrupted backfill
+pub async fn migrate_resume(
+    operations: Arc<MemoryOperations>,
+    embedding: &EmbeddingConfig,
+    embedding_cache: &EmbeddingCacheConfig,
+    data_dir: &str,
+) -> Result<()> {
+    let migrator = migrator(&operations);
+    let state = require_state(&migrator)?;
+    let client = target_client(&state.target, embedding, embedding_cache, data_dir)?;
+
+    println!(
+        "{} Resuming migration to {} ({} re-embedded so far)",
+        "🚚".bold(),
+        state.target.model_name,
+        state.copied
+    );
+    backfill(&migrator, &client).await
+}
+
+/// Show the progress of the current migration
+pub async fn migrate_status(operations: Arc<MemoryOperations>) -> Result<()> {
+    let migrator = migrator(&operations);
+    let Some(state) = migrator.state()? else {
+        println!("No embedding model migration for this tenant.");
+        return Ok(());
+    };
+
+    println!("{} Embedding model migration\n", "📊".bold());
+    println!("  Phase:        {}", state.phase.as_str());
+    println!(
+        "  Target model: {} ({}, dim {})",
+        state.target.model_name, state.target.provider, state.dimension
+    );
+    println!("  Started:      {}", state.started_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!("  Updated:      {}", state.updated_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!("  Re-embedded:  {}", state.copied);
+    println!("  Skipped:      {}", state.skipped);
+    println!("  Removed:      {}", state.removed);
+
+    if state.phase == MigrationPhase::Switched {
+        println!("  Collection:   {} → {}", state.source_collection, state.target_collection);
+        if let Some(old_collection) = &state.old_collection {
+            println!(
+                "\n  {}",
+                format!("Run `vector migrate switch` to drop {}", old_collection).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    let vector_store = operations.vector_store();
+    for (label, collection) in [
+        ("Source", &state.source_collection),
+        ("Target", &state.target_collection),
+    ] {
+        match vector_store.get_collection_points_count(collection).await {
+            Ok(n) => println!("  {} vectors: {:>8}  ({})", label, n, collection),
+            Err(e) => println!("  {} vectors: {} ({})", label, "?".yellow(), e),
+        }
+    }
+
+    match state.phase {
+        MigrationPhase::Backfilling => {
+            println!("\n  {}", "Run `vector migrate resume` to continue the backfill".yellow())
+        }
+        MigrationPhase::Ready => {
+            println!("\n  {}", "Run `vector migrate switch` to start using the new model".yellow())
+        }
+        MigrationPhase::Switching => {
+            println!("\n  {}", "Run `vector migrate switch` to finish the switch".yellow())
+        }
+        MigrationPhase::Switched => {}
+    }
+    Ok(())
+}
+
+/// Switch the collection to the new model
+pub async fn migrate_switch(
+    operations: Arc<MemoryOperations>,
+    embedding: &EmbeddingConfig,
+    embedding_cache: &EmbeddingCacheConfig,
+    data_dir: &str,
+) -> Result<()> {
+    let migrator = migrator(&operations);
+    let state = require_state(&migrator)?;
+    let client = target_client(&state.target, embedding, embedding_cache, data_dir)?;
+
+    if state.phase == MigrationPhase::Ready {
+        println!("{} Catching up before the switch...", "🔄".bold());
+    } else {
+        println!("{} Finishing the interrupted switch...", "🔄".bold());
+    }
+    let state = migrator.switch(&client, print_progress).await?;
+
+    println!("\n{} {} now uses {}", "✅".bold(), state.source_collection, state.target.model_name);
+    if state.removed > 0 {
+        println!(
+            "  Removed {} points deleted from the source during the migration",
+            state.removed
+        );
+    }
+    println!("  Update the [embedding] section of the config and restart running servers:");
+    println!("    provider = \"{}\"", state.target.provider);
+    println!("    api_base_url = \"{}\"", state.target.api_base_url);
+    println!("    model_name = \"{}\"", state.target.model_name);
+    if let Some(dir) = &state.target.local_model_dir {
+        println!("    local_model_dir = \"{}\"", dir);
+    }
+    let configured_dim = operations.vector_store().embedding_dim();
+    if configured_dim.is_some_and(|dim| dim != state.dimension) {
+        println!("  and set [qdrant] embedding_dim = {}", state.dimension);
+    }
+    Ok(())
+}
+
+/// Cancel the current migration
+pub async fn migrate_abort(operations: Arc<MemoryOperations>) -> Result<()> {
+    let migrator = migrator(&operations);
+    let state = migrator.abort().await?;
+    println!(
+        "{} Migration to {} aborted, dropped {}",
+        "🗑️".bold(),
+        state.target.model_name,
+        state.target_collection
+    );
+    Ok(())
+}
+
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
+fn migrator(operations: &MemoryOperations) -> VectorMigrator {
+    VectorMigrator::new(operations.vector_store(), operations.filesystem().base_path())
+}
+
+fn require_state(migrator: &VectorMigrator) -> Result<VectorMigration> {
+    match migrator.state()? {
+        Some(state) if state.phase != MigrationPhase::Switched || state.is_switch_pending() => {
+            Ok(state)
+        }
+        _ => anyhow::bail!("No embedding model migration in progress; run `vector migrate start`"),
+    }
+}
+
+/// Embedding client of the migration target (shares the configured API key)
+fn target_client(
+    target: &MigrationTarget,
+    embedding: &EmbeddingConfig,
+    embedding_cache: &EmbeddingCacheConfig,
+    data_dir: &str,
+) -> Result<EmbeddingClient> {
+    let config = target
+        .embedding_config(&embedding.api_key)
+        .with_persistent_cache(embedding_cache, data_dir);
+    Ok(EmbeddingClient::new(config)?)
+}
+
+async fn backfill(migrator: &VectorMigrator, client: &EmbeddingClient) -> Result<()> {
+    let state = migrator.backfill(client, print_progress).await?;
+    println!(
+        "\n{} Backfill complete: {} re-embedded, {} already present",
+        "✅".bold(),
+        state.copied,
+        state.skipped
+    );
+    println!("  Run `vector migrate switch` to start using {}", state.target.model_name);
+    Ok(())
+}
+
+fn print_progress(state: &VectorMigration) {
+    println!(
+        "  {} re-embedded, {} skipped, {} removed",
+        state.copied.to_string().green(),
+        state.skipped,
+        state.removed
+    );
+}
+
 async fn fetch_collection_stats(operations: Arc<MemoryOperations>) -> Result<(u64, u64)> {
     let vector_store = operations.vector_store();
     let collection_name = vector_store.collection_name().to_string();
@@ -1,13 +1,16 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use cortex_mem_config::Config;
+use cortex_mem_config::{Config, Provider};
+use cortex_mem_core::MigrationTarget;
 use cortex_mem_core::llm::LLMClientImpl;
 use cortex_mem_tools::MemoryOperations;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 mod commands;
-use commands::{add, delete, get, layers, list, search, session, stats, tenant, vector};
+use commands::{
+    add, cache, delete, get, import, layers, list, prompts, search, session, stats, tenant, vector,
+};
 
 /// Cortex-Mem CLI - File-based memory management for AI Agents
 #[derive(Parser)]
@@ -46,6 +49,20 @@ enum Commands {
         content: String,
     },
 
+    /// Import messages in bulk from a JSON array or JSONL file
+    Import {
+        /// Path to the file (entries: thread_id, role, content, optional timestamp)
+        file: PathBuf,
+
+        /// Thread ID for entries that don't specify one
+        #[arg(short, long)]
+        thread: Option<String>,
+
+        /// Close each imported session afterwards to trigger memory extraction
+        #[arg(long)]
+        extract: bool,
+    },
+
     /// Search for memories using semantic vector search
     Search {
         /// Search query
@@ -106,7 +123,11 @@ enum Commands {
     },
 
     /// Show statistics
-    Stats,
+    Stats {
+        /// Days of token usage to summarize (including today)
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+    },
 
     /// Layer management (L0/L1 files)
     Layers {
@@ -125,6 +146,18 @@ enum Commands {
         #[command(subcommand)]
         action: TenantAction,
     },
+
+    /// Persistent LLM result and embedding cache management
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Prompt template overrides of the tenant
+    Prompts {
+        #[command(subcommand)]
+        action: PromptsAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -159,6 +192,12 @@ enum LayersAction {
 
     /// Regenerate oversized .abstract files (> 2K characters)
     RegenerateOversized,
+
+    /// Report stale, oversized, empty or wrong-language L0/L1 files
+    Audit,
+
+    /// Regenerate L0/L1 files written by the extractive (LLM-free) summarizer
+    UpgradeExtractive,
 }
 
 #[derive(Subcommand)]
@@ -175,6 +214,50 @@ enum VectorAction {
         #[arg(long)]
         dry_run: bool,
     },
+
+    /// Move the collection to another embedding model without downtime
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Create the target collection and re-embed all vectors with the new model
+    Start {
+        /// Embedding model to migrate to
+        #[arg(long)]
+        model: String,
+
+        /// API flavour of the new model (default: the configured provider)
+        #[arg(long)]
+        provider: Option<Provider>,
+
+        /// Endpoint of the new model (default: the configured endpoint)
+        #[arg(long)]
+        api_base_url: Option<String>,
+
+        /// Compute vectors in-process from this model directory (needs `local-embedding`)
+        #[arg(long)]
+        local_model_dir: Option<String>,
+
+        /// Vector dimension of the new model (probed when omitted)
+        #[arg(long)]
+        dim: Option<usize>,
+    },
+
+    /// Continue an interrupted backfill
+    Resume,
+
+    /// Show the progress of the current migration
+    Status,
+
+    /// Catch up, point the collection name at the new vectors and drop the old ones
+    Switch,
+
+    /// Drop the target collection and cancel the migration
+    Abort,
 }
 
 #[derive(Subcommand)]
@@ -183,6 +266,32 @@ enum TenantAction {
     List,
 }
 
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Show entries and disk usage of the persistent LLM result and embedding caches
+    Stats,
+
+    /// Remove all cached LLM results and embeddings
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum PromptsAction {
+    /// List the prompt templates and whether the tenant overrides them
+    List,
+
+    /// Write the default templates to the tenant's prompts directory as a starting point
+    Dump {
+        /// Write to this directory instead of the tenant's prompts directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -236,32 +345,61 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle cache commands early (they only touch the cache directory)
+    if let Commands::Cache { action } = cli.command {
+        match action {
+            CacheAction::Stats => {
+                cache::stats(&data_dir, &config).await?;
+            }
+            CacheAction::Clear => {
+                cache::clear(&data_dir, &config).await?;
+            }
+        }
+        return Ok(());
+    }
+
+    // Handle prompt template commands early (they only touch the prompts directory)
+    if let Commands::Prompts { action } = cli.command {
+        match action {
+            PromptsAction::List => {
+                prompts::list(&data_dir, &cli.tenant).await?;
+            }
+            PromptsAction::Dump { dir, force } => {
+                prompts::dump(&data_dir, &cli.tenant, dir, force).await?;
+            }
+        }
+        return Ok(());
+    }
+
     // Initialize LLM client
     let model_name = config.llm.model_efficient.clone();
     let llm_config = cortex_mem_core::llm::LLMConfig {
+        provider: config.llm.provider,
         api_base_url: config.llm.api_base_url,
         api_key: config.llm.api_key,
         model_efficient: config.llm.model_efficient,
         temperature: config.llm.temperature,
         max_tokens: config.llm.max_tokens as usize,
+        api_version: config.llm.api_version,
+        models: config.llm.models,
     };
     let llm_client = Arc::new(LLMClientImpl::new(llm_config)?);
 
     // Initialize MemoryOperations with vector search
-    let operations = MemoryOperations::new(
-        &data_dir,
+    let operations = MemoryOperations::builder(
+        data_dir.as_str(),
         &cli.tenant,
         llm_client,
-        &config.qdrant.url,
-        &config.qdrant.collection_name,
-        config.qdrant.api_key.as_deref(),
-        &config.embedding.api_base_url,
-        &config.embedding.api_key,
-        &config.embedding.model_name,
-        config.qdrant.embedding_dim,
-        None,  // user_id parameter
-        config.cortex.enable_intent_analysis,
+        &config.qdrant,
+        &config.embedding,
     )
+    .with_intent_analysis(config.cortex.enable_intent_analysis)
+    .with_llm_cache(&config.llm_cache)
+    .with_embedding_cache(&config.embedding_cache)
+    .with_usage(&config.usage)
+    .with_fixtures(&config.fixtures)
+    .with_layers(&config.layers)
+    .build()
     .await?;
 
     if cli.verbose {
@@ -281,6 +419,13 @@ async fn main() -> Result<()> {
         } => {
             add::execute(operations, &thread, &role, &content).await?;
         }
+        Commands::Import {
+            file,
+            thread,
+            extract,
+        } => {
+            import::execute(operations, &file, thread.as_deref(), extract).await?;
+        }
         Commands::Search {
             query,
             thread,
@@ -321,8 +466,8 @@ async fn main() -> Result<()> {
                 session::close(operations, &thread).await?;
             }
         },
-        Commands::Stats => {
-            stats::execute(operations).await?;
+        Commands::Stats { days } => {
+            stats::execute(operations, days).await?;
         }
         Commands::Layers { action } => match action {
             LayersAction::EnsureAll => {
@@ -334,6 +479,12 @@ async fn main() -> Result<()> {
             LayersAction::RegenerateOversized => {
                 layers::regenerate_oversized(operations).await?;
             }
+            LayersAction::Audit => {
+                layers::audit(operations).await?;
+            }
+            LayersAction::UpgradeExtractive => {
+                layers::upgrade_extractive(operations).await?;
+            }
         },
         Commands::Vector { action } => match action {
             VectorAction::Status => {
@@ -345,8 +496,59 @@ async fn main() -> Result<()> {
             VectorAction::Prune { dry_run } => {
                 vector::prune(operations, dry_run).await?;
             }
+            VectorAction::Migrate { action } => match action {
+                MigrateAction::Start {
+                    model,
+                    provider,
+                    api_base_url,
+                    local_model_dir,
+                    dim,
+                } => {
+                    let target = MigrationTarget {
+                        provider: provider.unwrap_or(config.embedding.provider),
+                        api_base_url: api_base_url
+                            .unwrap_or_else(|| config.embedding.api_base_url.clone()),
+                        model_name: model,
+                        api_version: config.embedding.api_version.clone(),
+                        local_model_dir,
+                    };
+                    vector::migrate_start(
+                        operations,
+                        &config.embedding,
+                        &config.embedding_cache,
+                        &data_dir,
+                        target,
+                        dim,
+                    )
+                    .await?;
+                }
+                MigrateAction::Resume => {
+                    vector::migrate_resume(
+                        operations,
+                        &config.embedding,
+                        &config.embedding_cache,
+                        &data_dir,
+                    )
+                    .await?;
+                }
+                MigrateAction::Status => {
+                    vector::migrate_status(operations).await?;
+                }
+                MigrateAction::Switch => {
+                    vector::migrate_switch(
+                        operations,
+                        &config.embedding,
+                        &config.embedding_cache,
+                        &data_dir,
+                    )
+                    .await?;
+                }
+                MigrateAction::Abort => {
+                    vector::migrate_abort(operations).await?;
+                }
+            },
         },
-        Commands::Tenant { .. } => {
+        Commands::Tenant { .. } | Commands::Cache { .. } | Commands::Prompts { .. } => {
             // Already handled above
         }
     }
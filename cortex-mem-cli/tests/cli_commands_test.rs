@@ -36,6 +36,19 @@
 //! # 通过环境变量指定配置
 //! CONFIG_PATH=/path/to/config.toml TENANT_ID=my-tenant cargo test -p cortex-mem-cli -- --include-ignored
 //! ```
+//!
+//! # 离线回放
+//!
+//! 完整功能测试的 LLM / Embedding 调用可以先录制、再离线回放（仍需 Qdrant）。
+//! 回放按请求内容的哈希匹配，未录制过的请求直接报错：
+//!
+//! ```bash
+//! # 录制一次（需要真实的 LLM + Embedding）
+//! CORTEX_FIXTURES=record CORTEX_FIXTURES_DIR=tests/fixtures cargo test -p cortex-mem-cli -- --include-ignored
+//!
+//! # 之后离线、确定性地回放
+//! CORTEX_FIXTURES=replay CORTEX_FIXTURES_DIR=tests/fixtures cargo test -p cortex-mem-cli -- --include-ignored
+//! ```
 
 use assert_cmd::Command;
 use predicates::prelude::*;
@@ -255,6 +268,16 @@ fn test_stats_subcommand_help() {
     cli().args(["stats", "--help"]).assert().success();
 }
 
+/// B12: import 子命令的 --help 应包含文件与 extract 参数说明
+#[test]
+fn test_import_subcommand_help() {
+    cli()
+        .args(["import", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FILE").and(predicate::str::contains("extract")));
+}
+
 // ─── 2. Tenant 命令测试 ──────────────────────────────────────────────────────
 
 /// T01: tenant list 在有租户目录时应列出所有租户
@@ -381,6 +404,12 @@ fn test_session_close_missing_thread() {
     cli().args(["session", "close"]).assert().failure();
 }
 
+/// V07b: import 命令缺少文件位置参数时应以错误退出
+#[test]
+fn test_import_missing_file_arg() {
+    cli().args(["import"]).assert().failure();
+}
+
 /// V08: search 命令的 --min-score 参数超出范围（>1.0）应以错误退出
 /// 注意：参数验证发生在 MemoryOperations 初始化之后，因此该测试需要外部服务
 #[test]
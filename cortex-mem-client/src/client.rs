@@ -0,0 +1,356 @@
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+use crate::error::{ClientError, Result};
+use crate::models::*;
+
+/// Typed HTTP client for `cortex-mem-service` (`/api/v2`)
+///
+/// Every method maps 1:1 onto a REST route; the `ApiResponse` envelope is
+/// unwrapped and non-2xx answers are turned into [`ClientError::Api`].
+#[derive(Debug, Clone)]
+pub struct CortexMemClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl CortexMemClient {
+    /// Create a client for a service base URL such as `http://localhost:8085`
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(300))
+            .build()?;
+        Self::with_http_client(base_url, http)
+    }
+
+    /// Create a client reusing an existing `reqwest::Client` (custom timeouts, proxies, ...)
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Result<Self> {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(ClientError::InvalidUrl(format!(
+                "base URL must start with http:// or https://, got '{}'",
+                base_url
+            )));
+        }
+        Ok(Self { base_url, http })
+    }
+
+    /// Service base URL (without trailing slash)
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // ==================== Service ====================
+
+    /// `GET /health`
+    pub async fn health(&self) -> Result<HealthResponse> {
+        let resp = self.request(Method::GET, "/health").send().await?;
+        let bytes = success_body(resp).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// `GET /api/v2/openapi.json` — the OpenAPI 3 document served by the service
+    pub async fn openapi_spec(&self) -> Result<serde_json::Value> {
+        let resp = self.request(Method::GET, "/api/v2/openapi.json").send().await?;
+        let bytes = success_body(resp).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    // ==================== Sessions ====================
+
+    /// `GET /api/v2/sessions`
+    pub async fn list_sessions(&self) -> Result<Vec<SessionResponse>> {
+        self.send(self.request(Method::GET, "/api/v2/sessions")).await
+    }
+
+    /// `POST /api/v2/sessions`
+    pub async fn create_session(&self, req: &CreateSessionRequest) -> Result<SessionResponse> {
+        self.send(self.request(Method::POST, "/api/v2/sessions").json(req))
+            .await
+    }
+
+    /// `POST /api/v2/sessions/{thread_id}/messages`
+    ///
+    /// Returns the service's confirmation message containing the message URI.
+    pub async fn add_message(&self, thread_id: &str, req: &AddMessageRequest) -> Result<String> {
+        let path = format!("/api/v2/sessions/{}/messages", encode_segment(thread_id));
+        self.send(self.request(Method::POST, &path).json(req)).await
+    }
+
+    /// `POST /api/v2/sessions/batch` — bulk import across sessions
+    pub async fn add_messages_batch(
+        &self,
+        req: &BatchAddMessagesRequest,
+    ) -> Result<BatchAddMessagesResponse> {
+        self.send(self.request(Method::POST, "/api/v2/sessions/batch").json(req))
+            .await
+    }
+
+    /// `POST /api/v2/sessions/{thread_id}/close`
+    pub async fn close_session(&self, thread_id: &str) -> Result<SessionResponse> {
+        let path = format!("/api/v2/sessions/{}/close", encode_segment(thread_id));
+        self.send(self.request(Method::POST, &path)).await
+    }
+
+    /// `POST /api/v2/sessions/{thread_id}/close-and-wait`
+    pub async fn close_session_and_wait(
+        &self,
+        thread_id: &str,
+        req: &CloseAndWaitRequest,
+    ) -> Result<CloseAndWaitResponse> {
+        let path = format!(
+            "/api/v2/sessions/{}/close-and-wait",
+            encode_segment(thread_id)
+        );
+        // Leave headroom over the server-side wait so the HTTP timeout doesn't fire first
+        let timeout = Duration::from_secs(req.timeout_secs.saturating_add(30));
+        self.send(self.request(Method::POST, &path).json(req).timeout(timeout))
+            .await
+    }
+
+    // ==================== Search ====================
+
+    /// `POST /api/v2/search`
+    pub async fn search(&self, req: &SearchRequest) -> Result<Vec<SearchResultResponse>> {
+        self.send(self.request(Method::POST, "/api/v2/search").json(req))
+            .await
+    }
+
+    // ==================== Filesystem ====================
+
+    /// `GET /api/v2/filesystem/list`
+    pub async fn list_directory(&self, req: &LsRequest) -> Result<LsResponse> {
+        self.send(self.request(Method::GET, "/api/v2/filesystem/list").query(req))
+            .await
+    }
+
+    /// `GET /api/v2/filesystem/read/{path}`
+    pub async fn read_file(&self, uri: &str) -> Result<String> {
+        let path = uri
+            .trim_start_matches("cortex://")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(encode_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+        self.send(self.request(Method::GET, &format!("/api/v2/filesystem/read/{}", path)))
+            .await
+    }
+
+    /// `POST /api/v2/filesystem/write`
+    pub async fn write_file(&self, req: &WriteFileRequest) -> Result<String> {
+        self.send(self.request(Method::POST, "/api/v2/filesystem/write").json(req))
+            .await
+    }
+
+    /// `GET /api/v2/filesystem/stats`
+    pub async fn directory_stats(&self, uri: &str) -> Result<DirectoryStats> {
+        let query = StatsQuery {
+            uri: uri.to_string(),
+        };
+        self.send(self.request(Method::GET, "/api/v2/filesystem/stats").query(&query))
+            .await
+    }
+
+    /// `GET /api/v2/filesystem/abstract` — L0 layer
+    pub async fn get_abstract(&self, uri: &str) -> Result<LayerResponse> {
+        self.get_layer("/api/v2/filesystem/abstract", uri).await
+    }
+
+    /// `GET /api/v2/filesystem/overview` — L1 layer
+    pub async fn get_overview(&self, uri: &str) -> Result<LayerResponse> {
+        self.get_layer("/api/v2/filesystem/overview", uri).await
+    }
+
+    /// `GET /api/v2/filesystem/content` — L2 layer
+    pub async fn get_content(&self, uri: &str) -> Result<LayerResponse> {
+        self.get_layer("/api/v2/filesystem/content", uri).await
+    }
+
+    /// `POST /api/v2/filesystem/explore`
+    pub async fn explore(&self, req: &ExploreRequest) -> Result<ExploreResponse> {
+        self.send(self.request(Method::POST, "/api/v2/filesystem/explore").json(req))
+            .await
+    }
+
+    // ==================== Memories ====================
+
+    /// `POST /api/v2/memories` — remember a structured memory
+    pub async fn remember(&self, req: &RememberRequest) -> Result<MemoryEditResponse> {
+        self.send(self.request(Method::POST, "/api/v2/memories").json(req))
+            .await
+    }
+
+    /// `PUT /api/v2/memories` — replace the content of an existing memory
+    pub async fn update_memory(&self, req: &UpdateMemoryRequest) -> Result<MemoryEditResponse> {
+        self.send(self.request(Method::PUT, "/api/v2/memories").json(req))
+            .await
+    }
+
+    /// `DELETE /api/v2/memories` — forget a memory
+    pub async fn forget(&self, uri: &str) -> Result<MemoryEditResponse> {
+        let query = MemoryQuery {
+            uri: uri.to_string(),
+        };
+        self.send(self.request(Method::DELETE, "/api/v2/memories").query(&query))
+            .await
+    }
+
+    // ==================== Automation ====================
+
+    /// `POST /api/v2/automation/extract/{thread_id}`
+    pub async fn trigger_extraction(&self, thread_id: &str) -> Result<AutomationResponse> {
+        let path = format!("/api/v2/automation/extract/{}", encode_segment(thread_id));
+        self.send(self.request(Method::POST, &path)).await
+    }
+
+    /// `POST /api/v2/automation/reindex`
+    pub async fn trigger_reindex(&self) -> Result<AutomationResponse> {
+        self.send(self.request(Method::POST, "/api/v2/automation/reindex"))
+            .await
+    }
+
+    // ==================== Tenants ====================
+
+    /// `GET /api/v2/tenants`
+    pub async fn list_tenants(&self) -> Result<Vec<String>> {
+        self.send(self.request(Method::GET, "/api/v2/tenants")).await
+    }
+
+    /// `POST /api/v2/tenants/switch`
+    pub async fn switch_tenant(&self, tenant_id: &str) -> Result<String> {
+        let req = TenantSwitchRequest {
+            tenant_id: tenant_id.to_string(),
+        };
+        self.send(self.request(Method::POST, "/api/v2/tenants/switch").json(&req))
+            .await
+    }
+
+    // ==================== Usage ====================
+
+    /// `GET /api/v2/usage` — rate limit usage counters for the current UTC day
+    pub async fn usage(&self) -> Result<UsageResponse> {
+        self.send(self.request(Method::GET, "/api/v2/usage")).await
+    }
+
+    /// `GET /api/v2/usage/tokens` — LLM and embedding token usage of the
+    /// current tenant over the last `days` UTC days
+    pub async fn token_usage(&self, days: u32) -> Result<TokenUsageResponse> {
+        let query = TokenUsageQuery { days };
+        self.send(self.request(Method::GET, "/api/v2/usage/tokens").query(&query))
+            .await
+    }
+
+    // ── Helpers ──────────────────────────────────────────────────────────────
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http.request(method, format!("{}{}", self.base_url, path))
+    }
+
+    async fn get_layer(&self, path: &str, uri: &str) -> Result<LayerResponse> {
+        let query = LayerQuery {
+            uri: uri.to_string(),
+        };
+        self.send(self.request(Method::GET, path).query(&query))
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T> {
+        let resp = builder.send().await?;
+        unwrap_envelope(resp).await
+    }
+}
+
+/// Unwrap `ApiResponse<T>` or map the error body to [`ClientError::Api`]
+async fn unwrap_envelope<T: DeserializeOwned>(resp: Response) -> Result<T> {
+    let bytes = success_body(resp).await?;
+    let envelope: ApiResponse<T> = serde_json::from_slice(&bytes)?;
+    match envelope.data {
+        Some(data) if envelope.success => Ok(data),
+        _ => Err(ClientError::EmptyResponse(
+            envelope
+                .error
+                .unwrap_or_else(|| "response carried no data".to_string()),
+        )),
+    }
+}
+
+/// Read the body of a 2xx response, or turn the error body into a [`ClientError`]
+async fn success_body(resp: Response) -> Result<Vec<u8>> {
+    let status = resp.status();
+    let retry_after_secs = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    let bytes = resp.bytes().await?;
+    if status.is_success() {
+        return Ok(bytes.to_vec());
+    }
+
+    match api_error(status.as_u16(), &bytes) {
+        ClientError::Api { status: 429, message } => Err(ClientError::RateLimited {
+            message,
+            retry_after_secs,
+        }),
+        err => Err(err),
+    }
+}
+
+fn api_error(status: u16, body: &[u8]) -> ClientError {
+    let message = serde_json::from_slice::<ErrorResponse>(body)
+        .map(|e| e.error)
+        .unwrap_or_else(|_| String::from_utf8_lossy(body).into_owned());
+    ClientError::Api { status, message }
+}
+
+/// Percent-encode a single path segment (RFC 3986 unreserved characters pass through)
+fn encode_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("thread-1_a.b~c"), "thread-1_a.b~c");
+        assert_eq!(encode_segment("a b/c"), "a%20b%2Fc");
+        assert_eq!(encode_segment("会话"), "%E4%BC%9A%E8%AF%9D");
+    }
+
+    #[test]
+    fn test_base_url_validation() {
+        assert!(CortexMemClient::new("localhost:8085").is_err());
+
+        let client = CortexMemClient::new("http://localhost:8085/").unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8085");
+    }
+
+    #[test]
+    fn test_api_error_body() {
+        match api_error(404, br#"{"error":"Not found: cortex://user/x","status":404}"#) {
+            ClientError::Api { status, message } => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "Not found: cortex://user/x");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        match api_error(502, b"Bad Gateway") {
+            ClientError::Api { message, .. } => assert_eq!(message, "Bad Gateway"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
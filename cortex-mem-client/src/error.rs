@@ -0,0 +1,36 @@
+use thiserror::Error;
+
+/// Errors returned by [`CortexMemClient`](crate::CortexMemClient)
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Invalid base URL or request path
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    /// Transport-level failure (connection refused, timeout, TLS, ...)
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The service answered with a non-2xx status
+    #[error("API error ({status}): {message}")]
+    Api { status: u16, message: String },
+
+    /// The service rejected the request with `429 Too Many Requests`
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Seconds from the `Retry-After` header, if present
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The service answered 2xx but the envelope carried no data
+    #[error("Empty response: {0}")]
+    EmptyResponse(String),
+
+    /// Serialization/deserialization error
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Result type for client operations
+pub type Result<T> = std::result::Result<T, ClientError>;
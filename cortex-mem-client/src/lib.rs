@@ -0,0 +1,25 @@
+//! Typed Rust client for `cortex-mem-service`.
+//!
+//! The request/response types in [`models`] are the same types the service
+//! uses for its handlers and its OpenAPI document (`GET /api/v2/openapi.json`),
+//! so other Rust services can talk to Cortex Memory without redefining them.
+//!
+//! ```no_run
+//! use cortex_mem_client::{CortexMemClient, models::SearchRequest};
+//!
+//! # async fn demo() -> cortex_mem_client::Result<()> {
+//! let client = CortexMemClient::new("http://localhost:8085")?;
+//! let results = client.search(&SearchRequest::new("what does the user prefer?")).await?;
+//! for r in results {
+//!     println!("{} ({:.2}): {}", r.uri, r.score, r.snippet);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod models;
+
+pub use client::CortexMemClient;
+pub use error::{ClientError, Result};
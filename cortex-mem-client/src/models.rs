@@ -0,0 +1,582 @@
+//! Wire models shared by `cortex-mem-service` and its clients.
+//!
+//! With the `openapi` feature enabled every type also derives
+//! `utoipa::ToSchema` (and `IntoParams` for query strings), which is how the
+//! service builds its OpenAPI document.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::{IntoParams, ToSchema};
+
+// ==================== Envelope ====================
+
+/// Standard API response wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            success: true,
+            data: Some(data),
+            error: None,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Error body returned with every non-2xx status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ErrorResponse {
+    pub error: String,
+    pub status: u16,
+}
+
+/// Health check response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct HealthResponse {
+    pub status: String,
+    pub service: String,
+    pub version: String,
+    pub llm_available: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+// ==================== Filesystem ====================
+
+/// File entry response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct FileEntryResponse {
+    pub uri: String,
+    pub name: String,
+    pub is_directory: bool,
+    pub size: u64,
+    pub modified: DateTime<Utc>,
+    /// L0 abstract text (only included when include_abstracts=true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abstract_text: Option<String>,
+}
+
+/// List directory request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema, IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct LsRequest {
+    /// Directory URI to list
+    #[serde(default = "default_uri")]
+    pub uri: String,
+    /// Whether to recursively list subdirectories
+    #[serde(default)]
+    pub recursive: bool,
+    /// Whether to include L0 abstracts for files
+    #[serde(default)]
+    pub include_abstracts: bool,
+    /// Whether to include layer files (.abstract.md for L0, .overview.md for L1)
+    #[serde(default)]
+    pub include_layers: bool,
+    /// Glob (e.g. "*.md") or substring filter on entry names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Sort key: "name" (default), "modified" or "size"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    /// Reverse the sort order
+    #[serde(default)]
+    pub descending: bool,
+    /// Page size (all entries when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl Default for LsRequest {
+    fn default() -> Self {
+        Self {
+            uri: default_uri(),
+            recursive: false,
+            include_abstracts: false,
+            include_layers: false,
+            pattern: None,
+            sort: None,
+            descending: false,
+            limit: None,
+            cursor: None,
+        }
+    }
+}
+
+fn default_uri() -> String {
+    "cortex://session".to_string()
+}
+
+/// List directory response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LsResponse {
+    pub uri: String,
+    /// Entries matching the filter across all pages
+    pub total: usize,
+    pub entries: Vec<FileEntryResponse>,
+    /// Cursor for the next page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Write file request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WriteFileRequest {
+    /// Target URI (e.g. "cortex://resources/notes.md")
+    pub path: String,
+    pub content: String,
+}
+
+/// Directory stats query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema, IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct StatsQuery {
+    /// Directory or file URI to count
+    pub uri: String,
+}
+
+/// Directory stats response (recursive)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DirectoryStats {
+    pub file_count: u64,
+    pub total_size: u64,
+}
+
+/// Query for layered access endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema, IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct LayerQuery {
+    /// File or directory URI
+    pub uri: String,
+}
+
+/// Response for layered access endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LayerResponse {
+    pub uri: String,
+    pub content: String,
+    /// "L0", "L1" or "L2"
+    pub layer: String,
+    pub token_count: usize,
+}
+
+/// Explore request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExploreRequest {
+    pub query: String,
+    /// Starting URI for exploration
+    #[serde(default = "default_explore_start")]
+    pub start_uri: String,
+    /// Which layers to return in matches
+    #[serde(default = "default_return_layers")]
+    pub return_layers: Vec<String>,
+}
+
+fn default_explore_start() -> String {
+    "cortex://session".to_string()
+}
+
+/// Explore response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExploreResponse {
+    pub query: String,
+    /// Path taken during exploration
+    pub exploration_path: Vec<ExplorationPathItem>,
+    /// Matching results found
+    pub matches: Vec<SearchResultResponse>,
+    /// Total items explored
+    pub total_explored: usize,
+    /// Total matches found
+    pub total_matches: usize,
+}
+
+/// Item in exploration path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExplorationPathItem {
+    pub uri: String,
+    pub relevance_score: f32,
+    pub abstract_text: Option<String>,
+}
+
+// ==================== Sessions ====================
+
+/// Session response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SessionResponse {
+    pub thread_id: String,
+    pub status: String,
+    pub message_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create session request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreateSessionRequest {
+    /// Thread ID (a UUID is generated when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Owner of the memories extracted from this session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+}
+
+/// Message request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AddMessageRequest {
+    /// "user", "assistant" or "system"
+    pub role: String,
+    pub content: String,
+    /// Optional metadata (tags, importance, etc.)
+    /// Note: Currently accepted but not processed by the backend.
+    /// Reserved for future enhancement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// One message of a batch import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BatchMessageRequest {
+    /// Target session (auto-created if missing)
+    pub thread_id: String,
+    /// "user", "assistant" or "system"
+    pub role: String,
+    pub content: String,
+    /// Original message time; defaults to import time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Batch import request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BatchAddMessagesRequest {
+    pub messages: Vec<BatchMessageRequest>,
+    /// Close every touched session afterwards so extraction runs once per thread
+    #[serde(default)]
+    pub trigger_extraction: bool,
+}
+
+/// Per-thread outcome of a batch import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BatchThreadResult {
+    pub thread_id: String,
+    pub message_count: usize,
+    pub uris: Vec<String>,
+}
+
+/// Batch import response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BatchAddMessagesResponse {
+    pub total_messages: usize,
+    pub threads: Vec<BatchThreadResult>,
+    /// Sessions closed for extraction (empty unless `trigger_extraction` was set)
+    pub extraction_triggered: Vec<String>,
+}
+
+/// Close-and-wait request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CloseAndWaitRequest {
+    /// Maximum seconds to wait for extraction + indexing readiness.
+    #[serde(default = "default_close_wait_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Poll interval in milliseconds.
+    #[serde(default = "default_close_wait_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl Default for CloseAndWaitRequest {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_close_wait_timeout_secs(),
+            poll_interval_ms: default_close_wait_poll_interval_ms(),
+        }
+    }
+}
+
+fn default_close_wait_timeout_secs() -> u64 {
+    120
+}
+
+fn default_close_wait_poll_interval_ms() -> u64 {
+    500
+}
+
+/// Close-and-wait response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CloseAndWaitResponse {
+    pub thread_id: String,
+    pub status: String,
+    pub user_id: String,
+    pub agent_id: String,
+    pub waited_ms: u64,
+    pub user_index_exists: bool,
+    pub user_memory_count: usize,
+    pub session_summary_exists: bool,
+    pub session_summary_memory_count: usize,
+    pub vector_sync_confirmed: bool,
+    pub timeline_abstract_exists: bool,
+    pub timeline_overview_exists: bool,
+}
+
+// ==================== Search ====================
+
+/// Search request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SearchRequest {
+    pub query: String,
+    /// Scope to limit search. Supports two formats:
+    /// - Session ID: "abc" -> searches within cortex://session/abc
+    /// - Full URI: "cortex://user/default" -> searches user memories
+    /// - Omit to search across all dimensions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f32>,
+    /// Which layers to return: ["L0"], ["L0","L1"], ["L0","L1","L2"]
+    /// Default: ["L0"] (only snippets)
+    #[serde(default = "default_return_layers")]
+    pub return_layers: Vec<String>,
+}
+
+impl SearchRequest {
+    /// Search request with service defaults (all dimensions, L0 only)
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            thread: None,
+            limit: None,
+            min_score: None,
+            return_layers: default_return_layers(),
+        }
+    }
+}
+
+fn default_return_layers() -> Vec<String> {
+    vec!["L0".to_string()]
+}
+
+/// Search result response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SearchResultResponse {
+    pub uri: String,
+    pub score: f32,
+    /// L0 abstract/snippet
+    pub snippet: String,
+    /// L1 overview text (only when return_layers contains "L1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overview: Option<String>,
+    /// L2 full content (only when return_layers contains "L2")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    pub source: String,
+    /// Which layers are included in this result
+    pub layers: Vec<String>,
+}
+
+// ==================== Memories ====================
+
+/// Remember request: assert a structured memory directly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RememberRequest {
+    /// "preference", "entity", "event", "case", "personal_info", "work_history",
+    /// "relationship" or "goal"
+    pub memory_type: String,
+    /// Identifies the memory within its type; remembering an existing key updates it
+    pub key: String,
+    pub content: String,
+    /// 0.0–1.0, default 0.9
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Owner of user-scoped memories (default: "default")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Owner of cases (default: "default")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+}
+
+/// Update memory request: replace the content of an existing memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UpdateMemoryRequest {
+    /// Memory file URI, e.g. `cortex://user/{id}/preferences/pref_xxx.md`
+    pub uri: String,
+    pub content: String,
+    /// Defaults to the stored confidence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// Query naming the memory to forget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema, IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct MemoryQuery {
+    /// Memory file URI
+    pub uri: String,
+}
+
+/// Result of remember / update / forget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MemoryEditResponse {
+    pub uri: String,
+    pub memory_id: String,
+    pub memory_type: String,
+    pub key: String,
+    /// "created", "updated", "unchanged" or "deleted"
+    pub action: String,
+}
+
+// ==================== Automation & Tenants ====================
+
+/// Response for automation triggers (extraction, reindex)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AutomationResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    pub status: String,
+    pub message: String,
+}
+
+/// Tenant switch request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TenantSwitchRequest {
+    pub tenant_id: String,
+}
+
+// ==================== Usage ====================
+
+/// Usage counters of one tenant or API key for one endpoint category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UsageEntry {
+    /// "tenant" or "key"
+    pub subject: String,
+    /// Tenant ID, or the API key with everything after the first 4 characters masked
+    pub id: String,
+    /// "search", "write" or "extraction"
+    pub category: String,
+    pub requests_today: u64,
+    pub rejected_today: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_quota: Option<u64>,
+}
+
+/// Usage snapshot for the current UTC day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UsageResponse {
+    /// Whether limits are enforced (usage is counted either way)
+    pub enabled: bool,
+    /// UTC day the counters belong to (YYYY-MM-DD)
+    pub date: String,
+    pub entries: Vec<UsageEntry>,
+}
+
+/// Query for `GET /api/v2/usage/tokens`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema, IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct TokenUsageQuery {
+    /// Days to summarize, including today (default 7)
+    #[serde(default = "default_token_usage_days")]
+    pub days: u32,
+}
+
+fn default_token_usage_days() -> u32 {
+    7
+}
+
+/// Token and cost totals of a group of LLM / embedding calls
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TokenUsageTotals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Calls whose token counts were estimated because the provider reported none
+    pub estimated_calls: u64,
+    /// Estimated cost from `[usage.prices]`; 0 for unpriced models
+    pub cost_usd: f64,
+}
+
+/// Totals of one day, operation or model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TokenUsageBucket {
+    /// Day (YYYY-MM-DD), operation ("abstract", "overview", "extraction",
+    /// "intent", "embedding", "other") or model name
+    pub key: String,
+    pub usage: TokenUsageTotals,
+}
+
+/// Token usage of the current tenant over a range of UTC days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TokenUsageResponse {
+    /// Whether `[usage]` accounting is enabled; everything else is empty when not
+    pub enabled: bool,
+    /// First UTC day of the range (YYYY-MM-DD)
+    pub from: String,
+    /// Last UTC day of the range (YYYY-MM-DD)
+    pub to: String,
+    pub total: TokenUsageTotals,
+    pub by_day: Vec<TokenUsageBucket>,
+    pub by_operation: Vec<TokenUsageBucket>,
+    pub by_model: Vec<TokenUsageBucket>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_token_budget: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_cost_budget_usd: Option<f64>,
+    /// Whether today's usage exceeds a budget (budgeted tasks are being skipped)
+    pub over_budget: bool,
+}
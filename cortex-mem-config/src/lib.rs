@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Main configuration structure (V2 - simplified)
@@ -11,6 +12,30 @@ pub struct Config {
     pub server: ServerConfig,
     pub logging: LoggingConfig,
     pub cortex: CortexConfig,
+    /// Rate limits and quotas for cortex-mem-service (optional section)
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Agent-facing tool surface of cortex-mem-mcp and cortex-mem-rig (optional section)
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    /// Shared memory spaces and their access control lists (optional section)
+    #[serde(default)]
+    pub access: AccessConfig,
+    /// Cache of LLM results for L0/L1 generation and intent analysis (optional section)
+    #[serde(default)]
+    pub llm_cache: LlmCacheConfig,
+    /// Persistent cache of embedding vectors (optional section)
+    #[serde(default)]
+    pub embedding_cache: EmbeddingCacheConfig,
+    /// Token usage accounting, prices and daily budgets (optional section)
+    #[serde(default)]
+    pub usage: UsageConfig,
+    /// Record / replay of LLM and embedding calls for offline tests (optional section)
+    #[serde(default)]
+    pub fixtures: FixturesConfig,
+    /// How L0/L1 layers are generated (optional section)
+    #[serde(default)]
+    pub layers: LayersConfig,
 }
 
 /// Cortex Memory configuration
@@ -74,19 +99,84 @@ pub struct QdrantConfig {
     pub api_key: Option<String>,
 }
 
+/// API flavour of an LLM or embedding endpoint (`provider = "..."`)
+///
+/// - `openai`: OpenAI and OpenAI-compatible servers (vLLM, llama.cpp
+///   `llama-server`, LM Studio, ...); `api_base_url` includes `/v1`
+/// - `anthropic`: Anthropic Messages API (LLM only), e.g.
+///   `https://api.anthropic.com/v1`
+/// - `ollama`: Ollama's native API, e.g. `http://localhost:11434`
+/// - `azure`: Azure OpenAI; `api_base_url` is the resource endpoint, the model
+///   name is the deployment name and `api_version` selects the API version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    #[default]
+    OpenAi,
+    Anthropic,
+    Ollama,
+    Azure,
+}
+
+impl Provider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Provider::OpenAi => "openai",
+            Provider::Anthropic => "anthropic",
+            Provider::Ollama => "ollama",
+            Provider::Azure => "azure",
+        }
+    }
+}
+
+impl std::fmt::Display for Provider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Provider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "openai" => Ok(Provider::OpenAi),
+            "anthropic" => Ok(Provider::Anthropic),
+            "ollama" => Ok(Provider::Ollama),
+            "azure" => Ok(Provider::Azure),
+            other => Err(format!(
+                "unknown provider '{}' (expected openai, anthropic, ollama or azure)",
+                other
+            )),
+        }
+    }
+}
+
 /// Embedding configuration for vector search
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
+    /// API flavour of the endpoint (default: `openai`)
+    #[serde(default)]
+    pub provider: Provider,
     pub api_base_url: String,
     pub api_key: String,
     pub model_name: String,
     pub batch_size: usize,
     pub timeout_secs: u64,
+    /// API version (`azure` only)
+    #[serde(default)]
+    pub api_version: Option<String>,
+    /// Directory of a local sentence-transformer model; when set, embeddings
+    /// are computed in-process instead of calling the API (requires the
+    /// `local-embedding` cargo feature)
+    #[serde(default)]
+    pub local_model_dir: Option<String>,
 }
 
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         EmbeddingConfig {
+            provider: Provider::default(),
             api_base_url: std::env::var("EMBEDDING_API_BASE_URL")
                 .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
             api_key: std::env::var("EMBEDDING_API_KEY")
@@ -95,18 +185,93 @@ impl Default for EmbeddingConfig {
                 .unwrap_or_else(|_| "text-embedding-3-small".to_string()),
             batch_size: 10,
             timeout_secs: 30,
+            api_version: None,
+            local_model_dir: None,
         }
     }
 }
 
 /// LLM configuration for rig framework
+///
+/// `model_efficient` serves every task unless `[llm.models]` routes it to
+/// another model of the same endpoint:
+///
+/// ```toml
+/// [llm]
+/// provider = "anthropic"
+/// api_base_url = "https://api.anthropic.com/v1"
+/// model_efficient = "claude-3-5-haiku-latest"
+///
+/// [llm.models]
+/// extraction = "claude-sonnet-4-5"
+/// intent = "claude-sonnet-4-5"
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMConfig {
+    /// API flavour of the endpoint (default: `openai`)
+    #[serde(default)]
+    pub provider: Provider,
     pub api_base_url: String,
     pub api_key: String,
     pub model_efficient: String,
     pub temperature: f32,
     pub max_tokens: u32,
+    /// API version (`azure` only)
+    #[serde(default)]
+    pub api_version: Option<String>,
+    /// Per-task model routing
+    #[serde(default)]
+    pub models: LlmTaskModels,
+}
+
+/// Kind of work an LLM call does, used to route it to a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmTask {
+    /// L0 abstracts
+    Abstract,
+    /// L1 overviews
+    Overview,
+    /// Memory extraction from conversations
+    Extraction,
+    /// Query intent analysis before search
+    Intent,
+}
+
+impl LlmTask {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LlmTask::Abstract => "abstract",
+            LlmTask::Overview => "overview",
+            LlmTask::Extraction => "extraction",
+            LlmTask::Intent => "intent",
+        }
+    }
+}
+
+/// Model per task (`[llm.models]`); unset tasks use `model_efficient`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LlmTaskModels {
+    #[serde(default)]
+    pub r#abstract: Option<String>,
+    #[serde(default)]
+    pub overview: Option<String>,
+    #[serde(default)]
+    pub extraction: Option<String>,
+    #[serde(default)]
+    pub intent: Option<String>,
+}
+
+impl LlmTaskModels {
+    /// Model configured for a task, if any
+    pub fn get(&self, task: LlmTask) -> Option<&str> {
+        match task {
+            LlmTask::Abstract => self.r#abstract.as_deref(),
+            LlmTask::Overview => self.overview.as_deref(),
+            LlmTask::Extraction => self.extraction.as_deref(),
+            LlmTask::Intent => self.intent.as_deref(),
+        }
+    }
 }
 
 /// HTTP server configuration
@@ -117,6 +282,461 @@ pub struct ServerConfig {
     pub cors_origins: Vec<String>,
 }
 
+/// Rate limiting configuration (`[rate_limit]`)
+///
+/// Limits are tracked per tenant (all traffic of the tenant) and, when the
+/// request carries an `X-API-Key` header with an entry under `keys`, per key
+/// as well; both must allow the request. Tenant limits come from
+/// `tenants.<id>` and fall back to `defaults`.
+///
+/// ```toml
+/// [rate_limit]
+/// enabled = true
+///
+/// [rate_limit.defaults.search]
+/// requests_per_minute = 30
+/// daily_quota = 2000
+///
+/// [rate_limit.keys.agent-key-1.extraction]
+/// daily_quota = 50
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Enforce limits (usage is counted either way)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Limits applied to every tenant without its own entry
+    #[serde(default)]
+    pub defaults: RateLimitRules,
+    /// Per-tenant overrides, keyed by tenant ID
+    #[serde(default)]
+    pub tenants: HashMap<String, RateLimitRules>,
+    /// Per-API-key limits, keyed by the `X-API-Key` value
+    #[serde(default)]
+    pub keys: HashMap<String, RateLimitRules>,
+}
+
+/// Limits per endpoint category
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimitRules {
+    /// `POST /api/v2/search`
+    #[serde(default)]
+    pub search: Option<LimitRule>,
+    /// Session creation, message ingestion and filesystem writes
+    #[serde(default)]
+    pub write: Option<LimitRule>,
+    /// Session close / close-and-wait and automation triggers
+    #[serde(default)]
+    pub extraction: Option<LimitRule>,
+}
+
+/// A single limit; unset fields mean unlimited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitRule {
+    /// Sustained request rate (token bucket, burst = one minute's worth)
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    /// Requests per UTC day
+    #[serde(default)]
+    pub daily_quota: Option<u64>,
+}
+
+/// Tool surface configuration (`[tools]`)
+///
+/// Controls which memory tools are exposed to agents and how they are
+/// described. Applies to the MCP server and to `cortex-mem-rig` agents.
+///
+/// ```toml
+/// [tools]
+/// # Only expose tools that do not modify memory
+/// read_only = false
+/// disabled = ["delete", "index"]
+///
+/// [tools.overrides.search]
+/// description = "Search the team's shared support knowledge base"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolsConfig {
+    /// Hide every tool that writes or deletes memory
+    #[serde(default)]
+    pub read_only: bool,
+    /// Allowlist of tool names; every tool is exposed when unset
+    #[serde(default)]
+    pub enabled: Option<Vec<String>>,
+    /// Tool names to hide (applied after `enabled`)
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Per-tool overrides, keyed by tool name
+    #[serde(default)]
+    pub overrides: HashMap<String, ToolOverride>,
+}
+
+/// Override for a single tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolOverride {
+    /// Replaces the built-in description shown to the agent
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Force the tool on or off, taking precedence over `enabled` / `disabled`
+    /// (but not over `read_only`)
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// Shared memory spaces and access control (`[access]`)
+///
+/// When enabled, every agent only sees its own `cortex://agent/{agent_id}`
+/// memories and the current user's `cortex://user/{user_id}` memories, plus
+/// the shared spaces (`cortex://spaces/{name}`) its ACL grants. Members are
+/// agent IDs and user IDs; `"*"` matches everyone and writers can also read.
+///
+/// ```toml
+/// [access]
+/// enabled = true
+///
+/// [access.spaces.project-apollo]
+/// description = "Architecture decisions and runbooks for Apollo"
+/// read = { agents = ["support-agent"] }
+/// write = { agents = ["coding-agent"], users = ["alice"] }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessConfig {
+    /// Enforce the rules above (everything is readable and writable when off)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shared spaces, keyed by name
+    #[serde(default)]
+    pub spaces: HashMap<String, SpaceConfig>,
+}
+
+/// A shared memory space
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpaceConfig {
+    /// What the space is for (documentation only)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Members allowed to read the space
+    #[serde(default)]
+    pub read: SpaceMembers,
+    /// Members allowed to read and write the space
+    #[serde(default)]
+    pub write: SpaceMembers,
+}
+
+/// Agent and user IDs granted access to a space (`"*"` matches any ID)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpaceMembers {
+    #[serde(default)]
+    pub agents: Vec<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+impl SpaceMembers {
+    /// Whether the agent or the user is a member
+    pub fn contains(&self, agent_id: &str, user_id: &str) -> bool {
+        let matches = |ids: &[String], id: &str| ids.iter().any(|m| m == "*" || m == id);
+        matches(&self.agents, agent_id) || matches(&self.users, user_id)
+    }
+}
+
+/// LLM result cache (`[llm_cache]`)
+///
+/// Results are keyed by prompt template version, model and content hash. The
+/// in-memory LRU is per process; with `persistent = true` results are also
+/// stored as files under `{data_dir}/cache/llm` (or `dir`) and reused across
+/// restarts, CLI runs and processes sharing the data directory.
+///
+/// ```toml
+/// [llm_cache]
+/// persistent = true
+/// max_disk_mb = 512
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmCacheConfig {
+    /// Cache LLM results at all
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Maximum entries of the in-memory LRU
+    #[serde(default = "default_llm_cache_max_entries")]
+    pub max_entries: usize,
+    /// Time-to-live of in-memory entries (seconds)
+    #[serde(default = "default_llm_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Keep results on disk across restarts
+    #[serde(default)]
+    pub persistent: bool,
+    /// Directory of the persistent cache, relative to the data directory
+    /// unless absolute (default: `cache/llm`)
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Size limit of the persistent cache; least recently used entries are
+    /// evicted first
+    #[serde(default = "default_llm_cache_max_disk_mb")]
+    pub max_disk_mb: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_llm_cache_max_entries() -> usize {
+    1000
+}
+
+fn default_llm_cache_ttl_secs() -> u64 {
+    3600
+}
+
+fn default_llm_cache_max_disk_mb() -> u64 {
+    256
+}
+
+impl Default for LlmCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: default_llm_cache_max_entries(),
+            ttl_secs: default_llm_cache_ttl_secs(),
+            persistent: false,
+            dir: None,
+            max_disk_mb: default_llm_cache_max_disk_mb(),
+        }
+    }
+}
+
+/// Persistent embedding cache (`[embedding_cache]`)
+///
+/// `EmbeddingClient` always keeps an in-memory cache; with `persistent = true`
+/// vectors are also stored under `{data_dir}/cache/embeddings/{model}` (or
+/// `dir`) so reindexing, bootstrap syncs and evaluation runs reuse them
+/// across restarts. Vectors are keyed by model name and text hash, and a
+/// model's cache is dropped when the service returns a different dimension.
+///
+/// ```toml
+/// [embedding_cache]
+/// persistent = true
+/// max_disk_mb = 1024
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingCacheConfig {
+    /// Keep embeddings on disk across restarts
+    #[serde(default)]
+    pub persistent: bool,
+    /// Directory of the persistent cache, relative to the data directory
+    /// unless absolute (default: `cache/embeddings`)
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Size limit per model; least recently used vectors are evicted first
+    #[serde(default = "default_embedding_cache_max_disk_mb")]
+    pub max_disk_mb: u64,
+}
+
+fn default_embedding_cache_max_disk_mb() -> u64 {
+    1024
+}
+
+impl Default for EmbeddingCacheConfig {
+    fn default() -> Self {
+        Self {
+            persistent: false,
+            dir: None,
+            max_disk_mb: default_embedding_cache_max_disk_mb(),
+        }
+    }
+}
+
+/// Token usage accounting (`[usage]`)
+///
+/// Every LLM and embedding call is recorded per tenant, with the token counts
+/// reported by the provider (estimated from text length when it reports none)
+/// and a cost from `prices`. `cortex-mem stats` and `GET /api/v2/usage/tokens`
+/// aggregate the records per day, task and model.
+///
+/// When a tenant exceeds a daily budget, calls for the tasks listed in
+/// `skip_when_over_budget` are refused and their callers fall back (intent
+/// analysis uses the heuristic query analysis); other calls go through.
+///
+/// ```toml
+/// [usage]
+/// daily_token_budget = 2000000
+/// daily_cost_budget_usd = 5.0
+/// skip_when_over_budget = ["intent"]
+///
+/// [usage.prices."gpt-4o-mini"]
+/// prompt = 0.15       # USD per million prompt tokens
+/// completion = 0.60   # USD per million completion tokens
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageConfig {
+    /// Record usage at all
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Prompt + completion tokens per tenant and UTC day
+    #[serde(default)]
+    pub daily_token_budget: Option<u64>,
+    /// Cost per tenant and UTC day (models without a price cost nothing)
+    #[serde(default)]
+    pub daily_cost_budget_usd: Option<f64>,
+    /// Tasks refused once a budget is exceeded
+    #[serde(default = "default_skip_when_over_budget")]
+    pub skip_when_over_budget: Vec<LlmTask>,
+    /// Price per model name
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+}
+
+fn default_skip_when_over_budget() -> Vec<LlmTask> {
+    vec![LlmTask::Intent]
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            daily_token_budget: None,
+            daily_cost_budget_usd: None,
+            skip_when_over_budget: default_skip_when_over_budget(),
+            prices: HashMap::new(),
+        }
+    }
+}
+
+/// Record / replay of LLM and embedding calls (`[fixtures]`)
+///
+/// `record` passes every call through to the configured providers and saves
+/// each request/response pair as a JSON file keyed by the hash of the request;
+/// `replay` serves calls from those files only and fails on a request that was
+/// never recorded, so tests run deterministically without any provider.
+///
+/// When the section is absent, `CORTEX_FIXTURES` (`off` | `record` | `replay`)
+/// and `CORTEX_FIXTURES_DIR` select the mode and directory, so a test suite can
+/// switch an existing config file to replay.
+///
+/// ```toml
+/// [fixtures]
+/// mode = "replay"
+/// dir = "tests/fixtures"   # Default: {data_dir}/fixtures
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixturesConfig {
+    #[serde(default = "default_fixture_mode")]
+    pub mode: FixtureMode,
+    #[serde(default = "default_fixtures_dir")]
+    pub dir: Option<String>,
+}
+
+fn default_fixture_mode() -> FixtureMode {
+    std::env::var("CORTEX_FIXTURES")
+        .ok()
+        .and_then(|mode| mode.parse().ok())
+        .unwrap_or_default()
+}
+
+fn default_fixtures_dir() -> Option<String> {
+    std::env::var("CORTEX_FIXTURES_DIR").ok()
+}
+
+impl Default for FixturesConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_fixture_mode(),
+            dir: default_fixtures_dir(),
+        }
+    }
+}
+
+impl FixturesConfig {
+    /// Whether calls are recorded or replayed at all
+    pub fn is_active(&self) -> bool {
+        self.mode != FixtureMode::Off
+    }
+
+    /// Fixture directory, `{data_dir}/fixtures` unless configured
+    pub fn dir_or_default(&self, data_dir: impl AsRef<Path>) -> std::path::PathBuf {
+        match &self.dir {
+            Some(dir) => std::path::PathBuf::from(dir),
+            None => data_dir.as_ref().join("fixtures"),
+        }
+    }
+}
+
+/// What [`FixturesConfig`] does with LLM and embedding calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureMode {
+    /// Call the providers (default)
+    #[default]
+    Off,
+    /// Call the providers and save every request/response pair
+    Record,
+    /// Serve calls from saved pairs, never calling a provider
+    Replay,
+}
+
+impl std::str::FromStr for FixtureMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" | "" => Ok(FixtureMode::Off),
+            "record" => Ok(FixtureMode::Record),
+            "replay" => Ok(FixtureMode::Replay),
+            other => Err(format!(
+                "unknown fixture mode '{}' (expected off, record or replay)",
+                other
+            )),
+        }
+    }
+}
+
+/// L0/L1 layer generation (`[layers]`)
+///
+/// `auto` generates layers with the LLM and falls back to a deterministic
+/// extractive summary when the LLM call fails; `llm` surfaces such failures
+/// instead; `extractive` never calls the LLM. Extractive layers are marked in
+/// the file so they are regenerated once an LLM is available.
+///
+/// ```toml
+/// [layers]
+/// mode = "extractive"   # auto (default) | llm | extractive
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayersConfig {
+    #[serde(default)]
+    pub mode: LayerMode,
+}
+
+/// How [`LayersConfig`] produces L0 abstracts and L1 overviews
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayerMode {
+    /// LLM, with an extractive fallback when it is unavailable (default)
+    #[default]
+    Auto,
+    /// LLM only; generation fails when the LLM does
+    Llm,
+    /// Extractive summaries only, no LLM calls
+    Extractive,
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    #[serde(default)]
+    pub prompt: f64,
+    #[serde(default)]
+    pub completion: f64,
+}
+
+impl ModelPrice {
+    /// Cost of one call in USD
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.prompt + completion_tokens as f64 * self.completion)
+            / 1_000_000.0
+    }
+}
+
 /// Logging configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoggingConfig {
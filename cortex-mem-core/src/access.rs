@@ -0,0 +1,357 @@
+//! Access control for shared memory spaces
+//!
+//! An [`AccessPolicy`] is built once from the `[access]` config section and
+//! shared; an [`AccessGuard`] binds it to the agent and user a component acts
+//! as. The guard is enforced by `CortexFilesystem` (reads, writes, listings)
+//! and `VectorSearchEngine` (result filtering).
+//!
+//! Rules, by URI dimension:
+//! - `cortex://user/{id}`: only the user `id`
+//! - `cortex://agent/{id}`: only the agent `id`
+//! - `cortex://spaces/{name}`: the members of the space's ACL; spaces that
+//!   are not declared in the config are closed
+//! - `cortex://session`, `cortex://resources`: open
+//!
+//! Dimension roots (e.g. `cortex://user`) can be listed, but the listing only
+//! contains the owners the guard grants, and nothing can be written there.
+
+use crate::Error;
+use cortex_mem_config::{AccessConfig, SpaceConfig};
+use std::sync::Arc;
+
+/// Access rules for every space of a deployment
+#[derive(Debug, Clone, Default)]
+pub struct AccessPolicy {
+    config: AccessConfig,
+}
+
+impl AccessPolicy {
+    pub fn new(config: AccessConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether access control is enforced at all
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Look up a declared space
+    pub fn space(&self, name: &str) -> Option<&SpaceConfig> {
+        self.config.spaces.get(name)
+    }
+
+    /// All declared spaces, sorted by name
+    pub fn spaces(&self) -> Vec<(&str, &SpaceConfig)> {
+        let mut spaces: Vec<_> = self
+            .config
+            .spaces
+            .iter()
+            .map(|(name, space)| (name.as_str(), space))
+            .collect();
+        spaces.sort_by_key(|(name, _)| *name);
+        spaces
+    }
+
+    /// Bind the policy to the agent and user a component acts as
+    pub fn guard(
+        self: &Arc<Self>,
+        agent_id: impl Into<String>,
+        user_id: impl Into<String>,
+    ) -> AccessGuard {
+        AccessGuard {
+            policy: self.clone(),
+            agent_id: agent_id.into(),
+            user_id: user_id.into(),
+        }
+    }
+}
+
+/// Access a single URI requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+}
+
+/// A read or write the guard does not grant; converts into
+/// [`Error::PermissionDenied`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Permission denied: {0}")]
+pub struct AccessDenied(pub String);
+
+impl From<AccessDenied> for Error {
+    fn from(denied: AccessDenied) -> Self {
+        Error::PermissionDenied(denied.0)
+    }
+}
+
+/// An [`AccessPolicy`] bound to one agent and user
+#[derive(Debug, Clone)]
+pub struct AccessGuard {
+    policy: Arc<AccessPolicy>,
+    agent_id: String,
+    user_id: String,
+}
+
+impl AccessGuard {
+    pub fn policy(&self) -> &Arc<AccessPolicy> {
+        &self.policy
+    }
+
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Whether the URI (or anything below it) may be read
+    pub fn can_read(&self, uri: &str) -> bool {
+        self.allows(uri, Access::Read)
+    }
+
+    /// Whether the URI may be written or deleted
+    pub fn can_write(&self, uri: &str) -> bool {
+        self.allows(uri, Access::Write)
+    }
+
+    pub fn check_read(&self, uri: &str) -> Result<(), AccessDenied> {
+        self.check(uri, Access::Read)
+    }
+
+    pub fn check_write(&self, uri: &str) -> Result<(), AccessDenied> {
+        self.check(uri, Access::Write)
+    }
+
+    /// Whether the space may be read
+    pub fn can_read_space(&self, name: &str) -> bool {
+        self.space_allows(name, Access::Read)
+    }
+
+    /// Whether the space may be written
+    pub fn can_write_space(&self, name: &str) -> bool {
+        self.space_allows(name, Access::Write)
+    }
+
+    fn check(&self, uri: &str, access: Access) -> Result<(), AccessDenied> {
+        if self.allows(uri, access) {
+            return Ok(());
+        }
+        let verb = match access {
+            Access::Read => "read",
+            Access::Write => "write",
+        };
+        Err(AccessDenied(format!(
+            "agent '{}' (user '{}') may not {} {}",
+            self.agent_id, self.user_id, verb, uri
+        )))
+    }
+
+    fn allows(&self, uri: &str, access: Access) -> bool {
+        if !self.policy.is_enabled() {
+            return true;
+        }
+
+        let path = uri.strip_prefix("cortex://").unwrap_or(uri);
+        let path = path.split_once('?').map_or(path, |(p, _)| p);
+        let mut parts = path.split('/').filter(|s| !s.is_empty());
+        // `..` escapes the owner checked below (the URI parser rejects it too)
+        if parts.clone().any(|part| matches!(part, "." | "..")) {
+            return false;
+        }
+        let dimension = parts.next().unwrap_or_default();
+        let owner = parts.next();
+
+        match dimension {
+            "user" | "agent" | "spaces" => match owner {
+                // Dimension root (listings are filtered entry by entry) and
+                // dimension-level metadata such as `cortex://user/.abstract.md`
+                None => access == Access::Read,
+                Some(owner) if owner.starts_with('.') => access == Access::Read,
+                Some(owner) => match dimension {
+                    "user" => owner == self.user_id,
+                    "agent" => owner == self.agent_id,
+                    _ => self.space_allows(owner, access),
+                },
+            },
+            _ => true,
+        }
+    }
+
+    fn space_allows(&self, name: &str, access: Access) -> bool {
+        if !self.policy.is_enabled() {
+            return true;
+        }
+        let Some(space) = self.policy.space(name) else {
+            return false;
+        };
+        let writer = space.write.contains(&self.agent_id, &self.user_id);
+        match access {
+            Access::Read => writer || space.read.contains(&self.agent_id, &self.user_id),
+            Access::Write => writer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_mem_config::SpaceMembers;
+
+    fn policy() -> Arc<AccessPolicy> {
+        let mut config = AccessConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        config.spaces.insert(
+            "apollo".to_string(),
+            SpaceConfig {
+                description: None,
+                read: SpaceMembers {
+                    agents: vec!["support".to_string()],
+                    users: vec![],
+                },
+                write: SpaceMembers {
+                    agents: vec!["coding".to_string()],
+                    users: vec!["alice".to_string()],
+                },
+            },
+        );
+        config.spaces.insert(
+            "handbook".to_string(),
+            SpaceConfig {
+                read: SpaceMembers {
+                    agents: vec!["*".to_string()],
+                    users: vec![],
+                },
+                ..Default::default()
+            },
+        );
+        Arc::new(AccessPolicy::new(config))
+    }
+
+    #[test]
+    fn test_private_scopes() {
+        let guard = policy().guard("coding", "bob");
+
+        assert!(guard.can_write("cortex://agent/coding/cases/case_1.md"));
+        assert!(!guard.can_read("cortex://agent/support/cases/case_1.md"));
+        assert!(guard.can_read("cortex://user/bob/preferences"));
+        assert!(!guard.can_read("cortex://user/alice"));
+        assert!(guard.can_write("cortex://session/t1/timeline"));
+        assert!(guard.can_write("cortex://resources/doc.md"));
+
+        assert!(guard.can_read("cortex://agent"));
+        assert!(!guard.can_write("cortex://agent"));
+        assert!(guard.can_read("cortex://user/.abstract.md"));
+        assert!(matches!(
+            guard.check_read("cortex://agent/support"),
+            Err(AccessDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_dot_segments_denied() {
+        let guard = policy().guard("coding", "bob");
+
+        assert!(!guard.can_read("cortex://user/../agent/support/cases/case_1.md"));
+        assert!(!guard.can_read("cortex://agent/coding/../support/cases/case_1.md"));
+        assert!(!guard.can_write("cortex://agent/coding/../support/cases/case_1.md"));
+        assert!(!guard.can_read("cortex://agent/coding/./cases"));
+        assert!(matches!(
+            crate::filesystem::UriParser::parse("cortex://agent/coding/../support/cases/case_1.md"),
+            Err(Error::InvalidPath)
+        ));
+        assert!(matches!(
+            crate::filesystem::UriParser::parse("cortex://user/../agent/support"),
+            Err(Error::InvalidPath)
+        ));
+    }
+
+    #[test]
+    fn test_space_acl() {
+        let policy = policy();
+        let coding = policy.guard("coding", "bob");
+        let support = policy.guard("support", "bob");
+        let other = policy.guard("sales", "carol");
+        let alice = policy.guard("sales", "alice");
+
+        assert!(coding.can_write("cortex://spaces/apollo/memories/a.md"));
+        assert!(support.can_read("cortex://spaces/apollo/memories/a.md"));
+        assert!(!support.can_write("cortex://spaces/apollo/memories/a.md"));
+        assert!(!other.can_read("cortex://spaces/apollo"));
+        assert!(alice.can_write("cortex://spaces/apollo"));
+
+        assert!(other.can_read("cortex://spaces/handbook"));
+        assert!(!other.can_write("cortex://spaces/handbook"));
+        assert!(!coding.can_read("cortex://spaces/undeclared"));
+    }
+
+    #[tokio::test]
+    async fn test_guarded_filesystem() {
+        use crate::{CortexFilesystem, FilesystemOperations};
+
+        let dir = tempfile::tempdir().unwrap();
+        let raw = CortexFilesystem::with_tenant(dir.path(), "acme");
+        raw.write("cortex://agent/support/cases/case_1.md", "refund flow")
+            .await
+            .unwrap();
+        raw.write("cortex://agent/coding/cases/case_2.md", "build fix")
+            .await
+            .unwrap();
+
+        let fs = raw.clone().with_access(policy().guard("coding", "bob"));
+        let agents: Vec<_> = fs
+            .list("cortex://agent")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(agents, ["coding"]);
+
+        assert!(
+            fs.read("cortex://agent/coding/cases/case_2.md")
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            fs.read("cortex://agent/support/cases/case_1.md").await,
+            Err(Error::PermissionDenied(_))
+        ));
+        assert!(
+            !fs.exists("cortex://agent/support/cases/case_1.md")
+                .await
+                .unwrap()
+        );
+        assert!(fs.delete("cortex://agent/support").await.is_err());
+        assert!(
+            fs.read("cortex://agent/coding/../support/cases/case_1.md")
+                .await
+                .is_err()
+        );
+        assert!(
+            fs.write("cortex://user/../agent/support/cases/case_1.md", "x")
+                .await
+                .is_err()
+        );
+        assert!(
+            fs.write("cortex://spaces/apollo/memories/a.md", "x")
+                .await
+                .is_ok()
+        );
+        assert!(
+            fs.write("cortex://spaces/handbook/a.md", "x")
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_disabled_policy_allows_everything() {
+        let guard = Arc::new(AccessPolicy::default()).guard("coding", "bob");
+        assert!(guard.can_write("cortex://agent/support/cases/case_1.md"));
+        assert!(guard.can_write("cortex://spaces/undeclared/a.md"));
+    }
+}
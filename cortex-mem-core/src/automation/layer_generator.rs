@@ -1,9 +1,14 @@
+use crate::cascade_layer_updater::CascadeLayerUpdater;
+use crate::layers::extractive;
 use crate::layers::generator::{AbstractGenerator, OverviewGenerator};
-use crate::llm::LLMClient;
-use crate::{CortexFilesystem, FilesystemOperations, Result};
+use crate::layers::validation::{self, LayerIssue, LayerValidationConfig};
+use crate::llm::{LLMClient, PromptTemplates};
+use crate::llm_result_cache::LlmResultCache;
+use crate::{ContextLayer, CortexFilesystem, FilesystemOperations, LayerMode, LlmTask, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 /// 层级生成配置
@@ -19,6 +24,8 @@ pub struct LayerGenerationConfig {
     pub abstract_config: AbstractConfig,
     /// Overview 配置
     pub overview_config: OverviewConfig,
+    /// 生成方式（LLM / 抽取式 / LLM 失败时回退到抽取式）
+    pub mode: LayerMode,
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +61,7 @@ impl Default for LayerGenerationConfig {
                 max_tokens: 1500,
                 max_chars: 6000,
             },
+            mode: LayerMode::default(),
         }
     }
 }
@@ -75,6 +83,7 @@ pub struct LayerGenerator {
     overview_gen: OverviewGenerator,
     llm_client: Arc<dyn LLMClient>,
     config: LayerGenerationConfig,
+    llm_cache: Option<Arc<LlmResultCache>>,
 }
 
 impl LayerGenerator {
@@ -83,15 +92,24 @@ impl LayerGenerator {
         llm_client: Arc<dyn LLMClient>,
         config: LayerGenerationConfig,
     ) -> Self {
+        let templates = PromptTemplates::for_filesystem(&filesystem);
         Self {
             filesystem,
-            abstract_gen: AbstractGenerator::new(),
-            overview_gen: OverviewGenerator::new(),
+            abstract_gen: AbstractGenerator::with_templates(templates.clone())
+                .with_mode(config.mode),
+            overview_gen: OverviewGenerator::with_templates(templates).with_mode(config.mode),
             llm_client,
             config,
+            llm_cache: None,
         }
     }
 
+    /// 复用 LLM 结果缓存（与 CascadeLayerUpdater 共享相同的 L0/L1 缓存键）
+    pub fn with_cache(mut self, llm_cache: Arc<LlmResultCache>) -> Self {
+        self.llm_cache = Some(llm_cache);
+        self
+    }
+
     /// 扫描所有目录
     pub async fn scan_all_directories(&self) -> Result<Vec<String>> {
         let mut directories = Vec::new();
@@ -332,6 +350,57 @@ impl LayerGenerator {
         Ok(stats)
     }
 
+    /// Generate L0 abstract and L1 overview for aggregated content
+    ///
+    /// Uses the existing AbstractGenerator / OverviewGenerator; results are
+    /// looked up in and written to the LLM result cache when one is set.
+    async fn generate_with_cache(&self, content: &str) -> Result<(String, String)> {
+        let keys = self.llm_cache.as_ref().map(|cache| {
+            (
+                cache,
+                LlmResultCache::key(
+                    "l0",
+                    &self.abstract_gen.template_version(),
+                    self.llm_client.model_for(LlmTask::Abstract),
+                    content,
+                ),
+                LlmResultCache::key(
+                    "l1",
+                    &self.overview_gen.template_version(),
+                    self.llm_client.model_for(LlmTask::Overview),
+                    content,
+                ),
+            )
+        });
+
+        if let Some((cache, key_l0, key_l1)) = &keys {
+            if let (Some(l0), Some(l1)) = (cache.get(key_l0).await, cache.get(key_l1).await) {
+                debug!("LLM cache hit for L0/L1");
+                return Ok((l0, l1));
+            }
+        }
+
+        let abstract_text = self
+            .abstract_gen
+            .generate_with_llm(content, &self.llm_client, &[])
+            .await?;
+        let overview = self
+            .overview_gen
+            .generate_with_llm(content, &self.llm_client)
+            .await?;
+
+        // Extractive fallbacks are not cached, so the LLM gets another try
+        if let Some((cache, key_l0, key_l1)) = keys {
+            if !extractive::is_extractive(&abstract_text) {
+                cache.put(key_l0, abstract_text.clone()).await;
+            }
+            if !extractive::is_extractive(&overview) {
+                cache.put(key_l1, overview.clone()).await;
+            }
+        }
+        Ok((abstract_text, overview))
+    }
+
     /// Generate L0/L1 for a single directory
     async fn generate_layers_for_directory(&self, uri: &str) -> Result<()> {
         debug!("Generating layer files for: {}", uri);
@@ -350,17 +419,8 @@ impl LayerGenerator {
             return Ok(());
         }
 
-        // 3. Use existing AbstractGenerator to generate L0 abstract
-        let abstract_text = self
-            .abstract_gen
-            .generate_with_llm(&content, &self.llm_client, &[])
-            .await?;
-
-        // 4. Use existing OverviewGenerator to generate L1 overview
-        let overview = self
-            .overview_gen
-            .generate_with_llm(&content, &self.llm_client)
-            .await?;
+        // 3-4. Generate L0 abstract and L1 overview (or reuse cached results)
+        let (abstract_text, overview) = self.generate_with_cache(&content).await?;
 
         // 5. Enforce length limits
         let abstract_text = self.enforce_abstract_limit(abstract_text)?;
@@ -390,8 +450,9 @@ impl LayerGenerator {
     ///
     /// Check logic:
     /// 1. If .abstract.md or .overview.md doesn't exist → need to generate
-    /// 2. If files in directory are newer than .abstract.md → need to regenerate
-    /// 3. Otherwise → skip (avoid duplicate generation)
+    /// 2. If the layers are extractive and an LLM may be used → need to regenerate
+    /// 3. If files in directory are newer than .abstract.md → need to regenerate
+    /// 4. Otherwise → skip (avoid duplicate generation)
     async fn should_regenerate(&self, uri: &str) -> Result<bool> {
         let abstract_path = format!("{}/.abstract.md", uri);
         let overview_path = format!("{}/.overview.md", uri);
@@ -414,8 +475,19 @@ impl LayerGenerator {
             }
         };
 
+        if self.config.mode != LayerMode::Extractive && self.is_extractive(uri).await {
+            debug!("Extractive layers can be upgraded, need to regenerate: {}", uri);
+            return Ok(true);
+        }
+
+        self.sources_changed_since(uri, &abstract_content).await
+    }
+
+    /// Whether files in the directory are newer than the `Added` timestamp of
+    /// its .abstract.md (or the abstract has no timestamp)
+    async fn sources_changed_since(&self, uri: &str, abstract_content: &str) -> Result<bool> {
         // Extract "Added" timestamp
-        let abstract_timestamp = self.extract_added_timestamp(&abstract_content);
+        let abstract_timestamp = self.extract_added_timestamp(abstract_content);
 
         if abstract_timestamp.is_none() {
             debug!(".abstract.md missing timestamp, need to regenerate: {}", uri);
@@ -456,12 +528,12 @@ impl LayerGenerator {
         // Find "**Added**: YYYY-MM-DD HH:MM:SS UTC" format
         if let Some(start) = content.find("**Added**: ") {
             let timestamp_str = &content[start + 11..];
-            if let Some(end) = timestamp_str.find('\n') {
-                let timestamp_str = &timestamp_str[..end].trim();
-                // Parse timestamp
-                if let Ok(dt) = DateTime::parse_from_str(timestamp_str, "%Y-%m-%d %H:%M:%S UTC") {
-                    return Some(dt.with_timezone(&Utc));
-                }
+            // The marker is usually the last line of layer files
+            let end = timestamp_str.find('\n').unwrap_or(timestamp_str.len());
+            let timestamp_str = &timestamp_str[..end].trim();
+            // Parse timestamp
+            if let Ok(dt) = DateTime::parse_from_str(timestamp_str, "%Y-%m-%d %H:%M:%S UTC") {
+                return Some(dt.with_timezone(&Utc));
             }
         }
         None
@@ -629,6 +701,151 @@ impl LayerGenerator {
         Ok(stats)
     }
 
+    /// 检测目录的 L0/L1 是否由抽取式摘要生成
+    pub async fn is_extractive(&self, uri: &str) -> bool {
+        for name in [".abstract.md", ".overview.md"] {
+            if let Ok(content) = self.filesystem.read(&format!("{}/{}", uri, name)).await {
+                if extractive::is_extractive(&content) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// 过滤出 L0/L1 为抽取式摘要的目录
+    pub async fn filter_extractive_layers(&self, dirs: &[String]) -> Result<Vec<String>> {
+        let mut extractive = Vec::new();
+        for dir in dirs {
+            if self.is_extractive(dir).await {
+                extractive.push(dir.clone());
+            }
+        }
+        Ok(extractive)
+    }
+
+    /// Regenerate layers written by the extractive summarizer with the LLM
+    ///
+    /// Does nothing in `Extractive` mode. A directory whose layers are still
+    /// extractive afterwards (LLM unavailable again) counts as failed.
+    pub async fn upgrade_extractive_layers(&self) -> Result<GenerationStats> {
+        if self.config.mode == LayerMode::Extractive {
+            info!("Layer mode is extractive, nothing to upgrade");
+            return Ok(GenerationStats::default());
+        }
+
+        info!("Scanning for extractive L0/L1 layers...");
+        let directories = self.scan_all_directories().await?;
+        let extractive = self.filter_extractive_layers(&directories).await?;
+
+        let mut stats = GenerationStats {
+            total: extractive.len(),
+            ..Default::default()
+        };
+        for dir in &extractive {
+            match self.generate_layers_for_directory(dir).await {
+                Ok(_) if self.is_extractive(dir).await => {
+                    stats.failed += 1;
+                    warn!("LLM unavailable, layers stay extractive: {}", dir);
+                }
+                Ok(_) => {
+                    stats.generated += 1;
+                    debug!("Upgraded: {}", dir);
+                }
+                Err(e) => {
+                    stats.failed += 1;
+                    warn!("Failed to upgrade {}: {}", dir, e);
+                }
+            }
+        }
+
+        info!(
+            "Extractive layer upgrade completed: {} upgraded, {} failed",
+            stats.generated, stats.failed
+        );
+        Ok(stats)
+    }
+
+    /// Audit existing L0/L1 files
+    ///
+    /// Reports layers that are empty, oversized, written in another language
+    /// than their source, or stale. Staleness uses the source hash footer
+    /// written by `CascadeLayerUpdater`; layers without one (written by
+    /// `ensure-all`) fall back to the `Added` timestamp check.
+    pub async fn audit_layers(&self) -> Result<LayerAuditReport> {
+        info!("Auditing L0/L1 layer files...");
+        let directories = self.scan_all_directories().await?;
+        let validation = LayerValidationConfig::default();
+
+        // Only used for its source aggregation, no events are sent
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let cascade = CascadeLayerUpdater::new(
+            self.filesystem.clone(),
+            self.llm_client.clone(),
+            event_tx,
+        );
+
+        let mut report = LayerAuditReport {
+            directories: directories.len(),
+            ..Default::default()
+        };
+
+        for dir in &directories {
+            let layers = [
+                (ContextLayer::L0Abstract, format!("{}/.abstract.md", dir)),
+                (ContextLayer::L1Overview, format!("{}/.overview.md", dir)),
+            ];
+
+            let mut stored = Vec::new();
+            for (layer, path) in layers {
+                if let Ok(content) = self.filesystem.read(&path).await {
+                    stored.push((layer, path, content));
+                }
+            }
+            if stored.is_empty() {
+                continue;
+            }
+
+            let mut source = cascade.source_content(dir).await?;
+            let source_hash =
+                (!source.is_empty()).then(|| cascade.calculate_content_hash(&source));
+            if source.is_empty() {
+                source = self.aggregate_directory_content_recursive(dir).await?;
+            }
+
+            for (layer, path, content) in stored {
+                report.layers_checked += 1;
+                if extractive::is_extractive(&content) {
+                    report.extractive += 1;
+                }
+                let mut issues = validation::audit(layer, &source, &content, &validation);
+
+                let stale = match CascadeLayerUpdater::stored_source_hash(&content) {
+                    Some(stored_hash) => source_hash.as_deref() != Some(stored_hash),
+                    None => self.sources_changed_since(dir, &content).await?,
+                };
+                if stale {
+                    issues.push(LayerIssue::Stale);
+                }
+
+                if !issues.is_empty() {
+                    debug!("Layer {} has issues: {:?}", path, issues);
+                    report.entries.push(LayerAuditEntry {
+                        layer_uri: path,
+                        issues,
+                    });
+                }
+            }
+        }
+
+        info!(
+            "Layer audit completed: {} layers checked, {} with issues",
+            report.layers_checked,
+            report.entries.len()
+        );
+        Ok(report)
+    }
+
     /// 移除元数据（Added、Confidence等）
     fn strip_metadata(&self, content: &str) -> String {
         let mut result = content.to_string();
@@ -651,3 +868,23 @@ pub struct RegenerationStats {
     pub regenerated: usize,
     pub failed: usize,
 }
+
+/// 单个层级文件的审计结果
+#[derive(Debug, Clone)]
+pub struct LayerAuditEntry {
+    pub layer_uri: String,
+    pub issues: Vec<LayerIssue>,
+}
+
+/// 层级审计报告
+#[derive(Debug, Clone, Default)]
+pub struct LayerAuditReport {
+    /// 扫描的目录数
+    pub directories: usize,
+    /// 检查的 L0/L1 文件数
+    pub layers_checked: usize,
+    /// 其中由抽取式摘要生成（待 LLM 升级）的文件数
+    pub extractive: usize,
+    /// 存在问题的文件
+    pub entries: Vec<LayerAuditEntry>,
+}
@@ -5,8 +5,8 @@ mod sync;
 
 pub use indexer::{AutoIndexer, IndexStats, IndexerConfig};
 pub use layer_generator::{
-    AbstractConfig, GenerationStats, LayerGenerationConfig, LayerGenerator, OverviewConfig,
-    RegenerationStats,
+    AbstractConfig, GenerationStats, LayerAuditEntry, LayerAuditReport, LayerGenerationConfig,
+    LayerGenerator, OverviewConfig, RegenerationStats,
 };
 pub use manager::{AutomationConfig, AutomationManager};
 pub use sync::{SyncConfig, SyncManager, SyncStats};
@@ -43,6 +43,8 @@ pub struct SyncConfig {
     pub sync_users: bool,
     /// 是否同步global维度
     pub sync_global: bool,
+    /// 是否同步共享空间（spaces维度）
+    pub sync_spaces: bool,
 }
 
 impl Default for SyncConfig {
@@ -53,6 +55,7 @@ impl Default for SyncConfig {
             sync_threads: true,
             sync_users: true,
             sync_global: true,
+            sync_spaces: true,
         }
     }
 }
@@ -135,6 +138,16 @@ impl SyncManager {
             }
         }
 
+        // 同步共享空间
+        if self.config.sync_spaces {
+            if let Ok(entries) = self.filesystem.list("cortex://spaces").await {
+                if !entries.is_empty() {
+                    let stats = self.sync_directory("cortex://spaces", "L2").await?;
+                    total_stats.add(&stats);
+                }
+            }
+        }
+
         info!(
             "Sync completed: {} files processed, {} indexed, {} skipped, {} errors",
             total_stats.total_files,
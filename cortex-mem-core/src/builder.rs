@@ -6,11 +6,15 @@ use crate::{
     embedding::{EmbeddingClient, EmbeddingConfig},
     events::EventBus,
     filesystem::CortexFilesystem,
+    fixtures::FixtureStore,
+    incremental_memory_updater::IncrementalMemoryUpdater,
     llm::LLMClient,
     memory_event_coordinator::{CoordinatorConfig, MemoryEventCoordinator},
     session::{SessionConfig, SessionManager},
+    usage::{MeteredLLMClient, UsageTracker},
     vector_store::{QdrantVectorStore, VectorStore},
 };
+use cortex_mem_config::{FixturesConfig, UsageConfig};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -25,6 +29,10 @@ pub struct CortexMemBuilder {
     session_config: SessionConfig,
     /// 事件协调器配置
     coordinator_config: Option<CoordinatorConfig>,
+    /// Token 用量统计配置
+    usage_config: Option<UsageConfig>,
+    /// LLM / Embedding 调用录制与回放配置
+    fixtures_config: Option<FixturesConfig>,
 }
 
 impl CortexMemBuilder {
@@ -37,6 +45,8 @@ impl CortexMemBuilder {
             llm_client: None,
             session_config: SessionConfig::default(),
             coordinator_config: None,
+            usage_config: None,
+            fixtures_config: None,
         }
     }
 
@@ -70,8 +80,20 @@ impl CortexMemBuilder {
         self
     }
 
+    /// 配置 Token 用量统计与预算（记录写入 `{data_dir}/usage/`）
+    pub fn with_usage(mut self, config: UsageConfig) -> Self {
+        self.usage_config = Some(config);
+        self
+    }
+
+    /// 配置 LLM / Embedding 调用的录制与回放（离线测试）
+    pub fn with_fixtures(mut self, config: FixturesConfig) -> Self {
+        self.fixtures_config = Some(config);
+        self
+    }
+
     /// 🎯 构建完整的cortex-mem实例
-    pub async fn build(self) -> Result<CortexMem> {
+    pub async fn build(mut self) -> Result<CortexMem> {
         info!("Building Cortex Memory with incremental update support");
 
         // 1. 初始化文件系统
@@ -81,10 +103,41 @@ impl CortexMemBuilder {
         filesystem.initialize().await?;
         info!("Filesystem initialized at: {:?}", self.data_dir);
 
+        // 录制 / 回放：包在最内层，回放的调用同样计入用量
+        let fixtures = match self.fixtures_config.take() {
+            Some(cfg) => FixtureStore::from_config(&cfg, &self.data_dir)?,
+            None => None,
+        };
+        if let Some(store) = &fixtures {
+            self.llm_client = self.llm_client.take().map(|llm| store.wrap_llm(llm));
+        }
+
+        // 用量统计：LLM 客户端替换为计量包装，后续组件共享同一个 tracker
+        let usage = match self.usage_config.take() {
+            Some(cfg) if cfg.enabled => {
+                Some(Arc::new(UsageTracker::new(self.data_dir.join("usage"), &cfg)))
+            }
+            _ => None,
+        };
+        if let Some(tracker) = &usage {
+            self.llm_client = self.llm_client.take().map(|llm| {
+                Arc::new(MeteredLLMClient::new(llm, tracker.clone())) as Arc<dyn LLMClient>
+            });
+        }
+
         // 2. 初始化Embedding客户端（可选，使用全局限流器）
-        let embedding = if let Some(cfg) = self.embedding_config {
+        let embedding = if let Some(cfg) = self.embedding_config.take() {
             match EmbeddingClient::new_with_global_limiter(cfg).await {
-                Ok(client) => Some(Arc::new(client)),
+                Ok(client) => {
+                    let client = match &fixtures {
+                        Some(store) => client.with_fixtures(store.clone()),
+                        None => client,
+                    };
+                    Some(Arc::new(match &usage {
+                        Some(tracker) => client.with_usage(tracker.clone()),
+                        None => client,
+                    }))
+                }
                 Err(e) => {
                     warn!("Failed to create embedding client: {}", e);
                     None
@@ -119,7 +172,7 @@ impl CortexMemBuilder {
         let event_bus = Arc::new(event_bus);
 
         // 5. 创建 MemoryEventCoordinator（如果配置了所有必需组件）
-        let (coordinator_handle, memory_event_tx) =
+        let (coordinator_handle, memory_event_tx, memory_event_observer, memory_updater) =
             if let (Some(llm), Some(emb), Some(qdrant_store)) =
                 (&self.llm_client, &embedding, &qdrant_store_typed)
             {
@@ -134,14 +187,17 @@ impl CortexMemBuilder {
                     config,
                 );
 
+                let observer = coordinator.observer();
+                let memory_updater = coordinator.memory_updater();
+
                 // 启动事件协调器
                 let handle = tokio::spawn(coordinator.start(rx));
                 info!("✅ MemoryEventCoordinator started for incremental updates");
 
-                (Some(handle), Some(tx))
+                (Some(handle), Some(tx), Some(observer), Some(memory_updater))
             } else {
                 warn!("MemoryEventCoordinator disabled: missing LLM, embedding, or vector store");
-                (None, None)
+                (None, None, None, None)
             };
 
         // 6. 创建SessionManager（带 memory_event_tx）
@@ -237,9 +293,12 @@ impl CortexMemBuilder {
             event_bus,
             qdrant_store_typed,
             memory_event_tx,
+            memory_event_observer,
+            memory_updater,
             coordinator_handle,
             automation_handle,
             automation_tx_handle,
+            usage,
         })
     }
 }
@@ -257,12 +316,18 @@ pub struct CortexMem {
     qdrant_store_typed: Option<Arc<QdrantVectorStore>>,
     /// Memory event sender (for VectorSearchEngine / AutomationManager wiring)
     memory_event_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::memory_events::MemoryEvent>>,
+    /// MemoryEventCoordinator 处理过的事件的广播端（用于事件订阅）
+    memory_event_observer: Option<tokio::sync::broadcast::Sender<crate::memory_events::MemoryEvent>>,
+    /// MemoryEventCoordinator 的记忆更新器（用于显式 remember / update / forget）
+    memory_updater: Option<Arc<IncrementalMemoryUpdater>>,
     /// MemoryEventCoordinator 的后台任务句柄
     coordinator_handle: Option<tokio::task::JoinHandle<()>>,
     /// AutomationManager 的后台任务句柄
     automation_handle: Option<tokio::task::JoinHandle<()>>,
     /// AutomationManager 的 memory_event_tx 句柄（用于 tenant 切换时更新 coordinator sender）
     automation_tx_handle: Option<Arc<tokio::sync::RwLock<Option<tokio::sync::mpsc::UnboundedSender<crate::memory_events::MemoryEvent>>>>>,
+    /// Token 用量统计（未启用时为 None）
+    usage: Option<Arc<UsageTracker>>,
 }
 
 impl CortexMem {
@@ -291,6 +356,11 @@ impl CortexMem {
         self.llm_client.clone()
     }
 
+    /// 获取 Token 用量统计
+    pub fn usage(&self) -> Option<Arc<UsageTracker>> {
+        self.usage.clone()
+    }
+
     /// 获取具体类型的 Qdrant 存储（供需要 Arc<QdrantVectorStore> 的消费者使用）
     pub fn qdrant_store(&self) -> Option<Arc<QdrantVectorStore>> {
         self.qdrant_store_typed.clone()
@@ -303,6 +373,18 @@ impl CortexMem {
         self.memory_event_tx.clone()
     }
 
+    /// 订阅 MemoryEventCoordinator 处理的事件（协调器未启用时返回 None）
+    pub fn subscribe_memory_events(
+        &self,
+    ) -> Option<tokio::sync::broadcast::Receiver<crate::memory_events::MemoryEvent>> {
+        self.memory_event_observer.as_ref().map(|tx| tx.subscribe())
+    }
+
+    /// 获取 MemoryEventCoordinator 的记忆更新器（协调器未启用时返回 None）
+    pub fn memory_updater(&self) -> Option<Arc<IncrementalMemoryUpdater>> {
+        self.memory_updater.clone()
+    }
+
     /// 获取 AutomationManager 的 tx 句柄（用于 tenant 切换时替换 coordinator sender）
     pub fn automation_tx_handle(
         &self,
@@ -24,6 +24,7 @@
 
 use crate::cascade_layer_updater::CascadeLayerUpdater;
 use crate::memory_index::MemoryScope;
+use crate::metrics::METRICS;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -117,6 +118,7 @@ impl LayerUpdateDebouncer {
                     request_count: 1,
                 },
             );
+            METRICS.debouncer_backlog.inc();
             debug!("📝 Registered update request for {}", dir_uri);
             true
         }
@@ -156,6 +158,7 @@ impl LayerUpdateDebouncer {
         };
 
         let update_count = due_updates.len();
+        METRICS.debouncer_backlog.sub(update_count as i64);
 
         if update_count > 0 {
             info!(
@@ -205,6 +208,7 @@ impl LayerUpdateDebouncer {
         };
 
         let update_count = all_updates.len();
+        METRICS.debouncer_backlog.sub(update_count as i64);
 
         if update_count > 0 {
             info!(
@@ -239,7 +243,9 @@ impl LayerUpdateDebouncer {
     /// Clear all pending updates (useful for tests)
     #[cfg(test)]
     pub async fn clear(&self) {
-        self.pending.write().await.clear();
+        let mut pending = self.pending.write().await;
+        METRICS.debouncer_backlog.sub(pending.len() as i64);
+        pending.clear();
     }
 }
 
@@ -9,12 +9,13 @@
 /// - **Phase 3**: LLM Result Cache - Reuse results for same content (50-75% reduction)
 
 use crate::filesystem::{CortexFilesystem, FilesystemOperations};
+use crate::layers::extractive;
 use crate::layers::generator::{AbstractGenerator, OverviewGenerator};
-use crate::llm::LLMClient;
+use crate::llm::{LLMClient, PromptTemplates};
 use crate::llm_result_cache::{CacheConfig, LlmResultCache};
 use crate::memory_events::{ChangeType, MemoryEvent};
 use crate::memory_index::MemoryScope;
-use crate::{ContextLayer, Result};
+use crate::{ContextLayer, LayerMode, LlmTask, Result};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -77,6 +78,8 @@ pub struct CascadeLayerUpdater {
     stats: Arc<RwLock<UpdateStats>>,
     /// LLM result cache (Phase 3)
     llm_cache: Option<Arc<LlmResultCache>>,
+    /// LLM / extractive generation of the layers
+    mode: LayerMode,
 }
 
 impl CascadeLayerUpdater {
@@ -100,16 +103,40 @@ impl CascadeLayerUpdater {
             Arc::new(LlmResultCache::new(config))
         });
         
+        Self::with_shared_cache(filesystem, llm_client, event_tx, llm_cache)
+    }
+    
+    /// Create a new cascade layer updater using a cache shared with other components
+    pub fn with_shared_cache(
+        filesystem: Arc<CortexFilesystem>,
+        llm_client: Arc<dyn LLMClient>,
+        event_tx: mpsc::UnboundedSender<MemoryEvent>,
+        llm_cache: Option<Arc<LlmResultCache>>,
+    ) -> Self {
+        let templates = PromptTemplates::for_filesystem(&filesystem);
         Self {
             filesystem,
             llm_client,
-            l0_generator: AbstractGenerator::new(),
-            l1_generator: OverviewGenerator::new(),
+            l0_generator: AbstractGenerator::with_templates(templates.clone()),
+            l1_generator: OverviewGenerator::with_templates(templates),
             event_tx,
             stats: Arc::new(RwLock::new(UpdateStats::default())),
             llm_cache,
+            mode: LayerMode::default(),
         }
     }
+
+    /// Generate with the LLM, extractively, or LLM with extractive fallback
+    ///
+    /// Outside `Extractive` mode, layers previously written by the extractive
+    /// summarizer are regenerated on their next update even when the source
+    /// is unchanged.
+    pub fn with_mode(mut self, mode: LayerMode) -> Self {
+        self.l0_generator = self.l0_generator.with_mode(mode);
+        self.l1_generator = self.l1_generator.with_mode(mode);
+        self.mode = mode;
+        self
+    }
     
     /// Get current update statistics
     pub async fn get_stats(&self) -> UpdateStats {
@@ -122,8 +149,8 @@ impl CascadeLayerUpdater {
         *stats = UpdateStats::default();
     }
     
-    /// Calculate content hash for change detection
-    fn calculate_content_hash(&self, content: &str) -> String {
+    /// Calculate content hash for change detection (the hash stored in layer footers)
+    pub fn calculate_content_hash(&self, content: &str) -> String {
         let mut hasher = DefaultHasher::new();
         content.hash(&mut hasher);
         format!("{:x}", hasher.finish())
@@ -133,23 +160,70 @@ impl CascadeLayerUpdater {
     /// content aggregation used for hash comparison and parent-level summaries.
     ///
     /// Stripped lines:
-    /// - `<!-- source-hash: ... -->` — source hash footer
-    /// - `**Added**: ...`            — timestamp footer
+    /// - `<!-- source-hash: ... -->`       — source hash footer
+    /// - `**Added**: ...`                  — timestamp footer
+    /// - `<!-- generator: extractive -->`  — extractive layer marker
     fn strip_metadata_lines(content: &str) -> String {
         content
             .lines()
             .filter(|line| {
-                !line.starts_with("<!-- source-hash:") && !line.starts_with("**Added**:")
+                !line.starts_with("<!-- source-hash:")
+                    && !line.starts_with("**Added**:")
+                    && line.trim() != extractive::EXTRACTIVE_MARKER
             })
             .collect::<Vec<_>>()
             .join("\n")
     }
     
+    /// Source hash recorded in a layer file footer (`<!-- source-hash: {hex} -->`)
+    pub fn stored_source_hash(layer_content: &str) -> Option<&str> {
+        layer_content.lines().find_map(|line| {
+            line.strip_prefix("<!-- source-hash: ")
+                .and_then(|rest| rest.strip_suffix(" -->"))
+        })
+    }
+
+    /// Source content this updater feeds to the LLM for a directory
+    ///
+    /// Mirrors the aggregation used when the layers were written: child
+    /// abstracts for scope roots, all messages for a session timeline, the
+    /// whole month for timeline month directories and the direct files
+    /// everywhere else.
+    pub async fn source_content(&self, dir_uri: &str) -> Result<String> {
+        let (parent, name) = dir_uri.rsplit_once('/').unwrap_or(("", dir_uri));
+
+        if Self::is_scope_root(dir_uri) {
+            self.aggregate_child_abstracts(dir_uri).await
+        } else if dir_uri.starts_with("cortex://session/") && name == "timeline" {
+            self.aggregate_timeline_content(dir_uri).await
+        } else if parent.ends_with("/timeline") && name.len() == 7 && name.contains('-') {
+            self.aggregate_directory_content_recursive(dir_uri).await
+        } else {
+            self.aggregate_directory_content(dir_uri).await
+        }
+    }
+
+    /// Whether a URI is the root of a scope (`cortex://user/{id}`, `cortex://resources`, ...)
+    fn is_scope_root(uri: &str) -> bool {
+        let Some(path) = uri.strip_prefix("cortex://") else {
+            return false;
+        };
+        match path.split_once('/') {
+            None => path == "resources",
+            Some((scope, id)) => {
+                matches!(scope, "user" | "agent" | "session" | "spaces")
+                    && !id.is_empty()
+                    && !id.contains('/')
+            }
+        }
+    }
+
     /// Check if layer should be updated based on content hash
     /// 
     /// Returns true if:
     /// - Layer file doesn't exist
     /// - Source content hash (stored in the file footer) has changed
+    /// - The layer is extractive and an LLM may be used now
     ///
     /// The hash stored in the layer file footer uses the format:
     ///   `<!-- source-hash: {hex} -->`
@@ -158,16 +232,17 @@ impl CascadeLayerUpdater {
     async fn should_update_layer(&self, layer_uri: &str, new_content_hash: &str) -> Result<bool> {
         match self.filesystem.read(layer_uri).await {
             Ok(existing_content) => {
+                if self.mode != LayerMode::Extractive
+                    && self.has_extractive_layers(layer_uri, &existing_content).await
+                {
+                    return Ok(true);
+                }
                 // Look for stored source-hash comment in the file
-                for line in existing_content.lines() {
-                    if let Some(rest) = line.strip_prefix("<!-- source-hash: ") {
-                        if let Some(stored_hash) = rest.strip_suffix(" -->") {
-                            return Ok(stored_hash != new_content_hash);
-                        }
-                    }
+                match Self::stored_source_hash(&existing_content) {
+                    Some(stored_hash) => Ok(stored_hash != new_content_hash),
+                    // No hash found in old file (legacy format) → regenerate
+                    None => Ok(true),
                 }
-                // No hash found in old file (legacy format) → regenerate
-                Ok(true)
             }
             Err(_) => {
                 // File doesn't exist, need to create
@@ -176,6 +251,18 @@ impl CascadeLayerUpdater {
         }
     }
 
+    /// Whether the layer or its sibling overview was written by the extractive summarizer
+    async fn has_extractive_layers(&self, layer_uri: &str, layer_content: &str) -> bool {
+        if extractive::is_extractive(layer_content) {
+            return true;
+        }
+        let overview_uri = layer_uri.replace(".abstract.md", ".overview.md");
+        match self.filesystem.read(&overview_uri).await {
+            Ok(overview) if overview_uri != layer_uri => extractive::is_extractive(&overview),
+            _ => false,
+        }
+    }
+
     /// Handle a memory change event
     ///
     /// This is the main entry point for handling memory changes.
@@ -243,8 +330,18 @@ impl CascadeLayerUpdater {
         
         // 🔧 Phase 3: Try cache first
         let (abstract_text, overview) = if let Some(ref cache) = self.llm_cache {
-            let cache_key_l0 = format!("{}:L0", new_content_hash);
-            let cache_key_l1 = format!("{}:L1", new_content_hash);
+            let cache_key_l0 = LlmResultCache::key(
+                "l0",
+                &self.l0_generator.template_version(),
+                self.llm_client.model_for(LlmTask::Abstract),
+                &content,
+            );
+            let cache_key_l1 = LlmResultCache::key(
+                "l1",
+                &self.l1_generator.template_version(),
+                self.llm_client.model_for(LlmTask::Overview),
+                &content,
+            );
             
             let cached_l0 = cache.get(&cache_key_l0).await;
             let cached_l1 = cache.get(&cache_key_l1).await;
@@ -270,8 +367,13 @@ impl CascadeLayerUpdater {
                         .await?;
                     
                     // Cache the results
-                    cache.put(cache_key_l0, l0.clone()).await;
-                    cache.put(cache_key_l1, l1.clone()).await;
+                    // Extractive fallbacks are not cached, so the LLM gets another try
+                    if !extractive::is_extractive(&l0) {
+                        cache.put(cache_key_l0, l0.clone()).await;
+                    }
+                    if !extractive::is_extractive(&l1) {
+                        cache.put(cache_key_l1, l1.clone()).await;
+                    }
                     
                     let mut stats = self.stats.write().await;
                     stats.cache_misses += 2;
@@ -404,8 +506,18 @@ impl CascadeLayerUpdater {
         
         // 🔧 Phase 3: Try cache first
         let (abstract_text, overview) = if let Some(ref cache) = self.llm_cache {
-            let cache_key_l0 = format!("{}:L0:root", new_content_hash);
-            let cache_key_l1 = format!("{}:L1:root", new_content_hash);
+            let cache_key_l0 = LlmResultCache::key(
+                "l0_root",
+                &self.l0_generator.template_version(),
+                self.llm_client.model_for(LlmTask::Abstract),
+                &aggregated,
+            );
+            let cache_key_l1 = LlmResultCache::key(
+                "l1_root",
+                &self.l1_generator.template_version(),
+                self.llm_client.model_for(LlmTask::Overview),
+                &aggregated,
+            );
             
             let cached_l0 = cache.get(&cache_key_l0).await;
             let cached_l1 = cache.get(&cache_key_l1).await;
@@ -428,8 +540,13 @@ impl CascadeLayerUpdater {
                         .generate_with_llm(&aggregated, &self.llm_client)
                         .await?;
                     
-                    cache.put(cache_key_l0, l0.clone()).await;
-                    cache.put(cache_key_l1, l1.clone()).await;
+                    // Extractive fallbacks are not cached, so the LLM gets another try
+                    if !extractive::is_extractive(&l0) {
+                        cache.put(cache_key_l0, l0.clone()).await;
+                    }
+                    if !extractive::is_extractive(&l1) {
+                        cache.put(cache_key_l1, l1.clone()).await;
+                    }
                     
                     let mut stats = self.stats.write().await;
                     stats.cache_misses += 2;
@@ -582,6 +699,7 @@ impl CascadeLayerUpdater {
             MemoryScope::Agent => format!("cortex://agent/{}", owner_id),
             MemoryScope::Session => format!("cortex://session/{}", owner_id),
             MemoryScope::Resources => "cortex://resources".to_string(),
+            MemoryScope::Space => format!("cortex://spaces/{}", owner_id),
         }
     }
 
@@ -919,6 +1037,24 @@ mod tests {
         assert_eq!(updater.get_scope_root(&MemoryScope::Agent, "agent_001"), "cortex://agent/agent_001");
         assert_eq!(updater.get_scope_root(&MemoryScope::Session, "session_001"), "cortex://session/session_001");
         assert_eq!(updater.get_scope_root(&MemoryScope::Resources, ""), "cortex://resources");
+        assert_eq!(updater.get_scope_root(&MemoryScope::Space, "apollo"), "cortex://spaces/apollo");
+    }
+
+    #[test]
+    fn test_is_scope_root() {
+        assert!(CascadeLayerUpdater::is_scope_root("cortex://user/user_001"));
+        assert!(CascadeLayerUpdater::is_scope_root("cortex://resources"));
+        assert!(CascadeLayerUpdater::is_scope_root("cortex://spaces/apollo"));
+        assert!(!CascadeLayerUpdater::is_scope_root("cortex://user/user_001/preferences"));
+        assert!(!CascadeLayerUpdater::is_scope_root("cortex://resources/docs"));
+        assert!(!CascadeLayerUpdater::is_scope_root("cortex://user"));
+    }
+
+    #[test]
+    fn test_stored_source_hash() {
+        let layer = "Summary text\n\n**Added**: 2026-01-01 00:00:00 UTC\n<!-- source-hash: 1a2b3c -->";
+        assert_eq!(CascadeLayerUpdater::stored_source_hash(layer), Some("1a2b3c"));
+        assert_eq!(CascadeLayerUpdater::stored_source_hash("Summary text"), None);
     }
 
     #[test]
@@ -0,0 +1,235 @@
+//! File-backed key/value store shared by the persistent caches
+//!
+//! One `{key}.{ext}` file per entry. The modification time records the last
+//! access, so eviction removes the least recently used files once the store
+//! exceeds its size limit. Writes go through a temporary file and a rename,
+//! so processes sharing the directory never read a partial entry.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// Distinguishes the temporary files of concurrent writers in one process
+static TMP_SEQ: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) struct DiskStore {
+    dir: PathBuf,
+    ext: &'static str,
+    max_bytes: u64,
+    /// Approximate size; recomputed on every eviction since other processes
+    /// may share the directory
+    bytes: AtomicU64,
+}
+
+/// A file of the store
+pub(crate) struct DiskFile {
+    pub path: PathBuf,
+    pub key: String,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl DiskStore {
+    pub fn open(dir: PathBuf, ext: &'static str, max_bytes: u64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let bytes = Self::scan(&dir, ext)?.iter().map(|f| f.size).sum();
+        Ok(Self {
+            dir,
+            ext,
+            max_bytes,
+            bytes: AtomicU64::new(bytes),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Approximate size of the store
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Path of a key; keys that are not safe file names are not stored
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        let safe = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        safe.then(|| self.dir.join(format!("{}.{}", key, self.ext)))
+    }
+
+    pub async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.path(key)?;
+        let content = tokio::fs::read(&path).await.ok()?;
+
+        // Mark as recently used (best effort)
+        let _ = tokio::task::spawn_blocking(move || {
+            std::fs::File::options()
+                .append(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(SystemTime::now()))
+        })
+        .await;
+
+        Some(content)
+    }
+
+    pub async fn put(&self, key: &str, content: &[u8]) -> std::io::Result<()> {
+        let Some(path) = self.path(key) else {
+            return Ok(());
+        };
+
+        // Unique per writer: concurrent puts of one key must not share a file
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TMP_SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Err(e) = tokio::fs::write(&tmp, content).await {
+            // The directory was removed by another process (e.g. `cortex-mem cache clear`)
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(e);
+            }
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&tmp, content).await?;
+        }
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e);
+        }
+
+        let size = content.len() as u64;
+        if self.bytes.fetch_add(size, Ordering::Relaxed) + size > self.max_bytes {
+            self.evict().await?;
+        }
+        Ok(())
+    }
+
+    /// Remove least recently used files until the store is at 90% of its limit
+    async fn evict(&self) -> std::io::Result<()> {
+        let dir = self.dir.clone();
+        let ext = self.ext;
+        let target = self.max_bytes / 10 * 9;
+
+        let (removed, remaining) = tokio::task::spawn_blocking(move || {
+            let mut files = Self::scan(&dir, ext)?;
+            files.sort_by_key(|f| f.modified);
+
+            let mut total: u64 = files.iter().map(|f| f.size).sum();
+            let mut removed = 0;
+            for file in files {
+                if total <= target {
+                    break;
+                }
+                if std::fs::remove_file(&file.path).is_ok() {
+                    total -= file.size;
+                    removed += 1;
+                }
+            }
+            Ok::<_, std::io::Error>((removed, total))
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        self.bytes.store(remaining, Ordering::Relaxed);
+        debug!(
+            "🗑️  Evicted {} entries from {} ({} bytes left)",
+            removed,
+            self.dir.display(),
+            remaining
+        );
+        Ok(())
+    }
+
+    /// Remove every entry
+    pub async fn clear(&self) -> std::io::Result<()> {
+        let dir = self.dir.clone();
+        let ext = self.ext;
+        tokio::task::spawn_blocking(move || {
+            for file in Self::scan(&dir, ext)? {
+                let _ = std::fs::remove_file(&file.path);
+            }
+            Ok::<_, std::io::Error>(())
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        self.bytes.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// All entries of the store
+    pub async fn files(&self) -> std::io::Result<Vec<DiskFile>> {
+        let dir = self.dir.clone();
+        let ext = self.ext;
+        tokio::task::spawn_blocking(move || Self::scan(&dir, ext))
+            .await
+            .map_err(std::io::Error::other)?
+    }
+
+    fn scan(dir: &Path, ext: &str) -> std::io::Result<Vec<DiskFile>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let Some(key) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(ext))
+                .and_then(|name| name.strip_suffix('.'))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            // Removed concurrently by another process
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            files.push(DiskFile {
+                path,
+                key,
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_concurrent_puts_of_one_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(DiskStore::open(dir.path().to_path_buf(), "bin", u64::MAX).unwrap());
+
+        let writers: Vec<_> = (0..16u8)
+            .map(|i| {
+                let store = store.clone();
+                tokio::spawn(async move { store.put("key", &[i; 4096]).await })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap().unwrap();
+        }
+
+        // One complete entry wins, no temporary file is left behind
+        let content = store.get("key").await.unwrap();
+        assert_eq!(content.len(), 4096);
+        assert!(content.iter().all(|&b| b == content[0]));
+        let names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(names, vec!["key.bin"]);
+    }
+}
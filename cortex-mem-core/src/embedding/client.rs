@@ -1,6 +1,13 @@
+use super::persistent::EmbeddingDiskCache;
 use crate::Result;
+use crate::fixtures::FixtureStore;
+use crate::llm::providers::AZURE_DEFAULT_API_VERSION;
+use crate::metrics::METRICS;
+use crate::usage::{TokenUsage, UsageOperation, UsageTracker};
+use cortex_mem_config::{EmbeddingCacheConfig, Provider};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -217,6 +224,9 @@ impl InnerCache {
 /// Embedding 客户端配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
+    /// API 类型（Anthropic 不提供 embedding 接口）
+    #[serde(default)]
+    pub provider: Provider,
     pub api_base_url: String,
     pub api_key: String,
     pub model_name: String,
@@ -230,11 +240,28 @@ pub struct EmbeddingConfig {
     pub cache_max_entries: usize,
     /// 内存缓存 TTL（秒，默认 3600）
     pub cache_ttl_secs: u64,
+    /// 持久化缓存根目录（默认 None，仅内存缓存）；向量按模型存放在子目录中
+    #[serde(default)]
+    pub persist_dir: Option<PathBuf>,
+    /// 每个模型的持久化缓存大小上限（字节，默认 1 GiB）
+    #[serde(default = "default_persist_max_bytes")]
+    pub persist_max_bytes: u64,
+    /// Azure OpenAI 的 `api-version`（默认 [`AZURE_DEFAULT_API_VERSION`]）
+    #[serde(default)]
+    pub api_version: Option<String>,
+    /// 本地模型目录；设置后在进程内计算向量，不调用 API（需要 `local-embedding` feature）
+    #[serde(default)]
+    pub local_model_dir: Option<PathBuf>,
+}
+
+fn default_persist_max_bytes() -> u64 {
+    1024 * 1024 * 1024
 }
 
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
+            provider: Provider::default(),
             api_base_url: std::env::var("EMBEDDING_API_BASE_URL")
                 .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
             api_key: std::env::var("EMBEDDING_API_KEY")
@@ -247,21 +274,54 @@ impl Default for EmbeddingConfig {
             calls_per_minute: 30,
             cache_max_entries: 10_000,
             cache_ttl_secs: 3_600,
+            persist_dir: None,
+            persist_max_bytes: default_persist_max_bytes(),
+            api_version: None,
+            local_model_dir: None,
         }
     }
 }
 
+impl EmbeddingConfig {
+    /// 按 `[embedding_cache]` 配置启用持久化缓存；相对路径以数据目录为基准
+    pub fn with_persistent_cache(
+        mut self,
+        config: &EmbeddingCacheConfig,
+        data_dir: impl AsRef<Path>,
+    ) -> Self {
+        self.persist_dir = config.persistent.then(|| {
+            let dir = PathBuf::from(config.dir.as_deref().unwrap_or("cache/embeddings"));
+            if dir.is_relative() {
+                data_dir.as_ref().join(dir)
+            } else {
+                dir
+            }
+        });
+        self.persist_max_bytes = config.max_disk_mb * 1024 * 1024;
+        self
+    }
+}
+
 // ── EmbeddingClient ───────────────────────────────────────────────────────────
 
 /// Embedding 客户端
 ///
 /// 内置速率限制器（30 次/分钟单并发）和 LRU 内存缓存，
+/// 配置 `persist_dir` 时内存未命中会再查询持久化缓存，
+/// 配置 `local_model_dir` 时向量由进程内模型计算（不经过速率限制），
+/// 设置 `with_usage` 后每次 API / 本地计算都会记录 token 用量，
+/// 设置 `with_fixtures` 后向量按 fixture 录制或回放，
 /// 对外 API 与原版保持一致。
 pub struct EmbeddingClient {
     config: EmbeddingConfig,
     client: reqwest::Client,
     rate_limiter: Arc<RateLimiter>,
     cache: Arc<RwLock<InnerCache>>,
+    disk: Option<EmbeddingDiskCache>,
+    usage: Option<Arc<UsageTracker>>,
+    fixtures: Option<Arc<FixtureStore>>,
+    #[cfg(feature = "local-embedding")]
+    local: Option<Arc<super::local::LocalEmbedder>>,
 }
 
 impl EmbeddingClient {
@@ -270,6 +330,9 @@ impl EmbeddingClient {
     /// 注意：所有使用相同 API endpoint 和 API key 的 EmbeddingClient 实例
     /// 会共享同一个全局 RateLimiter，确保跨实例的速率控制正确性。
     pub fn new(config: EmbeddingConfig) -> Result<Self> {
+        if let Some(reason) = Self::unsupported_provider(&config) {
+            return Err(crate::Error::Embedding(reason.to_string()));
+        }
         let calls_per_minute = config.calls_per_minute;
         let cache = Arc::new(RwLock::new(InnerCache::new(
             config.cache_max_entries,
@@ -289,6 +352,11 @@ impl EmbeddingClient {
         );
 
         Ok(Self {
+            #[cfg(feature = "local-embedding")]
+            local: Self::load_local_model(&config)?,
+            disk: Self::open_disk_cache(&config),
+            usage: None,
+            fixtures: None,
             config,
             client,
             rate_limiter: Arc::new(RateLimiter::new(calls_per_minute)),
@@ -300,6 +368,9 @@ impl EmbeddingClient {
     /// 
     /// 推荐使用此方法创建 EmbeddingClient，确保跨实例的速率控制正确性。
     pub async fn new_with_global_limiter(config: EmbeddingConfig) -> Result<Self> {
+        if let Some(reason) = Self::unsupported_provider(&config) {
+            return Err(crate::Error::Embedding(reason.to_string()));
+        }
         let rate_limiter = get_or_create_global_rate_limiter(
             &config.api_base_url,
             &config.api_key,
@@ -323,6 +394,11 @@ impl EmbeddingClient {
         );
 
         Ok(Self {
+            #[cfg(feature = "local-embedding")]
+            local: Self::load_local_model(&config)?,
+            disk: Self::open_disk_cache(&config),
+            usage: None,
+            fixtures: None,
             config,
             client,
             rate_limiter,
@@ -330,6 +406,85 @@ impl EmbeddingClient {
         })
     }
 
+    /// 记录 token 用量（缓存命中不计）
+    pub fn with_usage(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage = Some(tracker);
+        self
+    }
+
+    /// 录制（或回放）向量到 fixture 目录，用于离线测试
+    pub fn with_fixtures(mut self, fixtures: Arc<FixtureStore>) -> Self {
+        self.fixtures = Some(fixtures);
+        self
+    }
+
+    /// 记录一次 API / 本地计算的用量；服务未返回 token 数时按文本长度估算
+    fn record_usage(&self, texts: &[String], prompt_tokens: Option<u64>) {
+        if let Some(tracker) = &self.usage {
+            let usage = match prompt_tokens {
+                Some(tokens) => TokenUsage::new(tokens, 0),
+                None => TokenUsage::estimate(texts.iter().map(String::len).sum(), 0),
+            };
+            tracker.record(UsageOperation::Embedding, &self.config.model_name, usage);
+        }
+    }
+
+    /// Why the configured backend cannot embed, if it cannot
+    fn unsupported_provider(config: &EmbeddingConfig) -> Option<&'static str> {
+        if config.local_model_dir.is_some() {
+            if cfg!(feature = "local-embedding") {
+                return None;
+            }
+            return Some(
+                "local_model_dir is set but cortex-mem was built without the `local-embedding` feature",
+            );
+        }
+        if config.provider == Provider::Anthropic {
+            return Some(
+                "Anthropic does not offer an embedding API; use an openai, azure or ollama endpoint",
+            );
+        }
+        None
+    }
+
+    /// 加载本地模型（未配置 `local_model_dir` 时为 None）
+    #[cfg(feature = "local-embedding")]
+    fn load_local_model(
+        config: &EmbeddingConfig,
+    ) -> Result<Option<Arc<super::local::LocalEmbedder>>> {
+        config
+            .local_model_dir
+            .as_ref()
+            .map(|dir| super::local::LocalEmbedder::load(dir).map(Arc::new))
+            .transpose()
+    }
+
+    /// 打开当前模型的持久化缓存（失败时退回仅内存缓存）
+    fn open_disk_cache(config: &EmbeddingConfig) -> Option<EmbeddingDiskCache> {
+        let root = config.persist_dir.as_ref()?;
+        match EmbeddingDiskCache::open(root, &config.model_name, config.persist_max_bytes) {
+            Ok(disk) => Some(disk),
+            Err(e) => {
+                warn!(
+                    "Failed to open persistent embedding cache at {}, using memory only: {}",
+                    root.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// 查询持久化缓存，命中时回填内存缓存
+    async fn disk_get(&self, cache_key: &str, text: &str) -> Option<Vec<f32>> {
+        let embedding = self.disk.as_ref()?.get(text).await?;
+        self.cache
+            .write()
+            .await
+            .put(cache_key.to_string(), embedding.clone());
+        Some(embedding)
+    }
+
     /// 嵌入单个文本（带缓存）
     pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
         let cache_key = InnerCache::compute_key(&self.config.model_name, text);
@@ -339,9 +494,16 @@ impl EmbeddingClient {
             let cache = self.cache.read().await;
             if let Some(cached) = cache.peek(&cache_key) {
                 debug!("Cache hit for text (len={})", text.chars().count());
+                METRICS.embedding_cache_lookups.with_label_values(&["hit"]).inc();
                 return Ok(cached);
             }
         }
+        if let Some(cached) = self.disk_get(&cache_key, text).await {
+            debug!("Persistent cache hit for text (len={})", text.chars().count());
+            METRICS.embedding_cache_lookups.with_label_values(&["hit"]).inc();
+            return Ok(cached);
+        }
+        METRICS.embedding_cache_lookups.with_label_values(&["miss"]).inc();
 
         // 缓存未命中，调用 API
         // 注意：embed_batch_raw 内部已处理速率控制，不再阻塞其他请求
@@ -356,6 +518,9 @@ impl EmbeddingClient {
             let mut cache = self.cache.write().await;
             cache.put(cache_key, embedding.clone());
         }
+        if let Some(disk) = &self.disk {
+            disk.put(text, &embedding).await;
+        }
 
         Ok(embedding)
     }
@@ -384,6 +549,29 @@ impl EmbeddingClient {
             }
         }
 
+        // 内存未命中的再查询持久化缓存
+        if self.disk.is_some() {
+            let mut still_missing = (Vec::new(), Vec::new());
+            for (text, idx) in miss_texts.into_iter().zip(miss_indices) {
+                let key = InnerCache::compute_key(&self.config.model_name, &text);
+                match self.disk_get(&key, &text).await {
+                    Some(cached) => results[idx] = Some(cached),
+                    None => {
+                        still_missing.0.push(text);
+                        still_missing.1.push(idx);
+                    }
+                }
+            }
+            (miss_texts, miss_indices) = still_missing;
+        }
+
+        let hits = (texts.len() - miss_texts.len()) as u64;
+        METRICS.embedding_cache_lookups.with_label_values(&["hit"]).inc_by(hits);
+        METRICS
+            .embedding_cache_lookups
+            .with_label_values(&["miss"])
+            .inc_by(miss_texts.len() as u64);
+
         if miss_texts.is_empty() {
             debug!("All {} embeddings served from cache", texts.len());
             return Ok(results.into_iter().map(|opt| opt.unwrap()).collect());
@@ -415,6 +603,11 @@ impl EmbeddingClient {
                 results[result_idx] = Some(embedding.clone());
             }
         }
+        if let Some(disk) = &self.disk {
+            for (text, embedding) in miss_texts.iter().zip(api_results.iter()) {
+                disk.put(text, embedding).await;
+            }
+        }
 
         Ok(results.into_iter().map(|opt| opt.unwrap()).collect())
     }
@@ -432,12 +625,83 @@ impl EmbeddingClient {
 
     // ── 私有方法 ──────────────────────────────────────────────────────────────
 
-    /// 实际调用 Embedding API 的原始方法（含速率控制，不经过缓存）
+    /// 按 provider 构造请求 URL 和请求体
+    fn embedding_request(&self, texts: &[String]) -> (String, serde_json::Value) {
+        let config = &self.config;
+        let base = config.api_base_url.trim_end_matches('/');
+        match config.provider {
+            // Azure 的模型由 deployment 决定，请求体中不带 model
+            Provider::Azure => (
+                format!(
+                    "{}/openai/deployments/{}/embeddings?api-version={}",
+                    base,
+                    config.model_name,
+                    config
+                        .api_version
+                        .as_deref()
+                        .unwrap_or(AZURE_DEFAULT_API_VERSION)
+                ),
+                serde_json::json!({ "input": texts }),
+            ),
+            Provider::Ollama => (
+                format!("{}/api/embed", base),
+                serde_json::json!({ "model": config.model_name, "input": texts }),
+            ),
+            Provider::OpenAi | Provider::Anthropic => (
+                format!("{}/embeddings", base),
+                serde_json::json!({ "model": config.model_name, "input": texts }),
+            ),
+        }
+    }
+
+    /// 用本地模型计算向量（在阻塞线程池中运行）
+    #[cfg(feature = "local-embedding")]
+    async fn embed_batch_local(
+        &self,
+        local: &Arc<super::local::LocalEmbedder>,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>> {
+        let local = local.clone();
+        let batch = texts.to_vec();
+
+        let timer = METRICS.embedding_duration.start_timer();
+        let result = tokio::task::spawn_blocking(move || local.embed_batch(&batch))
+            .await
+            .map_err(|e| crate::Error::Embedding(format!("Local embedding task failed: {}", e)))
+            .and_then(|r| r);
+        timer.observe_duration();
+
+        let status = if result.is_ok() { "ok" } else { "error" };
+        METRICS.embedding_requests.with_label_values(&[status]).inc();
+        if result.is_ok() {
+            METRICS.embedding_texts.inc_by(texts.len() as u64);
+            self.record_usage(texts, None);
+        }
+        result
+    }
+
+    /// 缓存未命中时的计算入口：回放模式只读 fixture，录制模式计算后保存
     async fn embed_batch_raw(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
-        #[derive(Serialize)]
-        struct EmbeddingRequest {
-            input: Vec<String>,
-            model: String,
+        let Some(fixtures) = &self.fixtures else {
+            return self.embed_batch_model(texts).await;
+        };
+        if fixtures.is_replay() {
+            return fixtures.embeddings(&self.config.model_name, texts).await;
+        }
+        let embeddings = self.embed_batch_model(texts).await?;
+        fixtures
+            .record_embeddings(&self.config.model_name, texts, &embeddings)
+            .await;
+        Ok(embeddings)
+    }
+
+    /// 实际调用 Embedding API 的原始方法（含速率控制，不经过缓存）
+    async fn embed_batch_model(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        #[cfg(feature = "local-embedding")]
+        {
+            if let Some(local) = &self.local {
+                return self.embed_batch_local(local, texts).await;
+            }
         }
 
         #[derive(Deserialize)]
@@ -445,41 +709,62 @@ impl EmbeddingClient {
             embedding: Vec<f32>,
         }
 
+        #[derive(Deserialize)]
+        struct EmbeddingUsage {
+            prompt_tokens: u64,
+        }
+
+        /// OpenAI / Azure 返回 `data` 和 `usage`，Ollama 返回 `embeddings` 和 `prompt_eval_count`
         #[derive(Deserialize)]
         struct EmbeddingResponse {
+            #[serde(default)]
             data: Vec<EmbeddingData>,
+            #[serde(default)]
+            embeddings: Vec<Vec<f32>>,
+            #[serde(default)]
+            usage: Option<EmbeddingUsage>,
+            #[serde(default)]
+            prompt_eval_count: Option<u64>,
         }
 
-        let request = EmbeddingRequest {
-            input: texts.to_vec(),
-            model: self.config.model_name.clone(),
-        };
-
-        let url = format!("{}/embeddings", self.config.api_base_url);
+        let (url, request) = self.embedding_request(texts);
 
         // 速率控制：等待令牌（保证单并发 + 最小间隔）
-        self.rate_limiter.acquire().await;
+        {
+            let _wait = METRICS.embedding_rate_limit_wait.start_timer();
+            self.rate_limiter.acquire().await;
+        }
 
-        let response = self
-            .client
-            .post(&url)
-            .header(
+        let timer = METRICS.embedding_duration.start_timer();
+        let builder = self.client.post(&url).json(&request);
+        let builder = match self.config.provider {
+            Provider::Azure => builder.header("api-key", &self.config.api_key),
+            // 本地 Ollama 通常不需要 key
+            Provider::Ollama if self.config.api_key.is_empty() => builder,
+            _ => builder.header(
                 "Authorization",
                 format!("Bearer {}", self.config.api_key),
-            )
-            .header("Content-Type", "application/json")
-            .json(&request)
+            ),
+        };
+        let response = builder
             .send()
             .await
-            .map_err(|e| crate::Error::Embedding(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| {
+                METRICS.embedding_requests.with_label_values(&["error"]).inc();
+                crate::Error::Embedding(format!("HTTP request failed: {}", e))
+            })?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
+            timer.observe_duration();
 
             // 遇到 429 额外退避
             if status.as_u16() == 429 {
+                METRICS.embedding_requests.with_label_values(&["rate_limited"]).inc();
                 self.rate_limiter.backoff_on_rate_limit().await;
+            } else {
+                METRICS.embedding_requests.with_label_values(&["error"]).inc();
             }
 
             return Err(crate::Error::Embedding(format!(
@@ -492,9 +777,25 @@ impl EmbeddingClient {
             .json()
             .await
             .map_err(|e| {
+                METRICS.embedding_requests.with_label_values(&["error"]).inc();
                 crate::Error::Embedding(format!("Failed to parse response: {}", e))
             })?;
+        timer.observe_duration();
+
+        METRICS.embedding_requests.with_label_values(&["ok"]).inc();
+        METRICS.embedding_texts.inc_by(texts.len() as u64);
+        self.record_usage(
+            texts,
+            embedding_response
+                .usage
+                .as_ref()
+                .map(|usage| usage.prompt_tokens)
+                .or(embedding_response.prompt_eval_count),
+        );
 
+        if embedding_response.data.is_empty() {
+            return Ok(embedding_response.embeddings);
+        }
         Ok(embedding_response
             .data
             .into_iter()
@@ -502,3 +803,58 @@ impl EmbeddingClient {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(provider: Provider, api_base_url: &str) -> Result<EmbeddingClient> {
+        EmbeddingClient::new(EmbeddingConfig {
+            provider,
+            api_base_url: api_base_url.to_string(),
+            model_name: "embed".to_string(),
+            ..EmbeddingConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_embedding_request_per_provider() {
+        let texts = vec!["a".to_string(), "b".to_string()];
+
+        let (url, body) = client(Provider::OpenAi, "https://api.openai.com/v1/")
+            .unwrap()
+            .embedding_request(&texts);
+        assert_eq!(url, "https://api.openai.com/v1/embeddings");
+        assert_eq!(body["model"], "embed");
+
+        let (url, body) = client(Provider::Ollama, "http://localhost:11434")
+            .unwrap()
+            .embedding_request(&texts);
+        assert_eq!(url, "http://localhost:11434/api/embed");
+        assert_eq!(body["input"][1], "b");
+
+        let (url, body) = client(Provider::Azure, "https://res.openai.azure.com")
+            .unwrap()
+            .embedding_request(&texts);
+        assert_eq!(
+            url,
+            format!(
+                "https://res.openai.azure.com/openai/deployments/embed/embeddings?api-version={}",
+                AZURE_DEFAULT_API_VERSION
+            )
+        );
+        assert!(body.get("model").is_none());
+
+        assert!(client(Provider::Anthropic, "https://api.anthropic.com/v1").is_err());
+    }
+
+    #[cfg(not(feature = "local-embedding"))]
+    #[test]
+    fn test_local_model_requires_feature() {
+        let result = EmbeddingClient::new(EmbeddingConfig {
+            local_model_dir: Some(PathBuf::from("/models/bge-small-en-v1.5")),
+            ..EmbeddingConfig::default()
+        });
+        assert!(result.is_err());
+    }
+}
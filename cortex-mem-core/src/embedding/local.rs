@@ -0,0 +1,164 @@
+//! 进程内 Embedding 模型（`local-embedding` feature）
+//!
+//! 从本地目录加载 sentence-transformers 格式的 BERT 系模型（bge、MiniLM、e5 等），
+//! 在 CPU 上用 candle 推理，不经过网络和速率限制。目录需包含：
+//!
+//! - `config.json`：BERT 模型配置
+//! - `tokenizer.json`：tokenizers 格式的分词器
+//! - `model.safetensors`：模型权重
+//! - `1_Pooling/config.json`（可选）：池化方式，缺省为 mean pooling
+//!
+//! 输出向量做 L2 归一化，与 OpenAI 等服务返回的向量一致。
+
+use crate::{Error, Result};
+use candle_core::{DType, Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::bert::{BertModel, Config, DTYPE};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+use tracing::info;
+
+/// BERT 系模型的最大输入长度（token）
+const MAX_SEQUENCE_LENGTH: usize = 512;
+
+/// 池化方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pooling {
+    /// 取 `[CLS]` token 的向量（bge 系列）
+    Cls,
+    /// 按 attention mask 对所有 token 取平均（多数 sentence-transformers 模型）
+    Mean,
+}
+
+/// `1_Pooling/config.json` 中用到的字段
+#[derive(Debug, Default, Deserialize)]
+struct PoolingConfig {
+    #[serde(default)]
+    pooling_mode_cls_token: bool,
+}
+
+fn embedding_error(what: &str, path: &Path, e: impl std::fmt::Display) -> Error {
+    Error::Embedding(format!("Failed to {} {}: {}", what, path.display(), e))
+}
+
+/// 本地 Embedding 模型
+pub struct LocalEmbedder {
+    model: BertModel,
+    tokenizer: Tokenizer,
+    pooling: Pooling,
+    device: Device,
+    dir: PathBuf,
+}
+
+impl LocalEmbedder {
+    /// 从模型目录加载
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let device = Device::Cpu;
+
+        let config_path = dir.join("config.json");
+        let config = std::fs::read_to_string(&config_path)
+            .map_err(|e| embedding_error("read", &config_path, e))?;
+        let config: Config =
+            serde_json::from_str(&config).map_err(|e| embedding_error("parse", &config_path, e))?;
+
+        let tokenizer_path = dir.join("tokenizer.json");
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| embedding_error("load", &tokenizer_path, e))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_SEQUENCE_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(|e| embedding_error("configure", &tokenizer_path, e))?;
+
+        let weights_path = dir.join("model.safetensors");
+        // SAFETY: 权重文件在模型生命周期内不会被修改
+        let vb = unsafe { VarBuilder::from_mmaped_safetensors(&[&weights_path], DTYPE, &device) }
+            .map_err(|e| embedding_error("load", &weights_path, e))?;
+        let model =
+            BertModel::load(vb, &config).map_err(|e| embedding_error("load", &weights_path, e))?;
+
+        let pooling = match std::fs::read_to_string(dir.join("1_Pooling").join("config.json")) {
+            Ok(content) => {
+                let config: PoolingConfig = serde_json::from_str(&content).unwrap_or_default();
+                if config.pooling_mode_cls_token {
+                    Pooling::Cls
+                } else {
+                    Pooling::Mean
+                }
+            }
+            Err(_) => Pooling::Mean,
+        };
+
+        info!(
+            "Local embedding model loaded from {} (pooling: {:?})",
+            dir.display(),
+            pooling
+        );
+
+        Ok(Self {
+            model,
+            tokenizer,
+            pooling,
+            device,
+            dir,
+        })
+    }
+
+    /// 模型目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 计算一批文本的向量（CPU 密集，异步上下文中应放在 `spawn_blocking` 里调用）
+    pub fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+        self.forward(texts)
+            .map_err(|e| Error::Embedding(format!("Local embedding failed: {}", e)))
+    }
+
+    fn forward(
+        &self,
+        texts: &[String],
+    ) -> std::result::Result<Vec<Vec<f32>>, Box<dyn std::error::Error + Send + Sync>> {
+        let encodings = self.tokenizer.encode_batch(texts.to_vec(), true)?;
+
+        let ids = encodings
+            .iter()
+            .map(|e| Tensor::new(e.get_ids(), &self.device))
+            .collect::<candle_core::Result<Vec<_>>>()?;
+        let masks = encodings
+            .iter()
+            .map(|e| Tensor::new(e.get_attention_mask(), &self.device))
+            .collect::<candle_core::Result<Vec<_>>>()?;
+        let input_ids = Tensor::stack(&ids, 0)?;
+        let attention_mask = Tensor::stack(&masks, 0)?;
+        let token_type_ids = input_ids.zeros_like()?;
+
+        // [batch, seq_len, hidden]
+        let hidden = self
+            .model
+            .forward(&input_ids, &token_type_ids, Some(&attention_mask))?;
+
+        let pooled = match self.pooling {
+            Pooling::Cls => hidden.narrow(1, 0, 1)?.squeeze(1)?,
+            Pooling::Mean => {
+                let mask = attention_mask.to_dtype(DType::F32)?.unsqueeze(2)?;
+                let summed = hidden.broadcast_mul(&mask)?.sum(1)?;
+                summed.broadcast_div(&mask.sum(1)?)?
+            }
+        };
+        let norm = pooled.sqr()?.sum_keepdim(1)?.sqrt()?;
+        let normalized = pooled.broadcast_div(&norm)?;
+
+        Ok(normalized.to_vec2::<f32>()?)
+    }
+}
@@ -1,3 +1,9 @@
 mod client;
+#[cfg(feature = "local-embedding")]
+mod local;
+mod persistent;
 
 pub use client::{EmbeddingClient, EmbeddingConfig};
+#[cfg(feature = "local-embedding")]
+pub use local::LocalEmbedder;
+pub use persistent::{EmbeddingDiskCache, EmbeddingDiskStats};
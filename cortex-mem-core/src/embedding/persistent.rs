@@ -0,0 +1,234 @@
+//! 持久化 Embedding 缓存
+//!
+//! 每个模型一个子目录 `{root}/{model}-{hash}`，向量以 `{sha256(text)}.f32`
+//! （小端 f32）存放，`model.json` 记录模型名和向量维度。切换模型即切换目录，
+//! 旧模型的向量不会被复用；同一模型返回的维度变化时整个目录被清空。
+
+use crate::disk_store::DiskStore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{info, warn};
+
+const META_FILE: &str = "model.json";
+
+/// `model.json` 内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModelMeta {
+    model: String,
+    dimension: usize,
+}
+
+/// 单个模型的持久化缓存统计
+#[derive(Debug, Clone)]
+pub struct EmbeddingDiskStats {
+    pub dir: PathBuf,
+    /// 模型名（目录中缺少 `model.json` 时为 None）
+    pub model: Option<String>,
+    pub dimension: Option<usize>,
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+/// 单个模型的持久化 Embedding 缓存
+pub struct EmbeddingDiskCache {
+    store: DiskStore,
+    model: String,
+    /// 已记录的维度（0 = 尚未写入）
+    dimension: AtomicUsize,
+}
+
+impl EmbeddingDiskCache {
+    /// 打开 `root` 下属于 `model` 的缓存目录
+    pub fn open(root: &Path, model: &str, max_bytes: u64) -> std::io::Result<Self> {
+        let dir = root.join(Self::dir_name(model));
+        let store = DiskStore::open(dir, "f32", max_bytes)?;
+        let dimension = Self::read_meta(store.dir())
+            .filter(|meta| meta.model == model)
+            .map_or(0, |meta| meta.dimension);
+
+        info!(
+            "💽 Persistent embedding cache at {} (dimension: {}, {} bytes used)",
+            store.dir().display(),
+            dimension,
+            store.bytes()
+        );
+
+        Ok(Self {
+            store,
+            model: model.to_string(),
+            dimension: AtomicUsize::new(dimension),
+        })
+    }
+
+    /// 模型目录名：可读的模型名 + 短哈希（避免 `a/b` 与 `a_b` 冲突）
+    fn dir_name(model: &str) -> String {
+        let readable: String = model
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let digest = format!("{:x}", Sha256::digest(model.as_bytes()));
+        format!("{}-{}", readable, &digest[..8])
+    }
+
+    fn key(text: &str) -> String {
+        format!("{:x}", Sha256::digest(text.as_bytes()))
+    }
+
+    fn read_meta(dir: &Path) -> Option<ModelMeta> {
+        let content = std::fs::read_to_string(dir.join(META_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 查询缓存的向量
+    pub async fn get(&self, text: &str) -> Option<Vec<f32>> {
+        let dimension = self.dimension.load(Ordering::Relaxed);
+        if dimension == 0 {
+            return None;
+        }
+
+        let bytes = self.store.get(&Self::key(text)).await?;
+        if bytes.len() != dimension * 4 {
+            return None;
+        }
+        Some(
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        )
+    }
+
+    /// 写入向量；维度与已记录的不同时先清空该模型的缓存
+    pub async fn put(&self, text: &str, embedding: &[f32]) {
+        if embedding.is_empty() {
+            return;
+        }
+
+        let dimension = self.dimension.load(Ordering::Relaxed);
+        if dimension != embedding.len() {
+            if dimension != 0 {
+                warn!(
+                    "Embedding dimension of {} changed ({} -> {}), dropping persisted vectors",
+                    self.model,
+                    dimension,
+                    embedding.len()
+                );
+                if let Err(e) = self.store.clear().await {
+                    warn!("Failed to clear persistent embedding cache: {}", e);
+                }
+            }
+            let meta = ModelMeta {
+                model: self.model.clone(),
+                dimension: embedding.len(),
+            };
+            let path = self.store.dir().join(META_FILE);
+            let content = serde_json::to_string_pretty(&meta).unwrap_or_default();
+            if let Err(e) = tokio::fs::write(&path, content).await {
+                warn!("Failed to write {}: {}", path.display(), e);
+                return;
+            }
+            self.dimension.store(embedding.len(), Ordering::Relaxed);
+        }
+
+        let bytes: Vec<u8> = embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+        if let Err(e) = self.store.put(&Self::key(text), &bytes).await {
+            warn!("Failed to persist embedding: {}", e);
+        }
+    }
+
+    /// `root` 下所有模型的缓存
+    pub fn list(root: &Path) -> std::io::Result<Vec<EmbeddingDiskStats>> {
+        let mut stats = Vec::new();
+        if !root.exists() {
+            return Ok(stats);
+        }
+
+        for entry in std::fs::read_dir(root)? {
+            let dir = entry?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let meta = Self::read_meta(&dir);
+            let (mut entries, mut bytes) = (0, 0);
+            for file in std::fs::read_dir(&dir)?.flatten() {
+                if file.path().extension().is_some_and(|ext| ext == "f32") {
+                    entries += 1;
+                    bytes += file.metadata().map_or(0, |m| m.len());
+                }
+            }
+            stats.push(EmbeddingDiskStats {
+                dir,
+                model: meta.as_ref().map(|m| m.model.clone()),
+                dimension: meta.map(|m| m.dimension),
+                entries,
+                bytes,
+            });
+        }
+        stats.sort_by(|a, b| a.dir.cmp(&b.dir));
+        Ok(stats)
+    }
+
+    /// 删除 `root` 下所有模型的缓存，返回删除的向量数
+    pub fn clear_all(root: &Path) -> std::io::Result<usize> {
+        let mut removed = 0;
+        for stats in Self::list(root)? {
+            std::fs::remove_dir_all(&stats.dir)?;
+            removed += stats.entries;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_vectors_survive_reopen() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = EmbeddingDiskCache::open(root.path(), "BAAI/bge-m3", 1 << 20).unwrap();
+        assert_eq!(cache.get("hello").await, None);
+
+        cache.put("hello", &[0.5, -1.25, 3.0]).await;
+        drop(cache);
+
+        let cache = EmbeddingDiskCache::open(root.path(), "BAAI/bge-m3", 1 << 20).unwrap();
+        assert_eq!(cache.get("hello").await, Some(vec![0.5, -1.25, 3.0]));
+
+        // Another model never sees these vectors
+        let other =
+            EmbeddingDiskCache::open(root.path(), "text-embedding-3-small", 1 << 20).unwrap();
+        assert_eq!(other.get("hello").await, None);
+
+        let stats = EmbeddingDiskCache::list(root.path()).unwrap();
+        assert_eq!(stats.len(), 2);
+        let bge = stats
+            .iter()
+            .find(|s| s.model.as_deref() == Some("BAAI/bge-m3"))
+            .unwrap();
+        assert_eq!((bge.dimension, bge.entries, bge.bytes), (Some(3), 1, 12));
+    }
+
+    #[tokio::test]
+    async fn test_dimension_change_drops_vectors() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = EmbeddingDiskCache::open(root.path(), "model", 1 << 20).unwrap();
+        cache.put("a", &[1.0, 2.0]).await;
+        cache.put("b", &[1.0, 2.0, 3.0]).await;
+
+        assert_eq!(cache.get("a").await, None);
+        assert_eq!(cache.get("b").await, Some(vec![1.0, 2.0, 3.0]));
+        assert_eq!(EmbeddingDiskCache::list(root.path()).unwrap()[0].entries, 1);
+
+        assert_eq!(EmbeddingDiskCache::clear_all(root.path()).unwrap(), 1);
+        assert!(EmbeddingDiskCache::list(root.path()).unwrap().is_empty());
+    }
+}
@@ -18,6 +18,9 @@ pub enum Error {
     #[error("Memory not found: {uri}")]
     NotFound { uri: String },
     
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -33,6 +36,9 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     Config(String),
     
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
+    
     #[error("Vector store error: {0}")]
     VectorStore(#[from] qdrant_client::QdrantError),
     
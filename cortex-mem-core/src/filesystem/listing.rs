@@ -0,0 +1,222 @@
+use crate::types::FileEntry;
+use std::cmp::Ordering;
+
+/// Sort key for directory listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    /// Path order, compared component by component: a directory comes right
+    /// before its contents, as in a depth-first walk of name-sorted
+    /// directories (equals name order for non-recursive listings)
+    #[default]
+    Name,
+    /// Last modification time
+    Modified,
+    /// File size in bytes
+    Size,
+}
+
+impl ListSort {
+    /// Parse a sort key ("name", "modified"/"mtime", "size")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "name" | "path" | "uri" => Some(Self::Name),
+            "modified" | "mtime" | "time" => Some(Self::Modified),
+            "size" => Some(Self::Size),
+            _ => None,
+        }
+    }
+
+    fn compare(self, a: &FileEntry, b: &FileEntry) -> Ordering {
+        let primary = match self {
+            Self::Name => Ordering::Equal,
+            Self::Modified => a.modified.cmp(&b.modified),
+            Self::Size => a.size.cmp(&b.size),
+        };
+        primary.then_with(|| a.uri.split('/').cmp(b.uri.split('/')))
+    }
+}
+
+/// Filtering, sorting and pagination applied to a directory listing
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Glob (`*`, `?`) matched against entry names; a plain string matches as a substring
+    pub pattern: Option<String>,
+    pub sort: ListSort,
+    pub descending: bool,
+    /// Offset of the first entry, from a previous page's `next_cursor`
+    pub offset: usize,
+    /// Page size (`None` returns everything after `offset`)
+    pub limit: Option<usize>,
+}
+
+/// One page of a directory listing
+#[derive(Debug, Clone)]
+pub struct ListPage {
+    pub entries: Vec<FileEntry>,
+    /// Number of entries matching the filter, across all pages
+    pub total: usize,
+    /// Cursor for the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl ListOptions {
+    /// Decode a cursor returned as `ListPage::next_cursor`
+    pub fn decode_cursor(cursor: &str) -> Option<usize> {
+        cursor.trim().parse().ok()
+    }
+
+    /// Filter, sort and slice the entries
+    pub fn apply(&self, mut entries: Vec<FileEntry>) -> ListPage {
+        if let Some(pattern) = self.pattern.as_deref().filter(|p| !p.is_empty()) {
+            entries.retain(|e| name_matches(pattern, &e.name));
+        }
+
+        entries.sort_by(|a, b| {
+            let ord = self.sort.compare(a, b);
+            if self.descending { ord.reverse() } else { ord }
+        });
+
+        let total = entries.len();
+        let start = self.offset.min(total);
+        let end = match self.limit {
+            Some(limit) => start.saturating_add(limit).min(total),
+            None => total,
+        };
+        let next_cursor = (end < total).then(|| end.to_string());
+        let entries = entries.drain(start..end).collect();
+
+        ListPage {
+            entries,
+            total,
+            next_cursor,
+        }
+    }
+}
+
+/// Match a name against a glob or, when the pattern has no wildcard, a substring (case-insensitive)
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    if pattern.contains(['*', '?']) {
+        glob_match(&pattern, &name)
+    } else {
+        name.contains(&pattern)
+    }
+}
+
+/// Glob matching supporting `*` (any run) and `?` (any single character)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<usize> = None;
+    let mut star_t = 0;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_t = t;
+            p += 1;
+        } else if let Some(s) = star {
+            p = s + 1;
+            star_t += 1;
+            t = star_t;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(name: &str, size: u64, ts: i64) -> FileEntry {
+        FileEntry {
+            uri: format!("cortex://session/t/{}", name),
+            name: name.to_string(),
+            is_directory: false,
+            size,
+            modified: Utc.timestamp_opt(ts, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.md", "10_00_00_abc.md"));
+        assert!(glob_match("pref_????.md", "pref_1a2b.md"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("*.md", "notes.txt"));
+        assert!(!glob_match("pref_?.md", "pref_12.md"));
+        assert!(name_matches("PREF", "pref_1a2b.md"));
+        assert!(name_matches("*.MD", "notes.md"));
+    }
+
+    #[test]
+    fn test_sort_and_paginate() {
+        let entries = vec![
+            entry("b.md", 30, 200),
+            entry("a.md", 10, 300),
+            entry("c.txt", 20, 100),
+        ];
+
+        let options = ListOptions {
+            sort: ListSort::Modified,
+            descending: true,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let page = options.apply(entries.clone());
+        let names: Vec<_> = page.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a.md", "b.md"]);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_cursor.as_deref(), Some("2"));
+
+        let options = ListOptions {
+            offset: ListOptions::decode_cursor(page.next_cursor.as_deref().unwrap()).unwrap(),
+            ..options
+        };
+        let page = options.apply(entries.clone());
+        assert_eq!(page.entries[0].name, "c.txt");
+        assert!(page.next_cursor.is_none());
+
+        let options = ListOptions {
+            pattern: Some("*.md".to_string()),
+            sort: ListSort::Size,
+            ..Default::default()
+        };
+        let page = options.apply(entries);
+        let names: Vec<_> = page.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a.md", "b.md"]);
+        assert_eq!(page.total, 2);
+    }
+
+    #[test]
+    fn test_name_sort_is_depth_first() {
+        let dir = |uri: &str| FileEntry {
+            uri: uri.to_string(),
+            name: uri.rsplit('/').next().unwrap().to_string(),
+            is_directory: true,
+            size: 0,
+            modified: Utc.timestamp_opt(0, 0).unwrap(),
+        };
+        let entries = vec![
+            dir("cortex://session/a-b"),
+            dir("cortex://session/a/x"),
+            dir("cortex://session/a"),
+        ];
+        let page = ListOptions::default().apply(entries);
+        let uris: Vec<_> = page.entries.iter().map(|e| e.uri.as_str()).collect();
+        assert_eq!(
+            uris,
+            ["cortex://session/a", "cortex://session/a/x", "cortex://session/a-b"]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod uri;
 pub mod operations;
+pub mod listing;
 
 pub use uri::{CortexUri, UriParser};
 pub use operations::{CortexFilesystem, FilesystemOperations};
+pub use listing::{ListOptions, ListPage, ListSort};
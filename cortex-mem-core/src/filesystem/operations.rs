@@ -1,4 +1,4 @@
-use crate::{Error, FileEntry, FileMetadata, MemoryMetadata, Result};
+use crate::{AccessDenied, AccessGuard, Error, FileEntry, FileMetadata, MemoryMetadata, Result};
 use async_trait::async_trait;
 use chrono::Utc;
 use std::path::{Path, PathBuf};
@@ -29,9 +29,12 @@ pub trait FilesystemOperations: Send + Sync {
 }
 
 /// Cortex filesystem implementation
+#[derive(Clone)]
 pub struct CortexFilesystem {
     root: PathBuf,
     tenant_id: Option<String>,
+    /// Access control applied to every operation (unrestricted when `None`)
+    access: Option<AccessGuard>,
 }
 
 impl CortexFilesystem {
@@ -40,6 +43,7 @@ impl CortexFilesystem {
         Self {
             root: root.as_ref().to_path_buf(),
             tenant_id: None,
+            access: None,
         }
     }
 
@@ -48,6 +52,35 @@ impl CortexFilesystem {
         Self {
             root: root.as_ref().to_path_buf(),
             tenant_id: Some(tenant_id.into()),
+            access: None,
+        }
+    }
+
+    /// Restrict the filesystem to what the guard grants
+    ///
+    /// Denied reads and writes fail with `Error::PermissionDenied`, `exists`
+    /// reports denied URIs as missing and listings leave them out.
+    pub fn with_access(mut self, guard: AccessGuard) -> Self {
+        self.access = Some(guard);
+        self
+    }
+
+    /// Get the access guard, if any
+    pub fn access(&self) -> Option<&AccessGuard> {
+        self.access.as_ref()
+    }
+
+    fn check_read(&self, uri: &str) -> std::result::Result<(), AccessDenied> {
+        match &self.access {
+            Some(guard) => guard.check_read(uri),
+            None => Ok(()),
+        }
+    }
+
+    fn check_write(&self, uri: &str) -> std::result::Result<(), AccessDenied> {
+        match &self.access {
+            Some(guard) => guard.check_write(uri),
+            None => Ok(()),
         }
     }
 
@@ -56,6 +89,16 @@ impl CortexFilesystem {
         &self.root
     }
 
+    /// Directory holding this filesystem's data
+    ///
+    /// `{root}/tenants/{tenant_id}` in tenant mode, the root otherwise.
+    pub fn base_path(&self) -> PathBuf {
+        match &self.tenant_id {
+            Some(tenant_id) => self.root.join("tenants").join(tenant_id),
+            None => self.root.clone(),
+        }
+    }
+
     /// Get the tenant ID
     pub fn tenant_id(&self) -> Option<&str> {
         self.tenant_id.as_deref()
@@ -69,13 +112,7 @@ impl CortexFilesystem {
     /// Initialize the filesystem structure
     pub async fn initialize(&self) -> Result<()> {
         // Get the base directory (with or without tenant)
-        let base_dir = if let Some(tenant_id) = &self.tenant_id {
-            // For tenant: /root/tenants/{tenant_id}/ (without extra cortex subfolder)
-            self.root.join("tenants").join(tenant_id)
-        } else {
-            // For non-tenant: /root/
-            self.root.clone()
-        };
+        let base_dir = self.base_path();
 
         // Create root directory
         fs::create_dir_all(&base_dir).await?;
@@ -83,8 +120,8 @@ impl CortexFilesystem {
         // 只有在tenant模式下才创建维度目录
         // Non-tenant模式（如cortex-mem-service全局实例）不应创建这些目录
         if self.tenant_id.is_some() {
-            // Create dimension directories (style: resources, user, agent, session)
-            for dimension in &["resources", "user", "agent", "session"] {
+            // Create dimension directories (style: resources, user, agent, session, spaces)
+            for dimension in &["resources", "user", "agent", "session", "spaces"] {
                 let dir = base_dir.join(dimension);
                 fs::create_dir_all(dir).await?;
             }
@@ -136,6 +173,7 @@ impl CortexFilesystem {
 #[async_trait]
 impl FilesystemOperations for CortexFilesystem {
     async fn list(&self, uri: &str) -> Result<Vec<FileEntry>> {
+        self.check_read(uri)?;
         let path = self.uri_to_path(uri)?;
 
         if !path.try_exists()? {
@@ -157,6 +195,9 @@ impl FilesystemOperations for CortexFilesystem {
             }
 
             let entry_uri = format!("{}/{}", uri.trim_end_matches('/'), name);
+            if self.access.as_ref().is_some_and(|guard| !guard.can_read(&entry_uri)) {
+                continue;
+            }
 
             entries.push(FileEntry {
                 uri: entry_uri,
@@ -174,6 +215,7 @@ impl FilesystemOperations for CortexFilesystem {
     }
 
     async fn read(&self, uri: &str) -> Result<String> {
+        self.check_read(uri)?;
         let path = self.uri_to_path(uri)?;
 
         if !path.try_exists()? {
@@ -187,6 +229,7 @@ impl FilesystemOperations for CortexFilesystem {
     }
 
     async fn write(&self, uri: &str, content: &str) -> Result<()> {
+        self.check_write(uri)?;
         let path = self.uri_to_path(uri)?;
 
         // Create parent directories
@@ -199,6 +242,7 @@ impl FilesystemOperations for CortexFilesystem {
     }
 
     async fn delete(&self, uri: &str) -> Result<()> {
+        self.check_write(uri)?;
         let path = self.uri_to_path(uri)?;
 
         if !path.try_exists()? {
@@ -217,11 +261,15 @@ impl FilesystemOperations for CortexFilesystem {
     }
 
     async fn exists(&self, uri: &str) -> Result<bool> {
+        if self.check_read(uri).is_err() {
+            return Ok(false);
+        }
         let path = self.uri_to_path(uri)?;
         Ok(path.try_exists().unwrap_or(false))
     }
 
     async fn metadata(&self, uri: &str) -> Result<FileMetadata> {
+        self.check_read(uri)?;
         let path = self.uri_to_path(uri)?;
 
         if !path.try_exists()? {
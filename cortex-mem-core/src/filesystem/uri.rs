@@ -188,6 +188,11 @@ impl UriParser {
             return Err(Error::InvalidPath);
         }
 
+        // `.` and `..` would resolve outside the directory the URI names
+        if parts.iter().any(|part| matches!(*part, "." | "..")) {
+            return Err(Error::InvalidPath);
+        }
+
         let dimension = Dimension::from_str(parts[0])
             .ok_or_else(|| Error::InvalidDimension(parts[0].to_string()))?;
 
@@ -0,0 +1,557 @@
+//! Record / replay of LLM and embedding calls
+//!
+//! Integration tests need the real pipeline (extraction, layer cascades,
+//! layered search) without a live model. In `record` mode every LLM and
+//! embedding call goes to the configured provider and the request/response
+//! pair is saved as a JSON file; in `replay` mode calls are served from those
+//! files only, so a recorded run can be repeated offline and deterministically.
+//!
+//! Files live under `{dir}/llm/{key}.json` and `{dir}/embedding/{key}.json`,
+//! keyed by the SHA-256 of the request:
+//! - LLM: call kind (chat or one of the structured extractions), system
+//!   prompt and prompt. The model is not part of the key, so fixtures survive
+//!   a model change in the config.
+//! - Embedding: model name and text, one file per text, so batching does not
+//!   affect replay.
+//!
+//! - [`RecordingLLMClient`] / [`ReplayLLMClient`] wrap an [`LLMClient`];
+//!   [`FixtureStore::wrap_llm`] picks the one matching the mode.
+//! - `EmbeddingClient::with_fixtures` records or replays embeddings.
+
+use crate::disk_store::DiskStore;
+use crate::llm::{
+    DetailedFactExtraction, LLMClient, LLMConfig, MemoryExtractionResponse,
+    StructuredFactExtraction,
+};
+use crate::usage::TokenUsage;
+use crate::{Error, Result};
+use cortex_mem_config::{FixtureMode, FixturesConfig, LlmTask};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Kind of a recorded LLM call
+const CHAT: &str = "chat";
+const EXTRACT_MEMORIES: &str = "extract_memories";
+const EXTRACT_STRUCTURED_FACTS: &str = "extract_structured_facts";
+const EXTRACT_DETAILED_FACTS: &str = "extract_detailed_facts";
+
+/// One recorded LLM call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmFixture {
+    pub kind: String,
+    /// Model that produced the response (informational)
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub prompt: String,
+    /// Completion text, or the extraction result as JSON
+    pub response: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// One recorded embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingFixture {
+    pub model: String,
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
+
+/// Directory of recorded LLM and embedding calls
+pub struct FixtureStore {
+    dir: PathBuf,
+    mode: FixtureMode,
+    llm: DiskStore,
+    embedding: DiskStore,
+}
+
+impl FixtureStore {
+    /// Open (and create) the fixture directory
+    pub fn open(dir: impl Into<PathBuf>, mode: FixtureMode) -> std::io::Result<Self> {
+        let dir = dir.into();
+        Ok(Self {
+            llm: DiskStore::open(dir.join("llm"), "json", u64::MAX)?,
+            embedding: DiskStore::open(dir.join("embedding"), "json", u64::MAX)?,
+            dir,
+            mode,
+        })
+    }
+
+    /// Store for `[fixtures]`, `None` when the mode is `off`
+    pub fn from_config(
+        config: &FixturesConfig,
+        data_dir: &Path,
+    ) -> std::io::Result<Option<Arc<Self>>> {
+        if !config.is_active() {
+            return Ok(None);
+        }
+        let dir = config.dir_or_default(data_dir);
+        info!(
+            "🎞️  {} LLM and embedding calls in {}",
+            match config.mode {
+                FixtureMode::Replay => "Replaying",
+                _ => "Recording",
+            },
+            dir.display()
+        );
+        Self::open(dir, config.mode).map(|store| Some(Arc::new(store)))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    /// Whether calls are served from the store instead of a provider
+    pub fn is_replay(&self) -> bool {
+        self.mode == FixtureMode::Replay
+    }
+
+    /// Wrap `inner` in the recording or replaying client of the mode
+    pub fn wrap_llm(self: &Arc<Self>, inner: Arc<dyn LLMClient>) -> Arc<dyn LLMClient> {
+        match self.mode {
+            FixtureMode::Record => Arc::new(RecordingLLMClient::new(inner, self.clone())),
+            FixtureMode::Replay => Arc::new(ReplayLLMClient::new(inner, self.clone())),
+            FixtureMode::Off => inner,
+        }
+    }
+
+    /// Key of a request: SHA-256 of its parts
+    fn key(parts: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn llm_key(kind: &str, system: Option<&str>, prompt: &str) -> String {
+        Self::key(&[kind, system.unwrap_or_default(), prompt])
+    }
+
+    async fn load<T: DeserializeOwned>(store: &DiskStore, key: &str) -> Option<T> {
+        let content = store.get(key).await?;
+        match serde_json::from_slice(&content) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!(
+                    "Ignoring unreadable fixture {} in {}: {}",
+                    key,
+                    store.dir().display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Save a fixture; LLM calls are pretty-printed so recorded prompts
+    /// stay reviewable, embeddings are kept compact
+    async fn save<T: Serialize>(store: &DiskStore, key: &str, value: &T, pretty: bool) {
+        let content = if pretty {
+            serde_json::to_vec_pretty(value)
+        } else {
+            serde_json::to_vec(value)
+        };
+        let result = match content {
+            Ok(content) => store.put(key, &content).await.map_err(Error::from),
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            warn!(
+                "Failed to record fixture {} in {}: {}",
+                key,
+                store.dir().display(),
+                e
+            );
+        }
+    }
+
+    /// Recorded LLM call, or an error naming the missing request
+    pub async fn llm_fixture(
+        &self,
+        kind: &str,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<LlmFixture> {
+        let key = Self::llm_key(kind, system, prompt);
+        Self::load(&self.llm, &key).await.ok_or_else(|| {
+            Error::Llm(format!(
+                "no recorded {} response for request {} in {} (record it with CORTEX_FIXTURES=record)",
+                kind,
+                key,
+                self.dir.display()
+            ))
+        })
+    }
+
+    /// Save an LLM call
+    pub async fn record_llm(&self, fixture: &LlmFixture) {
+        let key = Self::llm_key(&fixture.kind, fixture.system.as_deref(), &fixture.prompt);
+        debug!("Recording {} fixture {}", fixture.kind, key);
+        Self::save(&self.llm, &key, fixture, true).await;
+    }
+
+    /// Recorded embeddings of `texts`, failing on the first text never recorded
+    pub async fn embeddings(&self, model: &str, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            let key = Self::key(&[model, text.as_str()]);
+            let fixture: EmbeddingFixture =
+                Self::load(&self.embedding, &key).await.ok_or_else(|| {
+                    Error::Embedding(format!(
+                        "no recorded {} embedding for text {} in {} (record it with CORTEX_FIXTURES=record)",
+                        model,
+                        key,
+                        self.dir.display()
+                    ))
+                })?;
+            embeddings.push(fixture.embedding);
+        }
+        Ok(embeddings)
+    }
+
+    /// Save the embeddings of `texts`
+    pub async fn record_embeddings(&self, model: &str, texts: &[String], embeddings: &[Vec<f32>]) {
+        for (text, embedding) in texts.iter().zip(embeddings) {
+            let fixture = EmbeddingFixture {
+                model: model.to_string(),
+                text: text.clone(),
+                embedding: embedding.clone(),
+            };
+            let key = Self::key(&[model, text.as_str()]);
+            Self::save(&self.embedding, &key, &fixture, false).await;
+        }
+    }
+}
+
+/// [`LLMClient`] that saves every call of `inner` to a [`FixtureStore`]
+pub struct RecordingLLMClient {
+    inner: Arc<dyn LLMClient>,
+    store: Arc<FixtureStore>,
+}
+
+impl RecordingLLMClient {
+    pub fn new(inner: Arc<dyn LLMClient>, store: Arc<FixtureStore>) -> Self {
+        Self { inner, store }
+    }
+
+    async fn record<T: Serialize>(
+        &self,
+        kind: &str,
+        model: &str,
+        system: Option<&str>,
+        prompt: &str,
+        response: &T,
+        usage: Option<TokenUsage>,
+    ) -> Result<()> {
+        self.store
+            .record_llm(&LlmFixture {
+                kind: kind.to_string(),
+                model: model.to_string(),
+                system: system.map(str::to_string),
+                prompt: prompt.to_string(),
+                response: serde_json::to_value(response)?,
+                usage,
+            })
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMClient for RecordingLLMClient {
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        let response = self.inner.complete(prompt).await?;
+        self.record(CHAT, self.inner.model_name(), None, prompt, &response, None)
+            .await?;
+        Ok(response)
+    }
+
+    async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
+        let response = self.inner.complete_with_system(system, prompt).await?;
+        self.record(
+            CHAT,
+            self.inner.model_name(),
+            Some(system),
+            prompt,
+            &response,
+            None,
+        )
+        .await?;
+        Ok(response)
+    }
+
+    async fn extract_memories(&self, prompt: &str) -> Result<MemoryExtractionResponse> {
+        let response = self.inner.extract_memories(prompt).await?;
+        self.record(
+            EXTRACT_MEMORIES,
+            self.inner.model_name(),
+            None,
+            prompt,
+            &response,
+            None,
+        )
+        .await?;
+        Ok(response)
+    }
+
+    async fn extract_structured_facts(&self, prompt: &str) -> Result<StructuredFactExtraction> {
+        let response = self.inner.extract_structured_facts(prompt).await?;
+        self.record(
+            EXTRACT_STRUCTURED_FACTS,
+            self.inner.model_name(),
+            None,
+            prompt,
+            &response,
+            None,
+        )
+        .await?;
+        Ok(response)
+    }
+
+    async fn extract_detailed_facts(&self, prompt: &str) -> Result<DetailedFactExtraction> {
+        let response = self.inner.extract_detailed_facts(prompt).await?;
+        self.record(
+            EXTRACT_DETAILED_FACTS,
+            self.inner.model_name(),
+            None,
+            prompt,
+            &response,
+            None,
+        )
+        .await?;
+        Ok(response)
+    }
+
+    async fn complete_for_task(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<String> {
+        Ok(self
+            .complete_for_task_with_usage(task, system, prompt)
+            .await?
+            .0)
+    }
+
+    async fn complete_for_task_with_usage(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let (response, usage) = self
+            .inner
+            .complete_for_task_with_usage(task, system, prompt)
+            .await?;
+        self.record(
+            CHAT,
+            self.inner.model_for(task),
+            system,
+            prompt,
+            &response,
+            Some(usage),
+        )
+        .await?;
+        Ok((response, usage))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn model_for(&self, task: LlmTask) -> &str {
+        self.inner.model_for(task)
+    }
+
+    fn config(&self) -> &LLMConfig {
+        self.inner.config()
+    }
+}
+
+/// [`LLMClient`] serving calls from a [`FixtureStore`]
+///
+/// `inner` is never called; it only provides the model names and config, so
+/// LLM result cache keys match those of the recorded run.
+pub struct ReplayLLMClient {
+    inner: Arc<dyn LLMClient>,
+    store: Arc<FixtureStore>,
+}
+
+impl ReplayLLMClient {
+    pub fn new(inner: Arc<dyn LLMClient>, store: Arc<FixtureStore>) -> Self {
+        Self { inner, store }
+    }
+
+    async fn replay<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(T, Option<TokenUsage>)> {
+        let fixture = self.store.llm_fixture(kind, system, prompt).await?;
+        Ok((serde_json::from_value(fixture.response)?, fixture.usage))
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMClient for ReplayLLMClient {
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        Ok(self.replay(CHAT, None, prompt).await?.0)
+    }
+
+    async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
+        Ok(self.replay(CHAT, Some(system), prompt).await?.0)
+    }
+
+    async fn extract_memories(&self, prompt: &str) -> Result<MemoryExtractionResponse> {
+        Ok(self.replay(EXTRACT_MEMORIES, None, prompt).await?.0)
+    }
+
+    async fn extract_structured_facts(&self, prompt: &str) -> Result<StructuredFactExtraction> {
+        Ok(self.replay(EXTRACT_STRUCTURED_FACTS, None, prompt).await?.0)
+    }
+
+    async fn extract_detailed_facts(&self, prompt: &str) -> Result<DetailedFactExtraction> {
+        Ok(self.replay(EXTRACT_DETAILED_FACTS, None, prompt).await?.0)
+    }
+
+    async fn complete_for_task(
+        &self,
+        _task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<String> {
+        Ok(self.replay(CHAT, system, prompt).await?.0)
+    }
+
+    async fn complete_for_task_with_usage(
+        &self,
+        _task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let (response, usage): (String, _) = self.replay(CHAT, system, prompt).await?;
+        let usage = usage.unwrap_or_else(|| {
+            TokenUsage::estimate(system.map_or(0, str::len) + prompt.len(), response.len())
+        });
+        Ok((response, usage))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn model_for(&self, task: LlmTask) -> &str {
+        self.inner.model_for(task)
+    }
+
+    fn config(&self) -> &LLMConfig {
+        self.inner.config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::{EmbeddingClient, EmbeddingConfig};
+    use crate::llm::MockLLMClient;
+    use tempfile::TempDir;
+
+    fn store(dir: &TempDir, mode: FixtureMode) -> Arc<FixtureStore> {
+        Arc::new(FixtureStore::open(dir.path(), mode).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_llm() {
+        let dir = TempDir::new().unwrap();
+
+        let recorder = store(&dir, FixtureMode::Record)
+            .wrap_llm(Arc::new(MockLLMClient::with_response("recorded answer")));
+        let response = recorder
+            .complete_for_task(LlmTask::Abstract, Some("system"), "summarize this")
+            .await
+            .unwrap();
+        assert_eq!(response, "recorded answer");
+        recorder
+            .extract_memories("extract from this")
+            .await
+            .unwrap();
+
+        // The live client answers differently; replay must not call it
+        let replayer = store(&dir, FixtureMode::Replay)
+            .wrap_llm(Arc::new(MockLLMClient::with_response("live answer")));
+        let (response, usage) = replayer
+            .complete_for_task_with_usage(LlmTask::Abstract, Some("system"), "summarize this")
+            .await
+            .unwrap();
+        assert_eq!(response, "recorded answer");
+        assert!(usage.total() > 0);
+        // Keys ignore the task and the entry point, only the request matters
+        assert_eq!(
+            replayer
+                .complete_with_system("system", "summarize this")
+                .await
+                .unwrap(),
+            "recorded answer"
+        );
+        let extracted = replayer
+            .extract_memories("extract from this")
+            .await
+            .unwrap();
+        assert!(extracted.facts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_unrecorded_request_fails() {
+        let dir = TempDir::new().unwrap();
+        let replayer = store(&dir, FixtureMode::Replay)
+            .wrap_llm(Arc::new(MockLLMClient::with_response("live answer")));
+
+        let err = replayer
+            .complete_for_task(LlmTask::Intent, None, "never recorded")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no recorded chat response"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_embeddings() {
+        let dir = TempDir::new().unwrap();
+        let fixtures = store(&dir, FixtureMode::Replay);
+        fixtures
+            .record_embeddings(
+                "test-model",
+                &["hello".to_string(), "world".to_string()],
+                &[vec![1.0, 0.0], vec![0.0, 1.0]],
+            )
+            .await;
+
+        // Unreachable endpoint: replay never touches the network
+        let client = EmbeddingClient::new(EmbeddingConfig {
+            api_base_url: "http://127.0.0.1:9".to_string(),
+            model_name: "test-model".to_string(),
+            ..EmbeddingConfig::default()
+        })
+        .unwrap()
+        .with_fixtures(fixtures);
+
+        let embeddings = client
+            .embed_batch(&["world".to_string(), "hello".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(embeddings, vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        assert!(client.embed("unrecorded").await.is_err());
+    }
+}
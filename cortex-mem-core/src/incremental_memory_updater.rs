@@ -16,10 +16,10 @@ use crate::memory_index::{MemoryMetadata, MemoryScope, MemoryType, MemoryUpdateR
 use crate::memory_index_manager::MemoryIndexManager;
 use crate::memory_events::{DeleteReason, MemoryEvent};
 use crate::session::extraction::{
-    CaseMemory, EntityMemory, EventMemory, ExtractedMemories, GoalMemory,
-    PersonalInfoMemory, PreferenceMemory, RelationshipMemory, WorkHistoryMemory,
+    CaseMemory, EntityMemory, EventMemory, ExtractedMemories, GoalMemory, InstructionMemory,
+    PersonalInfoMemory, PreferenceMemory, RelationshipMemory, SkillMemory, WorkHistoryMemory,
 };
-use crate::Result;
+use crate::{Error, Result};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, info};
@@ -119,6 +119,42 @@ impl MemoryItem for CaseMemory {
     }
 }
 
+impl MemoryItem for SkillMemory {
+    fn key(&self) -> String { self.name.clone() }
+    fn memory_type(&self) -> MemoryType { MemoryType::Skill }
+    fn confidence(&self) -> f32 { self.confidence }
+    fn id_prefix(&self) -> &'static str { "skill" }
+    fn file_dir(&self) -> &'static str { "skills" }
+    fn format_content(&self) -> String {
+        let steps = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("{}. {}", i + 1, s))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "# {}\n\n**When to use**: {}\n\n## Steps\n\n{}\n\n**Confidence**: {:.2}",
+            self.name, self.when_to_use, steps, self.confidence
+        )
+    }
+}
+
+impl MemoryItem for InstructionMemory {
+    fn key(&self) -> String { self.topic.clone() }
+    fn memory_type(&self) -> MemoryType { MemoryType::Instruction }
+    fn confidence(&self) -> f32 { self.confidence }
+    fn id_prefix(&self) -> &'static str { "instr" }
+    fn file_dir(&self) -> &'static str { "instructions" }
+    fn format_content(&self) -> String {
+        let applies_to = self.applies_to.as_deref().unwrap_or("all tasks");
+        format!(
+            "# {}\n\n{}\n\n**Applies to**: {}\n\n**Confidence**: {:.2}",
+            self.topic, self.instruction, applies_to, self.confidence
+        )
+    }
+}
+
 impl MemoryItem for PersonalInfoMemory {
     fn key(&self) -> String { self.category.clone() }
     fn memory_type(&self) -> MemoryType { MemoryType::PersonalInfo }
@@ -177,6 +213,101 @@ impl MemoryItem for GoalMemory {
     }
 }
 
+// ── Manual memories ─────────────────────────────────────────────────────────
+
+/// Source recorded for memories written through `remember` / `update_memory`
+/// instead of being extracted from a session.
+pub const MANUAL_SOURCE: &str = "manual";
+
+/// Why a manual memory cannot be created
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidMemory {
+    #[error("conversation memories are stored through sessions, not remembered")]
+    Conversation,
+    #[error("memory key must not be empty")]
+    EmptyKey,
+}
+
+impl From<InvalidMemory> for Error {
+    fn from(e: InvalidMemory) -> Self {
+        Error::Other(e.to_string())
+    }
+}
+
+/// A memory asserted directly by an agent or user rather than extracted from a
+/// session. It is stored next to extracted memories of the same type (same ID
+/// prefix and directory), so both deduplicate against each other by key.
+#[derive(Debug, Clone)]
+pub struct ManualMemory {
+    memory_type: MemoryType,
+    key: String,
+    content: String,
+    confidence: f32,
+    id_prefix: &'static str,
+    file_dir: &'static str,
+}
+
+impl ManualMemory {
+    /// Create a manual memory; `Conversation` is not a storable memory type.
+    pub fn new(
+        memory_type: MemoryType,
+        key: impl Into<String>,
+        content: impl Into<String>,
+        confidence: f32,
+    ) -> std::result::Result<Self, InvalidMemory> {
+        let (id_prefix, file_dir) = match memory_type {
+            MemoryType::Preference => ("pref", "preferences"),
+            MemoryType::Entity => ("entity", "entities"),
+            MemoryType::Event => ("event", "events"),
+            MemoryType::Case => ("case", "cases"),
+            MemoryType::PersonalInfo => ("info", "personal_info"),
+            MemoryType::WorkHistory => ("work", "work_history"),
+            MemoryType::Relationship => ("rel", "relationships"),
+            MemoryType::Goal => ("goal", "goals"),
+            MemoryType::Skill => ("skill", "skills"),
+            MemoryType::Instruction => ("instr", "instructions"),
+            MemoryType::Conversation => return Err(InvalidMemory::Conversation),
+        };
+
+        let key = key.into().trim().to_string();
+        if key.is_empty() {
+            return Err(InvalidMemory::EmptyKey);
+        }
+
+        Ok(Self {
+            memory_type,
+            key,
+            content: content.into().trim().to_string(),
+            confidence: confidence.clamp(0.0, 1.0),
+            id_prefix,
+            file_dir,
+        })
+    }
+
+    /// Scope a memory type belongs to: cases, skills and instructions are
+    /// agent memories, all other types describe the user.
+    pub fn scope_of(memory_type: &MemoryType) -> MemoryScope {
+        match memory_type {
+            MemoryType::Case | MemoryType::Skill | MemoryType::Instruction => MemoryScope::Agent,
+            _ => MemoryScope::User,
+        }
+    }
+}
+
+impl MemoryItem for ManualMemory {
+    fn key(&self) -> String { self.key.clone() }
+    fn memory_type(&self) -> MemoryType { self.memory_type.clone() }
+    fn confidence(&self) -> f32 { self.confidence }
+    fn id_prefix(&self) -> &'static str { self.id_prefix }
+    fn file_dir(&self) -> &'static str { self.file_dir }
+    fn format_content(&self) -> String {
+        format!(
+            "# {}\n\n{}\n\n**Confidence**: {:.2}",
+            self.key, self.content, self.confidence
+        )
+    }
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 //  IncrementalMemoryUpdater
 // ────────────────────────────────────────────────────────────────────────────
@@ -234,6 +365,8 @@ impl IncrementalMemoryUpdater {
 
         // Process agent-scoped memory types
         self.process_items(&mut result, &MemoryScope::Agent, agent_id, session_id, &extracted.cases).await?;
+        self.process_items(&mut result, &MemoryScope::Agent, agent_id, session_id, &extracted.skills).await?;
+        self.process_items(&mut result, &MemoryScope::Agent, agent_id, session_id, &extracted.instructions).await?;
 
         // Record session extraction summary
         self.index_manager.record_session_extraction(
@@ -252,6 +385,104 @@ impl IncrementalMemoryUpdater {
         Ok(result)
     }
 
+    /// Remember a single memory outside of session extraction
+    ///
+    /// Goes through the same find-existing → compare → create / update flow as
+    /// extracted memories, so remembering an existing key updates that memory.
+    /// Returns the change counts and the stored metadata.
+    pub async fn remember<T: MemoryItem>(
+        &self,
+        scope: &MemoryScope,
+        owner_id: &str,
+        source: &str,
+        item: &T,
+    ) -> Result<(MemoryUpdateResult, MemoryMetadata)> {
+        let mut result = MemoryUpdateResult::default();
+        self.process_items(&mut result, scope, owner_id, source, std::slice::from_ref(item)).await?;
+
+        let metadata = self
+            .index_manager
+            .find_matching_memory(scope, owner_id, &item.memory_type(), &item.key())
+            .await?
+            .ok_or_else(|| Error::Other(format!("memory '{}' missing from index", item.key())))?;
+
+        info!(
+            "Remembered {} '{}' for {}/{}: {} created, {} updated",
+            item.memory_type(), item.key(), scope, owner_id, result.created, result.updated
+        );
+        Ok((result, metadata))
+    }
+
+    /// Replace the content of an existing memory, keeping its type and key
+    ///
+    /// `confidence` defaults to the stored one. Returns `None` when no memory
+    /// with `memory_id` exists.
+    pub async fn update_memory(
+        &self,
+        scope: &MemoryScope,
+        owner_id: &str,
+        memory_id: &str,
+        content: &str,
+        confidence: Option<f32>,
+        source: &str,
+    ) -> Result<Option<MemoryMetadata>> {
+        let index = self.index_manager.load_index(scope.clone(), owner_id.to_string()).await?;
+        let Some(existing) = index.memories.get(memory_id).cloned() else {
+            return Ok(None);
+        };
+
+        let item = ManualMemory::new(
+            existing.memory_type.clone(),
+            existing.key.clone(),
+            content,
+            confidence.unwrap_or(existing.confidence),
+        )?;
+        let content = item.format_content();
+        let content_hash = MemoryIndexManager::calculate_content_hash(&content);
+        let content_summary = MemoryIndexManager::generate_content_summary(&content, 200);
+
+        let mut result = MemoryUpdateResult::default();
+        self.do_update_memory(
+            &mut result, scope, owner_id, source,
+            existing, content, content_hash, content_summary, item.confidence(),
+        ).await?;
+
+        self.get_memory(scope, owner_id, memory_id).await
+    }
+
+    /// Look up the index entry of a memory
+    pub async fn get_memory(
+        &self,
+        scope: &MemoryScope,
+        owner_id: &str,
+        memory_id: &str,
+    ) -> Result<Option<MemoryMetadata>> {
+        let index = self.index_manager.load_index(scope.clone(), owner_id.to_string()).await?;
+        Ok(index.memories.get(memory_id).cloned())
+    }
+
+    /// Split a memory file URI (`cortex://{scope}/{owner}/{dir}/{memory_id}.md`)
+    /// into scope, owner ID and memory ID
+    ///
+    /// Only user and agent memories are indexed, so other scopes yield `None`.
+    pub fn parse_memory_uri(uri: &str) -> Option<(MemoryScope, String, String)> {
+        let path = uri.strip_prefix("cortex://")?;
+        let mut parts = path.split('/');
+        let scope = match parts.next()? {
+            "user" => MemoryScope::User,
+            "agent" => MemoryScope::Agent,
+            _ => return None,
+        };
+        let owner_id = parts.next().filter(|s| !s.is_empty())?;
+        let memory_id = parts.next_back()?.strip_suffix(".md")?;
+        // At least one directory between owner and file
+        parts.next()?;
+        if memory_id.is_empty() || memory_id.starts_with('.') {
+            return None;
+        }
+        Some((scope, owner_id.to_string(), memory_id.to_string()))
+    }
+
     // ────────────────────────────────────────────────────────────────────────
     //  Generic processing — the heart of the deduplication
     // ────────────────────────────────────────────────────────────────────────
@@ -488,3 +719,60 @@ impl IncrementalMemoryUpdater {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_memory_matches_extracted_layout() {
+        let item = ManualMemory::new(MemoryType::Preference, " indentation ", "Prefers tabs", 0.9).unwrap();
+        assert_eq!(item.key(), "indentation");
+        assert_eq!(item.id_prefix(), "pref");
+        assert_eq!(item.file_dir(), "preferences");
+        assert!(item.format_content().starts_with("# indentation\n\nPrefers tabs"));
+
+        assert_eq!(ManualMemory::scope_of(&MemoryType::Case), MemoryScope::Agent);
+        assert_eq!(ManualMemory::scope_of(&MemoryType::Instruction), MemoryScope::Agent);
+        assert_eq!(ManualMemory::scope_of(&MemoryType::Goal), MemoryScope::User);
+    }
+
+    #[test]
+    fn test_skill_memory_layout() {
+        let skill = SkillMemory {
+            name: "Release crate".to_string(),
+            when_to_use: "Publishing a new version".to_string(),
+            steps: vec!["Bump version".to_string(), "cargo publish".to_string()],
+            confidence: 0.8,
+        };
+        assert_eq!(skill.file_dir(), "skills");
+        assert!(skill.format_content().contains("1. Bump version\n2. cargo publish"));
+
+        let manual = ManualMemory::new(MemoryType::Skill, "Release crate", "...", 0.8).unwrap();
+        assert_eq!(manual.id_prefix(), skill.id_prefix());
+    }
+
+    #[test]
+    fn test_manual_memory_rejects_invalid_input() {
+        assert!(ManualMemory::new(MemoryType::Conversation, "k", "c", 0.5).is_err());
+        assert!(ManualMemory::new(MemoryType::Entity, "  ", "c", 0.5).is_err());
+        let item = ManualMemory::new(MemoryType::Entity, "k", "c", 3.0).unwrap();
+        assert_eq!(item.confidence(), 1.0);
+    }
+
+    #[test]
+    fn test_parse_memory_uri() {
+        assert_eq!(
+            IncrementalMemoryUpdater::parse_memory_uri("cortex://user/alice/preferences/pref_1a2b.md"),
+            Some((MemoryScope::User, "alice".to_string(), "pref_1a2b".to_string()))
+        );
+        assert_eq!(
+            IncrementalMemoryUpdater::parse_memory_uri("cortex://agent/bot/cases/case_9.md"),
+            Some((MemoryScope::Agent, "bot".to_string(), "case_9".to_string()))
+        );
+        assert_eq!(IncrementalMemoryUpdater::parse_memory_uri("cortex://user/alice/pref_1.md"), None);
+        assert_eq!(IncrementalMemoryUpdater::parse_memory_uri("cortex://session/t1/timeline/a.md"), None);
+        assert_eq!(IncrementalMemoryUpdater::parse_memory_uri("cortex://user/alice/preferences"), None);
+        assert_eq!(IncrementalMemoryUpdater::parse_memory_uri("cortex://user/alice/preferences/.abstract.md"), None);
+    }
+}
@@ -0,0 +1,524 @@
+//! Extractive, LLM-free L0/L1 summaries
+//!
+//! Abstracts and overviews are built from the source text itself: headings
+//! name the topics, keyword frequencies (words for alphabetic scripts,
+//! character bigrams for CJK) score the sentences, and the best sentences are
+//! kept in their original order. Used for `[layers] mode = "extractive"`, when
+//! the LLM is unavailable, and when an LLM answer still fails validation.
+//! Every such layer carries [`EXTRACTIVE_MARKER`] so it can be regenerated
+//! once an LLM is available.
+
+use crate::ContextLayer;
+use crate::layers::generator::AbstractGenerator;
+use crate::layers::validation;
+use std::collections::HashMap;
+
+/// Footer line marking a layer as extractive
+pub const EXTRACTIVE_MARKER: &str = "<!-- generator: extractive -->";
+
+/// Sentences in an L0 abstract
+const ABSTRACT_SENTENCES: usize = 2;
+/// Sentences in the `Summary` section of an L1 overview
+const SUMMARY_SENTENCES: usize = 3;
+/// Bullets in the `Core Topics` section
+const MAX_TOPICS: usize = 6;
+/// Bullets in the `Key Points` section
+const MAX_KEY_POINTS: usize = 8;
+
+/// Words that carry no topic
+const STOPWORDS: &[&str] = &[
+    "the",
+    "and",
+    "for",
+    "are",
+    "was",
+    "were",
+    "with",
+    "that",
+    "this",
+    "from",
+    "have",
+    "has",
+    "had",
+    "not",
+    "but",
+    "you",
+    "your",
+    "they",
+    "them",
+    "their",
+    "she",
+    "her",
+    "his",
+    "its",
+    "our",
+    "who",
+    "what",
+    "when",
+    "where",
+    "which",
+    "will",
+    "would",
+    "can",
+    "could",
+    "should",
+    "about",
+    "into",
+    "than",
+    "then",
+    "there",
+    "these",
+    "those",
+    "also",
+    "just",
+    "been",
+    "being",
+    "does",
+    "did",
+    "all",
+    "any",
+    "some",
+    "more",
+    "most",
+    "very",
+    "too",
+    "out",
+    "over",
+    "like",
+    "user",
+    "assistant",
+];
+
+/// CJK characters that are grammar rather than content; bigrams containing
+/// them are not keywords
+const CJK_STOPCHARS: &str = "的了是在和与及或也就都而这那有我你他她它们个之为对把被吗呢吧啊着过";
+
+/// `**Key**: value` metadata lines that say nothing about the content
+const METADATA_KEYS: &[&str] = &[
+    "id",
+    "timestamp",
+    "added",
+    "created",
+    "updated",
+    "confidence",
+    "importance",
+    "source",
+    "category",
+    "type",
+    "tags",
+];
+
+/// Headings that only structure stored files and messages
+const GENERIC_HEADINGS: &[&str] = &["content", "metadata", "user", "assistant", "system"];
+
+/// Whether a layer file was written by the extractive summarizer
+pub fn is_extractive(layer_content: &str) -> bool {
+    layer_content
+        .lines()
+        .any(|line| line.trim() == EXTRACTIVE_MARKER)
+}
+
+/// Summarize `source` as an L0 abstract or L1 overview of at most `max_tokens`
+///
+/// The result ends with [`EXTRACTIVE_MARKER`].
+pub fn summarize(layer: ContextLayer, source: &str, max_tokens: usize) -> String {
+    let document = Document::parse(source);
+    let text = match layer {
+        ContextLayer::L0Abstract => document.abstract_text(max_tokens),
+        _ => document.overview(max_tokens),
+    };
+    format!("{}\n\n{}", text, EXTRACTIVE_MARKER)
+}
+
+struct Sentence {
+    text: String,
+    /// Index of the sentence within its section (0 = leads the section)
+    position: usize,
+}
+
+struct Document {
+    headings: Vec<String>,
+    sentences: Vec<Sentence>,
+    /// Term frequencies over headings (weighted double) and sentences
+    frequencies: HashMap<String, usize>,
+    /// Terms in order of first appearance, for deterministic tie-breaking
+    term_order: Vec<String>,
+}
+
+impl Document {
+    fn parse(source: &str) -> Self {
+        let mut headings: Vec<String> = Vec::new();
+        let mut sentences = Vec::new();
+        let mut position = 0;
+        let mut in_code = false;
+
+        for line in source.lines() {
+            let line = line.trim();
+            if line.starts_with("```") {
+                in_code = !in_code;
+                continue;
+            }
+            if in_code || line.is_empty() || is_metadata_line(line) {
+                continue;
+            }
+
+            // File markers and headings start a new section
+            if line.starts_with("=== ") && line.ends_with(" ===") {
+                position = 0;
+                continue;
+            }
+            if line.starts_with('#') {
+                position = 0;
+                let heading = clean_inline(line.trim_start_matches('#'));
+                if is_topic_heading(&heading) && !headings.contains(&heading) {
+                    headings.push(heading);
+                }
+                continue;
+            }
+
+            let line = clean_inline(strip_list_marker(line));
+            for text in split_sentences(&line) {
+                sentences.push(Sentence { text, position });
+                position += 1;
+            }
+        }
+
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        let mut term_order = Vec::new();
+        let weighted = headings
+            .iter()
+            .map(|h| (h.as_str(), 2))
+            .chain(sentences.iter().map(|s| (s.text.as_str(), 1)));
+        for (text, weight) in weighted {
+            for term in terms(text) {
+                let count = frequencies.entry(term.clone()).or_insert(0);
+                if *count == 0 {
+                    term_order.push(term);
+                }
+                *count += weight;
+            }
+        }
+
+        Self {
+            headings,
+            sentences,
+            frequencies,
+            term_order,
+        }
+    }
+
+    /// Sentence score: keyword weight per term, with a bonus for sentences
+    /// that open a section
+    fn score(&self, sentence: &Sentence) -> f64 {
+        let mut unique = terms(&sentence.text);
+        unique.sort();
+        unique.dedup();
+        if unique.is_empty() {
+            return 0.0;
+        }
+        let weight: usize = unique.iter().map(|t| self.frequencies[t]).sum();
+        let score = weight as f64 / (unique.len() as f64).sqrt();
+        if sentence.position == 0 {
+            score * 1.5
+        } else {
+            score
+        }
+    }
+
+    /// Indices of the `count` best sentences, in document order
+    fn top_sentences(&self, count: usize, skip: &[usize]) -> Vec<usize> {
+        let mut ranked: Vec<(usize, f64)> = self
+            .sentences
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !skip.contains(i))
+            .map(|(i, s)| (i, self.score(s)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut picked: Vec<usize> = ranked.into_iter().take(count).map(|(i, _)| i).collect();
+        picked.sort_unstable();
+        picked
+    }
+
+    /// Most frequent terms, ties broken by first appearance
+    fn keywords(&self, count: usize) -> Vec<String> {
+        let mut ranked: Vec<(usize, &String)> = self.term_order.iter().enumerate().collect();
+        ranked.sort_by(|a, b| {
+            self.frequencies[b.1]
+                .cmp(&self.frequencies[a.1])
+                .then(a.0.cmp(&b.0))
+        });
+        ranked
+            .into_iter()
+            .filter(|(_, term)| self.frequencies[*term] > 1)
+            .take(count)
+            .map(|(_, term)| term.clone())
+            .collect()
+    }
+
+    fn join(&self, indices: &[usize]) -> String {
+        let mut text = String::new();
+        for &i in indices {
+            let sentence = &self.sentences[i].text;
+            let cjk_boundary = text.ends_with(|c: char| !c.is_ascii())
+                && sentence.starts_with(|c: char| !c.is_ascii());
+            if !text.is_empty() && !cjk_boundary {
+                text.push(' ');
+            }
+            text.push_str(sentence);
+        }
+        text
+    }
+
+    fn abstract_text(&self, max_tokens: usize) -> String {
+        let text = if self.sentences.is_empty() {
+            self.headings.join(", ")
+        } else {
+            self.join(&self.top_sentences(ABSTRACT_SENTENCES, &[]))
+        };
+        validation::truncate_to_tokens(ContextLayer::L0Abstract, &text, max_tokens)
+    }
+
+    fn overview(&self, max_tokens: usize) -> String {
+        let summary_indices = self.top_sentences(SUMMARY_SENTENCES, &[]);
+        let summary = validation::truncate_to_tokens(
+            ContextLayer::L0Abstract,
+            &self.join(&summary_indices),
+            max_tokens / 2,
+        );
+
+        let mut topics: Vec<String> = self.headings.iter().take(MAX_TOPICS).cloned().collect();
+        for keyword in self.keywords(MAX_TOPICS) {
+            if topics.len() >= MAX_TOPICS {
+                break;
+            }
+            if !topics.iter().any(|t| t.to_lowercase().contains(&keyword)) {
+                topics.push(keyword);
+            }
+        }
+
+        let mut overview = format!("## Summary\n\n{}", summary);
+        if !topics.is_empty() {
+            overview.push_str("\n\n## Core Topics\n");
+            for topic in &topics {
+                overview.push_str(&format!("\n- {}", topic));
+            }
+        }
+
+        // Key points are added while they fit the budget
+        overview.push_str("\n\n## Key Points\n");
+        let mut key_points = self.top_sentences(MAX_KEY_POINTS, &summary_indices);
+        if key_points.is_empty() {
+            key_points = summary_indices;
+        }
+        for i in key_points {
+            let bullet = format!("\n- {}", self.sentences[i].text);
+            if AbstractGenerator::estimate_tokens(&overview)
+                + AbstractGenerator::estimate_tokens(&bullet)
+                > max_tokens
+            {
+                break;
+            }
+            overview.push_str(&bullet);
+        }
+        overview
+    }
+}
+
+fn is_metadata_line(line: &str) -> bool {
+    if line.starts_with("<!--") || line == "---" || line.starts_with("[内容已截断") {
+        return true;
+    }
+    let Some(rest) = line.strip_prefix("**") else {
+        return false;
+    };
+    rest.split_once("**:")
+        .is_some_and(|(key, _)| METADATA_KEYS.contains(&key.trim().to_lowercase().as_str()))
+}
+
+fn is_topic_heading(heading: &str) -> bool {
+    let words: String = heading
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect();
+    let lower = words.trim().to_lowercase();
+    !lower.is_empty()
+        && !GENERIC_HEADINGS.contains(&lower.as_str())
+        && !lower.starts_with("message")
+        && !lower.starts_with("timeline")
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim_start_matches(['-', '*', '+', '>']).trim_start();
+    // Numbered items: "1. ", "12) "
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return rest.trim_start();
+        }
+    }
+    line
+}
+
+/// Drop inline Markdown emphasis and code markers
+fn clean_inline(text: &str) -> String {
+    text.replace("**", "")
+        .replace("__", "")
+        .replace('`', "")
+        .trim()
+        .to_string()
+}
+
+/// Sentences of a line; text without terminal punctuation is one sentence
+fn split_sentences(line: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        let ends = match c {
+            '。' | '！' | '？' | '；' => true,
+            // Only at a word boundary, so "3.5" or "v1.2" stay intact
+            '.' | '!' | '?' => chars.peek().is_none_or(|next| next.is_whitespace()),
+            _ => false,
+        };
+        if ends {
+            push_sentence(&mut result, &current);
+            current.clear();
+        }
+    }
+    push_sentence(&mut result, &current);
+    result
+}
+
+fn push_sentence(sentences: &mut Vec<String>, text: &str) {
+    let text = text.trim();
+    if text.chars().filter(|c| c.is_alphanumeric()).count() >= 2 {
+        sentences.push(text.to_string());
+    }
+}
+
+/// Keyword terms: lowercase words of alphabetic scripts, bigrams of CJK runs
+fn terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    let flush_word = |word: &mut String, terms: &mut Vec<String>| {
+        let lower = word.to_lowercase();
+        if lower.chars().count() >= 3
+            && !lower.chars().all(|c| c.is_ascii_digit())
+            && !STOPWORDS.contains(&lower.as_str())
+        {
+            terms.push(lower);
+        }
+        word.clear();
+    };
+    let flush_cjk = |run: &mut Vec<char>, terms: &mut Vec<String>| {
+        for pair in run.windows(2) {
+            if !pair.iter().any(|c| CJK_STOPCHARS.contains(*c)) {
+                terms.push(pair.iter().collect());
+            }
+        }
+        run.clear();
+    };
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut terms);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() || c == '-' || c == '_' {
+            flush_cjk(&mut cjk_run, &mut terms);
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut terms);
+            flush_cjk(&mut cjk_run, &mut terms);
+        }
+    }
+    flush_word(&mut word, &mut terms);
+    flush_cjk(&mut cjk_run, &mut terms);
+    terms
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::validation::LayerValidationConfig;
+
+    const ENGLISH: &str = "\n\n=== tea.md ===\n\n# Tea Preferences\n\n**Added**: 2026-01-01 00:00:00 UTC\n\nAlice drinks green tea every morning. She dislikes coffee because coffee upsets her stomach. Green tea keeps her focused.\n\n=== work.md ===\n\n# Work\n\nAlice works at Acme on the search team. She leads the search ranking project.\n\n```json\n{\"confidence\": 0.9}\n```\n";
+
+    const CHINESE: &str = "=== 偏好.md ===\n\n# 饮食偏好\n\n用户每天早上喝绿茶。用户不喝咖啡，因为咖啡让他胃不舒服。绿茶帮助用户保持专注。\n\n=== 工作.md ===\n\n用户在搜索团队工作，负责搜索排序项目。";
+
+    #[test]
+    fn test_abstract_keeps_leading_sentences() {
+        let l0 = summarize(ContextLayer::L0Abstract, ENGLISH, 400);
+        assert!(l0.ends_with(EXTRACTIVE_MARKER));
+        assert!(l0.starts_with("Alice drinks green tea every morning."));
+        assert!(!l0.contains("Added"));
+        assert!(!l0.contains("confidence"));
+    }
+
+    #[test]
+    fn test_overview_sections() {
+        let l1 = summarize(ContextLayer::L1Overview, ENGLISH, 2000);
+        assert!(l1.starts_with("## Summary\n\n"));
+        assert!(l1.contains("## Core Topics\n\n- Tea Preferences\n- Work"));
+        assert!(l1.contains("## Key Points\n\n- "));
+        assert!(is_extractive(&l1));
+
+        // Extractive output passes the generation-time validation
+        let config = LayerValidationConfig::default();
+        assert!(validation::validate(ContextLayer::L1Overview, ENGLISH, &l1, &config).is_empty());
+    }
+
+    #[test]
+    fn test_chinese_source() {
+        let l0 = summarize(ContextLayer::L0Abstract, CHINESE, 400);
+        assert!(l0.starts_with("用户每天早上喝绿茶。"));
+        assert!(!l0.contains(" 用户"));
+
+        let l1 = summarize(ContextLayer::L1Overview, CHINESE, 2000);
+        assert!(l1.contains("- 饮食偏好"));
+        let config = LayerValidationConfig::default();
+        assert!(validation::validate(ContextLayer::L1Overview, CHINESE, &l1, &config).is_empty());
+    }
+
+    #[test]
+    fn test_token_limit() {
+        let long = "Alice likes tea. ".repeat(200);
+        let l0 = summarize(ContextLayer::L0Abstract, &long, 10);
+        let body = l0.trim_end_matches(EXTRACTIVE_MARKER).trim();
+        assert!(AbstractGenerator::estimate_tokens(body) <= 10);
+
+        let l1 = summarize(ContextLayer::L1Overview, &long, 60);
+        let body = l1.trim_end_matches(EXTRACTIVE_MARKER).trim();
+        assert!(AbstractGenerator::estimate_tokens(body) <= 60);
+    }
+
+    #[test]
+    fn test_terms() {
+        assert_eq!(terms("The Rust compiler, v1.2"), vec!["rust", "compiler"]);
+        assert_eq!(terms("喝绿茶"), vec!["喝绿", "绿茶"]);
+        assert_eq!(terms("我的茶"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_is_extractive() {
+        assert!(is_extractive(
+            "text\n\n<!-- generator: extractive -->\n\n**Added**: x"
+        ));
+        assert!(!is_extractive(
+            "text\n\n**Added**: x\n<!-- source-hash: 1 -->"
+        ));
+    }
+}
@@ -1,7 +1,9 @@
-use crate::{Result, llm::LLMClient};
+use super::extractive;
+use super::validation::{self, LayerValidationConfig};
+use crate::{ContextLayer, LayerMode, LlmTask, Result, llm::LLMClient, llm::PromptTemplates};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Abstract (L0) generator
 ///
@@ -9,11 +11,43 @@ use tracing::{debug, info};
 /// for quick relevance checking and filtering.
 /// Supports entity preservation to prevent named entities from being
 /// compressed away during summarization.
-pub struct AbstractGenerator;
+/// Outputs are validated (see [`validation`]) and retried or replaced by an
+/// extractive summary when they fail; see [`LayerMode`] for LLM-free generation.
+pub struct AbstractGenerator {
+    templates: PromptTemplates,
+    validation: LayerValidationConfig,
+    mode: LayerMode,
+}
 
 impl AbstractGenerator {
     pub fn new() -> Self {
-        Self
+        Self::with_templates(PromptTemplates::builtin())
+    }
+
+    /// Use the given (possibly tenant-overridden) prompt templates
+    pub fn with_templates(templates: PromptTemplates) -> Self {
+        Self {
+            templates,
+            validation: LayerValidationConfig::default(),
+            mode: LayerMode::default(),
+        }
+    }
+
+    /// Replace the output guardrails
+    pub fn with_validation(mut self, validation: LayerValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Choose between LLM and extractive generation
+    pub fn with_mode(mut self, mode: LayerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Version of the prompts in use, for LLM result cache keys
+    pub fn template_version(&self) -> String {
+        self.templates.version(&["abstract", "abstract_system"])
     }
 
     /// Generate abstract from content using LLM.
@@ -27,26 +61,27 @@ impl AbstractGenerator {
         llm: &Arc<dyn LLMClient>,
         known_entities: &[String],
     ) -> Result<String> {
-        // 截断保护：content 最多取前 8000 个字符（abstract_generation_with_entities 内部也会截断，双重保护）
+        // 截断保护：content 最多取前 8000 个字符（abstract_prompt 内部也会截断，双重保护）
         let char_count = content.chars().count();
         info!(
             "Generating L0 Abstract (content: {} chars, entities: {:?})",
             char_count, known_entities
         );
 
-        let system = r#"You are an expert at creating concise abstracts.
-Your goal is to generate summaries that capture multiple key aspects of content for quick relevance checking.
-Keep abstracts under 100 tokens. Prioritize breadth over depth - cover more topics briefly rather than elaborating on one.
-Be direct and informative. Use compact phrasing to maximize information density.
-When asked to preserve specific named entities, include them verbatim in the abstract."#;
-
-        let prompt = crate::llm::prompts::Prompts::abstract_generation_with_entities(
-            content,
-            known_entities,
-        );
+        let system = self.templates.get("abstract_system");
+        let prompt = self.templates.abstract_prompt(content, known_entities);
         debug!("L0 Abstract prompt length: {} chars", prompt.chars().count());
 
-        let result = llm.complete_with_system(system, &prompt).await?;
+        let result = generate_layer(
+            llm,
+            LlmTask::Abstract,
+            &system,
+            &prompt,
+            content,
+            &self.validation,
+            self.mode,
+        )
+        .await?;
 
         info!("L0 Abstract generated ({} chars)", result.chars().count());
         Ok(result)
@@ -61,7 +96,11 @@ When asked to preserve specific named entities, include them verbatim in the abs
 /// Overview (L1) generator
 ///
 /// Generates structured overview (~500-2000 tokens) from content using LLM
-pub struct OverviewGenerator;
+pub struct OverviewGenerator {
+    templates: PromptTemplates,
+    validation: LayerValidationConfig,
+    mode: LayerMode,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Overview {
@@ -73,7 +112,33 @@ pub struct Overview {
 
 impl OverviewGenerator {
     pub fn new() -> Self {
-        Self
+        Self::with_templates(PromptTemplates::builtin())
+    }
+
+    /// Use the given (possibly tenant-overridden) prompt templates
+    pub fn with_templates(templates: PromptTemplates) -> Self {
+        Self {
+            templates,
+            validation: LayerValidationConfig::default(),
+            mode: LayerMode::default(),
+        }
+    }
+
+    /// Replace the output guardrails
+    pub fn with_validation(mut self, validation: LayerValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Choose between LLM and extractive generation
+    pub fn with_mode(mut self, mode: LayerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Version of the prompts in use, for LLM result cache keys
+    pub fn template_version(&self) -> String {
+        self.templates.version(&["overview", "overview_system"])
     }
 
     /// Generate overview from content using LLM
@@ -82,25 +147,112 @@ impl OverviewGenerator {
         content: &str,
         llm: &Arc<dyn LLMClient>,
     ) -> Result<String> {
-        // 截断保护：overview 内容最多取前 16000 个字符
-        let safe_content: String = content.chars().take(16000).collect();
-
+        // 截断保护：overview 内容最多取前 16000 个字符（在 overview_prompt 内部完成）
+        let char_count = content.chars().count();
         info!(
             "Generating L1 Overview (content: {} chars, truncated from {})",
-            safe_content.chars().count(),
-            content.chars().count()
+            char_count.min(16000),
+            char_count
         );
 
-        let system = r#"You are an expert at creating structured overviews.
-Your goal is to provide comprehensive yet concise summaries (500-2000 tokens) that help users understand and make decisions about content.
-Use clear markdown structure with sections for Summary, Core Topics, Key Points, Entities, and Context."#;
-
-        let prompt = crate::llm::prompts::Prompts::overview_generation(&safe_content);
+        let system = self.templates.get("overview_system");
+        let prompt = self.templates.overview_prompt(content);
         debug!("L1 Overview prompt length: {} chars", prompt.chars().count());
 
-        let result = llm.complete_with_system(system, &prompt).await?;
+        let result = generate_layer(
+            llm,
+            LlmTask::Overview,
+            &system,
+            &prompt,
+            content,
+            &self.validation,
+            self.mode,
+        )
+        .await?;
 
         info!("L1 Overview generated ({} chars)", result.chars().count());
         Ok(result)
     }
 }
+
+/// Produce a layer according to the generation mode
+///
+/// `Extractive` never calls the LLM; `Auto` falls back to an extractive
+/// summary when the LLM call fails (unreachable, unconfigured, over budget).
+async fn generate_layer(
+    llm: &Arc<dyn LLMClient>,
+    task: LlmTask,
+    system: &str,
+    prompt: &str,
+    content: &str,
+    config: &LayerValidationConfig,
+    mode: LayerMode,
+) -> Result<String> {
+    let layer = match task {
+        LlmTask::Abstract => ContextLayer::L0Abstract,
+        _ => ContextLayer::L1Overview,
+    };
+
+    match mode {
+        LayerMode::Extractive => Ok(extractive::summarize(
+            layer,
+            content,
+            config.token_limit(layer),
+        )),
+        LayerMode::Llm => {
+            complete_validated(llm, task, layer, system, prompt, content, config).await
+        }
+        LayerMode::Auto => {
+            match complete_validated(llm, task, layer, system, prompt, content, config).await {
+                Ok(result) => Ok(result),
+                Err(e) => {
+                    warn!("LLM unavailable for {:?} ({}), using extractive summary", layer, e);
+                    Ok(extractive::summarize(layer, content, config.token_limit(layer)))
+                }
+            }
+        }
+    }
+}
+
+/// Run a layer prompt and validate the answer against its source
+///
+/// Failing answers are retried with the problems appended to the prompt; once
+/// the retries are used up the last answer is repaired (truncated) or
+/// replaced by an extractive summary of the source.
+async fn complete_validated(
+    llm: &Arc<dyn LLMClient>,
+    task: LlmTask,
+    layer: ContextLayer,
+    system: &str,
+    prompt: &str,
+    content: &str,
+    config: &LayerValidationConfig,
+) -> Result<String> {
+    let mut result = llm.complete_for_task(task, Some(system), prompt).await?;
+    if !config.enabled {
+        return Ok(result);
+    }
+
+    let mut attempt = 0;
+    loop {
+        let issues = validation::validate(layer, content, &result, config);
+        if issues.is_empty() {
+            return Ok(result);
+        }
+
+        let summary = issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        if attempt >= config.max_retries {
+            warn!("{:?} still invalid after {} retries ({}), falling back", layer, attempt, summary);
+            return Ok(validation::repair(layer, content, &result, &issues, config));
+        }
+
+        attempt += 1;
+        warn!("{:?} failed validation ({}), retrying", layer, summary);
+        let retry_prompt = format!("{}\n\n{}", prompt, validation::retry_note(&issues));
+        result = llm.complete_for_task(task, Some(system), &retry_prompt).await?;
+    }
+}
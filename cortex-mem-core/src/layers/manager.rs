@@ -1,5 +1,5 @@
-use crate::{ContextLayer, CortexFilesystem, FilesystemOperations, Result, FileEntry};
-use crate::llm::LLMClient;
+use crate::{ContextLayer, CortexFilesystem, FilesystemOperations, LayerMode, Result, FileEntry};
+use crate::llm::{LLMClient, PromptTemplates};
 use std::sync::Arc;
 
 use super::generator::{AbstractGenerator, OverviewGenerator};
@@ -19,13 +19,21 @@ pub struct LayerManager {
 impl LayerManager {
     /// Create a new LayerManager with mandatory LLM client
     pub fn new(filesystem: Arc<CortexFilesystem>, llm_client: Arc<dyn LLMClient>) -> Self {
+        let templates = PromptTemplates::for_filesystem(&filesystem);
         Self {
             filesystem,
-            abstract_gen: AbstractGenerator::new(),
-            overview_gen: OverviewGenerator::new(),
+            abstract_gen: AbstractGenerator::with_templates(templates.clone()),
+            overview_gen: OverviewGenerator::with_templates(templates),
             llm_client,
         }
     }
+
+    /// Generate with the LLM, extractively, or LLM with extractive fallback
+    pub fn with_mode(mut self, mode: LayerMode) -> Self {
+        self.abstract_gen = self.abstract_gen.with_mode(mode);
+        self.overview_gen = self.overview_gen.with_mode(mode);
+        self
+    }
     
     /// Load content for a specific layer
     pub async fn load(&self, uri: &str, layer: ContextLayer) -> Result<String> {
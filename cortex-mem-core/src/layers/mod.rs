@@ -1,3 +1,5 @@
+pub mod extractive;
 pub mod generator;
 pub mod manager;
 pub mod reader;
+pub mod validation;
@@ -0,0 +1,584 @@
+//! Generation-time validation of L0 abstracts and L1 overviews
+//!
+//! LLM output is checked against the source it was generated from: token
+//! budget, script (language) of the text, capitalized names that never occur
+//! in the source, and the overview sections the prompt asks for. The
+//! generators retry with the problems spelled out and fall back to an
+//! extractive summary of the source when the retry still fails.
+
+use crate::ContextLayer;
+use crate::layers::extractive;
+use crate::layers::generator::AbstractGenerator;
+use std::fmt;
+
+/// Minimum number of letters before the script of a text is judged
+const MIN_SCRIPT_LETTERS: usize = 20;
+
+/// Share of CJK letters above which a text counts as CJK
+///
+/// Chinese notes routinely embed English identifiers, so a fifth of the
+/// letters being Han already means the text is written in Chinese.
+const CJK_SHARE: f32 = 0.2;
+
+/// Capitalized words that are not names, or that the model may legitimately
+/// derive from dates and roles in the source
+const ENTITY_STOPWORDS: &[&str] = &[
+    "The",
+    "This",
+    "That",
+    "These",
+    "Those",
+    "There",
+    "They",
+    "It",
+    "Its",
+    "We",
+    "He",
+    "She",
+    "User",
+    "Users",
+    "Assistant",
+    "Agent",
+    "Session",
+    "Memory",
+    "Memories",
+    "Markdown",
+    "None",
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Translated names accepted for the built-in overview section headings
+const SECTION_ALIASES: &[(&str, &[&str])] = &[
+    ("Summary", &["摘要", "概述", "总结", "概要"]),
+    ("Core Topics", &["核心主题", "主要主题", "主题"]),
+    ("Key Points", &["要点", "关键点", "关键要点"]),
+    ("Entities", &["实体"]),
+    ("Timeline Events", &["时间线", "事件"]),
+    ("Activities & Interests", &["活动", "兴趣"]),
+    ("Context", &["背景", "上下文"]),
+];
+
+/// Guardrails applied to generated L0/L1 layers
+#[derive(Debug, Clone)]
+pub struct LayerValidationConfig {
+    /// Validate (and retry / fall back) at all
+    pub enabled: bool,
+    /// Token budget of an L0 abstract (same estimate as `AbstractGenerator::estimate_tokens`)
+    pub max_abstract_tokens: usize,
+    /// Token budget of an L1 overview
+    pub max_overview_tokens: usize,
+    /// Require the output to be written in the script of the source
+    pub check_language: bool,
+    /// Number of unknown capitalized names tolerated before the output counts as hallucinated
+    pub max_unknown_entities: usize,
+    /// Headings every overview must contain
+    pub required_overview_sections: Vec<String>,
+    /// LLM retries before falling back
+    pub max_retries: usize,
+}
+
+impl Default for LayerValidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_abstract_tokens: 400,
+            max_overview_tokens: 2000,
+            check_language: true,
+            max_unknown_entities: 2,
+            required_overview_sections: vec!["Summary".to_string(), "Key Points".to_string()],
+            max_retries: 1,
+        }
+    }
+}
+
+impl LayerValidationConfig {
+    /// Token budget for the given layer
+    pub fn token_limit(&self, layer: ContextLayer) -> usize {
+        match layer {
+            ContextLayer::L0Abstract => self.max_abstract_tokens,
+            _ => self.max_overview_tokens,
+        }
+    }
+}
+
+/// Writing system of a text, used as a cheap language proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Cjk,
+    Latin,
+    Cyrillic,
+    Other,
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Script::Cjk => "CJK",
+            Script::Latin => "Latin",
+            Script::Cyrillic => "Cyrillic",
+            Script::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A problem found in a generated (or stored) layer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerIssue {
+    /// No text besides metadata
+    Empty,
+    /// Over the token budget
+    Oversized { tokens: usize, limit: usize },
+    /// Written in a different script than the source
+    LanguageMismatch { expected: Script, found: Script },
+    /// Names that do not occur in the source
+    UnknownEntities(Vec<String>),
+    /// Required overview headings that are absent
+    MissingSections(Vec<String>),
+    /// Source content changed since the layer was generated
+    Stale,
+}
+
+impl fmt::Display for LayerIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerIssue::Empty => write!(f, "empty"),
+            LayerIssue::Oversized { tokens, limit } => {
+                write!(f, "oversized (~{} tokens, limit {})", tokens, limit)
+            }
+            LayerIssue::LanguageMismatch { expected, found } => {
+                write!(f, "wrong language ({} text for {} source)", found, expected)
+            }
+            LayerIssue::UnknownEntities(names) => {
+                write!(f, "names not in source: {}", names.join(", "))
+            }
+            LayerIssue::MissingSections(sections) => {
+                write!(f, "missing sections: {}", sections.join(", "))
+            }
+            LayerIssue::Stale => write!(f, "stale (source changed)"),
+        }
+    }
+}
+
+/// Validate a generated layer against its source content
+pub fn validate(
+    layer: ContextLayer,
+    source: &str,
+    output: &str,
+    config: &LayerValidationConfig,
+) -> Vec<LayerIssue> {
+    let mut issues = Vec::new();
+    let output = output.trim();
+
+    if output.is_empty() {
+        issues.push(LayerIssue::Empty);
+        return issues;
+    }
+
+    let tokens = AbstractGenerator::estimate_tokens(output);
+    let limit = config.token_limit(layer);
+    if tokens > limit {
+        issues.push(LayerIssue::Oversized { tokens, limit });
+    }
+
+    if config.check_language {
+        issues.extend(check_language(source, output));
+    }
+
+    let unknown = unknown_entities(source, output);
+    if unknown.len() > config.max_unknown_entities {
+        issues.push(LayerIssue::UnknownEntities(unknown));
+    }
+
+    if layer == ContextLayer::L1Overview {
+        let missing = missing_sections(output, &config.required_overview_sections);
+        if !missing.is_empty() {
+            issues.push(LayerIssue::MissingSections(missing));
+        }
+    }
+
+    issues
+}
+
+/// Check a stored layer file (footer included) against its source content
+///
+/// Only the problems that can be judged after the fact are reported: empty,
+/// oversized and wrong-language layers. Staleness needs the source hash and
+/// is decided by the caller.
+pub fn audit(
+    layer: ContextLayer,
+    source: &str,
+    layer_content: &str,
+    config: &LayerValidationConfig,
+) -> Vec<LayerIssue> {
+    let body = strip_layer_metadata(layer_content);
+    if body.is_empty() {
+        return vec![LayerIssue::Empty];
+    }
+
+    let mut issues = Vec::new();
+    let tokens = AbstractGenerator::estimate_tokens(&body);
+    let limit = config.token_limit(layer);
+    if tokens > limit {
+        issues.push(LayerIssue::Oversized { tokens, limit });
+    }
+    if config.check_language {
+        issues.extend(check_language(source, &body));
+    }
+    issues
+}
+
+/// Layer text without the `**Added**` / `<!-- ... -->` footer
+pub fn strip_layer_metadata(layer_content: &str) -> String {
+    let body = layer_content
+        .lines()
+        .filter(|line| !line.starts_with("**Added**:") && !line.starts_with("<!--"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    body.trim().trim_end_matches("---").trim().to_string()
+}
+
+/// Compare the dominant script of source and output
+pub fn check_language(source: &str, output: &str) -> Option<LayerIssue> {
+    let expected = dominant_script(source)?;
+    let found = dominant_script(output)?;
+    (expected != found).then_some(LayerIssue::LanguageMismatch { expected, found })
+}
+
+/// Dominant script of a text, `None` when it has too few letters to tell
+pub fn dominant_script(text: &str) -> Option<Script> {
+    let (mut cjk, mut latin, mut cyrillic, mut other) = (0usize, 0usize, 0usize, 0usize);
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        if is_cjk(c) {
+            cjk += 1;
+        } else if c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c) {
+            latin += 1;
+        } else if ('\u{0400}'..='\u{04FF}').contains(&c) {
+            cyrillic += 1;
+        } else {
+            other += 1;
+        }
+    }
+
+    let total = cjk + latin + cyrillic + other;
+    if total < MIN_SCRIPT_LETTERS {
+        return None;
+    }
+    if cjk as f32 / total as f32 >= CJK_SHARE {
+        return Some(Script::Cjk);
+    }
+
+    let script = if latin >= cyrillic && latin >= other {
+        Script::Latin
+    } else if cyrillic >= other {
+        Script::Cyrillic
+    } else {
+        Script::Other
+    };
+    Some(script)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}')
+}
+
+/// Capitalized words of the output that never occur in the source
+///
+/// Only mid-sentence words are considered (sentence starts are capitalized
+/// anyway) and headings are skipped, so what remains are names. CJK names
+/// cannot be told apart from ordinary words and are not checked.
+pub fn unknown_entities(source: &str, output: &str) -> Vec<String> {
+    let source_lower = source.to_lowercase();
+    let mut unknown: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        // A list item starts a sentence just like a line does
+        let line =
+            line.trim_start_matches(|c: char| matches!(c, '-' | '*' | '+') || c.is_whitespace());
+
+        let mut sentence_start = true;
+        let mut word = String::new();
+        for c in line.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() || c == '\'' || c == '-' {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                let candidate = word.trim_matches(|c| c == '\'' || c == '-');
+                if candidate.is_empty() {
+                    word.clear();
+                    continue;
+                }
+                if !sentence_start
+                    && is_name_like(candidate)
+                    && !source_lower.contains(&candidate.to_lowercase())
+                    && !unknown.iter().any(|u| u == candidate)
+                {
+                    unknown.push(candidate.to_string());
+                }
+                sentence_start = false;
+                word.clear();
+            }
+            if matches!(c, '.' | '!' | '?' | ':' | '。' | '！' | '？' | '：') {
+                sentence_start = true;
+            }
+        }
+    }
+
+    unknown
+}
+
+fn is_name_like(word: &str) -> bool {
+    let mut chars = word.chars();
+    let first_upper = chars.next().is_some_and(|c| c.is_ascii_uppercase());
+    first_upper && word.chars().count() >= 3 && !ENTITY_STOPWORDS.contains(&word)
+}
+
+/// Required sections that have no matching heading in the overview
+pub fn missing_sections(overview: &str, required: &[String]) -> Vec<String> {
+    let headings: Vec<String> = overview
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect();
+
+    required
+        .iter()
+        .filter(|section| {
+            let name = section.to_lowercase();
+            let aliases = SECTION_ALIASES
+                .iter()
+                .find(|(canonical, _)| canonical.eq_ignore_ascii_case(section))
+                .map(|(_, aliases)| *aliases)
+                .unwrap_or(&[]);
+            !headings
+                .iter()
+                .any(|h| h.contains(&name) || aliases.iter().any(|a| h.contains(a)))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Note appended to the prompt when the previous answer failed validation
+pub fn retry_note(issues: &[LayerIssue]) -> String {
+    let mut note =
+        String::from("Your previous answer was rejected. Write it again and fix these problems:\n");
+    for issue in issues {
+        let line = match issue {
+            LayerIssue::Empty => "- The answer was empty.".to_string(),
+            LayerIssue::Oversized { limit, .. } => {
+                format!("- It was too long; stay under about {} tokens.", limit)
+            }
+            LayerIssue::LanguageMismatch { expected, .. } => format!(
+                "- It was not written in the language of the content ({} script).",
+                expected
+            ),
+            LayerIssue::UnknownEntities(names) => format!(
+                "- It mentioned names that do not appear in the content: {}. Only use names from the content.",
+                names.join(", ")
+            ),
+            LayerIssue::MissingSections(sections) => {
+                format!("- It lacked the sections: {}.", sections.join(", "))
+            }
+            LayerIssue::Stale => continue,
+        };
+        note.push_str(&line);
+        note.push('\n');
+    }
+    note
+}
+
+/// Make an output that still fails validation usable
+///
+/// An answer that is only too long is cut back at a sentence (L0) or
+/// paragraph (L1) boundary; anything else is replaced by an extractive
+/// summary of the source.
+pub fn repair(
+    layer: ContextLayer,
+    source: &str,
+    output: &str,
+    issues: &[LayerIssue],
+    config: &LayerValidationConfig,
+) -> String {
+    let only_oversized = issues
+        .iter()
+        .all(|issue| matches!(issue, LayerIssue::Oversized { .. }));
+    if only_oversized {
+        truncate_to_tokens(layer, output.trim(), config.token_limit(layer))
+    } else {
+        extractive::summarize(layer, source, config.token_limit(layer))
+    }
+}
+
+/// Cut text down to roughly `limit` tokens at a natural boundary
+pub fn truncate_to_tokens(layer: ContextLayer, text: &str, limit: usize) -> String {
+    // estimate_tokens counts 3 bytes per token
+    let max_bytes = limit * 3;
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &text[..end];
+
+    let boundary = match layer {
+        ContextLayer::L0Abstract => head
+            .rfind(['。', '.', '?', '!', '！', '？'])
+            .map(|pos| pos + head[pos..].chars().next().map_or(1, char::len_utf8)),
+        _ => head.rfind("\n\n"),
+    };
+
+    match boundary {
+        Some(pos) if pos > 0 => head[..pos].trim_end().to_string(),
+        _ => format!("{}...", head.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dominant_script() {
+        assert_eq!(
+            dominant_script("The user prefers dark mode in every editor they use."),
+            Some(Script::Latin)
+        );
+        assert_eq!(
+            dominant_script("用户喜欢在所有编辑器中使用 dark mode 深色主题，并且经常切换字体。"),
+            Some(Script::Cjk)
+        );
+        assert_eq!(dominant_script("ok"), None);
+    }
+
+    #[test]
+    fn test_language_mismatch() {
+        let source = "用户正在学习 Rust 语言，计划下个月完成第一个开源项目，并每天练习两个小时。";
+        let english =
+            "The user is learning Rust and plans to finish an open source project next month.";
+        assert_eq!(
+            check_language(source, english),
+            Some(LayerIssue::LanguageMismatch {
+                expected: Script::Cjk,
+                found: Script::Latin
+            })
+        );
+        assert_eq!(check_language(source, source), None);
+    }
+
+    #[test]
+    fn test_unknown_entities() {
+        let source = "Alice met Bob in Berlin to discuss the cortex roadmap.";
+        let output = "The meeting between Alice and Bob in Berlin covered the Cortex roadmap with Carol from Acme.";
+        assert_eq!(unknown_entities(source, output), vec!["Carol", "Acme"]);
+    }
+
+    #[test]
+    fn test_missing_sections() {
+        let required = vec!["Summary".to_string(), "Key Points".to_string()];
+        assert!(
+            missing_sections("## Summary\n\ntext\n\n## Key Points\n\n- a", &required).is_empty()
+        );
+        assert!(missing_sections("## 摘要\n\n内容\n\n## 要点\n\n- 一", &required).is_empty());
+        assert_eq!(
+            missing_sections("## Summary\n\ntext", &required),
+            vec!["Key Points".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_validate_overview() {
+        let config = LayerValidationConfig::default();
+        let source = "Alice prefers tea over coffee and drinks it every morning before work.";
+        let good =
+            "## Summary\n\nAlice prefers tea.\n\n## Key Points\n\n- Drinks tea every morning";
+        assert!(validate(ContextLayer::L1Overview, source, good, &config).is_empty());
+        assert_eq!(
+            validate(ContextLayer::L1Overview, source, "   ", &config),
+            vec![LayerIssue::Empty]
+        );
+    }
+
+    #[test]
+    fn test_audit_stored_layer() {
+        let config = LayerValidationConfig::default();
+        let source = "Alice prefers tea over coffee and drinks it every morning before work.";
+        let footer_only =
+            "\n\n---\n\n**Added**: 2026-01-01 00:00:00 UTC\n<!-- source-hash: 1a2b -->";
+        assert_eq!(
+            audit(ContextLayer::L1Overview, source, footer_only, &config),
+            vec![LayerIssue::Empty]
+        );
+
+        let chinese = "用户喜欢喝茶，不喝咖啡，每天早上上班前都会喝一杯绿茶。\n\n**Added**: 2026-01-01 00:00:00 UTC";
+        assert_eq!(
+            audit(ContextLayer::L0Abstract, source, chinese, &config),
+            vec![LayerIssue::LanguageMismatch {
+                expected: Script::Latin,
+                found: Script::Cjk
+            }]
+        );
+    }
+
+    #[test]
+    fn test_repair_truncates_oversized_abstract() {
+        let config = LayerValidationConfig {
+            max_abstract_tokens: 10,
+            ..Default::default()
+        };
+        let output = "First sentence here. Second sentence is much longer than the limit allows.";
+        let issues = validate(ContextLayer::L0Abstract, output, output, &config);
+        assert!(matches!(issues.as_slice(), [LayerIssue::Oversized { .. }]));
+        assert_eq!(
+            repair(ContextLayer::L0Abstract, output, output, &issues, &config),
+            "First sentence here."
+        );
+    }
+
+    #[test]
+    fn test_repair_falls_back_to_extractive() {
+        let config = LayerValidationConfig::default();
+        let source = "Alice drinks green tea every morning. She dislikes coffee.";
+        let output = "Bob and Carol from Initech love espresso and Dave agrees.";
+        let issues = validate(ContextLayer::L0Abstract, source, output, &config);
+        assert!(matches!(
+            issues.as_slice(),
+            [LayerIssue::UnknownEntities(_)]
+        ));
+
+        let repaired = repair(ContextLayer::L0Abstract, source, output, &issues, &config);
+        assert!(repaired.starts_with("Alice drinks green tea every morning."));
+        assert!(extractive::is_extractive(&repaired));
+    }
+}
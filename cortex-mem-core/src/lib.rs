@@ -36,21 +36,25 @@
 //!
 //! ## 模块说明
 //!
-//! - [`filesystem`]: 文件系统操作和 URI 处理
-//! - [`session`]: 会话管理和消息处理
-//! - [`vector_store`]: 向量存储接口
-//! - [`embedding`]: Embedding 生成客户端
-//! - [`search`]: 向量搜索引擎
-//! - [`automation`]: 自动化索引和提取
-//! - [`extraction`]: 记忆提取和分类
-//! - [`llm`]: LLM 客户端接口
-//! - [`memory_index`]: 记忆索引和版本追踪
-//! - [`memory_events`]: 记忆事件系统
-//! - [`memory_index_manager`]: 记忆索引管理器
-//! - [`incremental_memory_updater`]: 增量记忆更新器
-//! - [`cascade_layer_updater`]: 层级联动更新器
-//! - [`vector_sync_manager`]: 向量同步管理器
-//! - [`memory_event_coordinator`]: 记忆事件协调器
+//! - [`filesystem`][]: 文件系统操作和 URI 处理
+//! - [`session`][]: 会话管理和消息处理
+//! - [`vector_store`][]: 向量存储接口
+//! - [`embedding`][]: Embedding 生成客户端
+//! - [`search`][]: 向量搜索引擎
+//! - [`automation`][]: 自动化索引和提取
+//! - [`extraction`](session::extraction): 记忆提取和分类
+//! - [`llm`][]: LLM 客户端接口
+//! - [`memory_index`][]: 记忆索引和版本追踪
+//! - [`memory_events`][]: 记忆事件系统
+//! - [`memory_index_manager`][]: 记忆索引管理器
+//! - [`incremental_memory_updater`][]: 增量记忆更新器
+//! - [`cascade_layer_updater`][]: 层级联动更新器
+//! - [`vector_sync_manager`][]: 向量同步管理器
+//! - [`memory_event_coordinator`][]: 记忆事件协调器
+//! - [`metrics`][]: Prometheus 管道指标
+//! - [`access`][]: 共享记忆空间与访问控制
+//! - [`usage`][]: Token 用量统计与预算
+//! - [`fixtures`][]: LLM / Embedding 调用的录制与回放（离线测试）
 
 pub mod config;
 pub mod error;
@@ -76,9 +80,14 @@ pub mod incremental_memory_updater;
 pub mod cascade_layer_updater;
 pub mod cascade_layer_debouncer;  // Phase 2 optimization
 pub mod llm_result_cache;          // Phase 3 optimization (LLM cache only)
+mod disk_store;                    // File-backed store behind the persistent caches
 pub mod vector_sync_manager;
 pub mod memory_event_coordinator;
 pub mod memory_cleanup;  // Phase v2.6: forgetting mechanism
+pub mod metrics;         // Prometheus pipeline metrics
+pub mod access;          // Shared spaces and access control
+pub mod usage;           // Token usage accounting and budgets
+pub mod fixtures;        // Record / replay of LLM and embedding calls
 
 // Re-exports
 pub use config::*;
@@ -93,15 +102,21 @@ pub use automation::{
 };
 pub use builder::{CortexMem, CortexMemBuilder};
 // Note: MemoryExtractor is exported from session module
-pub use embedding::{EmbeddingClient, EmbeddingConfig};
-pub use filesystem::{CortexFilesystem, FilesystemOperations};
-pub use llm::LLMClient;
+pub use embedding::{EmbeddingClient, EmbeddingConfig, EmbeddingDiskCache, EmbeddingDiskStats};
+#[cfg(feature = "local-embedding")]
+pub use embedding::LocalEmbedder;
+pub use filesystem::{CortexFilesystem, FilesystemOperations, ListOptions, ListPage, ListSort};
+pub use llm::{LLMClient, PromptTemplate, PromptTemplates};
 pub use search::{SearchOptions, VectorSearchEngine, SearchResult, QueryIntentType, EnhancedQueryIntent};
 pub use session::{
-    CaseMemory, EntityMemory, EventMemory, ExtractedMemories, MemoryExtractor, Message,
-    MessageRole, Participant, ParticipantManager, PreferenceMemory, SessionConfig, SessionManager,
+    CaseMemory, EntityMemory, EventMemory, ExtractedMemories, ImportedThread, InstructionMemory,
+    MemoryExtractor, Message, MessageRole, Participant, ParticipantManager, PreferenceMemory, SessionConfig,
+    SessionManager, SkillMemory,
+};
+pub use vector_store::{
+    MigrationPhase, MigrationTarget, QdrantVectorStore, VectorMigration, VectorMigrator, VectorStore,
+    parse_vector_id, uri_to_vector_id,
 };
-pub use vector_store::{QdrantVectorStore, VectorStore, parse_vector_id, uri_to_vector_id};
 
 // MemoryType from memory_index is the primary type for
 pub use memory_index::{
@@ -112,13 +127,26 @@ pub use memory_events::{
     ChangeType, DeleteReason, EventStats, MemoryEvent,
 };
 pub use memory_index_manager::MemoryIndexManager;
-pub use incremental_memory_updater::{IncrementalMemoryUpdater, MemoryItem};
+pub use incremental_memory_updater::{
+    IncrementalMemoryUpdater, InvalidMemory, MANUAL_SOURCE, ManualMemory, MemoryItem,
+};
 pub use cascade_layer_updater::{CascadeLayerUpdater, UpdateStats};
 pub use cascade_layer_debouncer::{LayerUpdateDebouncer, DebouncerConfig};  // Phase 2
-pub use llm_result_cache::{LlmResultCache, CacheConfig, CacheStats};      // Phase 3
+pub use llm_result_cache::{LlmResultCache, CacheConfig, CacheStats, DiskCacheStats};      // Phase 3
 pub use vector_sync_manager::{VectorSyncManager, VectorSyncStats};
 pub use memory_event_coordinator::{MemoryEventCoordinator, CoordinatorConfig};  // Phase 2
 pub use memory_cleanup::{MemoryCleanupService, MemoryCleanupConfig, CleanupStats};  // v2.6
+pub use access::{AccessDenied, AccessGuard, AccessPolicy};
+pub use usage::{
+    MeteredLLMClient, TokenUsage, UsageOperation, UsageRecord, UsageSummary, UsageTotals,
+    UsageTracker,
+};
+pub use fixtures::{FixtureStore, RecordingLLMClient, ReplayLLMClient};
+pub use cortex_mem_config::{
+    AccessConfig, EmbeddingCacheConfig, FixtureMode, FixturesConfig, LayerMode, LayersConfig,
+    LlmCacheConfig, LlmTask, LlmTaskModels, ModelPrice, Provider, SpaceConfig, SpaceMembers,
+    UsageConfig,
+};
 
 // Session-related re-exports
 pub use session::message::MessageStorage;
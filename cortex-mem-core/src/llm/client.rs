@@ -1,20 +1,44 @@
+use super::providers::HttpChat;
 use crate::Result;
+use crate::metrics::METRICS;
+use crate::usage::TokenUsage;
+use cortex_mem_config::{LlmTask, LlmTaskModels, Provider};
 use rig::providers::openai::Client;
 use serde::{Deserialize, Serialize};
 
+/// System prompt of completions without one
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a helpful assistant.";
+
 /// LLM configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMConfig {
+    /// API flavour of the endpoint (OpenAI-compatible endpoints go through rig)
+    #[serde(default)]
+    pub provider: Provider,
     pub api_base_url: String,
     pub api_key: String,
     pub model_efficient: String,
     pub temperature: f32,
     pub max_tokens: usize,
+    /// API version (Azure only)
+    #[serde(default)]
+    pub api_version: Option<String>,
+    /// Per-task models; unset tasks use `model_efficient`
+    #[serde(default)]
+    pub models: LlmTaskModels,
+}
+
+impl LLMConfig {
+    /// Model serving a task
+    pub fn model_for(&self, task: LlmTask) -> &str {
+        self.models.get(task).unwrap_or(&self.model_efficient)
+    }
 }
 
 impl Default for LLMConfig {
     fn default() -> Self {
         Self {
+            provider: Provider::default(),
             api_base_url: std::env::var("LLM_API_BASE_URL")
                 .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
             api_key: std::env::var("LLM_API_KEY")
@@ -23,6 +47,8 @@ impl Default for LLMConfig {
                 .unwrap_or_else(|_| "gpt-3.5-turbo".to_string()),
             temperature: 0.1,
             max_tokens: 4096,
+            api_version: None,
+            models: LlmTaskModels::default(),
         }
     }
 }
@@ -79,8 +105,42 @@ pub trait LLMClient: Send + Sync {
     /// Extract detailed facts using rig extractor
     async fn extract_detailed_facts(&self, prompt: &str) -> Result<crate::llm::extractor_types::DetailedFactExtraction>;
     
+    /// Completion routed to the model configured for `task`
+    ///
+    /// Clients without per-task routing use their single model.
+    async fn complete_for_task(
+        &self,
+        _task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<String> {
+        match system {
+            Some(system) => self.complete_with_system(system, prompt).await,
+            None => self.complete(prompt).await,
+        }
+    }
+
+    /// [`complete_for_task`](Self::complete_for_task) with the token usage of the call
+    ///
+    /// Clients whose backend reports no usage estimate it from text length.
+    async fn complete_for_task_with_usage(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let response = self.complete_for_task(task, system, prompt).await?;
+        let usage = TokenUsage::estimate(system.map_or(0, str::len) + prompt.len(), response.len());
+        Ok((response, usage))
+    }
+
     /// Get the model name
     fn model_name(&self) -> &str;
+
+    /// Model serving `task` (part of LLM result cache keys)
+    fn model_for(&self, _task: LlmTask) -> &str {
+        self.model_name()
+    }
     
     /// Get the config
     fn config(&self) -> &LLMConfig;
@@ -90,8 +150,12 @@ pub trait LLMClient: Send + Sync {
 /// 
 /// This is a lightweight wrapper that creates agents for LLM interactions.
 /// Following the rig pattern: Client -> CompletionModel -> Agent
+///
+/// Providers without an OpenAI-compatible API (Anthropic, Ollama, Azure)
+/// are served by a native HTTP backend instead.
 pub struct LLMClientImpl {
     client: Client,
+    http: Option<HttpChat>,
     config: LLMConfig,
 }
 
@@ -107,8 +171,14 @@ impl LLMClientImpl {
             .base_url(&config.api_base_url)
             .build()
             .map_err(|e| crate::Error::Llm(format!("Failed to build OpenAI client: {:?}", e)))?;
+        let http = (config.provider != Provider::OpenAi).then(|| HttpChat::new(config.clone()));
 
-        Ok(Self { client, config })
+        tracing::info!(
+            "LLM client initialized [provider: {}, model: {}]",
+            config.provider,
+            config.model_efficient
+        );
+        Ok(Self { client, http, config })
     }
 
     /// Create a default LLM config
@@ -123,66 +193,102 @@ impl LLMClientImpl {
     /// 
     /// Note: In rig-core 0.31.0, the default agent uses ResponsesCompletionModel.
     /// We use .completions_api() to get the traditional CompletionModel.
+    /// Only OpenAI-compatible endpoints can be used this way.
     pub async fn create_agent(&self, system_prompt: &str) -> Result<rig::agent::Agent<rig::providers::openai::CompletionModel>> {
+        Ok(self.agent(&self.config.model_efficient, system_prompt))
+    }
+
+    fn agent(&self, model: &str, system_prompt: &str) -> rig::agent::Agent<rig::providers::openai::CompletionModel> {
         use rig::client::CompletionClient;
         
         // Clone the client to avoid moving out of self
-        let agent = self.client.clone()
+        self.client.clone()
             .completions_api()  // Use completions API to get CompletionModel
-            .agent(&self.config.model_efficient)
+            .agent(model)
             .preamble(system_prompt)
-            .build();
-            
-        Ok(agent)
+            .build()
     }
 
-    /// Simple completion without tools or streaming
-    /// For basic use cases - creates a temporary agent
-    pub async fn complete(&self, prompt: &str) -> Result<String> {
+    /// Completion with the given model, through rig or the native backend
+    async fn complete_with_model(&self, model: &str, system: Option<&str>, prompt: &str) -> Result<String> {
+        Ok(self.complete_with_model_usage(model, system, prompt).await?.0)
+    }
+
+    /// Completion with the given model and its token usage
+    ///
+    /// Both backends return the usage reported by the provider; it is only
+    /// estimated from text length when the endpoint reports none.
+    async fn complete_with_model_usage(
+        &self,
+        model: &str,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
         use rig::completion::Prompt;
         
-        tracing::info!("LLM call started [model: {}]", self.config.model_efficient);
-        tracing::debug!("Prompt length: {} chars", prompt.len());
+        tracing::info!("LLM call started [provider: {}, model: {}]", self.config.provider, model);
+        tracing::debug!("System: {}..., Prompt length: {} chars", 
+            system.unwrap_or_default().chars().take(50).collect::<String>(), prompt.len());
         
         let start = std::time::Instant::now();
         
-        let agent = self.create_agent("You are a helpful assistant.").await?;
-        let response = agent
-            .prompt(prompt)
-            .await
-            .map_err(|e| crate::Error::Llm(format!("LLM completion failed: {}", e)))?;
+        let system_prompt = system.unwrap_or(DEFAULT_SYSTEM_PROMPT);
+        let prompt_len = system.map_or(0, str::len) + prompt.len();
+        let result = match &self.http {
+            Some(http) => http.complete(model, system_prompt, prompt).await,
+            None => self
+                .agent(model, system_prompt)
+                .prompt(prompt)
+                .extended_details()
+                .await
+                .map(|response| {
+                    let reported = response.total_usage;
+                    let usage = if reported.input_tokens == 0 && reported.output_tokens == 0 {
+                        TokenUsage::estimate(prompt_len, response.output.len())
+                    } else {
+                        TokenUsage::new(reported.input_tokens, reported.output_tokens)
+                    };
+                    (response.output, usage)
+                })
+                .map_err(|e| crate::Error::Llm(format!("LLM completion failed: {}", e))),
+        };
+        METRICS.observe_llm_call(
+            prompt_len,
+            result.as_ref().ok().map(|(response, _)| response.as_str()),
+            start.elapsed().as_secs_f64(),
+        );
+        let (response, usage) = result?;
 
         let elapsed = start.elapsed();
-        tracing::info!("LLM call completed [elapsed: {:.2}s, response: {} chars]", elapsed.as_secs_f64(), response.len());
+        tracing::info!(
+            "LLM call completed [elapsed: {:.2}s, response: {} chars, tokens: {}+{}]",
+            elapsed.as_secs_f64(),
+            response.len(),
+            usage.prompt_tokens,
+            usage.completion_tokens
+        );
         
-        Ok(response)
+        Ok((response, usage))
+    }
+
+    /// Simple completion without tools or streaming
+    pub async fn complete(&self, prompt: &str) -> Result<String> {
+        self.complete_with_model(&self.config.model_efficient, None, prompt).await
     }
 
     /// Generate completion with system message
     pub async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
-        use rig::completion::Prompt;
-        
-        tracing::info!("LLM call started (with system) [model: {}]", self.config.model_efficient);
-        tracing::debug!("System: {}..., Prompt length: {} chars", 
-            &system.chars().take(50).collect::<String>(), prompt.len());
-        
-        let start = std::time::Instant::now();
-        
-        let agent = self.create_agent(system).await?;
-        let response = agent
-            .prompt(prompt)
-            .await
-            .map_err(|e| crate::Error::Llm(format!("LLM completion failed: {}", e)))?;
-            
-        let elapsed = start.elapsed();
-        tracing::info!("LLM call completed [elapsed: {:.2}s, response: {} chars]", elapsed.as_secs_f64(), response.len());
-        
-        Ok(response)
+        self.complete_with_model(&self.config.model_efficient, Some(system), prompt).await
+    }
+
+    /// Completion for memory extraction (uses the `extraction` model)
+    async fn complete_extraction(&self, prompt: &str) -> Result<String> {
+        self.complete_with_model(self.config.model_for(LlmTask::Extraction), None, prompt).await
     }
 
     /// Extract memories from conversation
     pub async fn extract_memories(&self, prompt: &str) -> Result<MemoryExtractionResponse> {
-        let response: String = self.complete(prompt).await?;
+        let response: String = self.complete_extraction(prompt).await?;
         
         // Extract JSON from response (handles markdown code blocks)
         let json_str = Self::extract_json_from_response_static(&response);
@@ -285,48 +391,33 @@ impl LLMClientImpl {
 #[async_trait::async_trait]
 impl LLMClient for LLMClientImpl {
     async fn complete(&self, prompt: &str) -> Result<String> {
-        use rig::completion::Prompt;
-        
-        tracing::info!("LLM call started [model: {}]", self.config.model_efficient);
-        tracing::debug!("Prompt length: {} chars", prompt.len());
-        
-        let start = std::time::Instant::now();
-        
-        let agent = self.create_agent("You are a helpful assistant.").await?;
-        let response = agent
-            .prompt(prompt)
-            .await
-            .map_err(|e| crate::Error::Llm(format!("LLM completion failed: {}", e)))?;
+        LLMClientImpl::complete(self, prompt).await
+    }
 
-        let elapsed = start.elapsed();
-        tracing::info!("LLM call completed [elapsed: {:.2}s, response: {} chars]", elapsed.as_secs_f64(), response.len());
+    async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
+        LLMClientImpl::complete_with_system(self, system, prompt).await
+    }
 
-        Ok(response)
+    async fn complete_for_task(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<String> {
+        self.complete_with_model(self.config.model_for(task), system, prompt).await
     }
 
-    async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
-        use rig::completion::Prompt;
-        
-        tracing::info!("LLM call started (with system) [model: {}]", self.config.model_efficient);
-        tracing::debug!("System: {}..., Prompt length: {} chars", 
-            &system.chars().take(50).collect::<String>(), prompt.len());
-        
-        let start = std::time::Instant::now();
-        
-        let agent = self.create_agent(system).await?;
-        let response = agent
-            .prompt(prompt)
-            .await
-            .map_err(|e| crate::Error::Llm(format!("LLM completion failed: {}", e)))?;
-            
-        let elapsed = start.elapsed();
-        tracing::info!("LLM call completed [elapsed: {:.2}s, response: {} chars]", elapsed.as_secs_f64(), response.len());
-            
-        Ok(response)
+    async fn complete_for_task_with_usage(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        self.complete_with_model_usage(self.config.model_for(task), system, prompt).await
     }
 
     async fn extract_memories(&self, prompt: &str) -> Result<MemoryExtractionResponse> {
-        let response: String = self.complete(prompt).await?;
+        let response: String = self.complete_extraction(prompt).await?;
         
         // Extract JSON from response (handles markdown code blocks)
         let json_str = Self::extract_json_from_response_static(&response);
@@ -396,7 +487,7 @@ If no facts are found, return: {{"facts": []}}
             prompt
         );
 
-        let response = self.complete(&extraction_prompt).await?;
+        let response = self.complete_extraction(&extraction_prompt).await?;
         
         // Try to extract JSON from the response
         let json_str = Self::extract_json_from_response_static(&response);
@@ -451,7 +542,7 @@ If no facts are found, return: {{"facts": []}}
             prompt
         );
 
-        let response = self.complete(&extraction_prompt).await?;
+        let response = self.complete_extraction(&extraction_prompt).await?;
         
         // Try to extract JSON from the response
         let json_str = Self::extract_json_from_response_static(&response);
@@ -473,6 +564,10 @@ If no facts are found, return: {{"facts": []}}
         &self.config.model_efficient
     }
 
+    fn model_for(&self, task: LlmTask) -> &str {
+        self.config.model_for(task)
+    }
+
     fn config(&self) -> &LLMConfig {
         &self.config
     }
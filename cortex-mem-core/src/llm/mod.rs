@@ -1,10 +1,13 @@
 pub mod client;
 pub mod extractor_types;
 pub mod prompts;
+pub mod providers;
+pub mod templates;
 
 pub use client::{LLMClient, LLMClientImpl, LLMConfig, MemoryExtractionResponse, ExtractedFactRaw, ExtractedDecisionRaw, ExtractedEntityRaw};
 pub use extractor_types::{StructuredFactExtraction, DetailedFactExtraction, StructuredFact};
 pub use prompts::Prompts;
+pub use templates::{PromptTemplate, PromptTemplates};
 
 /// Type alias for boxed LLMClient trait object
 pub type BoxedLLMClient = Box<dyn LLMClient>;
@@ -72,11 +75,14 @@ impl LLMClient for MockLLMClient {
     fn config(&self) -> &LLMConfig {
         static CONFIG: std::sync::OnceLock<LLMConfig> = std::sync::OnceLock::new();
         CONFIG.get_or_init(|| LLMConfig {
+            provider: Default::default(),
             api_base_url: String::new(),
             api_key: String::new(),
             model_efficient: "mock-llm".to_string(),
             temperature: 0.7,
             max_tokens: 2048,
+            api_version: None,
+            models: Default::default(),
         })
     }
 }
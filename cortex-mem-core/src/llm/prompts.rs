@@ -1,13 +1,29 @@
-/// Prompt templates for various LLM tasks
-pub struct Prompts;
-
-impl Prompts {
-    /// Prompt for generating L0 abstract
-    ///
-    /// ~100 tokens for quick relevance checking and filtering
-    pub fn abstract_generation(content: &str) -> String {
-        format!(
-            r#"Generate a concise abstract (~100 tokens maximum) for the following content.
+//! Built-in prompt templates
+//!
+//! Templates use `{{name}}` placeholders and are rendered through
+//! [`PromptTemplates`], which lets a tenant override any of them.
+
+use super::templates::PromptTemplates;
+
+/// Version of the built-in prompt templates below
+///
+/// Part of every LLM result cache key: bump it whenever a template changes so
+/// persisted results produced by the old prompt are no longer served.
+/// Overridden templates are versioned by their content instead.
+pub const PROMPTS_VERSION: u32 = 1;
+
+/// System prompt for L0 abstract generation
+pub const ABSTRACT_SYSTEM: &str = r#"You are an expert at creating concise abstracts.
+Your goal is to generate summaries that capture multiple key aspects of content for quick relevance checking.
+Keep abstracts under 100 tokens. Prioritize breadth over depth - cover more topics briefly rather than elaborating on one.
+Be direct and informative. Use compact phrasing to maximize information density.
+When asked to preserve specific named entities, include them verbatim in the abstract."#;
+
+/// Prompt for generating L0 abstract
+///
+/// ~100 tokens for quick relevance checking and filtering.
+/// Placeholders: `content`, `entity_hint` (empty unless entities must be preserved)
+pub const ABSTRACT: &str = r#"Generate a concise abstract (~100 tokens maximum) for the following content.
 
 Requirements:
 - Stay within ~100 tokens limit
@@ -21,23 +37,23 @@ Requirements:
   - If content is in Chinese, write abstract in Chinese
   - If content is in English, write abstract in English
   - If content is in other languages, use that language
-  - Preserve the original linguistic and cultural context
+  - Preserve the original linguistic and cultural context{{entity_hint}}
 
 Content:
-{}
+{{content}}
 
-Abstract (max 100 tokens, in the same language as the content):"#,
-            content
-        )
-    }
+Abstract (max 100 tokens, in the same language as the content):"#;
 
-    /// Prompt for generating L1 overview
-    ///
-    /// ~2K tokens, structured overview
-    /// for decision-making and planning
-    pub fn overview_generation(content: &str) -> String {
-        format!(
-            r#"Generate a structured overview (~500-2000 tokens) of the following content.
+/// System prompt for L1 overview generation
+pub const OVERVIEW_SYSTEM: &str = r#"You are an expert at creating structured overviews.
+Your goal is to provide comprehensive yet concise summaries (500-2000 tokens) that help users understand and make decisions about content.
+Use clear markdown structure with sections for Summary, Core Topics, Key Points, Entities, and Context."#;
+
+/// Prompt for generating L1 overview
+///
+/// ~2K tokens, structured overview
+/// for decision-making and planning. Placeholder: `content`
+pub const OVERVIEW: &str = r#"Generate a structured overview (~500-2000 tokens) of the following content.
 
 Structure your response as markdown with these sections:
 
@@ -79,17 +95,12 @@ Requirements:
   - Preserve cultural references and linguistic nuances
 
 Content:
-{}
+{{content}}
 
-Structured Overview (in the same language as the content):"#,
-            content
-        )
-    }
+Structured Overview (in the same language as the content):"#;
 
-    /// Prompt for memory extraction from conversation
-    pub fn memory_extraction(conversation: &str) -> String {
-        format!(
-            r#"Analyze the following conversation and extract structured memory information.
+/// Prompt for memory extraction from conversation. Placeholder: `conversation`
+pub const MEMORY_EXTRACTION: &str = r#"Analyze the following conversation and extract structured memory information.
 
 Extract ALL of the following categories that are present:
 
@@ -110,44 +121,36 @@ Extract ALL of the following categories that are present:
 8. **Agent Learnings**: Insights useful for future interactions
 
 Format your response as JSON:
-{{
-  "events": [{{ "title": "...", "date": "exact date/time string", "description": "...", "participants": [] }}],
-  "personal_info": [{{ "person": "...", "category": "career|relationship|identity|research|other", "content": "..." }}],
-  "activities": [{{ "person": "...", "activity": "...", "context": "signed up|participates in|enjoys|etc" }}],
-  "future_plans": [{{ "person": "...", "event": "...", "date": "...", "description": "..." }}],
-  "relationships": [{{ "persons": ["...", "..."], "type": "...", "description": "..." }}],
-  "facts": [{{ "content": "...", "confidence": 0.9 }}],
-  "user_preferences": [{{ "category": "...", "content": "..." }}],
-  "agent_learnings": [{{ "task_type": "...", "learned_approach": "...", "success_rate": 0.8 }}]
-}}
+{
+  "events": [{ "title": "...", "date": "exact date/time string", "description": "...", "participants": [] }],
+  "personal_info": [{ "person": "...", "category": "career|relationship|identity|research|other", "content": "..." }],
+  "activities": [{ "person": "...", "activity": "...", "context": "signed up|participates in|enjoys|etc" }],
+  "future_plans": [{ "person": "...", "event": "...", "date": "...", "description": "..." }],
+  "relationships": [{ "persons": ["...", "..."], "type": "...", "description": "..." }],
+  "facts": [{ "content": "...", "confidence": 0.9 }],
+  "user_preferences": [{ "category": "...", "content": "..." }],
+  "agent_learnings": [{ "task_type": "...", "learned_approach": "...", "success_rate": 0.8 }]
+}
 
 Conversation:
-{}
+{{conversation}}
 
-Extracted Memories (JSON):"#,
-            conversation
-        )
-    }
+Extracted Memories (JSON):"#;
 
-    /// 统一查询意图分析 Prompt（一次 LLM 请求返回所有检索所需信息）
-    ///
-    /// 返回：改写查询、关键词、实体列表、意图类型、时间约束
-    /// 支持中英文及混合语言查询
-    pub fn unified_query_analysis(query: &str) -> String {
-        // 截断保护：query 最多取前 500 个字符（使用 chars 保证 Unicode 安全）
-        let safe_query: String = query.chars().take(500).collect();
-
-        format!(
-            r#"Analyze the following search query and return a JSON object with all fields filled.
+/// 统一查询意图分析 Prompt（一次 LLM 请求返回所有检索所需信息）
+///
+/// 返回：改写查询、关键词、实体列表、意图类型、时间约束
+/// 支持中英文及混合语言查询。占位符：`query`
+pub const QUERY_ANALYSIS: &str = r#"Analyze the following search query and return a JSON object with all fields filled.
 
 ## Output JSON Format
-{{
+{
   "rewritten_query": "expanded query for better vector retrieval (keep original meaning, add synonyms, max 150 chars)",
   "keywords": ["keyword1", "keyword2"],
   "entities": ["entity1", "entity2"],
   "intent_type": "entity_lookup|factual|temporal|relational|search|general",
-  "time_constraint": {{ "start": "...", "end": "..." }}
-}}
+  "time_constraint": { "start": "...", "end": "..." }
+}
 
 ## Field Rules
 - **rewritten_query**: Aggressively expand the query with synonyms and related concepts for maximum vector recall. Max 150 chars.
@@ -168,11 +171,279 @@ Extracted Memories (JSON):"#,
 - **time_constraint**: Set to `null` if no time reference in query. Otherwise fill start/end as descriptive strings.
 
 ## Query
-{}
+{{query}}
+
+## Response (valid JSON only, no markdown, no explanation):"#;
+
+/// Prompt for extracting memories when a session closes
+/// (used by `MemoryEventCoordinator`). Placeholder: `messages`
+pub const SESSION_EXTRACTION: &str = r#"Analyze the following conversation and extract memories in JSON format.
+
+## CRITICAL LANGUAGE RULES
+
+1. **Language Consistency** (MANDATORY):
+   - Extract memories in the SAME language as the conversation
+   - If conversation is in Chinese (中文) → memories MUST be in Chinese
+   - If conversation is in English → memories in English
+   - If mixed language → use the dominant language (>60% of content)
+   - **DO NOT translate** the conversation content into another language
+
+2. **Preserve Technical Terms** (MANDATORY):
+   - Keep technical terminology unchanged in their original language
+   - Programming languages: Rust, Python, TypeScript, JavaScript, Go
+   - Frameworks: Cortex Memory, Rig, React, Vue
+   - Personality types: INTJ, ENTJ, MBTI, DISC
+   - Proper nouns: names, companies, projects
+   - Acronyms: LLM, AI, ML, API, HTTP, REST
+
+3. **Examples**:
+   ✅ CORRECT (Chinese conversation):
+   - "Cortex Memory 是基于 Rust 的长期记忆系统"
+   - "用户喜欢吃牛肉汉堡，搭配酸黄瓜、芝士和可乐"
+
+   ❌ WRONG (Chinese conversation, should NOT translate to English):
+   - "User likes beef burgers with pickles, cheese, and coke"
+
+## Instructions
+
+Extract the following types of memories:
+
+1. **Personal Info** (user's personal information):
+   - category: "age", "occupation", "education", "location", etc.
+   - content: The specific information
+   - confidence: 0.0-1.0 confidence level
+
+2. **Work History** (user's work experience):
+   - company: Company name
+   - role: Job title/role
+   - duration: Time period (optional)
+   - description: Brief description
+   - confidence: 0.0-1.0 confidence level
+
+3. **Preferences** (user preferences by topic):
+   - topic: The topic/subject area
+   - preference: The user's stated preference
+   - confidence: 0.0-1.0 confidence level
+
+4. **Relationships** (people user mentions):
+   - person: Person's name
+   - relation_type: "family", "colleague", "friend", etc.
+   - context: How they're related
+   - confidence: 0.0-1.0 confidence level
+
+5. **Goals** (user's goals and aspirations):
+   - goal: The specific goal
+   - category: "career", "personal", "health", "learning", etc.
+   - timeline: When they want to achieve it (optional)
+   - confidence: 0.0-1.0 confidence level
+
+6. **Entities** (people, projects, organizations mentioned):
+   - name: Entity name
+   - entity_type: "person", "project", "organization", "technology", etc.
+   - description: Brief description
+   - context: How it was mentioned
+
+7. **Events** (decisions, milestones, important occurrences):
+   - title: Event title
+   - event_type: "decision", "milestone", "occurrence"
+   - summary: Brief summary
+   - timestamp: If mentioned
+
+8. **Cases** (problems encountered and solutions found):
+   - title: Case title
+   - problem: The problem encountered
+   - solution: How it was solved
+   - lessons_learned: Array of lessons learned
+
+9. **Skills** (reusable procedures the assistant worked out and could apply again):
+   - name: Short skill name
+   - when_to_use: The kind of task it applies to
+   - steps: Ordered array of steps
+   - confidence: 0.0-1.0 confidence level
+
+10. **Instructions** (standing rules the user told the assistant to follow from now on):
+   - topic: Short topic of the instruction
+   - instruction: The rule itself
+   - applies_to: Tasks it is limited to (omit if it always applies)
+   - confidence: 0.0-1.0 confidence level
+
+## Response Format
+
+Return ONLY a JSON object with this structure:
+
+{
+  "personal_info": [{ "category": "...", "content": "...", "confidence": 0.9 }],
+  "work_history": [{ "company": "...", "role": "...", "duration": "...", "description": "...", "confidence": 0.9 }],
+  "preferences": [{ "topic": "...", "preference": "...", "confidence": 0.9 }],
+  "relationships": [{ "person": "...", "relation_type": "...", "context": "...", "confidence": 0.9 }],
+  "goals": [{ "goal": "...", "category": "...", "timeline": "...", "confidence": 0.9 }],
+  "entities": [{ "name": "...", "entity_type": "...", "description": "...", "context": "..." }],
+  "events": [{ "title": "...", "event_type": "...", "summary": "...", "timestamp": "..." }],
+  "cases": [{ "title": "...", "problem": "...", "solution": "...", "lessons_learned": ["..."] }],
+  "skills": [{ "name": "...", "when_to_use": "...", "steps": ["..."], "confidence": 0.8 }],
+  "instructions": [{ "topic": "...", "instruction": "...", "applies_to": "...", "confidence": 0.9 }]
+}
+
+Only include memories that are clearly stated in the conversation. Set empty arrays for categories with no data.
+
+## Conversation
+
+{{messages}}
+
+## Response
+
+Return ONLY the JSON object. No additional text before or after."#;
+
+/// Prompt used by `MemoryExtractor`. Placeholder: `messages`
+pub const MEMORY_EXTRACTOR: &str = r#"Analyze the following conversation and extract memories in JSON format.
+
+## CRITICAL LANGUAGE RULES
+
+1. **Language Consistency** (MANDATORY):
+   - Extract memories in the SAME language as the conversation
+   - If conversation is in Chinese (中文) → memories in Chinese
+   - If conversation is in English → memories in English
+   - If mixed language → use the dominant language (>60% of content)
+
+2. **Preserve Technical Terms** (MANDATORY):
+   - Keep technical terminology unchanged in their original language
+   - Programming languages: Rust, Python, TypeScript, JavaScript, Go
+   - Frameworks: Cortex Memory, Rig, React, Vue
+   - Personality types: INTJ, ENTJ, MBTI, DISC
+   - Proper nouns: names, companies, projects
+   - Acronyms: LLM, AI, ML, API, HTTP, REST
+
+3. **Examples**:
+   ✅ CORRECT (Chinese conversation):
+   - "Cortex Memory 是基于 Rust 的长期记忆系统"
+   - "用户是 INTJ 人格类型，擅长 Python 和 Rust"
+
+   ❌ WRONG (Chinese conversation):
+   - "Cortex Memory is based on 铁锈 long-term memory system"
+   - "User is an INTJ personality type skilled in 蟒蛇 and 铁锈"
+
+   ✅ CORRECT (English conversation):
+   - "User works at SGNetworks as a Rust engineer"
+   - "Cortex Memory is a long-term memory system for Agent"
+
+   ❌ WRONG (English conversation):
+   - "用户 works at SGNetworks as a Rust 工程师"
+   - "Cortex Memory is a 长期记忆 system for Agent"
+
+## Instructions
+
+Extract the following types of memories:
+
+1. **Personal Info** (user's personal information):
+   - category: "age", "occupation", "education", "location", "nationality", etc.
+   - content: The specific information
+   - confidence: 0.0-1.0 confidence level
+
+2. **Work History** (user's work experience):
+   - company: Company name
+   - role: Job title/role
+   - duration: Time period (optional)
+   - description: Brief description of role/responsibilities
+   - confidence: 0.0-1.0 confidence level
+
+3. **Preferences** (user preferences by topic):
+   - topic: The topic/subject area
+   - preference: The user's stated preference
+   - confidence: 0.0-1.0 confidence level
+
+4. **Relationships** (people user mentions):
+   - person: Person's name
+   - relation_type: "family", "colleague", "friend", "mentor", etc.
+   - context: How they're related/context
+   - confidence: 0.0-1.0 confidence level
+
+5. **Goals** (user's goals and aspirations):
+   - goal: The specific goal
+   - category: "career", "personal", "health", "learning", "financial", etc.
+   - timeline: When they want to achieve it (optional)
+   - confidence: 0.0-1.0 confidence level
+
+6. **Entities** (people, projects, organizations mentioned):
+   - name: Entity name
+   - entity_type: "person", "project", "organization", "technology", etc.
+   - description: Brief description
+   - context: How it was mentioned
+
+7. **Events** (decisions, milestones, important occurrences):
+   - title: Event title
+   - event_type: "decision", "milestone", "occurrence"
+   - summary: Brief summary
+   - timestamp: If mentioned
+
+8. **Cases** (problems encountered and solutions found):
+   - title: Case title
+   - problem: The problem encountered
+   - solution: How it was solved
+   - lessons_learned: Array of lessons learned
+
+9. **Skills** (reusable procedures the assistant worked out and could apply again):
+   - name: Short skill name
+   - when_to_use: The kind of task it applies to
+   - steps: Ordered array of steps
+   - confidence: 0.0-1.0 confidence level
+
+10. **Instructions** (standing rules the user told the assistant to follow from now on):
+   - topic: Short topic of the instruction
+   - instruction: The rule itself
+   - applies_to: Tasks it is limited to (omit if it always applies)
+   - confidence: 0.0-1.0 confidence level
+
+## Response Format
+
+Return ONLY a JSON object with this structure:
+
+{
+  "personal_info": [{"category": "age", "content": "30岁", "confidence": 0.9}],
+  "work_history": [{"company": "...", "role": "...", "duration": "...", "description": "...", "confidence": 0.9}],
+  "preferences": [{"topic": "...", "preference": "...", "confidence": 0.9}],
+  "relationships": [{"person": "...", "relation_type": "...", "context": "...", "confidence": 0.9}],
+  "goals": [{"goal": "...", "category": "...", "timeline": "...", "confidence": 0.9}],
+  "entities": [{"name": "...", "entity_type": "...", "description": "...", "context": "..."}],
+  "events": [{"title": "...", "event_type": "...", "summary": "...", "timestamp": "..."}],
+  "cases": [{"title": "...", "problem": "...", "solution": "...", "lessons_learned": ["..."]}],
+  "skills": [{"name": "...", "when_to_use": "...", "steps": ["..."], "confidence": 0.8}],
+  "instructions": [{"topic": "...", "instruction": "...", "applies_to": "...", "confidence": 0.9}]
+}
+
+Only include memories that are clearly stated in the conversation. Set empty arrays for categories with no data.
+
+## Conversation
+
+{{messages}}
+
+## Response
+
+Return ONLY the JSON object. No additional text before or after."#;
+
+/// Prompt builders using the built-in templates
+pub struct Prompts;
 
-## Response (valid JSON only, no markdown, no explanation):"#,
-            safe_query
-        )
+impl Prompts {
+    /// Prompt for generating L0 abstract
+    pub fn abstract_generation(content: &str) -> String {
+        PromptTemplates::builtin()
+            .render("abstract", &[("content", content), ("entity_hint", "")])
+    }
+
+    /// Prompt for generating L1 overview
+    pub fn overview_generation(content: &str) -> String {
+        PromptTemplates::builtin().overview_prompt(content)
+    }
+
+    /// Prompt for memory extraction from conversation
+    pub fn memory_extraction(conversation: &str) -> String {
+        PromptTemplates::builtin()
+            .render("memory_extraction", &[("conversation", conversation)])
+    }
+
+    /// 统一查询意图分析 Prompt
+    pub fn unified_query_analysis(query: &str) -> String {
+        PromptTemplates::builtin().query_analysis_prompt(query)
     }
 
     /// Prompt for abstract generation with optional entity preservation
@@ -180,41 +451,6 @@ Extracted Memories (JSON):"#,
     /// When `known_entities` is non-empty, the LLM is instructed to retain
     /// those entity names in the generated abstract.
     pub fn abstract_generation_with_entities(content: &str, known_entities: &[String]) -> String {
-        // 截断保护：content 最多取前 8000 个字符
-        let safe_content: String = content.chars().take(8000).collect();
-
-        let entity_hint = if known_entities.is_empty() {
-            String::new()
-        } else {
-            format!(
-                "\n\nIMPORTANT: The following named entities MUST appear verbatim in the abstract \
-                if they are present in the content: {}",
-                known_entities.join(", ")
-            )
-        };
-
-        format!(
-            r#"Generate a concise abstract (~100 tokens maximum) for the following content.
-
-Requirements:
-- Stay within ~100 tokens limit
-- Cover MULTIPLE key aspects when content is rich (who, what, key topics, important outcomes)
-- Prioritize information breadth over depth - mention more topics rather than elaborating on one
-- Use compact phrasing: "discussed X, Y, and Z" instead of long explanations
-- For multi-topic content: list key themes briefly rather than focusing on just one
-- Use clear, direct language
-- Avoid filler words and unnecessary details
-- **CRITICAL: Use the SAME LANGUAGE as the input content**
-  - If content is in Chinese, write abstract in Chinese
-  - If content is in English, write abstract in English
-  - If content is in other languages, use that language
-  - Preserve the original linguistic and cultural context{}
-
-Content:
-{}
-
-Abstract (max 100 tokens, in the same language as the content):"#,
-            entity_hint, safe_content
-        )
+        PromptTemplates::builtin().abstract_prompt(content, known_entities)
     }
 }
@@ -0,0 +1,287 @@
+//! Native chat backends for providers without an OpenAI-compatible API
+//!
+//! OpenAI-compatible endpoints go through rig (see [`LLMClientImpl`]);
+//! Anthropic, Ollama and Azure OpenAI are called directly over HTTP.
+//!
+//! [`LLMClientImpl`]: super::LLMClientImpl
+
+use super::client::LLMConfig;
+use crate::usage::TokenUsage;
+use crate::{Error, Result};
+use cortex_mem_config::Provider;
+use serde_json::{Value, json};
+
+/// Azure OpenAI API version used when `api_version` is not configured
+pub const AZURE_DEFAULT_API_VERSION: &str = "2024-10-21";
+
+/// `anthropic-version` header of the Messages API
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// HTTP chat client for a single endpoint
+pub(crate) struct HttpChat {
+    client: reqwest::Client,
+    config: LLMConfig,
+}
+
+impl HttpChat {
+    pub fn new(config: LLMConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config,
+        }
+    }
+
+    /// Send one system + user message exchange and return the reply text
+    /// with the token usage of the call
+    pub async fn complete(
+        &self,
+        model: &str,
+        system: &str,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let provider = self.config.provider;
+        let (url, body) = chat_request(&self.config, model, system, prompt);
+
+        let request = self.client.post(&url).json(&body);
+        let request = match provider {
+            Provider::Anthropic => request
+                .header("x-api-key", &self.config.api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION),
+            Provider::Azure => request.header("api-key", &self.config.api_key),
+            // Local servers usually run without a key
+            Provider::OpenAi | Provider::Ollama if self.config.api_key.is_empty() => request,
+            Provider::OpenAi | Provider::Ollama => request.bearer_auth(&self.config.api_key),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Llm(format!("{} request failed: {}", provider, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Llm(format!(
+                "{} API error ({}): {}",
+                provider, status, body
+            )));
+        }
+
+        let value: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Llm(format!("Failed to parse {} response: {}", provider, e)))?;
+        let response = parse_chat_response(provider, &value).ok_or_else(|| {
+            Error::Llm(format!(
+                "Unexpected {} response: {}",
+                provider,
+                value.to_string().chars().take(500).collect::<String>()
+            ))
+        })?;
+        let usage = parse_usage(provider, &value).unwrap_or_else(|| {
+            TokenUsage::estimate(system.len() + prompt.len(), response.len())
+        });
+        Ok((response, usage))
+    }
+}
+
+/// URL and JSON body of a chat request
+fn chat_request(config: &LLMConfig, model: &str, system: &str, prompt: &str) -> (String, Value) {
+    let base = config.api_base_url.trim_end_matches('/');
+    let messages = json!([
+        { "role": "system", "content": system },
+        { "role": "user", "content": prompt },
+    ]);
+
+    match config.provider {
+        Provider::OpenAi => (
+            format!("{}/chat/completions", base),
+            json!({
+                "model": model,
+                "messages": messages,
+                "temperature": config.temperature,
+                "max_tokens": config.max_tokens,
+            }),
+        ),
+        Provider::Azure => (
+            format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                base,
+                model,
+                config
+                    .api_version
+                    .as_deref()
+                    .unwrap_or(AZURE_DEFAULT_API_VERSION)
+            ),
+            json!({
+                "messages": messages,
+                "temperature": config.temperature,
+                "max_tokens": config.max_tokens,
+            }),
+        ),
+        Provider::Anthropic => (
+            format!("{}/messages", base),
+            json!({
+                "model": model,
+                "system": system,
+                "messages": [{ "role": "user", "content": prompt }],
+                "temperature": config.temperature,
+                "max_tokens": config.max_tokens,
+            }),
+        ),
+        Provider::Ollama => (
+            format!("{}/api/chat", base),
+            json!({
+                "model": model,
+                "messages": messages,
+                "stream": false,
+                "options": {
+                    "temperature": config.temperature,
+                    "num_predict": config.max_tokens,
+                },
+            }),
+        ),
+    }
+}
+
+/// Reply text of a chat response
+fn parse_chat_response(provider: Provider, value: &Value) -> Option<String> {
+    match provider {
+        Provider::OpenAi | Provider::Azure => value["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string),
+        Provider::Anthropic => value["content"].as_array().map(|blocks| {
+            blocks
+                .iter()
+                .filter(|block| block["type"] == "text")
+                .filter_map(|block| block["text"].as_str())
+                .collect::<String>()
+        }),
+        Provider::Ollama => value["message"]["content"].as_str().map(str::to_string),
+    }
+}
+
+/// Token usage reported in a chat response
+fn parse_usage(provider: Provider, value: &Value) -> Option<TokenUsage> {
+    let (prompt, completion) = match provider {
+        Provider::OpenAi | Provider::Azure => (
+            &value["usage"]["prompt_tokens"],
+            &value["usage"]["completion_tokens"],
+        ),
+        Provider::Anthropic => (
+            &value["usage"]["input_tokens"],
+            &value["usage"]["output_tokens"],
+        ),
+        Provider::Ollama => (&value["prompt_eval_count"], &value["eval_count"]),
+    };
+    Some(TokenUsage::new(prompt.as_u64()?, completion.as_u64().unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: Provider, api_base_url: &str) -> LLMConfig {
+        LLMConfig {
+            provider,
+            api_base_url: api_base_url.to_string(),
+            api_key: "key".to_string(),
+            model_efficient: "model".to_string(),
+            temperature: 0.1,
+            max_tokens: 512,
+            api_version: None,
+            models: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_chat_request_per_provider() {
+        let (url, body) = chat_request(
+            &config(Provider::Anthropic, "https://api.anthropic.com/v1/"),
+            "claude",
+            "sys",
+            "hi",
+        );
+        assert_eq!(url, "https://api.anthropic.com/v1/messages");
+        assert_eq!(body["system"], "sys");
+        assert_eq!(body["messages"][0]["content"], "hi");
+        assert_eq!(body["max_tokens"], 512);
+
+        let (url, body) = chat_request(
+            &config(Provider::Ollama, "http://localhost:11434"),
+            "llama3",
+            "sys",
+            "hi",
+        );
+        assert_eq!(url, "http://localhost:11434/api/chat");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["messages"][1]["content"], "hi");
+
+        let mut azure = config(Provider::Azure, "https://res.openai.azure.com");
+        let (url, body) = chat_request(&azure, "my-deployment", "sys", "hi");
+        assert_eq!(
+            url,
+            format!(
+                "https://res.openai.azure.com/openai/deployments/my-deployment/chat/completions?api-version={}",
+                AZURE_DEFAULT_API_VERSION
+            )
+        );
+        assert!(body.get("model").is_none());
+        azure.api_version = Some("2025-01-01-preview".to_string());
+        assert!(
+            chat_request(&azure, "d", "s", "p")
+                .0
+                .ends_with("=2025-01-01-preview")
+        );
+    }
+
+    #[test]
+    fn test_parse_chat_response() {
+        let anthropic = json!({
+            "content": [
+                { "type": "text", "text": "Hello" },
+                { "type": "text", "text": " world" },
+            ]
+        });
+        assert_eq!(
+            parse_chat_response(Provider::Anthropic, &anthropic).unwrap(),
+            "Hello world"
+        );
+
+        let ollama = json!({ "message": { "role": "assistant", "content": "ok" } });
+        assert_eq!(
+            parse_chat_response(Provider::Ollama, &ollama).unwrap(),
+            "ok"
+        );
+
+        let azure = json!({ "choices": [{ "message": { "content": "done" } }] });
+        assert_eq!(
+            parse_chat_response(Provider::Azure, &azure).unwrap(),
+            "done"
+        );
+
+        assert!(parse_chat_response(Provider::Ollama, &json!({ "error": "x" })).is_none());
+    }
+
+    #[test]
+    fn test_parse_usage() {
+        let openai = json!({ "usage": { "prompt_tokens": 12, "completion_tokens": 5 } });
+        assert_eq!(
+            parse_usage(Provider::OpenAi, &openai),
+            Some(TokenUsage::new(12, 5))
+        );
+
+        let anthropic = json!({ "usage": { "input_tokens": 30, "output_tokens": 7 } });
+        assert_eq!(
+            parse_usage(Provider::Anthropic, &anthropic),
+            Some(TokenUsage::new(30, 7))
+        );
+
+        let ollama = json!({ "prompt_eval_count": 40, "eval_count": 9 });
+        assert_eq!(
+            parse_usage(Provider::Ollama, &ollama),
+            Some(TokenUsage::new(40, 9))
+        );
+
+        assert_eq!(parse_usage(Provider::Azure, &json!({ "choices": [] })), None);
+    }
+}
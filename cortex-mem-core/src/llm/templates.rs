@@ -0,0 +1,430 @@
+//! Prompt templates with per-tenant overrides
+//!
+//! Every prompt has a built-in default (see [`prompts`]). A
+//! tenant overrides one by placing `{name}.md` in the `prompts/` directory of
+//! its data dir (`cortex-mem prompts dump` writes the defaults there as a
+//! starting point). Templates use `{{name}}` placeholders; unknown
+//! placeholders are left untouched.
+//!
+//! Override files are cached by their metadata (modification and change
+//! time, size and inode), so edits apply without a restart while renders only
+//! `stat` the file. Files modified within [`RACY_WINDOW`] of being read are
+//! re-read on every render until they settle, since a same-size edit inside
+//! the filesystem's timestamp granularity leaves the metadata unchanged.
+//! Cached LLM results are keyed by [`PromptTemplates::version`], which hashes
+//! the text of overridden templates: editing one invalidates the results it
+//! produced.
+
+use super::prompts::{self, PROMPTS_VERSION};
+use crate::filesystem::CortexFilesystem;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::warn;
+
+/// Override files read so far, shared by every `PromptTemplates` of the process
+static OVERRIDES: LazyLock<Mutex<HashMap<PathBuf, CachedOverride>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// How recently an override file may have been modified for its cached
+/// content to be trusted
+pub const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Content of an override file and the metadata it was read at
+struct CachedOverride {
+    stamp: FileStamp,
+    /// Modified within `RACY_WINDOW` of the read, so the stamp may miss a later edit
+    racy: bool,
+    text: Option<String>,
+}
+
+/// Metadata that changes whenever a file is written or replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+    #[cfg(unix)]
+    inode: u64,
+    #[cfg(unix)]
+    changed: (i64, i64),
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        #[cfg(unix)]
+        use std::os::unix::fs::MetadataExt;
+        Ok(Self {
+            modified: metadata.modified()?,
+            len: metadata.len(),
+            #[cfg(unix)]
+            inode: metadata.ino(),
+            #[cfg(unix)]
+            changed: (metadata.ctime(), metadata.ctime_nsec()),
+        })
+    }
+
+    /// Whether the file was modified too recently for the stamp to be trusted
+    fn is_racy(&self) -> bool {
+        SystemTime::now()
+            .duration_since(self.modified)
+            .map_or(true, |age| age < RACY_WINDOW)
+    }
+}
+
+/// The override cache (a panic while holding it leaves it usable)
+fn overrides() -> std::sync::MutexGuard<'static, HashMap<PathBuf, CachedOverride>> {
+    OVERRIDES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Directory of the override files, relative to the tenant data dir
+pub const PROMPTS_DIR: &str = "prompts";
+
+/// A built-in prompt template
+#[derive(Debug, Clone, Copy)]
+pub struct PromptTemplate {
+    /// Name of the template; overrides live in `{name}.md`
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Placeholders filled in when rendering
+    pub placeholders: &'static [&'static str],
+    pub default: &'static str,
+}
+
+/// All templates that can be overridden
+pub const TEMPLATES: &[PromptTemplate] = &[
+    PromptTemplate {
+        name: "abstract",
+        description: "L0 abstract generation",
+        placeholders: &["content", "entity_hint"],
+        default: prompts::ABSTRACT,
+    },
+    PromptTemplate {
+        name: "abstract_system",
+        description: "System prompt for L0 abstract generation",
+        placeholders: &[],
+        default: prompts::ABSTRACT_SYSTEM,
+    },
+    PromptTemplate {
+        name: "overview",
+        description: "L1 overview generation",
+        placeholders: &["content"],
+        default: prompts::OVERVIEW,
+    },
+    PromptTemplate {
+        name: "overview_system",
+        description: "System prompt for L1 overview generation",
+        placeholders: &[],
+        default: prompts::OVERVIEW_SYSTEM,
+    },
+    PromptTemplate {
+        name: "query_analysis",
+        description: "Search query intent analysis",
+        placeholders: &["query"],
+        default: prompts::QUERY_ANALYSIS,
+    },
+    PromptTemplate {
+        name: "session_extraction",
+        description: "Memory extraction when a session closes",
+        placeholders: &["messages"],
+        default: prompts::SESSION_EXTRACTION,
+    },
+    PromptTemplate {
+        name: "memory_extractor",
+        description: "Memory extraction by MemoryExtractor",
+        placeholders: &["messages"],
+        default: prompts::MEMORY_EXTRACTOR,
+    },
+    PromptTemplate {
+        name: "memory_extraction",
+        description: "Structured memory extraction from a conversation",
+        placeholders: &["conversation"],
+        default: prompts::MEMORY_EXTRACTION,
+    },
+];
+
+/// Resolves prompt templates, preferring override files over the built-ins
+#[derive(Debug, Clone, Default)]
+pub struct PromptTemplates {
+    dir: Option<PathBuf>,
+}
+
+impl PromptTemplates {
+    /// Built-in templates only
+    pub fn builtin() -> Self {
+        Self { dir: None }
+    }
+
+    /// Templates overridden by `{name}.md` files in `dir`
+    pub fn from_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Templates overridden in the `prompts/` directory of a filesystem's tenant
+    pub fn for_filesystem(filesystem: &CortexFilesystem) -> Self {
+        Self::from_dir(filesystem.base_path().join(PROMPTS_DIR))
+    }
+
+    /// Directory searched for override files
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Built-in template by name
+    pub fn find(name: &str) -> Option<&'static PromptTemplate> {
+        TEMPLATES.iter().find(|t| t.name == name)
+    }
+
+    /// Path of the override file of a template
+    pub fn override_path(&self, name: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.md", name)))
+    }
+
+    /// Content of the override file, if one exists and is not empty
+    pub fn load_override(&self, name: &str) -> Option<String> {
+        let path = self.override_path(name)?;
+        let warn_unreadable = |e: std::io::Error| {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(
+                    "Failed to read prompt template {}, using the default: {}",
+                    path.display(),
+                    e
+                );
+            }
+        };
+        let stamp = match FileStamp::of(&path) {
+            Ok(stamp) => stamp,
+            Err(e) => {
+                warn_unreadable(e);
+                overrides().remove(&path);
+                return None;
+            }
+        };
+        if let Some(cached) = overrides().get(&path)
+            && cached.stamp == stamp
+            && !cached.racy
+        {
+            return cached.text.clone();
+        }
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) if !text.trim().is_empty() => Some(text),
+            Ok(_) => None,
+            Err(e) => {
+                warn_unreadable(e);
+                return None;
+            }
+        };
+        overrides().insert(
+            path,
+            CachedOverride {
+                stamp,
+                racy: stamp.is_racy(),
+                text: text.clone(),
+            },
+        );
+        text
+    }
+
+    /// Effective text of a template (empty for unknown names)
+    pub fn get(&self, name: &str) -> Cow<'static, str> {
+        match self.load_override(name) {
+            Some(text) => Cow::Owned(text),
+            None => Cow::Borrowed(Self::find(name).map_or("", |t| t.default)),
+        }
+    }
+
+    /// Render a template with the given placeholder values
+    pub fn render(&self, name: &str, vars: &[(&str, &str)]) -> String {
+        render(&self.get(name), vars)
+    }
+
+    /// Version of a set of templates, used in LLM result cache keys
+    ///
+    /// `v{PROMPTS_VERSION}` while all of them are built-in, otherwise
+    /// `t` followed by a hash of their effective text.
+    pub fn version(&self, names: &[&str]) -> String {
+        let overrides: Vec<_> = names
+            .iter()
+            .map(|name| (name, self.load_override(name)))
+            .collect();
+        if overrides.iter().all(|(_, text)| text.is_none()) {
+            return format!("v{}", PROMPTS_VERSION);
+        }
+
+        let mut hasher = Sha256::new();
+        for (name, text) in overrides {
+            let text = text.map_or_else(|| self.get(name), Cow::Owned);
+            hasher.update(name.as_bytes());
+            hasher.update([0u8]);
+            hasher.update(text.as_bytes());
+            hasher.update([0u8]);
+        }
+        let digest = format!("{:x}", hasher.finalize());
+        format!("t{}", &digest[..12])
+    }
+
+    /// L0 abstract prompt; `known_entities` must appear verbatim in the abstract
+    pub fn abstract_prompt(&self, content: &str, known_entities: &[String]) -> String {
+        // 截断保护：content 最多取前 8000 个字符
+        let safe_content: String = content.chars().take(8000).collect();
+
+        let entity_hint = if known_entities.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n\nIMPORTANT: The following named entities MUST appear verbatim in the abstract \
+                if they are present in the content: {}",
+                known_entities.join(", ")
+            )
+        };
+
+        self.render(
+            "abstract",
+            &[
+                ("content", safe_content.as_str()),
+                ("entity_hint", entity_hint.as_str()),
+            ],
+        )
+    }
+
+    /// L1 overview prompt
+    pub fn overview_prompt(&self, content: &str) -> String {
+        // 截断保护：overview 内容最多取前 16000 个字符
+        let safe_content: String = content.chars().take(16000).collect();
+        self.render("overview", &[("content", safe_content.as_str())])
+    }
+
+    /// Query intent analysis prompt
+    pub fn query_analysis_prompt(&self, query: &str) -> String {
+        // 截断保护：query 最多取前 500 个字符（使用 chars 保证 Unicode 安全）
+        let safe_query: String = query.chars().take(500).collect();
+        self.render("query_analysis", &[("query", safe_query.as_str())])
+    }
+}
+
+/// Replace `{{name}}` placeholders in a single pass
+///
+/// Values are inserted verbatim (placeholders inside them are not expanded);
+/// unknown placeholders are kept as they are.
+pub fn render(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let name = after[..end].trim();
+        match vars.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let rendered = render(
+            "Hi {{name}}, {{ name }}! {{unknown}} {\"json\": 1}",
+            &[("name", "{{name}}")],
+        );
+        assert_eq!(rendered, "Hi {{name}}, {{name}}! {{unknown}} {\"json\": 1}");
+        assert_eq!(render("open {{ end", &[("end", "x")]), "open {{ end");
+    }
+
+    #[test]
+    fn test_builtin_templates_render_all_placeholders() {
+        let templates = PromptTemplates::builtin();
+        for template in TEMPLATES {
+            let vars: Vec<_> = template
+                .placeholders
+                .iter()
+                .map(|p| (*p, "VALUE"))
+                .collect();
+            let rendered = templates.render(template.name, &vars);
+            for placeholder in template.placeholders {
+                assert!(
+                    !rendered.contains(&format!("{{{{{}}}}}", placeholder)),
+                    "{} leaves {{{{{}}}}} unrendered",
+                    template.name,
+                    placeholder
+                );
+            }
+        }
+
+        let prompt = templates.abstract_prompt("Alice met Bob", &["Alice".to_string()]);
+        assert!(prompt.contains("Alice met Bob"));
+        assert!(prompt.contains("MUST appear verbatim in the abstract"));
+    }
+
+    #[test]
+    fn test_override_changes_prompt_and_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = PromptTemplates::from_dir(dir.path());
+        let builtin = format!("v{}", PROMPTS_VERSION);
+        assert_eq!(templates.version(&["abstract", "abstract_system"]), builtin);
+
+        std::fs::write(dir.path().join("abstract.md"), "Summarize: {{content}}").unwrap();
+        assert_eq!(templates.abstract_prompt("text", &[]), "Summarize: text");
+        let v1 = templates.version(&["abstract", "abstract_system"]);
+        assert!(v1.starts_with('t'));
+        // Other templates keep the built-in version
+        assert_eq!(templates.version(&["overview"]), builtin);
+
+        std::fs::write(dir.path().join("abstract.md"), "Abstract of: {{content}}").unwrap();
+        let v2 = templates.version(&["abstract", "abstract_system"]);
+        assert_ne!(v1, v2);
+
+        // Empty files do not override
+        std::fs::write(dir.path().join("abstract.md"), "\n").unwrap();
+        assert_eq!(templates.version(&["abstract", "abstract_system"]), builtin);
+    }
+
+    #[test]
+    fn test_override_same_size_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = PromptTemplates::from_dir(dir.path());
+        let path = dir.path().join("overview.md");
+
+        std::fs::write(&path, "Overview A: {{content}}").unwrap();
+        assert_eq!(
+            templates.render("overview", &[("content", "x")]),
+            "Overview A: x"
+        );
+
+        // Same size, possibly within the same timestamp tick
+        std::fs::write(&path, "Overview B: {{content}}").unwrap();
+        assert_eq!(
+            templates.render("overview", &[("content", "x")]),
+            "Overview B: x"
+        );
+
+        // Replaced by a new file of the same size
+        let tmp = dir.path().join("overview.md.tmp");
+        std::fs::write(&tmp, "Overview C: {{content}}").unwrap();
+        std::fs::rename(&tmp, &path).unwrap();
+        assert_eq!(
+            templates.render("overview", &[("content", "x")]),
+            "Overview C: x"
+        );
+    }
+}
@@ -3,6 +3,12 @@
 //! Implements caching for LLM-generated layer content (L0/L1) to avoid redundant API calls.
 //! Uses content hash as cache key and implements LRU eviction with TTL expiration.
 //!
+//! With `persist_dir` set, results are also written to a file-backed store that
+//! survives restarts: one file per key, evicted least recently used first once
+//! the store exceeds `max_disk_bytes`. Keys combine the prompt template version,
+//! the model and a hash of the input (see [`LlmResultCache::key`]), so persisted
+//! results never outlive the prompt or model that produced them.
+//!
 //! ## Benefits:
 //! - Reduces LLM API costs by 50-75% in scenarios with repeated content
 //! - Improves response time for cached results (instant vs 2-5 seconds)
@@ -15,11 +21,20 @@
 //! With cache (75% hit rate): 5 LLM calls (75% cost reduction)
 //! ```
 
-use std::collections::HashMap;
+use crate::disk_store::DiskStore;
+use crate::llm::prompts::PROMPTS_VERSION;
+use crate::metrics::METRICS;
+use cortex_mem_config::LlmCacheConfig;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// Persistent store location relative to the data directory
+const DEFAULT_PERSIST_DIR: &str = "cache/llm";
 
 /// Configuration for LLM result cache
 #[derive(Debug, Clone)]
@@ -32,6 +47,12 @@ pub struct CacheConfig {
     
     /// Time-to-live for cache entries (seconds)
     pub ttl_secs: u64,
+    
+    /// Directory of the persistent store (`None` = in-memory only)
+    pub persist_dir: Option<PathBuf>,
+    
+    /// Size limit of the persistent store (bytes)
+    pub max_disk_bytes: u64,
 }
 
 impl Default for CacheConfig {
@@ -40,6 +61,30 @@ impl Default for CacheConfig {
             enabled: true,
             max_entries: 1000,      // 1000 entries ~= 1-2MB memory
             ttl_secs: 3600,         // 1 hour TTL
+            persist_dir: None,
+            max_disk_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Build from the `[llm_cache]` config section; a relative `dir` is
+    /// resolved against the data directory
+    pub fn from_config(config: &LlmCacheConfig, data_dir: impl AsRef<Path>) -> Self {
+        let persist_dir = config.persistent.then(|| {
+            let dir = PathBuf::from(config.dir.as_deref().unwrap_or(DEFAULT_PERSIST_DIR));
+            if dir.is_relative() {
+                data_dir.as_ref().join(dir)
+            } else {
+                dir
+            }
+        });
+        Self {
+            enabled: config.enabled,
+            max_entries: config.max_entries,
+            ttl_secs: config.ttl_secs,
+            persist_dir,
+            max_disk_bytes: config.max_disk_mb * 1024 * 1024,
         }
     }
 }
@@ -98,6 +143,9 @@ pub struct CacheStats {
     
     /// Number of entries expired due to TTL
     pub expirations: usize,
+    
+    /// Hits served by the persistent store (included in `hits`)
+    pub disk_hits: usize,
 }
 
 impl CacheStats {
@@ -110,6 +158,20 @@ impl CacheStats {
     }
 }
 
+/// Contents of the persistent store
+#[derive(Debug, Clone, Default)]
+pub struct DiskCacheStats {
+    pub dir: PathBuf,
+    pub entries: usize,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    /// Entries and bytes per kind of result (`l0`, `l1`, `intent`, ...)
+    pub by_kind: BTreeMap<String, (usize, u64)>,
+    /// Entries written for an older prompt version; they are never hit again
+    /// and age out through eviction
+    pub stale_entries: usize,
+}
+
 /// LLM Result Cache
 ///
 /// Thread-safe cache for LLM-generated content with LRU eviction and TTL expiration.
@@ -117,6 +179,9 @@ pub struct LlmResultCache {
     /// Cache storage
     cache: Arc<RwLock<HashMap<String, CachedResult>>>,
     
+    /// Persistent store behind the in-memory LRU
+    disk: Option<DiskStore>,
+    
     /// Configuration
     config: CacheConfig,
     
@@ -124,6 +189,14 @@ pub struct LlmResultCache {
     stats: Arc<RwLock<CacheStats>>,
 }
 
+impl std::fmt::Debug for LlmResultCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LlmResultCache")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
 impl LlmResultCache {
     /// Create a new cache
     pub fn new(config: CacheConfig) -> Self {
@@ -136,56 +209,119 @@ impl LlmResultCache {
             info!("⚠️  LLM result cache disabled");
         }
         
+        let disk = match (&config.persist_dir, config.enabled) {
+            (Some(dir), true) => match DiskStore::open(dir.clone(), "txt", config.max_disk_bytes) {
+                Ok(disk) => {
+                    info!(
+                        "💽 Persistent LLM cache at {} ({} bytes used, limit {})",
+                        dir.display(),
+                        disk.bytes(),
+                        config.max_disk_bytes
+                    );
+                    Some(disk)
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to open persistent LLM cache at {}, using memory only: {}",
+                        dir.display(),
+                        e
+                    );
+                    None
+                }
+            },
+            _ => None,
+        };
+        
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
+            disk,
             config,
             stats: Arc::new(RwLock::new(CacheStats::default())),
         }
     }
     
+    /// Cache key for an LLM result
+    ///
+    /// `kind` names the result (`l0`, `l1`, `intent`, ...; ASCII letters,
+    /// digits and `_`). `prompt_version` is the version of the prompt
+    /// templates that produce it (see
+    /// [`PromptTemplates::version`](crate::llm::PromptTemplates::version)). The
+    /// prompt version and the model are part of the key, so changing either
+    /// never serves results of the old one.
+    pub fn key(kind: &str, prompt_version: &str, model: &str, input: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(model.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(input.as_bytes());
+        format!("{}-{}-{:x}", kind, prompt_version, hasher.finalize())
+    }
+    
+    /// Whether results are persisted across restarts
+    pub fn is_persistent(&self) -> bool {
+        self.disk.is_some()
+    }
+    
     /// Get cached result
     ///
-    /// Returns Some(content) if found and not expired, None otherwise
+    /// Returns Some(content) if found and not expired, None otherwise.
+    /// In-memory misses fall back to the persistent store.
     pub async fn get(&self, key: &str) -> Option<String> {
         if !self.config.enabled {
             return None;
         }
         
-        let mut cache = self.cache.write().await;
-        let mut stats = self.stats.write().await;
-        
-        stats.lookups += 1;
-        
-        if let Some(entry) = cache.get_mut(key) {
-            // Check expiration
-            let ttl = Duration::from_secs(self.config.ttl_secs);
-            if entry.is_expired(ttl) {
-                // Expired, remove
-                cache.remove(key);
-                stats.misses += 1;
-                stats.expirations += 1;
-                debug!("🗑️  Cache expired for key: {}", &key[..8]);
-                return None;
-            }
+        {
+            let mut cache = self.cache.write().await;
+            let mut stats = self.stats.write().await;
             
-            // Hit! Update access time
-            entry.access();
-            stats.hits += 1;
+            stats.lookups += 1;
             
-            debug!(
-                "✅ Cache HIT for key: {} (accessed {} times, age: {:.1}s)",
-                &key[..8],
-                entry.access_count,
-                entry.created_at.elapsed().as_secs_f64()
-            );
-            
-            Some(entry.content.clone())
-        } else {
-            // Miss
-            stats.misses += 1;
-            debug!("❌ Cache MISS for key: {}", &key[..8]);
-            None
+            if let Some(entry) = cache.get_mut(key) {
+                // Check expiration
+                let ttl = Duration::from_secs(self.config.ttl_secs);
+                if entry.is_expired(ttl) {
+                    // Expired, remove (the persistent store may still have it)
+                    cache.remove(key);
+                    stats.expirations += 1;
+                    debug!("🗑️  Cache expired for key: {}", &key[..8]);
+                } else {
+                    // Hit! Update access time
+                    entry.access();
+                    stats.hits += 1;
+                    METRICS.llm_cache_lookups.with_label_values(&["hit"]).inc();
+                    
+                    debug!(
+                        "✅ Cache HIT for key: {} (accessed {} times, age: {:.1}s)",
+                        &key[..8],
+                        entry.access_count,
+                        entry.created_at.elapsed().as_secs_f64()
+                    );
+                    
+                    return Some(entry.content.clone());
+                }
+            }
+        }
+        
+        if let Some(disk) = &self.disk {
+            if let Some(content) = disk.get(key).await.and_then(|b| String::from_utf8(b).ok()) {
+                {
+                    let mut stats = self.stats.write().await;
+                    stats.hits += 1;
+                    stats.disk_hits += 1;
+                }
+                METRICS.llm_cache_lookups.with_label_values(&["hit"]).inc();
+                debug!("✅ Persistent cache HIT for key: {}", key);
+                
+                self.insert(key.to_string(), content.clone()).await;
+                return Some(content);
+            }
         }
+        
+        // Miss
+        self.stats.write().await.misses += 1;
+        METRICS.llm_cache_lookups.with_label_values(&["miss"]).inc();
+        debug!("❌ Cache MISS for key: {}", key);
+        None
     }
     
     /// Put result into cache
@@ -194,6 +330,16 @@ impl LlmResultCache {
             return;
         }
         
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.put(&key, content.as_bytes()).await {
+                warn!("Failed to persist LLM cache entry {}: {}", key, e);
+            }
+        }
+        self.insert(key, content).await;
+    }
+    
+    /// Insert into the in-memory LRU
+    async fn insert(&self, key: String, content: String) {
         let mut cache = self.cache.write().await;
         
         // Check if we need to evict (LRU)
@@ -233,14 +379,51 @@ impl LlmResultCache {
         }
     }
     
-    /// Clear all cached entries
+    /// Clear all cached entries, including the persistent store
     pub async fn clear(&self) {
         let mut cache = self.cache.write().await;
         cache.clear();
         
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.clear().await {
+                warn!("Failed to clear persistent LLM cache: {}", e);
+            }
+        }
+        
         info!("🗑️  Cache cleared");
     }
     
+    /// Contents of the persistent store (None when not persistent)
+    pub async fn disk_stats(&self) -> Option<DiskCacheStats> {
+        let disk = self.disk.as_ref()?;
+        let files = disk.files().await.ok()?;
+        
+        let current = format!("v{}", PROMPTS_VERSION);
+        let mut stats = DiskCacheStats {
+            dir: disk.dir().to_path_buf(),
+            max_bytes: disk.max_bytes(),
+            ..Default::default()
+        };
+        for file in files {
+            // Keys are `{kind}-{version}-{digest}` (see `LlmResultCache::key`);
+            // versions of overridden templates (`t...`) cannot be told stale
+            let mut parts = file.key.rsplitn(3, '-');
+            let _digest = parts.next();
+            let version = parts.next().unwrap_or_default();
+            let kind = parts.next().unwrap_or("other");
+            
+            stats.entries += 1;
+            stats.bytes += file.size;
+            let by_kind = stats.by_kind.entry(kind.to_string()).or_default();
+            by_kind.0 += 1;
+            by_kind.1 += file.size;
+            if version.starts_with('v') && version != current {
+                stats.stale_entries += 1;
+            }
+        }
+        Some(stats)
+    }
+    
     /// Get cache statistics
     pub async fn stats(&self) -> CacheStats {
         self.stats.read().await.clone()
@@ -320,6 +503,7 @@ mod tests {
             enabled: true,
             max_entries: 2,
             ttl_secs: 3600,
+            ..Default::default()
         };
         let cache = LlmResultCache::new(config);
         
@@ -348,6 +532,7 @@ mod tests {
             enabled: false,
             max_entries: 100,
             ttl_secs: 3600,
+            ..Default::default()
         };
         let cache = LlmResultCache::new(config);
         
@@ -364,6 +549,7 @@ mod tests {
             enabled: true,
             max_entries: 100,
             ttl_secs: 0,  // Immediate expiration for testing
+            ..Default::default()
         };
         let cache = LlmResultCache::new(config);
         
@@ -378,4 +564,66 @@ mod tests {
         let stats = cache.stats().await;
         assert_eq!(stats.expirations, 1);
     }
+    
+    fn persistent_config(dir: &Path, max_disk_bytes: u64) -> CacheConfig {
+        CacheConfig {
+            persist_dir: Some(dir.to_path_buf()),
+            max_disk_bytes,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_key_covers_kind_version_model_and_input() {
+        let v = format!("v{}", PROMPTS_VERSION);
+        let key = LlmResultCache::key("l0", &v, "gpt-5-mini", "content");
+        assert!(key.starts_with(&format!("l0-{}-", v)));
+        assert_eq!(key, LlmResultCache::key("l0", &v, "gpt-5-mini", "content"));
+        assert_ne!(key, LlmResultCache::key("l1", &v, "gpt-5-mini", "content"));
+        assert_ne!(key, LlmResultCache::key("l0", "t0123456789ab", "gpt-5-mini", "content"));
+        assert_ne!(key, LlmResultCache::key("l0", &v, "other-model", "content"));
+        assert_ne!(key, LlmResultCache::key("l0", &v, "gpt-5-mini", "other content"));
+    }
+    
+    #[tokio::test]
+    async fn test_persistent_cache_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = LlmResultCache::key("l0", &format!("v{}", PROMPTS_VERSION), "model", "content");
+        
+        let cache = LlmResultCache::new(persistent_config(dir.path(), 1024 * 1024));
+        assert!(cache.is_persistent());
+        cache.put(key.clone(), "abstract".to_string()).await;
+        drop(cache);
+        
+        let cache = LlmResultCache::new(persistent_config(dir.path(), 1024 * 1024));
+        assert_eq!(cache.get(&key).await, Some("abstract".to_string()));
+        // Promoted into memory
+        assert_eq!(cache.get(&key).await, Some("abstract".to_string()));
+        
+        let stats = cache.stats().await;
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.disk_hits, 1);
+        
+        let disk = cache.disk_stats().await.unwrap();
+        assert_eq!(disk.entries, 1);
+        assert_eq!(disk.by_kind["l0"], (1, 8));
+        assert_eq!(disk.stale_entries, 0);
+        
+        cache.clear().await;
+        assert_eq!(cache.disk_stats().await.unwrap().entries, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_persistent_cache_evicts_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = LlmResultCache::new(persistent_config(dir.path(), 250));
+        
+        for i in 0..5 {
+            cache.put(format!("l1-v1-{}", i), "x".repeat(100)).await;
+        }
+        
+        let disk = cache.disk_stats().await.unwrap();
+        assert!(disk.bytes <= 250, "store not evicted: {} bytes", disk.bytes);
+        assert!(disk.entries >= 1);
+    }
 }
@@ -8,24 +8,25 @@
 //! - Reduces redundant LLM calls by 70-90%
 //! - Configurable debounce delay (default: 30 seconds)
 
-use crate::Result;
+use crate::{LayerMode, LlmTask, Result};
 use crate::cascade_layer_debouncer::{DebouncerConfig, LayerUpdateDebouncer};
 use crate::cascade_layer_updater::CascadeLayerUpdater;
 use crate::embedding::EmbeddingClient;
 use crate::filesystem::{CortexFilesystem, FilesystemOperations};
 use crate::incremental_memory_updater::IncrementalMemoryUpdater;
-use crate::llm::LLMClient;
-use crate::llm_result_cache::CacheConfig;
+use crate::llm::{LLMClient, PromptTemplates};
+use crate::llm_result_cache::{CacheConfig, LlmResultCache};
 use crate::memory_events::{ChangeType, DeleteReason, EventStats, MemoryEvent};
 use crate::memory_index::MemoryScope;
 use crate::memory_index_manager::MemoryIndexManager;
+use crate::metrics::METRICS;
 use crate::session::extraction::ExtractedMemories;
 use crate::vector_store::QdrantVectorStore;
 use crate::vector_sync_manager::VectorSyncManager;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
-use tokio::sync::{RwLock, mpsc, watch};
+use tokio::sync::{RwLock, broadcast, mpsc, watch};
 use tracing::{debug, error, info, trace, warn};
 
 /// Configuration for event coordinator
@@ -39,6 +40,11 @@ pub struct CoordinatorConfig {
     pub enable_cache: bool,
     /// Cache configuration
     pub cache_config: CacheConfig,
+    /// Cache shared with other components (layer generator, intent analysis);
+    /// used instead of building one from `cache_config`
+    pub llm_cache: Option<Arc<LlmResultCache>>,
+    /// LLM / extractive generation of cascaded L0/L1 layers
+    pub layer_mode: LayerMode,
 }
 
 impl Default for CoordinatorConfig {
@@ -48,6 +54,8 @@ impl Default for CoordinatorConfig {
             debouncer_config: DebouncerConfig::default(),
             enable_cache: true, // Enable cache by default
             cache_config: CacheConfig::default(),
+            llm_cache: None,
+            layer_mode: LayerMode::default(),
         }
     }
 }
@@ -86,8 +94,13 @@ pub struct MemoryEventCoordinator {
     ///
     /// 使用 scope-granular 集合而非全局 bool，避免误压制不同 scope 的用户。
     suppress_layer_cascade_scopes: Arc<tokio::sync::RwLock<std::collections::HashSet<String>>>,
+    /// 事件观察者（只读旁路，例如 gRPC 事件流）；没有订阅者时发送直接丢弃
+    observers: broadcast::Sender<MemoryEvent>,
 }
 
+/// Capacity of the observer channel; slow subscribers skip ahead (lagged) instead of blocking
+const OBSERVER_CAPACITY: usize = 1024;
+
 impl MemoryEventCoordinator {
     /// Create a new memory event coordinator with default config
     ///
@@ -139,18 +152,23 @@ impl MemoryEventCoordinator {
         ));
 
         // Create layer updater with event sender and optional cache
-        let cache_config = if config.enable_cache {
-            Some(config.cache_config.clone())
+        let llm_cache = if config.enable_cache {
+            Some(config.llm_cache.clone().unwrap_or_else(|| {
+                Arc::new(LlmResultCache::new(config.cache_config.clone()))
+            }))
         } else {
             None
         };
 
-        let layer_updater = Arc::new(CascadeLayerUpdater::new_with_cache(
-            filesystem.clone(),
-            llm_client.clone(),
-            event_tx.clone(),
-            cache_config,
-        ));
+        let layer_updater = Arc::new(
+            CascadeLayerUpdater::with_shared_cache(
+                filesystem.clone(),
+                llm_client.clone(),
+                event_tx.clone(),
+                llm_cache,
+            )
+            .with_mode(config.layer_mode),
+        );
 
         // Create vector sync manager
         let vector_sync = Arc::new(VectorSyncManager::new(
@@ -175,6 +193,7 @@ impl MemoryEventCoordinator {
         // 创建任务完成通知机制
         let pending_tasks = Arc::new(AtomicUsize::new(0));
         let (task_completion_tx, task_completion_rx) = watch::channel(0);
+        let (observers, _) = broadcast::channel(OBSERVER_CAPACITY);
 
         let coordinator = Arc::new(Self {
             filesystem,
@@ -192,6 +211,7 @@ impl MemoryEventCoordinator {
             suppress_layer_cascade_scopes: Arc::new(tokio::sync::RwLock::new(
                 std::collections::HashSet::new(),
             )),
+            observers,
         });
 
         (coordinator, event_tx, event_rx)
@@ -229,6 +249,7 @@ impl MemoryEventCoordinator {
                                 tokio::spawn(async move {
                                     // 在取出事件时就增加计数
                                     coordinator.pending_tasks.fetch_add(1, Ordering::SeqCst);
+                                    METRICS.coordinator_pending_tasks.inc();
 
                                     if let Err(e) = coordinator.handle_event_inner(event).await {
                                         error!("Event handling failed: {}", e);
@@ -236,6 +257,7 @@ impl MemoryEventCoordinator {
 
                                     // 减少计数并通知
                                     let remaining = coordinator.pending_tasks.fetch_sub(1, Ordering::SeqCst) - 1;
+                                    METRICS.coordinator_pending_tasks.dec();
                                     let _ = coordinator.task_completion_tx.send(remaining);
                                 });
                             }
@@ -284,6 +306,22 @@ impl MemoryEventCoordinator {
         self.task_completion_rx.clone()
     }
 
+    /// 订阅协调器处理的每个事件（只读旁路）
+    ///
+    /// Returns a sender handle so callers can create receivers later, even
+    /// after the coordinator itself has been moved into its event loop.
+    pub fn observer(&self) -> broadcast::Sender<MemoryEvent> {
+        self.observers.clone()
+    }
+
+    /// 获取记忆更新器
+    ///
+    /// The updater shares the coordinator's index manager (and its cache), so
+    /// memories written through it stay consistent with extracted ones.
+    pub fn memory_updater(&self) -> Arc<IncrementalMemoryUpdater> {
+        self.memory_updater.clone()
+    }
+
     /// 获取当前待处理任务数量
     pub fn pending_task_count(&self) -> usize {
         self.pending_tasks.load(Ordering::SeqCst)
@@ -427,6 +465,8 @@ impl MemoryEventCoordinator {
             let mut stats = self.stats.write().await;
             stats.record(&event);
         }
+        METRICS.events.with_label_values(&[event.kind()]).inc();
+        let _ = self.observers.send(event.clone());
 
         debug!("Handling event: {}", event);
 
@@ -750,11 +790,13 @@ impl MemoryEventCoordinator {
         let extracted = self.extract_memories_from_session(session_id).await?;
 
         info!(
-            "Extracted memories: {} preferences, {} entities, {} events, {} cases",
+            "Extracted memories: {} preferences, {} entities, {} events, {} cases, {} skills, {} instructions",
             extracted.preferences.len(),
             extracted.entities.len(),
             extracted.events.len(),
-            extracted.cases.len()
+            extracted.cases.len(),
+            extracted.skills.len(),
+            extracted.instructions.len()
         );
 
         // 2. Update user memories
@@ -805,9 +847,9 @@ impl MemoryEventCoordinator {
                 warn!("Failed to update user L0/L1 layers: {}", e);
             }
 
-            if !extracted.cases.is_empty() {
+            if extracted.has_agent_memories() {
                 info!(
-                    "Generating L0/L1 for agent/{} after case memory extraction...",
+                    "Generating L0/L1 for agent/{} after agent memory extraction...",
                     agent_id
                 );
                 if let Err(e) = self
@@ -951,7 +993,11 @@ impl MemoryEventCoordinator {
         let prompt = self.build_extraction_prompt(&messages);
 
         debug!("Calling LLM for memory extraction...");
-        let response = match self.llm_client.complete(&prompt).await {
+        let response = match self
+            .llm_client
+            .complete_for_task(LlmTask::Extraction, None, &prompt)
+            .await
+        {
             Ok(resp) => {
                 debug!("LLM response received ({} chars)", resp.len());
                 resp
@@ -969,7 +1015,9 @@ impl MemoryEventCoordinator {
             extracted.preferences.len()
                 + extracted.entities.len()
                 + extracted.events.len()
-                + extracted.cases.len(),
+                + extracted.cases.len()
+                + extracted.skills.len()
+                + extracted.instructions.len(),
             session_id
         );
 
@@ -1007,112 +1055,8 @@ impl MemoryEventCoordinator {
     /// Build the extraction prompt
     fn build_extraction_prompt(&self, messages: &[String]) -> String {
         let messages_text = messages.join("\n\n---\n\n");
-
-        format!(
-            r#"Analyze the following conversation and extract memories in JSON format.
-
-## CRITICAL LANGUAGE RULES
-
-1. **Language Consistency** (MANDATORY):
-   - Extract memories in the SAME language as the conversation
-   - If conversation is in Chinese (中文) → memories MUST be in Chinese
-   - If conversation is in English → memories in English
-   - If mixed language → use the dominant language (>60% of content)
-   - **DO NOT translate** the conversation content into another language
-
-2. **Preserve Technical Terms** (MANDATORY):
-   - Keep technical terminology unchanged in their original language
-   - Programming languages: Rust, Python, TypeScript, JavaScript, Go
-   - Frameworks: Cortex Memory, Rig, React, Vue
-   - Personality types: INTJ, ENTJ, MBTI, DISC
-   - Proper nouns: names, companies, projects
-   - Acronyms: LLM, AI, ML, API, HTTP, REST
-
-3. **Examples**:
-   ✅ CORRECT (Chinese conversation):
-   - "Cortex Memory 是基于 Rust 的长期记忆系统"
-   - "用户喜欢吃牛肉汉堡，搭配酸黄瓜、芝士和可乐"
-
-   ❌ WRONG (Chinese conversation, should NOT translate to English):
-   - "User likes beef burgers with pickles, cheese, and coke"
-
-## Instructions
-
-Extract the following types of memories:
-
-1. **Personal Info** (user's personal information):
-   - category: "age", "occupation", "education", "location", etc.
-   - content: The specific information
-   - confidence: 0.0-1.0 confidence level
-
-2. **Work History** (user's work experience):
-   - company: Company name
-   - role: Job title/role
-   - duration: Time period (optional)
-   - description: Brief description
-   - confidence: 0.0-1.0 confidence level
-
-3. **Preferences** (user preferences by topic):
-   - topic: The topic/subject area
-   - preference: The user's stated preference
-   - confidence: 0.0-1.0 confidence level
-
-4. **Relationships** (people user mentions):
-   - person: Person's name
-   - relation_type: "family", "colleague", "friend", etc.
-   - context: How they're related
-   - confidence: 0.0-1.0 confidence level
-
-5. **Goals** (user's goals and aspirations):
-   - goal: The specific goal
-   - category: "career", "personal", "health", "learning", etc.
-   - timeline: When they want to achieve it (optional)
-   - confidence: 0.0-1.0 confidence level
-
-6. **Entities** (people, projects, organizations mentioned):
-   - name: Entity name
-   - entity_type: "person", "project", "organization", "technology", etc.
-   - description: Brief description
-   - context: How it was mentioned
-
-7. **Events** (decisions, milestones, important occurrences):
-   - title: Event title
-   - event_type: "decision", "milestone", "occurrence"
-   - summary: Brief summary
-   - timestamp: If mentioned
-
-8. **Cases** (problems encountered and solutions found):
-   - title: Case title
-   - problem: The problem encountered
-   - solution: How it was solved
-   - lessons_learned: Array of lessons learned
-
-## Response Format
-
-Return ONLY a JSON object with this structure:
-
-{{
-  "personal_info": [{{ "category": "...", "content": "...", "confidence": 0.9 }}],
-  "work_history": [{{ "company": "...", "role": "...", "duration": "...", "description": "...", "confidence": 0.9 }}],
-  "preferences": [{{ "topic": "...", "preference": "...", "confidence": 0.9 }}],
-  "relationships": [{{ "person": "...", "relation_type": "...", "context": "...", "confidence": 0.9 }}],
-  "goals": [{{ "goal": "...", "category": "...", "timeline": "...", "confidence": 0.9 }}],
-  "entities": [{{ "name": "...", "entity_type": "...", "description": "...", "context": "..." }}],
-  "events": [{{ "title": "...", "event_type": "...", "summary": "...", "timestamp": "..." }}],
-  "cases": [{{ "title": "...", "problem": "...", "solution": "...", "lessons_learned": ["..."] }}]
-}}
-
-Only include memories that are clearly stated in the conversation. Set empty arrays for categories with no data.
-
-## Conversation
-
-{}
-
-## Response
-
-Return ONLY the JSON object. No additional text before or after."#,
-            messages_text
-        )
+        PromptTemplates::for_filesystem(&self.filesystem)
+            .render("session_extraction", &[("messages", messages_text.as_str())])
     }
 
     /// Parse the LLM extraction response with detailed error logging
@@ -1172,6 +1116,7 @@ Return ONLY the JSON object. No additional text before or after."#,
             MemoryScope::Agent => format!("cortex://agent/{}", owner_id),
             MemoryScope::Session => format!("cortex://session/{}", owner_id),
             MemoryScope::Resources => "cortex://resources".to_string(),
+            MemoryScope::Space => format!("cortex://spaces/{}", owner_id),
         };
 
         self.vector_sync.sync_directory(&root_uri).await?;
@@ -1227,21 +1172,12 @@ mod tests {
 
         // Build prompt directly (doesn't need coordinator)
         let messages_text = messages.join("\n\n---\n\n");
-        let prompt = format!(
-            r#"Analyze the following conversation and extract memories in JSON format.
-
-## Conversation
-
-{}
-
-## Response
-
-Return ONLY the JSON object. No additional text before or after."#,
-            messages_text
-        );
+        let prompt = PromptTemplates::builtin()
+            .render("session_extraction", &[("messages", messages_text.as_str())]);
 
         assert!(prompt.contains("I prefer Rust"));
         assert!(prompt.contains("conversation"));
+        assert!(!prompt.contains("{{messages}}"));
     }
 
     #[test]
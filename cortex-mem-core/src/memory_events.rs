@@ -135,6 +135,34 @@ impl MemoryEvent {
         }
     }
 
+    /// Short snake_case name of the event variant (used as a metrics label)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MemoryEvent::MemoryCreated { .. } => "memory_created",
+            MemoryEvent::MemoryUpdated { .. } => "memory_updated",
+            MemoryEvent::MemoryDeleted { .. } => "memory_deleted",
+            MemoryEvent::MemoryAccessed { .. } => "memory_accessed",
+            MemoryEvent::LayersUpdated { .. } => "layers_updated",
+            MemoryEvent::SessionClosed { .. } => "session_closed",
+            MemoryEvent::LayerUpdateNeeded { .. } => "layer_update_needed",
+            MemoryEvent::VectorSyncNeeded { .. } => "vector_sync_needed",
+        }
+    }
+
+    /// The file or directory URI this event refers to, if any
+    pub fn uri(&self) -> Option<&str> {
+        match self {
+            MemoryEvent::MemoryCreated { file_uri, .. } => Some(file_uri),
+            MemoryEvent::MemoryUpdated { file_uri, .. } => Some(file_uri),
+            MemoryEvent::MemoryDeleted { file_uri, .. } => Some(file_uri),
+            MemoryEvent::MemoryAccessed { .. } => None,
+            MemoryEvent::LayersUpdated { directory_uri, .. } => Some(directory_uri),
+            MemoryEvent::SessionClosed { .. } => None,
+            MemoryEvent::LayerUpdateNeeded { directory_uri, .. } => Some(directory_uri),
+            MemoryEvent::VectorSyncNeeded { file_uri, .. } => Some(file_uri),
+        }
+    }
+
     /// Check if this event requires layer cascade update
     pub fn requires_cascade_update(&self) -> bool {
         matches!(
@@ -21,6 +21,10 @@ pub enum MemoryType {
     Relationship,
     Goal,
     Conversation,
+    /// Reusable procedure the agent learned (agent scope)
+    Skill,
+    /// Standing instruction the agent was given (agent scope)
+    Instruction,
 }
 
 impl std::fmt::Display for MemoryType {
@@ -35,6 +39,8 @@ impl std::fmt::Display for MemoryType {
             MemoryType::Relationship => write!(f, "relationship"),
             MemoryType::Goal => write!(f, "goal"),
             MemoryType::Conversation => write!(f, "conversation"),
+            MemoryType::Skill => write!(f, "skill"),
+            MemoryType::Instruction => write!(f, "instruction"),
         }
     }
 }
@@ -53,6 +59,8 @@ impl std::str::FromStr for MemoryType {
             "relationship" => Ok(MemoryType::Relationship),
             "goal" => Ok(MemoryType::Goal),
             "conversation" => Ok(MemoryType::Conversation),
+            "skill" => Ok(MemoryType::Skill),
+            "instruction" => Ok(MemoryType::Instruction),
             _ => Err(format!("Unknown memory type: {}", s)),
         }
     }
@@ -66,6 +74,8 @@ pub enum MemoryScope {
     Agent,
     Session,
     Resources,
+    /// Shared space (`cortex://spaces/{name}`), owner ID is the space name
+    Space,
 }
 
 impl std::fmt::Display for MemoryScope {
@@ -75,6 +85,7 @@ impl std::fmt::Display for MemoryScope {
             MemoryScope::Agent => write!(f, "agent"),
             MemoryScope::Session => write!(f, "session"),
             MemoryScope::Resources => write!(f, "resources"),
+            MemoryScope::Space => write!(f, "spaces"),
         }
     }
 }
@@ -39,6 +39,7 @@ impl MemoryIndexManager {
             MemoryScope::Agent => format!("cortex://agent/{}/.memory_index.json", owner_id),
             MemoryScope::Session => format!("cortex://session/{}/.memory_index.json", owner_id),
             MemoryScope::Resources => "cortex://resources/.memory_index.json".to_string(),
+            MemoryScope::Space => format!("cortex://spaces/{}/.memory_index.json", owner_id),
         }
     }
 
@@ -315,6 +316,7 @@ impl MemoryIndexManager {
                 MemoryScope::Agent => "agent",
                 MemoryScope::Session => "session",
                 MemoryScope::Resources => "resources",
+                MemoryScope::Space => "spaces",
             },
             owner_id,
             directory
@@ -0,0 +1,271 @@
+//! Pipeline Metrics Module
+//!
+//! Prometheus counters, gauges and histograms for the memory pipeline:
+//! search stages, LLM calls, embedding calls and cache, event coordinator
+//! queue depth, debouncer backlog and vector sync operations.
+//!
+//! All metrics live in one process-wide registry (like the global embedding
+//! rate limiters), so every tenant runtime reports into the same series.
+//! Core only records; exporters such as cortex-mem-service render the
+//! registry with [`encode_text`].
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+
+/// Content type of the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Latency buckets (seconds) spanning cache hits to slow LLM calls
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Global metrics instance
+pub static METRICS: LazyLock<PipelineMetrics> = LazyLock::new(PipelineMetrics::new);
+
+/// All pipeline metrics
+pub struct PipelineMetrics {
+    registry: Registry,
+
+    // ── Search ──
+    /// Search requests, labelled by `mode` ("layered" / "semantic")
+    pub search_requests: IntCounterVec,
+    /// End-to-end search latency, labelled by `mode`
+    pub search_duration: HistogramVec,
+    /// Per-stage search latency, labelled by `stage` ("intent" / "embed" / "vector" / "l0" / "l1" / "l2")
+    pub search_stage_duration: HistogramVec,
+
+    // ── LLM ──
+    /// LLM calls, labelled by `status` ("ok" / "error")
+    pub llm_requests: IntCounterVec,
+    /// LLM call latency
+    pub llm_duration: Histogram,
+    /// Estimated tokens (chars / 4), labelled by `direction` ("prompt" / "completion")
+    pub llm_tokens: IntCounterVec,
+    /// L0/L1 LLM result cache lookups, labelled by `result` ("hit" / "miss")
+    pub llm_cache_lookups: IntCounterVec,
+
+    // ── Embedding ──
+    /// Embedding API calls, labelled by `status` ("ok" / "error" / "rate_limited")
+    pub embedding_requests: IntCounterVec,
+    /// Embedding API call latency (excluding rate limiter wait)
+    pub embedding_duration: Histogram,
+    /// Texts embedded through the API
+    pub embedding_texts: IntCounter,
+    /// Embedding cache lookups, labelled by `result` ("hit" / "miss")
+    pub embedding_cache_lookups: IntCounterVec,
+    /// Time spent waiting on the embedding rate limiter
+    pub embedding_rate_limit_wait: Histogram,
+
+    // ── Event coordinator ──
+    /// Memory events handled, labelled by `kind`
+    pub events: IntCounterVec,
+    /// Events currently being processed by coordinators
+    pub coordinator_pending_tasks: IntGauge,
+    /// Directories waiting in the layer update debouncer
+    pub debouncer_backlog: IntGauge,
+    /// Vector sync operations, labelled by `op` ("indexed" / "updated" / "deleted" / "skipped" / "errors")
+    pub vector_sync_operations: IntCounterVec,
+
+    // ── Inventory (set by exporters at scrape time) ──
+    /// Memories per tenant and scope
+    pub memories: IntGaugeVec,
+}
+
+impl PipelineMetrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("cortex".to_string()), None)
+            .expect("valid metrics namespace");
+
+        let metrics = Self {
+            search_requests: IntCounterVec::new(
+                Opts::new("search_requests_total", "Search requests"),
+                &["mode"],
+            )
+            .expect("valid metric"),
+            search_duration: HistogramVec::new(
+                HistogramOpts::new("search_duration_seconds", "End-to-end search latency")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                &["mode"],
+            )
+            .expect("valid metric"),
+            search_stage_duration: HistogramVec::new(
+                HistogramOpts::new("search_stage_duration_seconds", "Search latency per stage")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                &["stage"],
+            )
+            .expect("valid metric"),
+            llm_requests: IntCounterVec::new(
+                Opts::new("llm_requests_total", "LLM completion calls"),
+                &["status"],
+            )
+            .expect("valid metric"),
+            llm_duration: Histogram::with_opts(
+                HistogramOpts::new("llm_request_duration_seconds", "LLM completion latency")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+            )
+            .expect("valid metric"),
+            llm_tokens: IntCounterVec::new(
+                Opts::new(
+                    "llm_tokens_estimated_total",
+                    "Estimated LLM tokens (characters / 4)",
+                ),
+                &["direction"],
+            )
+            .expect("valid metric"),
+            llm_cache_lookups: IntCounterVec::new(
+                Opts::new("llm_cache_lookups_total", "LLM result cache lookups"),
+                &["result"],
+            )
+            .expect("valid metric"),
+            embedding_requests: IntCounterVec::new(
+                Opts::new("embedding_requests_total", "Embedding API calls"),
+                &["status"],
+            )
+            .expect("valid metric"),
+            embedding_duration: Histogram::with_opts(
+                HistogramOpts::new("embedding_request_duration_seconds", "Embedding API latency")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+            )
+            .expect("valid metric"),
+            embedding_texts: IntCounter::new(
+                "embedding_texts_total",
+                "Texts embedded through the API",
+            )
+            .expect("valid metric"),
+            embedding_cache_lookups: IntCounterVec::new(
+                Opts::new("embedding_cache_lookups_total", "Embedding cache lookups"),
+                &["result"],
+            )
+            .expect("valid metric"),
+            embedding_rate_limit_wait: Histogram::with_opts(
+                HistogramOpts::new(
+                    "embedding_rate_limit_wait_seconds",
+                    "Time spent waiting on the embedding rate limiter",
+                )
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            )
+            .expect("valid metric"),
+            events: IntCounterVec::new(
+                Opts::new("memory_events_total", "Memory events handled by the coordinator"),
+                &["kind"],
+            )
+            .expect("valid metric"),
+            coordinator_pending_tasks: IntGauge::new(
+                "coordinator_pending_tasks",
+                "Events currently being processed by the memory event coordinator",
+            )
+            .expect("valid metric"),
+            debouncer_backlog: IntGauge::new(
+                "debouncer_backlog",
+                "Directories waiting in the layer update debouncer",
+            )
+            .expect("valid metric"),
+            vector_sync_operations: IntCounterVec::new(
+                Opts::new("vector_sync_operations_total", "Vector sync operations"),
+                &["op"],
+            )
+            .expect("valid metric"),
+            memories: IntGaugeVec::new(
+                Opts::new("memories", "Memories per tenant and scope"),
+                &["tenant", "scope"],
+            )
+            .expect("valid metric"),
+            registry,
+        };
+
+        metrics.register_all();
+        metrics
+    }
+
+    fn register_all(&self) {
+        let collectors: Vec<Box<dyn prometheus::core::Collector>> = vec![
+            Box::new(self.search_requests.clone()),
+            Box::new(self.search_duration.clone()),
+            Box::new(self.search_stage_duration.clone()),
+            Box::new(self.llm_requests.clone()),
+            Box::new(self.llm_duration.clone()),
+            Box::new(self.llm_tokens.clone()),
+            Box::new(self.llm_cache_lookups.clone()),
+            Box::new(self.embedding_requests.clone()),
+            Box::new(self.embedding_duration.clone()),
+            Box::new(self.embedding_texts.clone()),
+            Box::new(self.embedding_cache_lookups.clone()),
+            Box::new(self.embedding_rate_limit_wait.clone()),
+            Box::new(self.events.clone()),
+            Box::new(self.coordinator_pending_tasks.clone()),
+            Box::new(self.debouncer_backlog.clone()),
+            Box::new(self.vector_sync_operations.clone()),
+            Box::new(self.memories.clone()),
+        ];
+        for collector in collectors {
+            self.registry
+                .register(collector)
+                .expect("metric registered once");
+        }
+    }
+
+    /// Record one LLM call
+    pub fn observe_llm_call(&self, prompt_chars: usize, response: Option<&str>, elapsed_secs: f64) {
+        self.llm_duration.observe(elapsed_secs);
+        self.llm_tokens
+            .with_label_values(&["prompt"])
+            .inc_by(estimate_tokens(prompt_chars));
+        match response {
+            Some(text) => {
+                self.llm_requests.with_label_values(&["ok"]).inc();
+                self.llm_tokens
+                    .with_label_values(&["completion"])
+                    .inc_by(estimate_tokens(text.len()));
+            }
+            None => self.llm_requests.with_label_values(&["error"]).inc(),
+        }
+    }
+
+    /// Start a timer for one search stage; the duration is recorded on drop
+    pub fn search_stage(&self, stage: &str) -> prometheus::HistogramTimer {
+        self.search_stage_duration
+            .with_label_values(&[stage])
+            .start_timer()
+    }
+}
+
+/// Rough token estimate for calls whose provider reports no usage
+pub(crate) fn estimate_tokens(chars: usize) -> u64 {
+    chars.div_ceil(4) as u64
+}
+
+/// Render all metrics in the Prometheus text format
+pub fn encode_text() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&METRICS.registry.gather(), &mut buffer) {
+        tracing::warn!("Failed to encode metrics: {}", e);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_includes_namespace() {
+        METRICS.search_requests.with_label_values(&["layered"]).inc();
+        METRICS.observe_llm_call(40, Some("abcdefgh"), 0.2);
+
+        let text = encode_text();
+        assert!(text.contains("cortex_search_requests_total{mode=\"layered\"}"));
+        assert!(text.contains("cortex_llm_requests_total{status=\"ok\"}"));
+        assert!(text.contains("cortex_llm_tokens_estimated_total{direction=\"prompt\"}"));
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(0), 0);
+        assert_eq!(estimate_tokens(1), 1);
+        assert_eq!(estimate_tokens(8), 2);
+    }
+}
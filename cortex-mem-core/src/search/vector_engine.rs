@@ -1,14 +1,16 @@
 use crate::{
-    ContextLayer, FilesystemOperations, Result,
+    AccessGuard, ContextLayer, FilesystemOperations, LlmTask, Result,
     embedding::EmbeddingClient,
     filesystem::CortexFilesystem,
     llm::LLMClient,
+    llm_result_cache::LlmResultCache,
     memory_events::MemoryEvent,
     memory_index::MemoryScope,
     memory_index_manager::MemoryIndexManager,
+    metrics::METRICS,
     vector_store::{QdrantVectorStore, VectorStore, uri_to_vector_id},
 };
-use crate::llm::prompts::Prompts;
+use crate::llm::PromptTemplates;
 use super::{EnhancedQueryIntent, QueryIntentType, TimeConstraint};
 use super::weight_model;
 use serde::{Deserialize, Serialize};
@@ -54,6 +56,7 @@ pub struct SearchResult {
 }
 
 /// Vector search engine with L0/L1/L2 layered search support
+#[derive(Clone)]
 pub struct VectorSearchEngine {
     qdrant: Arc<QdrantVectorStore>,
     embedding: Arc<EmbeddingClient>,
@@ -68,6 +71,10 @@ pub struct VectorSearchEngine {
     /// When `false`, the raw query is used directly (skips rewriting/threshold tuning).
     /// Default: `true`.
     enable_intent_analysis: bool,
+    /// Optional access guard; results the guard does not grant are dropped
+    access: Option<AccessGuard>,
+    /// Optional LLM result cache for intent analysis responses
+    llm_cache: Option<Arc<LlmResultCache>>,
 }
 
 impl VectorSearchEngine {
@@ -85,6 +92,8 @@ impl VectorSearchEngine {
             memory_event_tx: None,
             index_manager: None,
             enable_intent_analysis: true,
+            access: None,
+            llm_cache: None,
         }
     }
 
@@ -103,6 +112,8 @@ impl VectorSearchEngine {
             memory_event_tx: None,
             index_manager: None,
             enable_intent_analysis: true,
+            access: None,
+            llm_cache: None,
         }
     }
 
@@ -131,6 +142,36 @@ impl VectorSearchEngine {
         self
     }
 
+    /// Restrict search results to what the guard grants
+    ///
+    /// Vectors are shared by every agent of a tenant, so results from private
+    /// scopes and spaces the guard does not grant are removed before ranking
+    /// is truncated to the requested limit.
+    pub fn with_access(mut self, guard: AccessGuard) -> Self {
+        self.access = Some(guard);
+        self
+    }
+
+    /// Cache LLM intent analysis responses, keyed by model and query
+    pub fn with_llm_cache(mut self, llm_cache: Arc<LlmResultCache>) -> Self {
+        self.llm_cache = Some(llm_cache);
+        self
+    }
+
+    /// Drop results the access guard does not grant
+    fn filter_access(&self, results: &mut Vec<SearchResult>) {
+        if let Some(guard) = &self.access {
+            let before = results.len();
+            results.retain(|r| guard.can_read(&r.uri));
+            if results.len() < before {
+                debug!(
+                    "Filtered {} search results outside the access policy",
+                    before - results.len()
+                );
+            }
+        }
+    }
+
     /// Filter out archived memories from a result list.
     ///
     /// Loads the index for each unique (scope, owner_id) combination found in the
@@ -244,6 +285,7 @@ impl VectorSearchEngine {
             "agent" => MemoryScope::Agent,
             "session" => MemoryScope::Session,
             "resources" => MemoryScope::Resources,
+            "spaces" => MemoryScope::Space,
             _ => return None,
         };
         let owner_id = parts[1].to_string();
@@ -268,6 +310,12 @@ impl VectorSearchEngine {
         query: &str,
         options: &SearchOptions,
     ) -> Result<Vec<SearchResult>> {
+        METRICS.search_requests.with_label_values(&["semantic"]).inc();
+        let _search_timer = METRICS
+            .search_duration
+            .with_label_values(&["semantic"])
+            .start_timer();
+
         let intent = self.analyze_intent(query).await?;
         let query_text = if intent.rewritten_query.trim().is_empty() {
             query
@@ -275,23 +323,28 @@ impl VectorSearchEngine {
             &intent.rewritten_query
         };
 
-        let query_vec = self.embedding.embed(query_text).await?;
+        let query_vec = {
+            let _t = METRICS.search_stage("embed");
+            self.embedding.embed(query_text).await?
+        };
 
         let mut filters = crate::types::Filters::default();
         if let Some(scope) = &options.root_uri {
             filters.uri_prefix = Some(scope.clone());
         }
 
-        let scored = self
-            .qdrant
-            .as_ref()
-            .search_with_threshold(
-                &query_vec,
-                &filters,
-                options.limit.saturating_mul(3).max(options.limit),
-                Some(options.threshold),
-            )
-            .await?;
+        let scored = {
+            let _t = METRICS.search_stage("vector");
+            self.qdrant
+                .as_ref()
+                .search_with_threshold(
+                    &query_vec,
+                    &filters,
+                    options.limit.saturating_mul(3).max(options.limit),
+                    Some(options.threshold),
+                )
+                .await?
+        };
 
         let scope_prefix = options.root_uri.as_ref();
         let scored: Vec<_> = scored
@@ -333,6 +386,7 @@ impl VectorSearchEngine {
             });
         }
 
+        self.filter_access(&mut results);
         Self::rerank_results(&mut results, &intent);
         Self::dedup_results(&mut results);
         results.truncate(options.limit);
@@ -355,6 +409,12 @@ impl VectorSearchEngine {
         query: &str,
         options: &SearchOptions,
     ) -> Result<Vec<SearchResult>> {
+        METRICS.search_requests.with_label_values(&["layered"]).inc();
+        let _search_timer = METRICS
+            .search_duration
+            .with_label_values(&["layered"])
+            .start_timer();
+
         // 1. LLM 统一意图分析（单次请求）
         let intent = self.analyze_intent(query).await?;
 
@@ -364,7 +424,10 @@ impl VectorSearchEngine {
         );
 
         // 2. 用改写后的查询生成 embedding
-        let query_vec = self.embedding.embed(&intent.rewritten_query).await?;
+        let query_vec = {
+            let _t = METRICS.search_stage("embed");
+            self.embedding.embed(&intent.rewritten_query).await?
+        };
 
         // 3. 根据意图类型动态调整 L0 阈值
         let adaptive_threshold = Self::adaptive_l0_threshold(&intent.intent_type);
@@ -379,15 +442,17 @@ impl VectorSearchEngine {
             l0_filters.uri_prefix = Some(scope.clone());
         }
 
-        let l0_results = self
-            .qdrant
-            .search_with_threshold(
-                &query_vec,
-                &l0_filters,
-                options.limit * 3,
-                Some(adaptive_threshold),
-            )
-            .await?;
+        let l0_results = {
+            let _t = METRICS.search_stage("l0");
+            self.qdrant
+                .search_with_threshold(
+                    &query_vec,
+                    &l0_filters,
+                    options.limit * 3,
+                    Some(adaptive_threshold),
+                )
+                .await?
+        };
 
         // Application-level URI prefix filter
         let scope_prefix = options.root_uri.as_ref();
@@ -415,15 +480,17 @@ impl VectorSearchEngine {
                 "Fallback: retrying L0 with relaxed threshold {}",
                 relaxed_threshold
             );
-            let relaxed_results = self
-                .qdrant
-                .search_with_threshold(
-                    &query_vec,
-                    &l0_filters,
-                    options.limit * 5,
-                    Some(relaxed_threshold),
-                )
-                .await?;
+            let relaxed_results = {
+                let _t = METRICS.search_stage("l0");
+                self.qdrant
+                    .search_with_threshold(
+                        &query_vec,
+                        &l0_filters,
+                        options.limit * 5,
+                        Some(relaxed_threshold),
+                    )
+                    .await?
+            };
 
             let relaxed_results: Vec<_> = relaxed_results
                 .into_iter()
@@ -474,6 +541,7 @@ impl VectorSearchEngine {
         );
 
         info!("Stage 2: Exploring L1 overview layer");
+        let l1_timer = METRICS.search_stage("l1");
         let mut candidates = Vec::new();
 
         for l0_result in l0_results {
@@ -502,8 +570,11 @@ impl VectorSearchEngine {
             }
         }
 
+        drop(l1_timer);
+
         info!("Found {} candidates after L1 stage", candidates.len());
         info!("Stage 3: Searching L2 detail layer");
+        let l2_timer = METRICS.search_stage("l2");
         let mut final_results = Vec::new();
 
         for (dir_uri, l0_score, l1_score) in candidates {
@@ -543,6 +614,9 @@ impl VectorSearchEngine {
             }
         }
 
+        drop(l2_timer);
+
+        self.filter_access(&mut final_results);
         Self::rerank_results(&mut final_results, intent);
         Self::dedup_results(&mut final_results);
         final_results.truncate(options.limit);
@@ -561,6 +635,7 @@ impl VectorSearchEngine {
 
     /// 统一意图分析（优先使用 LLM 单次调用，LLM 不可用时使用最小 fallback）
     async fn analyze_intent(&self, query: &str) -> Result<EnhancedQueryIntent> {
+        let _t = METRICS.search_stage("intent");
         if self.enable_intent_analysis {
             if let Some(llm) = &self.llm_client {
                 match self.analyze_intent_with_llm(llm.as_ref(), query).await {
@@ -582,8 +657,30 @@ impl VectorSearchEngine {
         llm: &dyn LLMClient,
         query: &str,
     ) -> Result<EnhancedQueryIntent> {
-        let prompt = Prompts::unified_query_analysis(query);
-        let response = llm.complete(&prompt).await?;
+        let templates = PromptTemplates::for_filesystem(&self.filesystem);
+        let cache_key = self.llm_cache.as_ref().map(|_| {
+            LlmResultCache::key(
+                "intent",
+                &templates.version(&["query_analysis"]),
+                llm.model_for(LlmTask::Intent),
+                query,
+            )
+        });
+        let cached = match (&self.llm_cache, &cache_key) {
+            (Some(cache), Some(key)) => cache.get(key).await,
+            _ => None,
+        };
+        let from_cache = cached.is_some();
+        let response = match cached {
+            Some(response) => {
+                debug!("LLM cache hit for intent analysis");
+                response
+            }
+            None => {
+                let prompt = templates.query_analysis_prompt(query);
+                llm.complete_for_task(LlmTask::Intent, None, &prompt).await?
+            }
+        };
 
         // 提取 JSON（兼容 markdown 代码块包裹）
         let json_str = crate::llm::client::LLMClientImpl::extract_json_from_response_static(&response);
@@ -595,6 +692,13 @@ impl VectorSearchEngine {
             ))
         })?;
 
+        // 仅缓存可解析的响应
+        if !from_cache {
+            if let (Some(cache), Some(key)) = (&self.llm_cache, cache_key) {
+                cache.put(key, response.clone()).await;
+            }
+        }
+
         let intent_type = match val["intent_type"].as_str().unwrap_or("general") {
             "entity_lookup" => QueryIntentType::EntityLookup,
             "factual" => QueryIntentType::Factual,
@@ -5,8 +5,9 @@
 //! - Extract entities (people, projects)
 //! - Extract events/decisions
 //! - Extract agent cases (problem + solution)
+//! - Extract agent skills (reusable procedures) and standing instructions
 
-use crate::{CortexFilesystem, Error, Result, llm::LLMClient};
+use crate::{CortexFilesystem, Error, LlmTask, Result, llm::LLMClient, llm::PromptTemplates};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -37,6 +38,12 @@ pub struct ExtractedMemories {
     /// Goals (career goals, personal goals)
     #[serde(default)]
     pub goals: Vec<GoalMemory>,
+    /// Agent skills (reusable procedures)
+    #[serde(default)]
+    pub skills: Vec<SkillMemory>,
+    /// Standing instructions for the agent
+    #[serde(default)]
+    pub instructions: Vec<InstructionMemory>,
 }
 
 impl Default for ExtractedMemories {
@@ -50,6 +57,8 @@ impl Default for ExtractedMemories {
             work_history: Vec::new(),
             relationships: Vec::new(),
             goals: Vec::new(),
+            skills: Vec::new(),
+            instructions: Vec::new(),
         }
     }
 }
@@ -65,6 +74,13 @@ impl ExtractedMemories {
             && self.work_history.is_empty()
             && self.relationships.is_empty()
             && self.goals.is_empty()
+            && self.skills.is_empty()
+            && self.instructions.is_empty()
+    }
+
+    /// Check if anything was extracted for the agent (cases, skills, instructions)
+    pub fn has_agent_memories(&self) -> bool {
+        !self.cases.is_empty() || !self.skills.is_empty() || !self.instructions.is_empty()
     }
 }
 
@@ -103,6 +119,34 @@ pub struct CaseMemory {
     pub lessons_learned: Vec<String>,
 }
 
+/// Skill memory (a reusable procedure the agent can apply again)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillMemory {
+    pub name: String,
+    /// Kind of task the skill applies to
+    pub when_to_use: String,
+    pub steps: Vec<String>,
+    #[serde(default = "default_agent_confidence")]
+    pub confidence: f32,
+}
+
+/// Instruction memory (a standing rule the agent was told to follow)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstructionMemory {
+    /// Short topic the instruction is about, used to update it later
+    pub topic: String,
+    pub instruction: String,
+    /// Tasks it is limited to (`None` = always applies)
+    #[serde(default)]
+    pub applies_to: Option<String>,
+    #[serde(default = "default_agent_confidence")]
+    pub confidence: f32,
+}
+
+fn default_agent_confidence() -> f32 {
+    0.8
+}
+
 /// Personal information memory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonalInfoMemory {
@@ -142,7 +186,6 @@ pub struct GoalMemory {
 /// Memory extractor for session commit
 pub struct MemoryExtractor {
     llm_client: Arc<dyn LLMClient>,
-    #[allow(dead_code)]
     filesystem: Arc<CortexFilesystem>,
     #[allow(dead_code)]
     user_id: String,
@@ -180,12 +223,15 @@ impl MemoryExtractor {
         let prompt = self.build_extraction_prompt(messages);
         tracing::debug!("Memory extraction prompt length: {} chars", prompt.len());
 
-        let response = self.llm_client.complete(&prompt).await?;
+        let response = self
+            .llm_client
+            .complete_for_task(LlmTask::Extraction, None, &prompt)
+            .await?;
 
         let memories = self.parse_extraction_response(&response)?;
 
         tracing::info!(
-            "Memory extraction completed: preferences={}, entities={}, events={}, cases={}, personal_info={}, work_history={}, relationships={}, goals={}",
+            "Memory extraction completed: preferences={}, entities={}, events={}, cases={}, personal_info={}, work_history={}, relationships={}, goals={}, skills={}, instructions={}",
             memories.preferences.len(),
             memories.entities.len(),
             memories.events.len(),
@@ -193,7 +239,9 @@ impl MemoryExtractor {
             memories.personal_info.len(),
             memories.work_history.len(),
             memories.relationships.len(),
-            memories.goals.len()
+            memories.goals.len(),
+            memories.skills.len(),
+            memories.instructions.len()
         );
 
         Ok(memories)
@@ -202,120 +250,8 @@ impl MemoryExtractor {
     /// Build the extraction prompt
     fn build_extraction_prompt(&self, messages: &[String]) -> String {
         let messages_text = messages.join("\n\n---\n\n");
-
-        format!(
-            r#"Analyze the following conversation and extract memories in JSON format.
-
-## CRITICAL LANGUAGE RULES
-
-1. **Language Consistency** (MANDATORY):
-   - Extract memories in the SAME language as the conversation
-   - If conversation is in Chinese (中文) → memories in Chinese
-   - If conversation is in English → memories in English
-   - If mixed language → use the dominant language (>60% of content)
-
-2. **Preserve Technical Terms** (MANDATORY):
-   - Keep technical terminology unchanged in their original language
-   - Programming languages: Rust, Python, TypeScript, JavaScript, Go
-   - Frameworks: Cortex Memory, Rig, React, Vue
-   - Personality types: INTJ, ENTJ, MBTI, DISC
-   - Proper nouns: names, companies, projects
-   - Acronyms: LLM, AI, ML, API, HTTP, REST
-
-3. **Examples**:
-   ✅ CORRECT (Chinese conversation):
-   - "Cortex Memory 是基于 Rust 的长期记忆系统"
-   - "用户是 INTJ 人格类型，擅长 Python 和 Rust"
-
-   ❌ WRONG (Chinese conversation):
-   - "Cortex Memory is based on 铁锈 long-term memory system"
-   - "User is an INTJ personality type skilled in 蟒蛇 and 铁锈"
-
-   ✅ CORRECT (English conversation):
-   - "User works at SGNetworks as a Rust engineer"
-   - "Cortex Memory is a long-term memory system for Agent"
-
-   ❌ WRONG (English conversation):
-   - "用户 works at SGNetworks as a Rust 工程师"
-   - "Cortex Memory is a 长期记忆 system for Agent"
-
-## Instructions
-
-Extract the following types of memories:
-
-1. **Personal Info** (user's personal information):
-   - category: "age", "occupation", "education", "location", "nationality", etc.
-   - content: The specific information
-   - confidence: 0.0-1.0 confidence level
-
-2. **Work History** (user's work experience):
-   - company: Company name
-   - role: Job title/role
-   - duration: Time period (optional)
-   - description: Brief description of role/responsibilities
-   - confidence: 0.0-1.0 confidence level
-
-3. **Preferences** (user preferences by topic):
-   - topic: The topic/subject area
-   - preference: The user's stated preference
-   - confidence: 0.0-1.0 confidence level
-
-4. **Relationships** (people user mentions):
-   - person: Person's name
-   - relation_type: "family", "colleague", "friend", "mentor", etc.
-   - context: How they're related/context
-   - confidence: 0.0-1.0 confidence level
-
-5. **Goals** (user's goals and aspirations):
-   - goal: The specific goal
-   - category: "career", "personal", "health", "learning", "financial", etc.
-   - timeline: When they want to achieve it (optional)
-   - confidence: 0.0-1.0 confidence level
-
-6. **Entities** (people, projects, organizations mentioned):
-   - name: Entity name
-   - entity_type: "person", "project", "organization", "technology", etc.
-   - description: Brief description
-   - context: How it was mentioned
-
-7. **Events** (decisions, milestones, important occurrences):
-   - title: Event title
-   - event_type: "decision", "milestone", "occurrence"
-   - summary: Brief summary
-   - timestamp: If mentioned
-
-8. **Cases** (problems encountered and solutions found):
-   - title: Case title
-   - problem: The problem encountered
-   - solution: How it was solved
-   - lessons_learned: Array of lessons learned
-
-## Response Format
-
-Return ONLY a JSON object with this structure:
-
-{{
-  "personal_info": [{{"category": "age", "content": "30岁", "confidence": 0.9}}],
-  "work_history": [{{"company": "...", "role": "...", "duration": "...", "description": "...", "confidence": 0.9}}],
-  "preferences": [{{"topic": "...", "preference": "...", "confidence": 0.9}}],
-  "relationships": [{{"person": "...", "relation_type": "...", "context": "...", "confidence": 0.9}}],
-  "goals": [{{"goal": "...", "category": "...", "timeline": "...", "confidence": 0.9}}],
-  "entities": [{{"name": "...", "entity_type": "...", "description": "...", "context": "..."}}],
-  "events": [{{"title": "...", "event_type": "...", "summary": "...", "timestamp": "..."}}],
-  "cases": [{{"title": "...", "problem": "...", "solution": "...", "lessons_learned": ["..."]}}]
-}}
-
-Only include memories that are clearly stated in the conversation. Set empty arrays for categories with no data.
-
-## Conversation
-
-{}
-
-## Response
-
-Return ONLY the JSON object. No additional text before or after."#,
-            messages_text
-        )
+        PromptTemplates::for_filesystem(&self.filesystem)
+            .render("memory_extractor", &[("messages", messages_text.as_str())])
     }
 
     /// Parse the LLM response into ExtractedMemories
@@ -360,5 +296,20 @@ mod tests {
         assert_eq!(parsed.preferences.len(), 1);
         assert_eq!(parsed.preferences[0].topic, "language");
         assert_eq!(parsed.entities.len(), 1);
+        assert!(!parsed.has_agent_memories());
+    }
+
+    #[test]
+    fn test_parse_agent_memories() {
+        let json = r#"{
+            "skills": [{"name": "Release crate", "when_to_use": "Publishing a new version", "steps": ["Bump version", "cargo publish"]}],
+            "instructions": [{"topic": "commit style", "instruction": "Use conventional commits", "confidence": 0.95}]
+        }"#;
+
+        let parsed: ExtractedMemories = serde_json::from_str(json).unwrap();
+        assert!(parsed.has_agent_memories());
+        assert_eq!(parsed.skills[0].steps.len(), 2);
+        assert_eq!(parsed.skills[0].confidence, 0.8);
+        assert_eq!(parsed.instructions[0].applies_to, None);
     }
 }
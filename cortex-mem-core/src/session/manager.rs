@@ -1,8 +1,11 @@
 use crate::events::{CortexEvent, EventBus, SessionEvent};
 use crate::llm::LLMClient;
+use crate::memory_events::{ChangeType, MemoryEvent};
+use crate::memory_index::MemoryScope;
 use crate::{CortexFilesystem, FilesystemOperations, MessageStorage, ParticipantManager, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tracing::{info, warn};
 
@@ -181,6 +184,16 @@ pub struct ExtractionStats {
     pub work_history: usize,
     pub relationships: usize,
     pub goals: usize,
+    pub skills: usize,
+    pub instructions: usize,
+}
+
+/// Messages written into one session by [`SessionManager::import_messages`]
+#[derive(Debug, Clone)]
+pub struct ImportedThread {
+    pub thread_id: String,
+    /// URIs of the written messages, in timestamp order
+    pub uris: Vec<String>,
 }
 
 /// Session manager
@@ -456,6 +469,163 @@ impl SessionManager {
 
         Ok(message)
     }
+
+    /// 批量添加消息（用于历史对话导入）
+    ///
+    /// Messages keep their own ids and timestamps. Unlike calling `add_message` in a loop,
+    /// session metadata is updated once and a single `MessageAdded` event (carrying the last
+    /// message id) is published, so downstream indexing runs once per batch instead of per message.
+    pub async fn add_messages_batch(
+        &self,
+        thread_id: &str,
+        messages: &[crate::session::Message],
+    ) -> Result<Vec<String>> {
+        let Some(last) = messages.last() else {
+            return Ok(Vec::new());
+        };
+
+        let uris = self.message_storage.batch_save(thread_id, messages).await?;
+
+        let mut metadata = self.load_session(thread_id).await?;
+        metadata.update_message_count(metadata.message_count + messages.len());
+        self.update_session(&metadata).await?;
+
+        if let Some(ref bus) = self.event_bus {
+            let _ = bus.publish(CortexEvent::Session(SessionEvent::MessageAdded {
+                session_id: thread_id.to_string(),
+                message_id: last.id.clone(),
+            }));
+        }
+
+        Ok(uris)
+    }
+
+    /// Import messages of several sessions at once, keeping their timestamps
+    ///
+    /// Messages are grouped per thread (in first-seen order), sorted by their
+    /// timestamps and written with one [`add_messages_batch`](Self::add_messages_batch)
+    /// per thread; missing sessions are created. Layer updates are coalesced
+    /// into one `LayerUpdateNeeded` per timeline day directory, with the last
+    /// written file as the trigger, instead of one per message.
+    pub async fn import_messages(
+        &self,
+        messages: impl IntoIterator<Item = (String, crate::session::Message)>,
+    ) -> Result<Vec<ImportedThread>> {
+        let mut thread_order: Vec<String> = Vec::new();
+        let mut grouped: HashMap<String, Vec<crate::session::Message>> = HashMap::new();
+        for (thread_id, message) in messages {
+            if !grouped.contains_key(&thread_id) {
+                thread_order.push(thread_id.clone());
+            }
+            grouped.entry(thread_id).or_default().push(message);
+        }
+
+        let mut threads = Vec::with_capacity(thread_order.len());
+        for thread_id in thread_order {
+            let mut messages = grouped.remove(&thread_id).unwrap_or_default();
+            // Stable sort: messages sharing a timestamp keep their input order
+            messages.sort_by_key(|m| m.timestamp);
+
+            if !self.session_exists(&thread_id).await? {
+                self.create_session_with_ids(&thread_id, None, None).await?;
+                info!("Auto-created session '{}' for batch import", thread_id);
+            }
+            let uris = self.add_messages_batch(&thread_id, &messages).await?;
+
+            if let Some(ref tx) = self.memory_event_tx {
+                let mut day_dirs: BTreeMap<String, String> = BTreeMap::new();
+                for uri in &uris {
+                    if let Some((dir, _)) = uri.rsplit_once('/') {
+                        day_dirs.insert(dir.to_string(), uri.clone());
+                    }
+                }
+                for (directory_uri, changed_file) in day_dirs {
+                    if let Err(e) = tx.send(MemoryEvent::LayerUpdateNeeded {
+                        scope: MemoryScope::Session,
+                        owner_id: thread_id.clone(),
+                        directory_uri,
+                        change_type: ChangeType::Add,
+                        changed_file,
+                    }) {
+                        warn!(
+                            "Failed to send LayerUpdateNeeded for session {}: {}",
+                            thread_id, e
+                        );
+                    }
+                }
+            }
+
+            info!(
+                "Imported {} messages into session {}",
+                uris.len(),
+                thread_id
+            );
+            threads.push(ImportedThread { thread_id, uris });
+        }
+        Ok(threads)
+    }
 }
 
 // 核心功能测试已迁移至 cortex-mem-tools/tests/core_functionality_tests.rs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Message;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_import_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let filesystem = Arc::new(CortexFilesystem::new(dir.path()));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let manager =
+            SessionManager::new(filesystem, SessionConfig::default()).with_memory_event_tx(tx);
+
+        let at = |thread: &str, message: Message, day, hour| {
+            let timestamp = Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap();
+            (thread.to_string(), message.with_timestamp(timestamp))
+        };
+        let imported = manager
+            .import_messages([
+                at("a", Message::user("late"), 2, 9),
+                at("b", Message::user("other"), 1, 9),
+                at("a", Message::user("early"), 1, 9),
+                at("a", Message::assistant("reply"), 1, 10),
+            ])
+            .await
+            .unwrap();
+
+        // Threads in first-seen order, messages in timestamp order
+        let threads: Vec<&str> = imported.iter().map(|t| t.thread_id.as_str()).collect();
+        assert_eq!(threads, ["a", "b"]);
+        assert_eq!(imported[0].uris.len(), 3);
+        assert!(imported[0].uris[0].contains("/timeline/2026-03/01/09_00_00_"));
+        assert!(imported[0].uris[1].contains("/timeline/2026-03/01/10_00_00_"));
+        assert!(imported[0].uris[2].contains("/timeline/2026-03/02/09_00_00_"));
+
+        // Sessions were created and count the imported messages
+        assert_eq!(manager.load_session("a").await.unwrap().message_count, 3);
+        assert_eq!(manager.load_session("b").await.unwrap().message_count, 1);
+
+        // One layer update per day directory, triggered by its last file
+        let mut updates = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let MemoryEvent::LayerUpdateNeeded {
+                owner_id,
+                directory_uri,
+                changed_file,
+                ..
+            } = event
+            {
+                updates.push((owner_id, directory_uri, changed_file));
+            }
+        }
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[0].0, "a");
+        assert_eq!(updates[0].1, "cortex://session/a/timeline/2026-03/01");
+        assert_eq!(updates[0].2, imported[0].uris[1]);
+        assert_eq!(updates[1].1, "cortex://session/a/timeline/2026-03/02");
+        assert_eq!(updates[2].1, "cortex://session/b/timeline/2026-03/01");
+    }
+}
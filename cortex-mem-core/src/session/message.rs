@@ -12,6 +12,18 @@ pub enum MessageRole {
     System,
 }
 
+impl MessageRole {
+    /// Parse a role name ("user", "assistant" or "system", in any case)
+    pub fn parse(role: &str) -> Option<Self> {
+        match role.to_lowercase().as_str() {
+            "user" => Some(Self::User),
+            "assistant" => Some(Self::Assistant),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+}
+
 /// A message in a session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
@@ -52,6 +64,13 @@ impl Message {
         Self::new(MessageRole::System, content)
     }
 
+    /// Override the timestamp (e.g. when importing historical conversations)
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self.created_at = timestamp;
+        self
+    }
+
     /// Add metadata
     pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
         self.metadata = Some(metadata);
@@ -4,11 +4,11 @@ pub mod timeline;
 pub mod participant;
 pub mod extraction;
 
-pub use manager::{SessionManager, SessionConfig, SessionMetadata, SessionStatus};
+pub use manager::{ImportedThread, SessionManager, SessionConfig, SessionMetadata, SessionStatus};
 
 // SessionStatus and SessionMetadata are available but not currently exported
 // pub use manager::{SessionMetadata, SessionStatus};
 pub use message::{Message, MessageRole, MessageStorage};
 pub use timeline::{TimelineGenerator, TimelineEntry, TimelineAggregation};
 pub use participant::{Participant, ParticipantRole, ParticipantManager};
-pub use extraction::{MemoryExtractor, ExtractedMemories, PreferenceMemory, EntityMemory, EventMemory, CaseMemory};
+pub use extraction::{MemoryExtractor, ExtractedMemories, PreferenceMemory, EntityMemory, EventMemory, CaseMemory, SkillMemory, InstructionMemory};
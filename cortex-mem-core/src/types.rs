@@ -13,6 +13,8 @@ pub enum Dimension {
     Agent,
     /// Session/conversation memories
     Session,
+    /// Shared spaces (team / project memories shared between agents)
+    Spaces,
 }
 
 impl Dimension {
@@ -22,6 +24,7 @@ impl Dimension {
             Dimension::User => "user",
             Dimension::Agent => "agent",
             Dimension::Session => "session",
+            Dimension::Spaces => "spaces",
         }
     }
 
@@ -31,6 +34,7 @@ impl Dimension {
             "user" => Some(Dimension::User),
             "agent" => Some(Dimension::Agent),
             "session" => Some(Dimension::Session),
+            "spaces" => Some(Dimension::Spaces),
             _ => None,
         }
     }
@@ -0,0 +1,701 @@
+//! Token usage accounting
+//!
+//! Every LLM and embedding call of a tenant is appended as one JSON line to
+//! `{tenant dir}/usage/{YYYY-MM-DD}.jsonl` (UTC days) with its task, model,
+//! token counts and cost. Appends are single small writes, so several
+//! processes sharing a data directory can record into the same files. They
+//! happen in order on a background thread, so recording never blocks the
+//! caller on disk I/O.
+//!
+//! - [`UsageTracker`] records calls, keeps today's totals for budget checks
+//!   and aggregates the files into a [`UsageSummary`].
+//! - [`MeteredLLMClient`] wraps an [`LLMClient`] so every completion and
+//!   extraction is recorded, and refuses the tasks listed in `skip_when_over_budget` once
+//!   the tenant is over its daily budget.
+//! - `EmbeddingClient::with_usage` records embedding calls.
+//!
+//! Token counts come from the provider when it reports them and are
+//! estimated from text length otherwise (marked `estimated`).
+
+use crate::llm::{
+    DetailedFactExtraction, LLMClient, LLMConfig, MemoryExtractionResponse,
+    StructuredFactExtraction,
+};
+use crate::metrics::estimate_tokens;
+use crate::{Error, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use cortex_mem_config::{LlmTask, UsageConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread::JoinHandle;
+use tracing::{debug, warn};
+
+/// Token counts of one call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Estimated from text length because the provider reported no usage
+    #[serde(default)]
+    pub estimated: bool,
+}
+
+impl TokenUsage {
+    /// Usage reported by a provider
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            estimated: false,
+        }
+    }
+
+    /// Usage estimated from prompt and completion length (bytes)
+    pub fn estimate(prompt_len: usize, completion_len: usize) -> Self {
+        Self {
+            prompt_tokens: estimate_tokens(prompt_len),
+            completion_tokens: estimate_tokens(completion_len),
+            estimated: true,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// What a call was made for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageOperation {
+    /// L0 abstract generation
+    Abstract,
+    /// L1 overview generation
+    Overview,
+    /// Memory extraction
+    Extraction,
+    /// Search intent analysis
+    Intent,
+    /// Embedding of memories and queries
+    Embedding,
+    /// Completions without a task
+    Other,
+}
+
+impl UsageOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageOperation::Abstract => "abstract",
+            UsageOperation::Overview => "overview",
+            UsageOperation::Extraction => "extraction",
+            UsageOperation::Intent => "intent",
+            UsageOperation::Embedding => "embedding",
+            UsageOperation::Other => "other",
+        }
+    }
+}
+
+impl From<LlmTask> for UsageOperation {
+    fn from(task: LlmTask) -> Self {
+        match task {
+            LlmTask::Abstract => UsageOperation::Abstract,
+            LlmTask::Overview => UsageOperation::Overview,
+            LlmTask::Extraction => UsageOperation::Extraction,
+            LlmTask::Intent => UsageOperation::Intent,
+        }
+    }
+}
+
+/// One recorded call (a line of a usage file)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub timestamp: DateTime<Utc>,
+    pub operation: UsageOperation,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+/// Aggregated usage of a set of calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Calls whose token counts are estimated
+    pub estimated_calls: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.calls += 1;
+        self.prompt_tokens += record.prompt_tokens;
+        self.completion_tokens += record.completion_tokens;
+        self.estimated_calls += u64::from(record.estimated);
+        self.cost_usd += record.cost_usd;
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Usage of a tenant over a range of days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: UsageTotals,
+    pub by_day: BTreeMap<NaiveDate, UsageTotals>,
+    pub by_operation: BTreeMap<UsageOperation, UsageTotals>,
+    pub by_model: BTreeMap<String, UsageTotals>,
+}
+
+/// Work for the thread appending to the usage files
+enum WriterMessage {
+    Append(NaiveDate, UsageRecord),
+    /// Acknowledged once every earlier record is written
+    Flush(mpsc::Sender<()>),
+}
+
+/// Records the usage of one tenant
+pub struct UsageTracker {
+    dir: PathBuf,
+    config: UsageConfig,
+    /// Totals of the current UTC day, seeded from its file at startup
+    today: Mutex<(NaiveDate, UsageTotals)>,
+    /// Queue of the writer thread; dropped first on drop so the thread ends
+    writer: Option<mpsc::Sender<WriterMessage>>,
+    writer_thread: Option<JoinHandle<()>>,
+}
+
+impl UsageTracker {
+    /// Tracker writing to `dir` (usually `{tenant dir}/usage`)
+    pub fn new(dir: impl Into<PathBuf>, config: &UsageConfig) -> Self {
+        let dir = dir.into();
+        let date = Utc::now().date_naive();
+        let mut totals = UsageTotals::default();
+        for record in read_day(&dir, date) {
+            totals.add(&record);
+        }
+
+        let (writer, queue) = mpsc::channel();
+        let writer_dir = dir.clone();
+        let writer_thread = std::thread::Builder::new()
+            .name("usage-writer".to_string())
+            .spawn(move || write_records(&writer_dir, queue))
+            .expect("failed to spawn the usage writer thread");
+
+        Self {
+            dir,
+            config: config.clone(),
+            today: Mutex::new((date, totals)),
+            writer: Some(writer),
+            writer_thread: Some(writer_thread),
+        }
+    }
+
+    /// Directory of the usage files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn config(&self) -> &UsageConfig {
+        &self.config
+    }
+
+    /// Record one call (its line is written in the background)
+    pub fn record(&self, operation: UsageOperation, model: &str, usage: TokenUsage) {
+        let cost_usd = self.config.prices.get(model).map_or(0.0, |price| {
+            price.cost(usage.prompt_tokens, usage.completion_tokens)
+        });
+        let record = UsageRecord {
+            timestamp: Utc::now(),
+            operation,
+            model: model.to_string(),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            estimated: usage.estimated,
+            cost_usd,
+        };
+        let date = record.timestamp.date_naive();
+
+        {
+            let mut today = self.today.lock().unwrap_or_else(|e| e.into_inner());
+            if today.0 != date {
+                *today = (date, UsageTotals::default());
+            }
+            today.1.add(&record);
+        }
+        if let Some(writer) = &self.writer {
+            let _ = writer.send(WriterMessage::Append(date, record));
+        }
+    }
+
+    /// Wait until every call recorded so far is written to its file
+    pub fn flush(&self) {
+        let (done, acknowledged) = mpsc::channel();
+        if let Some(writer) = &self.writer
+            && writer.send(WriterMessage::Flush(done)).is_ok()
+        {
+            let _ = acknowledged.recv();
+        }
+    }
+
+    /// Usage of the current UTC day
+    pub fn today(&self) -> UsageTotals {
+        let today = self.today.lock().unwrap_or_else(|e| e.into_inner());
+        if today.0 == Utc::now().date_naive() {
+            today.1.clone()
+        } else {
+            UsageTotals::default()
+        }
+    }
+
+    /// Whether today's usage exceeds a configured budget
+    pub fn over_budget(&self) -> bool {
+        let today = self.today();
+        self.config
+            .daily_token_budget
+            .is_some_and(|budget| today.total_tokens() >= budget)
+            || self
+                .config
+                .daily_cost_budget_usd
+                .is_some_and(|budget| today.cost_usd >= budget)
+    }
+
+    /// Whether a call for `task` may be made
+    pub fn allows(&self, task: LlmTask) -> bool {
+        !self.config.skip_when_over_budget.contains(&task) || !self.over_budget()
+    }
+
+    /// Aggregate the usage of `from..=to`, including calls not yet written
+    pub fn summary(&self, from: NaiveDate, to: NaiveDate) -> UsageSummary {
+        self.flush();
+        Self::summarize(&self.dir, from, to)
+    }
+
+    /// Aggregate the usage files in `dir` for `from..=to`
+    pub fn summarize(dir: &Path, from: NaiveDate, to: NaiveDate) -> UsageSummary {
+        let mut summary = UsageSummary {
+            from,
+            to,
+            total: UsageTotals::default(),
+            by_day: BTreeMap::new(),
+            by_operation: BTreeMap::new(),
+            by_model: BTreeMap::new(),
+        };
+
+        for date in from.iter_days().take_while(|date| *date <= to) {
+            for record in read_day(dir, date) {
+                summary.total.add(&record);
+                summary.by_day.entry(date).or_default().add(&record);
+                summary
+                    .by_operation
+                    .entry(record.operation)
+                    .or_default()
+                    .add(&record);
+                summary
+                    .by_model
+                    .entry(record.model.clone())
+                    .or_default()
+                    .add(&record);
+            }
+        }
+        summary
+    }
+}
+
+impl Drop for UsageTracker {
+    fn drop(&mut self) {
+        // Closing the queue ends the writer once the pending records are written
+        self.writer.take();
+        if let Some(thread) = self.writer_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Writer thread: appends queued records in order until the tracker is dropped
+fn write_records(dir: &Path, queue: mpsc::Receiver<WriterMessage>) {
+    for message in queue {
+        match message {
+            WriterMessage::Append(date, record) => {
+                if let Err(e) = append(dir, date, &record) {
+                    warn!("Failed to record usage in {}: {}", dir.display(), e);
+                }
+            }
+            WriterMessage::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+fn append(dir: &Path, date: NaiveDate, record: &UsageRecord) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(day_path(dir, date))?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn day_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.jsonl", date.format("%Y-%m-%d")))
+}
+
+/// Records of one day; unreadable lines (e.g. cut off by a crash) are skipped
+fn read_day(dir: &Path, date: NaiveDate) -> Vec<UsageRecord> {
+    let path = day_path(dir, date);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            warn!("Failed to read usage file {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                debug!("Skipping malformed usage line in {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// [`LLMClient`] that records the usage of every completion and extraction
+///
+/// Extraction helpers return parsed results without provider usage, so they
+/// are recorded as [`UsageOperation::Extraction`] with estimated counts.
+pub struct MeteredLLMClient {
+    inner: Arc<dyn LLMClient>,
+    tracker: Arc<UsageTracker>,
+}
+
+impl MeteredLLMClient {
+    pub fn new(inner: Arc<dyn LLMClient>, tracker: Arc<UsageTracker>) -> Self {
+        Self { inner, tracker }
+    }
+
+    pub fn tracker(&self) -> &Arc<UsageTracker> {
+        &self.tracker
+    }
+
+    /// Error refusing a call for `task` while today's usage is over budget
+    fn refusal(&self, task: LlmTask) -> Option<Error> {
+        (!self.tracker.allows(task)).then(|| {
+            Error::BudgetExceeded(format!(
+                "daily LLM budget reached, skipping {} call",
+                task.as_str()
+            ))
+        })
+    }
+
+    async fn metered(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        if let Some(e) = self.refusal(task) {
+            return Err(e);
+        }
+        let (response, usage) = self
+            .inner
+            .complete_for_task_with_usage(task, system, prompt)
+            .await?;
+        self.tracker
+            .record(task.into(), self.inner.model_for(task), usage);
+        Ok((response, usage))
+    }
+
+    /// Completion without a task, recorded as [`UsageOperation::Other`]
+    fn record_other(&self, system: Option<&str>, prompt: &str, response: &str) {
+        self.tracker.record(
+            UsageOperation::Other,
+            self.inner.model_name(),
+            TokenUsage::estimate(system.map_or(0, str::len) + prompt.len(), response.len()),
+        );
+    }
+
+    /// Extraction, recorded with usage estimated from the prompt and the
+    /// serialized result
+    async fn extraction<T: Serialize>(
+        &self,
+        prompt: &str,
+        extract: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if let Some(e) = self.refusal(LlmTask::Extraction) {
+            return Err(e);
+        }
+        let result = extract.await?;
+        let response_len = serde_json::to_string(&result).map_or(0, |json| json.len());
+        self.tracker.record(
+            UsageOperation::Extraction,
+            self.inner.model_for(LlmTask::Extraction),
+            TokenUsage::estimate(prompt.len(), response_len),
+        );
+        Ok(result)
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMClient for MeteredLLMClient {
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        let response = self.inner.complete(prompt).await?;
+        self.record_other(None, prompt, &response);
+        Ok(response)
+    }
+
+    async fn complete_with_system(&self, system: &str, prompt: &str) -> Result<String> {
+        let response = self.inner.complete_with_system(system, prompt).await?;
+        self.record_other(Some(system), prompt, &response);
+        Ok(response)
+    }
+
+    async fn extract_memories(&self, prompt: &str) -> Result<MemoryExtractionResponse> {
+        self.extraction(prompt, self.inner.extract_memories(prompt))
+            .await
+    }
+
+    async fn extract_structured_facts(&self, prompt: &str) -> Result<StructuredFactExtraction> {
+        self.extraction(prompt, self.inner.extract_structured_facts(prompt))
+            .await
+    }
+
+    async fn extract_detailed_facts(&self, prompt: &str) -> Result<DetailedFactExtraction> {
+        self.extraction(prompt, self.inner.extract_detailed_facts(prompt))
+            .await
+    }
+
+    async fn complete_for_task(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<String> {
+        Ok(self.metered(task, system, prompt).await?.0)
+    }
+
+    async fn complete_for_task_with_usage(
+        &self,
+        task: LlmTask,
+        system: Option<&str>,
+        prompt: &str,
+    ) -> Result<(String, TokenUsage)> {
+        self.metered(task, system, prompt).await
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn model_for(&self, task: LlmTask) -> &str {
+        self.inner.model_for(task)
+    }
+
+    fn config(&self) -> &LLMConfig {
+        self.inner.config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::MockLLMClient;
+    use cortex_mem_config::ModelPrice;
+
+    fn config() -> UsageConfig {
+        let mut config = UsageConfig::default();
+        config.prices.insert(
+            "gpt-4o-mini".to_string(),
+            ModelPrice {
+                prompt: 0.15,
+                completion: 0.60,
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_record_and_summarize() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = UsageTracker::new(dir.path(), &config());
+        tracker.record(
+            UsageOperation::Extraction,
+            "gpt-4o-mini",
+            TokenUsage::new(1_000_000, 100_000),
+        );
+        tracker.record(
+            UsageOperation::Embedding,
+            "text-embedding-3-small",
+            TokenUsage::new(500, 0),
+        );
+        tracker.record(
+            UsageOperation::Intent,
+            "gpt-4o-mini",
+            TokenUsage::estimate(40, 8),
+        );
+
+        let today = Utc::now().date_naive();
+        let summary = tracker.summary(today, today);
+        assert_eq!(summary.total.calls, 3);
+        assert_eq!(summary.total.prompt_tokens, 1_000_510);
+        assert_eq!(summary.total.estimated_calls, 1);
+        assert!((summary.total.cost_usd - 0.21).abs() < 1e-4);
+        assert_eq!(summary.by_operation[&UsageOperation::Embedding].calls, 1);
+        assert_eq!(
+            summary.by_operation[&UsageOperation::Embedding].cost_usd,
+            0.0
+        );
+        assert_eq!(summary.by_model["gpt-4o-mini"].calls, 2);
+        assert_eq!(summary.by_day[&today], summary.total);
+
+        // A new tracker picks up today's totals from the file
+        let reopened = UsageTracker::new(dir.path(), &config());
+        assert_eq!(reopened.today(), summary.total);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let today = Utc::now().date_naive();
+        let tracker = UsageTracker::new(dir.path(), &config());
+        tracker.record(UsageOperation::Other, "m", TokenUsage::new(1, 2));
+        tracker.flush();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(day_path(dir.path(), today))
+            .unwrap();
+        file.write_all(b"{\"timestamp\":\"2026-").unwrap();
+
+        assert_eq!(
+            UsageTracker::summarize(dir.path(), today, today)
+                .total
+                .calls,
+            1
+        );
+    }
+
+    #[test]
+    fn test_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = UsageTracker::new(
+            dir.path(),
+            &UsageConfig {
+                daily_token_budget: Some(100),
+                ..config()
+            },
+        );
+        assert!(!tracker.over_budget());
+        tracker.record(UsageOperation::Extraction, "m", TokenUsage::new(80, 20));
+
+        assert!(tracker.over_budget());
+        assert!(!tracker.allows(LlmTask::Intent));
+        assert!(tracker.allows(LlmTask::Extraction));
+    }
+
+    #[tokio::test]
+    async fn test_metered_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = Arc::new(UsageTracker::new(
+            dir.path(),
+            &UsageConfig {
+                daily_token_budget: Some(5),
+                ..config()
+            },
+        ));
+        let client = MeteredLLMClient::new(
+            Arc::new(MockLLMClient::with_response("a response")),
+            tracker.clone(),
+        );
+
+        let response = client
+            .complete_for_task(LlmTask::Intent, None, "a prompt of some length")
+            .await
+            .unwrap();
+        assert_eq!(response, "a response");
+        let today = tracker.today();
+        assert_eq!(today.calls, 1);
+        assert_eq!(today.estimated_calls, 1);
+
+        // Over budget: intent analysis is refused, extraction still runs
+        assert!(matches!(
+            client.complete_for_task(LlmTask::Intent, None, "q").await,
+            Err(Error::BudgetExceeded(_))
+        ));
+        assert!(
+            client
+                .complete_for_task(LlmTask::Extraction, None, "q")
+                .await
+                .is_ok()
+        );
+        assert_eq!(tracker.today().calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_metered_extraction() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = Arc::new(UsageTracker::new(
+            dir.path(),
+            &UsageConfig {
+                daily_token_budget: Some(1_000),
+                skip_when_over_budget: vec![LlmTask::Extraction],
+                ..config()
+            },
+        ));
+        let client = MeteredLLMClient::new(Arc::new(MockLLMClient::new()), tracker.clone());
+
+        client.extract_memories("a conversation").await.unwrap();
+        client.extract_structured_facts("some text").await.unwrap();
+        client.extract_detailed_facts("more text").await.unwrap();
+
+        let today = Utc::now().date_naive();
+        let summary = tracker.summary(today, today);
+        let extraction = &summary.by_operation[&UsageOperation::Extraction];
+        assert_eq!(extraction.calls, 3);
+        assert_eq!(extraction.estimated_calls, 3);
+        assert_eq!(summary.by_model["mock-llm"].calls, 3);
+
+        // Extraction is refused over budget when configured to be skipped
+        tracker.record(UsageOperation::Other, "m", TokenUsage::new(1_000, 0));
+        assert!(matches!(
+            client.extract_memories("q").await,
+            Err(Error::BudgetExceeded(_))
+        ));
+        assert_eq!(tracker.today().calls, 4);
+    }
+
+    #[test]
+    fn test_records_written_by_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = UsageTracker::new(dir.path(), &config());
+        for _ in 0..10 {
+            tracker.record(UsageOperation::Embedding, "e", TokenUsage::new(3, 0));
+        }
+        drop(tracker);
+
+        let today = Utc::now().date_naive();
+        let summary = UsageTracker::summarize(dir.path(), today, today);
+        assert_eq!(summary.total.calls, 10);
+        assert_eq!(summary.total.prompt_tokens, 30);
+    }
+}
@@ -0,0 +1,571 @@
+//! Embedding model migration
+//!
+//! Moving a tenant to another embedding model goes through a second Qdrant
+//! collection so search keeps working on the old vectors until the new ones
+//! are complete:
+//!
+//! 1. `start`: create `{collection}__{model}` with the new model's dimension
+//!    and record the migration in `.vector_migration.json` in the tenant dir.
+//!    `MemoryOperations` started from then on dual-write every insert into
+//!    the target collection.
+//! 2. `backfill`: re-embed the `content` of every point of the source
+//!    collection into the target, page by page. The scroll offset is saved
+//!    after each page, and points the target already has with the same
+//!    content are skipped, so an interrupted backfill resumes where it stopped.
+//! 3. `switch`: run a last catch-up pass (re-embedding points that are
+//!    missing or whose content changed, and deleting target points the source
+//!    no longer has), point the configured collection name (an alias from now
+//!    on) at the target collection, save the state and only then drop the old
+//!    collection. Each step is recorded, so an interrupted switch is finished
+//!    by running it again. The `[embedding]` config must then name the new
+//!    model.
+//!
+//! Moving an existing alias is one atomic alias update. On a tenant's first
+//! migration, however, the configured name is still a real collection, which
+//! Qdrant only lets an alias replace once it is deleted: between deleting it
+//! and creating the alias (two consecutive requests) searches and writes on
+//! that name fail. Run the first switch while the tenant is idle.
+
+use super::qdrant::QdrantVectorStore;
+use crate::embedding::{EmbeddingClient, EmbeddingConfig};
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use cortex_mem_config::Provider;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// State file of a migration, relative to the tenant data dir
+pub const MIGRATION_FILE: &str = ".vector_migration.json";
+
+/// Points re-embedded per page
+const PAGE_SIZE: u32 = 64;
+
+/// Phase of a migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationPhase {
+    /// Target collection created, existing vectors are being re-embedded
+    Backfilling,
+    /// All existing vectors are re-embedded; waiting for `switch`
+    Ready,
+    /// Catch-up done, the alias is being moved; `switch` finishes it
+    Switching,
+    /// Search uses the target collection
+    Switched,
+}
+
+impl MigrationPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationPhase::Backfilling => "backfilling",
+            MigrationPhase::Ready => "ready",
+            MigrationPhase::Switching => "switching",
+            MigrationPhase::Switched => "switched",
+        }
+    }
+
+    /// Whether inserts should also go to the target collection
+    pub fn is_dual_write(&self) -> bool {
+        matches!(
+            self,
+            MigrationPhase::Backfilling | MigrationPhase::Ready | MigrationPhase::Switching
+        )
+    }
+}
+
+/// Embedding endpoint of the new model (API keys are never persisted)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationTarget {
+    #[serde(default)]
+    pub provider: Provider,
+    pub api_base_url: String,
+    pub model_name: String,
+    #[serde(default)]
+    pub api_version: Option<String>,
+    #[serde(default)]
+    pub local_model_dir: Option<String>,
+}
+
+impl MigrationTarget {
+    /// Embedding client config of the target model
+    pub fn embedding_config(&self, api_key: &str) -> EmbeddingConfig {
+        EmbeddingConfig {
+            provider: self.provider,
+            api_base_url: self.api_base_url.clone(),
+            api_key: api_key.to_string(),
+            model_name: self.model_name.clone(),
+            api_version: self.api_version.clone(),
+            local_model_dir: self.local_model_dir.as_ref().map(PathBuf::from),
+            ..EmbeddingConfig::default()
+        }
+    }
+}
+
+/// Persisted state of a migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorMigration {
+    /// Configured collection name (tenant-suffixed); an alias after the switch
+    pub source_collection: String,
+    pub target_collection: String,
+    pub target: MigrationTarget,
+    pub dimension: usize,
+    pub phase: MigrationPhase,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Scroll offset of the next backfill page (None = from the start)
+    #[serde(default)]
+    pub offset: Option<String>,
+    /// Points re-embedded so far
+    #[serde(default)]
+    pub copied: u64,
+    /// Points the target already had
+    #[serde(default)]
+    pub skipped: u64,
+    /// Target points deleted by the catch-up because the source lost them
+    #[serde(default)]
+    pub removed: u64,
+    /// Collection the alias pointed at before the switch, until it is dropped
+    #[serde(default)]
+    pub old_collection: Option<String>,
+}
+
+impl VectorMigration {
+    /// Name of the target collection of a model
+    ///
+    /// Readable model name plus a short hash, e.g.
+    /// `cortex-mem_acme__bge-m3-1a2b3c4d`.
+    pub fn target_collection_name(source: &str, model: &str) -> String {
+        let readable: String = model
+            .rsplit('/')
+            .next()
+            .unwrap_or(model)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let digest = format!("{:x}", Sha256::digest(model.as_bytes()));
+        format!("{}__{}-{}", source, readable, &digest[..8])
+    }
+
+    /// Load the migration state of a tenant
+    pub fn load(tenant_dir: &Path) -> std::io::Result<Option<Self>> {
+        let path = tenant_dir.join(MIGRATION_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the state atomically (temp file + rename)
+    pub fn save(&self, tenant_dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(tenant_dir)?;
+        let path = tenant_dir.join(MIGRATION_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Whether `switch` has work left: moving the alias or dropping the old
+    /// collection
+    pub fn is_switch_pending(&self) -> bool {
+        match self.phase {
+            MigrationPhase::Ready | MigrationPhase::Switching => true,
+            MigrationPhase::Switched => self.old_collection.is_some(),
+            MigrationPhase::Backfilling => false,
+        }
+    }
+
+    fn remove(tenant_dir: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(tenant_dir.join(MIGRATION_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Why there is no migration to backfill or abort
+#[derive(Debug, thiserror::Error)]
+enum InactiveMigration {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("The switch to {0} has begun; run switch again to finish it")]
+    Switching(String),
+    #[error("No embedding model migration in progress")]
+    None,
+}
+
+impl From<InactiveMigration> for Error {
+    fn from(e: InactiveMigration) -> Self {
+        match e {
+            InactiveMigration::Io(e) => Error::Io(e),
+            e => Error::Config(e.to_string()),
+        }
+    }
+}
+
+/// Drives the migration of one tenant's collection
+pub struct VectorMigrator {
+    store: QdrantVectorStore,
+    tenant_dir: PathBuf,
+}
+
+impl VectorMigrator {
+    /// `store` is the tenant's store on the configured collection
+    pub fn new(store: &QdrantVectorStore, tenant_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            store: store.clone(),
+            tenant_dir: tenant_dir.into(),
+        }
+    }
+
+    /// Current migration, if any
+    pub fn state(&self) -> std::io::Result<Option<VectorMigration>> {
+        VectorMigration::load(&self.tenant_dir)
+    }
+
+    /// Migration that has not started switching yet
+    fn active_state(&self) -> std::result::Result<VectorMigration, InactiveMigration> {
+        match self.state()? {
+            Some(state)
+                if matches!(
+                    state.phase,
+                    MigrationPhase::Backfilling | MigrationPhase::Ready
+                ) =>
+            {
+                Ok(state)
+            }
+            Some(state) if state.is_switch_pending() => {
+                Err(InactiveMigration::Switching(state.target.model_name))
+            }
+            _ => Err(InactiveMigration::None),
+        }
+    }
+
+    /// Create the target collection and record the migration
+    ///
+    /// `embedding` must be the target model's client; it is used to probe the
+    /// dimension when `dimension` is None.
+    pub async fn start(
+        &self,
+        target: MigrationTarget,
+        embedding: &EmbeddingClient,
+        dimension: Option<usize>,
+    ) -> Result<VectorMigration> {
+        if let Some(state) = self.state()? {
+            if state.phase != MigrationPhase::Switched || state.old_collection.is_some() {
+                return Err(Error::Config(format!(
+                    "A migration to {} is already {}; resume, switch or abort it first",
+                    state.target.model_name,
+                    state.phase.as_str()
+                )));
+            }
+        }
+
+        let source = self.store.collection_name().to_string();
+        let target_collection =
+            VectorMigration::target_collection_name(&source, &target.model_name);
+        if self.store.resolve_alias(&source).await?.as_deref() == Some(target_collection.as_str()) {
+            return Err(Error::Config(format!(
+                "{} already uses {}",
+                source, target.model_name
+            )));
+        }
+
+        let dimension = match dimension {
+            Some(dimension) => dimension,
+            None => embedding.dimension().await?,
+        };
+        self.store
+            .for_collection(&target_collection, dimension)
+            .ensure_collection_with_dim(dimension)
+            .await?;
+
+        let now = Utc::now();
+        let state = VectorMigration {
+            source_collection: source,
+            target_collection,
+            target,
+            dimension,
+            phase: MigrationPhase::Backfilling,
+            started_at: now,
+            updated_at: now,
+            offset: None,
+            copied: 0,
+            skipped: 0,
+            removed: 0,
+            old_collection: None,
+        };
+        state.save(&self.tenant_dir)?;
+        info!(
+            "Started embedding migration {} -> {} ({}, dim {})",
+            state.source_collection, state.target_collection, state.target.model_name, dimension
+        );
+        Ok(state)
+    }
+
+    /// Re-embed the source collection into the target, from the saved offset
+    ///
+    /// `progress` is called after every page with the saved state.
+    pub async fn backfill(
+        &self,
+        embedding: &EmbeddingClient,
+        mut progress: impl FnMut(&VectorMigration),
+    ) -> Result<VectorMigration> {
+        let mut state = self.active_state()?;
+        if state.phase == MigrationPhase::Ready {
+            return Ok(state);
+        }
+
+        loop {
+            let page = self
+                .store
+                .copy_page_reembedded(
+                    &state.source_collection,
+                    &state.target_collection,
+                    state.offset.as_deref(),
+                    PAGE_SIZE,
+                    embedding,
+                )
+                .await?;
+
+            state.copied += page.copied as u64;
+            state.skipped += page.skipped as u64;
+            state.offset = page.next_offset;
+            if state.offset.is_none() {
+                state.phase = MigrationPhase::Ready;
+            }
+            state.updated_at = Utc::now();
+            state.save(&self.tenant_dir)?;
+            progress(&state);
+
+            if state.phase == MigrationPhase::Ready {
+                info!(
+                    "Backfill of {} complete: {} re-embedded, {} already present",
+                    state.target_collection, state.copied, state.skipped
+                );
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Catch up and move the configured collection name to the target
+    ///
+    /// Runs another pass over the source (cheap: unchanged points are skipped)
+    /// for points written or updated by processes that did not dual-write, and
+    /// one over the target for points they deleted. Then points the alias at
+    /// the target and drops the old collection. The state is saved after each
+    /// step, so a switch that failed halfway is finished by calling it again;
+    /// it never reads the source once the alias moved.
+    pub async fn switch(
+        &self,
+        embedding: &EmbeddingClient,
+        mut progress: impl FnMut(&VectorMigration),
+    ) -> Result<VectorMigration> {
+        let mut state = match self.state()? {
+            Some(state) if state.is_switch_pending() => state,
+            Some(state) if state.phase == MigrationPhase::Backfilling => {
+                return Err(Error::Config(
+                    "Backfill is not complete; resume the migration first".to_string(),
+                ));
+            }
+            _ => {
+                return Err(Error::Config(
+                    "No embedding model migration in progress".to_string(),
+                ));
+            }
+        };
+        let alias = state.source_collection.clone();
+
+        if state.phase == MigrationPhase::Ready {
+            state.phase = MigrationPhase::Backfilling;
+            state.offset = None;
+            state.save(&self.tenant_dir)?;
+            state = self.backfill(embedding, &mut progress).await?;
+            self.prune(&mut state, &mut progress).await?;
+
+            state.old_collection = Some(
+                self.store
+                    .resolve_alias(&alias)
+                    .await?
+                    .unwrap_or_else(|| alias.clone()),
+            );
+            state.phase = MigrationPhase::Switching;
+            state.updated_at = Utc::now();
+            state.save(&self.tenant_dir)?;
+        }
+
+        if state.phase == MigrationPhase::Switching {
+            let current = self.store.resolve_alias(&alias).await?;
+            if current.as_deref() != Some(state.target_collection.as_str()) {
+                // An alias cannot share its name with a collection: on the
+                // first migration the configured name is still a collection,
+                // and all of its points are in the target by now. Requests on
+                // that name fail until the alias exists (see the module docs)
+                if current.is_none() && self.store.collection_exists(&alias).await? {
+                    self.store.delete_collection(&alias).await?;
+                }
+                self.store
+                    .set_alias(&alias, &state.target_collection)
+                    .await?;
+            }
+            state.phase = MigrationPhase::Switched;
+            state.updated_at = Utc::now();
+            state.save(&self.tenant_dir)?;
+            info!(
+                "{} now uses {} ({})",
+                alias, state.target_collection, state.target.model_name
+            );
+        }
+
+        if let Some(old_collection) = state.old_collection.clone() {
+            if old_collection != alias && self.store.collection_exists(&old_collection).await? {
+                self.store.delete_collection(&old_collection).await?;
+            }
+            state.old_collection = None;
+            state.updated_at = Utc::now();
+            state.save(&self.tenant_dir)?;
+        }
+        Ok(state)
+    }
+
+    /// Delete target points the source no longer has
+    async fn prune(
+        &self,
+        state: &mut VectorMigration,
+        mut progress: impl FnMut(&VectorMigration),
+    ) -> Result<()> {
+        let mut offset = None;
+        loop {
+            let page = self
+                .store
+                .prune_page(
+                    &state.source_collection,
+                    &state.target_collection,
+                    offset.as_deref(),
+                    PAGE_SIZE,
+                )
+                .await?;
+            offset = page.next_offset;
+            if page.removed > 0 {
+                state.removed += page.removed as u64;
+                state.updated_at = Utc::now();
+                state.save(&self.tenant_dir)?;
+                progress(state);
+            }
+            if offset.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Drop the target collection and forget the migration
+    pub async fn abort(&self) -> Result<VectorMigration> {
+        let state = self.active_state()?;
+        self.store
+            .delete_collection(&state.target_collection)
+            .await?;
+        VectorMigration::remove(&self.tenant_dir)?;
+        info!("Aborted embedding migration to {}", state.target.model_name);
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(phase: MigrationPhase) -> VectorMigration {
+        VectorMigration {
+            source_collection: "cortex-mem_acme".to_string(),
+            target_collection: "cortex-mem_acme__bge-m3-00000000".to_string(),
+            target: MigrationTarget {
+                provider: Provider::Ollama,
+                api_base_url: "http://localhost:11434".to_string(),
+                model_name: "bge-m3".to_string(),
+                api_version: None,
+                local_model_dir: None,
+            },
+            dimension: 1024,
+            phase,
+            started_at: Utc::now(),
+            updated_at: Utc::now(),
+            offset: Some("3f1c2a9e-0000-0000-0000-000000000000".to_string()),
+            copied: 128,
+            skipped: 3,
+            removed: 0,
+            old_collection: None,
+        }
+    }
+
+    #[test]
+    fn test_target_collection_name() {
+        let name = VectorMigration::target_collection_name("cortex-mem_acme", "BAAI/bge-m3");
+        assert!(name.starts_with("cortex-mem_acme__bge-m3-"));
+        assert_eq!(name.len(), "cortex-mem_acme__bge-m3-".len() + 8);
+        // Same readable name, different model: different collection
+        assert_ne!(
+            name,
+            VectorMigration::target_collection_name("cortex-mem_acme", "other/bge-m3")
+        );
+        assert!(
+            VectorMigration::target_collection_name("c", "text-embedding-3.small")
+                .starts_with("c__text-embedding-3_small-")
+        );
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(VectorMigration::load(dir.path()).unwrap().is_none());
+
+        state(MigrationPhase::Backfilling).save(dir.path()).unwrap();
+        let loaded = VectorMigration::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.phase, MigrationPhase::Backfilling);
+        assert!(loaded.phase.is_dual_write());
+        assert_eq!(loaded.target.provider, Provider::Ollama);
+        assert_eq!(
+            loaded.offset.as_deref(),
+            Some("3f1c2a9e-0000-0000-0000-000000000000")
+        );
+        assert_eq!(loaded.copied, 128);
+
+        let config = loaded.target.embedding_config("key");
+        assert_eq!(config.model_name, "bge-m3");
+        assert_eq!(config.api_key, "key");
+
+        VectorMigration::remove(dir.path()).unwrap();
+        assert!(VectorMigration::load(dir.path()).unwrap().is_none());
+        assert!(!MigrationPhase::Switched.is_dual_write());
+    }
+
+    #[test]
+    fn test_switch_pending() {
+        assert!(!state(MigrationPhase::Backfilling).is_switch_pending());
+        assert!(state(MigrationPhase::Ready).is_switch_pending());
+
+        // Interrupted between the alias update and dropping the old collection
+        let dir = tempfile::tempdir().unwrap();
+        let mut switching = state(MigrationPhase::Switching);
+        switching.old_collection = Some("cortex-mem_acme__old-11111111".to_string());
+        switching.save(dir.path()).unwrap();
+        let loaded = VectorMigration::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.phase, MigrationPhase::Switching);
+        assert!(loaded.phase.is_dual_write());
+        assert!(loaded.is_switch_pending());
+
+        let mut switched = loaded;
+        switched.phase = MigrationPhase::Switched;
+        assert!(switched.is_switch_pending());
+        switched.old_collection = None;
+        assert!(!switched.is_switch_pending());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod migration;
 pub mod qdrant;
 
 use crate::{
@@ -6,7 +7,8 @@ use crate::{
 };
 use async_trait::async_trait;
 
-pub use qdrant::QdrantVectorStore;
+pub use migration::{MigrationPhase, MigrationTarget, VectorMigration, VectorMigrator};
+pub use qdrant::{CopyPage, PrunePage, QdrantVectorStore};
 
 /// Generate normalized vector ID from URI and layer
 /// 
@@ -2,28 +2,80 @@ use async_trait::async_trait;
 use qdrant_client::{
     Qdrant,
     qdrant::{
-        Condition, CountPoints, CreateCollection, DeletePoints, Distance, FieldCondition, Filter,
-        GetPoints, IsEmptyCondition, Match, PointId, PointStruct, PointsIdsList, PointsSelector,
-        Range, ScoredPoint, ScrollPoints, SearchPoints, UpsertPoints, VectorParams, VectorsConfig,
-        condition, point_id, points_selector, r#match, vector_output, vectors_config,
-        vectors_output,
+        AliasOperations, ChangeAliases, Condition, CountPoints, CreateAlias, CreateCollection,
+        DeleteAlias, DeletePoints, Distance, FieldCondition, Filter, GetPoints, IsEmptyCondition,
+        Match, PointId, PointStruct, PointsIdsList, PointsSelector, Range, ScoredPoint,
+        ScrollPoints, SearchPoints, UpsertPoints, VectorParams, VectorsConfig, alias_operations,
+        collections_client::CollectionsClient, condition, point_id, points_selector, r#match,
+        vector_output, vectors_config, vectors_output,
     },
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tonic::{
+    Request, Status,
+    codegen::http::uri::{InvalidUri, Uri},
+    metadata::{Ascii, MetadataValue},
+    service::{Interceptor, interceptor::InterceptedService},
+    transport::{Channel, ClientTlsConfig},
+};
 use tracing::{debug, error, info, warn};
 
 use crate::{
     config::QdrantConfig,
+    embedding::EmbeddingClient,
     error::{Error, Result},
     types::{Filters, Memory, MemoryMetadata, ScoredMemory},
     vector_store::VectorStore,
 };
 
+/// Adds the Qdrant API key, if any, to raw gRPC requests
+struct ApiKeyInterceptor(Option<MetadataValue<Ascii>>);
+
+impl Interceptor for ApiKeyInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> std::result::Result<Request<()>, Status> {
+        if let Some(api_key) = &self.0 {
+            request.metadata_mut().insert("api-key", api_key.clone());
+        }
+        Ok(request)
+    }
+}
+
 /// Qdrant vector store implementation
 pub struct QdrantVectorStore {
     client: Qdrant,
     collection_name: String,
     embedding_dim: Option<usize>,
+    /// Second collection written during an embedding model migration
+    dual_write: Option<Arc<DualWrite>>,
+}
+
+/// Target of dual writes: every insert is re-embedded with the new model
+/// and written to the migration's target collection as well
+struct DualWrite {
+    collection: String,
+    embedding: Arc<EmbeddingClient>,
+}
+
+/// Result of copying one page of points to a migration target
+#[derive(Debug, Clone, Default)]
+pub struct CopyPage {
+    /// Points re-embedded and written to the target (missing or changed)
+    pub copied: usize,
+    /// Points the target already had with the same content (written by dual
+    /// writes or an earlier run)
+    pub skipped: usize,
+    /// Offset of the next page, None after the last page
+    pub next_offset: Option<String>,
+}
+
+/// Result of one page of [`QdrantVectorStore::prune_page`]
+#[derive(Debug, Clone, Default)]
+pub struct PrunePage {
+    /// Target points deleted because the source no longer has them
+    pub removed: usize,
+    /// Offset of the next page, None after the last page
+    pub next_offset: Option<String>,
 }
 
 impl QdrantVectorStore {
@@ -43,7 +95,7 @@ impl QdrantVectorStore {
                     .or_else(|| std::env::var("QDRANT_API_KEY").ok()),
             )
             .build()
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         // Use tenant-aware collection name
         let collection_name = config.get_collection_name();
@@ -52,6 +104,7 @@ impl QdrantVectorStore {
             client,
             collection_name,
             embedding_dim: config.embedding_dim,
+            dual_write: None,
         };
 
         // Auto-create collection if embedding_dim is set
@@ -77,7 +130,7 @@ impl QdrantVectorStore {
                     .or_else(|| std::env::var("QDRANT_API_KEY").ok()),
             )
             .build()
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         // Use tenant-aware collection name
         let collection_name = config.get_collection_name();
@@ -86,6 +139,7 @@ impl QdrantVectorStore {
             client,
             collection_name,
             embedding_dim: config.embedding_dim,
+            dual_write: None,
         };
 
         // Auto-detect embedding dimension if not specified
@@ -113,18 +167,7 @@ impl QdrantVectorStore {
     /// This is used by callers (e.g. `MemoryOperations`) that probe the actual
     /// embedding dimension at runtime when the config does not specify it.
     pub async fn ensure_collection_with_dim(&self, embedding_dim: usize) -> Result<()> {
-        let collections = self
-            .client
-            .list_collections()
-            .await
-            .map_err(|e| crate::error::Error::VectorStore(e))?;
-
-        let collection_exists = collections
-            .collections
-            .iter()
-            .any(|c| c.name == self.collection_name);
-
-        if !collection_exists {
+        if !self.collection_exists(&self.collection_name).await? {
             info!(
                 "Creating collection: {} with dimension: {} (probed at runtime)",
                 self.collection_name, embedding_dim
@@ -143,7 +186,7 @@ impl QdrantVectorStore {
                     ..Default::default()
                 })
                 .await
-                .map_err(|e| crate::error::Error::VectorStore(e))?;
+                .map_err(Error::VectorStore)?;
             info!("Collection created successfully: {}", self.collection_name);
         } else {
             debug!("Collection already exists: {}", self.collection_name);
@@ -154,18 +197,7 @@ impl QdrantVectorStore {
 
     /// Ensure the collection exists, create if not
     async fn ensure_collection(&self) -> Result<()> {
-        let collections = self
-            .client
-            .list_collections()
-            .await
-            .map_err(|e| Error::VectorStore(e))?;
-
-        let collection_exists = collections
-            .collections
-            .iter()
-            .any(|c| c.name == self.collection_name);
-
-        if !collection_exists {
+        if !self.collection_exists(&self.collection_name).await? {
             let embedding_dim = self.embedding_dim.ok_or_else(|| {
                 Error::Config(
                     "Embedding dimension not set. Use new_with_llm_client for auto-detection."
@@ -193,7 +225,7 @@ impl QdrantVectorStore {
                     ..Default::default()
                 })
                 .await
-                .map_err(|e| Error::VectorStore(e))?;
+                .map_err(Error::VectorStore)?;
 
             info!("Collection created successfully: {}", self.collection_name);
         } else {
@@ -208,13 +240,29 @@ impl QdrantVectorStore {
         Ok(())
     }
 
+    /// Whether a collection or an alias with this name exists
+    ///
+    /// After an embedding model migration the configured collection name is an
+    /// alias of the migrated collection.
+    pub(crate) async fn collection_exists(&self, name: &str) -> Result<bool> {
+        let collections = self
+            .client
+            .list_collections()
+            .await
+            .map_err(Error::VectorStore)?;
+        if collections.collections.iter().any(|c| c.name == name) {
+            return Ok(true);
+        }
+        Ok(self.resolve_alias(name).await?.is_some())
+    }
+
     /// Verify that the existing collection has the expected dimension
     async fn verify_collection_dimension(&self, expected_dim: usize) -> Result<()> {
         let collection_info = self
             .client
             .collection_info(&self.collection_name)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         if let Some(collection_config) = collection_info.result {
             if let Some(config) = collection_config.config {
@@ -821,6 +869,7 @@ impl Clone for QdrantVectorStore {
             client: self.client.clone(),
             collection_name: self.collection_name.clone(),
             embedding_dim: self.embedding_dim,
+            dual_write: self.dual_write.clone(),
         }
     }
 }
@@ -844,7 +893,7 @@ impl QdrantVectorStore {
             .client
             .list_collections()
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         let names: Vec<String> = response.collections.into_iter().map(|c| c.name).collect();
         Ok(names)
@@ -856,7 +905,7 @@ impl QdrantVectorStore {
             .client
             .collection_info(collection_name)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         let count = response
             .result
@@ -892,7 +941,7 @@ impl QdrantVectorStore {
                 .client
                 .scroll(scroll_points)
                 .await
-                .map_err(|e| Error::VectorStore(e))?;
+                .map_err(Error::VectorStore)?;
 
             if response.result.is_empty() {
                 break;
@@ -942,7 +991,7 @@ impl QdrantVectorStore {
             .client
             .count(count_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         Ok(response.result.map(|r| r.count).unwrap_or(0))
     }
@@ -980,7 +1029,7 @@ impl QdrantVectorStore {
         self.client
             .delete_points(delete_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         debug!("Deleted points by filter from collection: {}", collection_name);
         Ok(())
@@ -1036,7 +1085,7 @@ impl QdrantVectorStore {
         self.client
             .delete_points(delete_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         debug!("Deleted {} points from collection: {}", ids.len(), collection_name);
         Ok(())
@@ -1046,6 +1095,391 @@ impl QdrantVectorStore {
     pub fn collection_name(&self) -> &str {
         &self.collection_name
     }
+
+    // ── Embedding Model Migration ────────────────────────────────────────────
+
+    /// Also write every insert to `collection`, embedded with `embedding`
+    ///
+    /// Used while an embedding model migration backfills its target collection.
+    pub fn with_dual_write(
+        mut self,
+        collection: impl Into<String>,
+        embedding: Arc<EmbeddingClient>,
+    ) -> Self {
+        self.dual_write = Some(Arc::new(DualWrite {
+            collection: collection.into(),
+            embedding,
+        }));
+        self
+    }
+
+    /// Collection receiving dual writes, if any
+    pub fn dual_write_collection(&self) -> Option<&str> {
+        self.dual_write.as_ref().map(|d| d.collection.as_str())
+    }
+
+    /// Same connection, different collection (without dual writes)
+    pub fn for_collection(&self, collection_name: impl Into<String>, embedding_dim: usize) -> Self {
+        Self {
+            client: self.client.clone(),
+            collection_name: collection_name.into(),
+            embedding_dim: Some(embedding_dim),
+            dual_write: None,
+        }
+    }
+
+    /// Write an already converted point to the dual-write collection
+    async fn insert_dual(&self, dual: &DualWrite, memory: &Memory, point: &PointStruct) {
+        let embedding = match dual.embedding.embed(&memory.content).await {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                warn!(
+                    "Dual write to {} skipped for {}: {}",
+                    dual.collection, memory.id, e
+                );
+                return;
+            }
+        };
+
+        let upsert_request = UpsertPoints {
+            collection_name: dual.collection.clone(),
+            points: vec![PointStruct {
+                vectors: Some(embedding.into()),
+                ..point.clone()
+            }],
+            ..Default::default()
+        };
+        if let Err(e) = self.client.upsert_points(upsert_request).await {
+            warn!("Dual write to {} failed for {}: {}", dual.collection, memory.id, e);
+        }
+    }
+
+    /// Collection an alias points to (None if `alias` is not an alias)
+    pub async fn resolve_alias(&self, alias: &str) -> Result<Option<String>> {
+        let response = self
+            .client
+            .list_aliases()
+            .await
+            .map_err(Error::VectorStore)?;
+
+        Ok(response
+            .aliases
+            .into_iter()
+            .find(|a| a.alias_name == alias)
+            .map(|a| a.collection_name))
+    }
+
+    /// Point `alias` at `collection`, replacing the alias if it exists
+    ///
+    /// The delete and create actions go in one `update_aliases` request, which
+    /// Qdrant applies atomically: readers never see the alias missing. An
+    /// alias cannot replace a collection of the same name, so the caller must
+    /// delete such a collection first, and readers do see that gap. The
+    /// high-level client sends a single action per request, so this goes
+    /// through the raw collections client on a connection of its own.
+    pub async fn set_alias(&self, alias: &str, collection: &str) -> Result<()> {
+        let mut actions: Vec<AliasOperations> = Vec::with_capacity(2);
+        if self.resolve_alias(alias).await?.is_some() {
+            actions.push(
+                alias_operations::Action::from(DeleteAlias {
+                    alias_name: alias.to_string(),
+                })
+                .into(),
+            );
+        }
+        actions.push(
+            alias_operations::Action::from(CreateAlias {
+                collection_name: collection.to_string(),
+                alias_name: alias.to_string(),
+            })
+            .into(),
+        );
+
+        self.collections_client()
+            .await?
+            .update_aliases(ChangeAliases {
+                actions,
+                timeout: None,
+            })
+            .await
+            .map_err(|status| Error::VectorStore(status.into()))?;
+
+        info!("Alias {} now points to {}", alias, collection);
+        Ok(())
+    }
+
+    /// Raw gRPC collections client with the same endpoint settings and API key
+    /// as `self.client`
+    async fn collections_client(
+        &self,
+    ) -> Result<CollectionsClient<InterceptedService<Channel, ApiKeyInterceptor>>> {
+        let config = &self.client.config;
+        let uri: Uri = config
+            .uri
+            .parse()
+            .map_err(|e: InvalidUri| Error::VectorStore(e.into()))?;
+        let tls = uri.scheme_str() == Some("https");
+
+        let mut endpoint = Channel::builder(uri)
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .keep_alive_while_idle(config.keep_alive_while_idle);
+        if tls {
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new().with_native_roots())
+                .map_err(|e| {
+                    Error::VectorStore(Status::internal(format!("TLS config: {}", e)).into())
+                })?;
+        }
+        let channel = endpoint.connect().await.map_err(|e| {
+            Error::VectorStore(
+                Status::unavailable(format!("Failed to connect to {}: {}", config.uri, e)).into(),
+            )
+        })?;
+
+        let api_key = match &config.api_key {
+            Some(key) => Some(
+                key.parse::<MetadataValue<Ascii>>()
+                    .map_err(|_| Error::Config("Malformed Qdrant API key".to_string()))?,
+            ),
+            None => None,
+        };
+        Ok(CollectionsClient::with_interceptor(
+            channel,
+            ApiKeyInterceptor(api_key),
+        ))
+    }
+
+    /// Delete a collection and all of its points
+    pub async fn delete_collection(&self, collection_name: &str) -> Result<()> {
+        self.client
+            .delete_collection(collection_name)
+            .await
+            .map_err(Error::VectorStore)?;
+
+        info!("Deleted collection: {}", collection_name);
+        Ok(())
+    }
+
+    /// Re-embed one page of `source` points into `target`
+    ///
+    /// Points keep their ID and payload; only the vector is recomputed from the
+    /// `content` payload. Points already present in `target` with the same
+    /// content (by the `hash` payload, or the content itself for points without
+    /// one) are not touched, so a copy can be interrupted and resumed from the
+    /// last returned offset, and a second pass only re-embeds what changed.
+    pub async fn copy_page_reembedded(
+        &self,
+        source: &str,
+        target: &str,
+        offset: Option<&str>,
+        limit: u32,
+        embedding: &EmbeddingClient,
+    ) -> Result<CopyPage> {
+        let response = self
+            .client
+            .scroll(ScrollPoints {
+                collection_name: source.to_string(),
+                limit: Some(limit),
+                offset: offset.map(parse_point_id),
+                with_payload: Some(true.into()),
+                with_vectors: Some(false.into()),
+                ..Default::default()
+            })
+            .await
+            .map_err(Error::VectorStore)?;
+
+        let ids: Vec<PointId> = response.result.iter().filter_map(|p| p.id.clone()).collect();
+        // Content fingerprint of the points the target already has
+        let existing: HashMap<String, Option<String>> = if ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.client
+                .get_points(GetPoints {
+                    collection_name: target.to_string(),
+                    ids,
+                    with_payload: Some(vec!["hash", "content"].into()),
+                    with_vectors: Some(false.into()),
+                    ..Default::default()
+                })
+                .await
+                .map_err(Error::VectorStore)?
+                .result
+                .iter()
+                .filter_map(|p| {
+                    let id = p.id.as_ref().and_then(point_id_to_string)?;
+                    Some((id, content_fingerprint(&p.payload).map(str::to_string)))
+                })
+                .collect()
+        };
+
+        let mut page = CopyPage {
+            next_offset: response
+                .next_page_offset
+                .as_ref()
+                .and_then(point_id_to_string),
+            ..Default::default()
+        };
+
+        let mut missing = Vec::new();
+        for point in response.result {
+            let id = point.id.as_ref().and_then(point_id_to_string);
+            let fingerprint = content_fingerprint(&point.payload);
+            let unchanged = id
+                .and_then(|id| existing.get(&id))
+                .is_some_and(|target| fingerprint.is_some() && target.as_deref() == fingerprint);
+            if unchanged {
+                page.skipped += 1;
+                continue;
+            }
+            let Some(content) = payload_str(&point.payload, "content").map(str::to_string) else {
+                page.skipped += 1;
+                continue;
+            };
+            missing.push((point, content));
+        }
+        if missing.is_empty() {
+            return Ok(page);
+        }
+
+        let texts: Vec<String> = missing.iter().map(|(_, content)| content.clone()).collect();
+        let embeddings = embedding.embed_batch(&texts).await?;
+        let points: Vec<PointStruct> = missing
+            .into_iter()
+            .zip(embeddings)
+            .map(|((point, _), embedding)| PointStruct {
+                id: point.id,
+                payload: point.payload,
+                vectors: Some(embedding.into()),
+            })
+            .collect();
+        page.copied = points.len();
+
+        self.client
+            .upsert_points(UpsertPoints {
+                collection_name: target.to_string(),
+                points,
+                ..Default::default()
+            })
+            .await
+            .map_err(Error::VectorStore)?;
+
+        Ok(page)
+    }
+
+    /// Delete one page of `target` points that `source` no longer has
+    ///
+    /// Catches deletions made by processes that did not dual-write. Inserts
+    /// reach the source before the target, so a point is never pruned while
+    /// it is being written.
+    pub async fn prune_page(
+        &self,
+        source: &str,
+        target: &str,
+        offset: Option<&str>,
+        limit: u32,
+    ) -> Result<PrunePage> {
+        let response = self
+            .client
+            .scroll(ScrollPoints {
+                collection_name: target.to_string(),
+                limit: Some(limit),
+                offset: offset.map(parse_point_id),
+                with_payload: Some(false.into()),
+                with_vectors: Some(false.into()),
+                ..Default::default()
+            })
+            .await
+            .map_err(Error::VectorStore)?;
+
+        let mut page = PrunePage {
+            next_offset: response
+                .next_page_offset
+                .as_ref()
+                .and_then(point_id_to_string),
+            ..Default::default()
+        };
+        let ids: Vec<PointId> = response.result.into_iter().filter_map(|p| p.id).collect();
+        if ids.is_empty() {
+            return Ok(page);
+        }
+
+        let in_source: HashSet<String> = self
+            .client
+            .get_points(GetPoints {
+                collection_name: source.to_string(),
+                ids: ids.clone(),
+                with_payload: Some(false.into()),
+                with_vectors: Some(false.into()),
+                ..Default::default()
+            })
+            .await
+            .map_err(Error::VectorStore)?
+            .result
+            .iter()
+            .filter_map(|p| p.id.as_ref().and_then(point_id_to_string))
+            .collect();
+        let stale: Vec<PointId> = ids
+            .into_iter()
+            .filter(|id| point_id_to_string(id).is_some_and(|id| !in_source.contains(&id)))
+            .collect();
+        if stale.is_empty() {
+            return Ok(page);
+        }
+
+        page.removed = stale.len();
+        self.client
+            .delete_points(DeletePoints {
+                collection_name: target.to_string(),
+                points: Some(PointsSelector {
+                    points_selector_one_of: Some(points_selector::PointsSelectorOneOf::Points(
+                        PointsIdsList { ids: stale },
+                    )),
+                }),
+                ..Default::default()
+            })
+            .await
+            .map_err(Error::VectorStore)?;
+        Ok(page)
+    }
+}
+
+fn payload_str<'a>(
+    payload: &'a HashMap<String, qdrant_client::qdrant::Value>,
+    key: &str,
+) -> Option<&'a str> {
+    match payload.get(key) {
+        Some(qdrant_client::qdrant::Value {
+            kind: Some(qdrant_client::qdrant::value::Kind::StringValue(value)),
+        }) => Some(value),
+        _ => None,
+    }
+}
+
+/// What identifies the content of a point: its `hash` payload, or the
+/// content itself for points written without one
+fn content_fingerprint(payload: &HashMap<String, qdrant_client::qdrant::Value>) -> Option<&str> {
+    payload_str(payload, "hash")
+        .filter(|hash| !hash.is_empty())
+        .or_else(|| payload_str(payload, "content"))
+}
+
+fn point_id_to_string(id: &PointId) -> Option<String> {
+    match &id.point_id_options {
+        Some(point_id::PointIdOptions::Uuid(uuid)) => Some(uuid.clone()),
+        Some(point_id::PointIdOptions::Num(num)) => Some(num.to_string()),
+        None => None,
+    }
+}
+
+fn parse_point_id(id: &str) -> PointId {
+    let options = match id.parse::<u64>() {
+        Ok(num) => point_id::PointIdOptions::Num(num),
+        Err(_) => point_id::PointIdOptions::Uuid(id.to_string()),
+    };
+    PointId {
+        point_id_options: Some(options),
+    }
 }
 
 #[async_trait]
@@ -1055,14 +1489,18 @@ impl VectorStore for QdrantVectorStore {
 
         let upsert_request = UpsertPoints {
             collection_name: self.collection_name.clone(),
-            points: vec![point],
+            points: vec![point.clone()],
             ..Default::default()
         };
 
         self.client
             .upsert_points(upsert_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
+
+        if let Some(dual) = &self.dual_write {
+            self.insert_dual(dual, memory, &point).await;
+        }
 
         debug!("Inserted memory with ID: {}", memory.id);
         Ok(())
@@ -1103,7 +1541,7 @@ impl VectorStore for QdrantVectorStore {
             .client
             .search_points(search_points)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         let mut results = Vec::new();
         for point in response.result {
@@ -1148,14 +1586,25 @@ impl VectorStore for QdrantVectorStore {
 
         let delete_request = DeletePoints {
             collection_name: self.collection_name.clone(),
-            points: Some(points_selector),
+            points: Some(points_selector.clone()),
             ..Default::default()
         };
 
         self.client
             .delete_points(delete_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
+
+        if let Some(dual) = &self.dual_write {
+            let delete_request = DeletePoints {
+                collection_name: dual.collection.clone(),
+                points: Some(points_selector),
+                ..Default::default()
+            };
+            if let Err(e) = self.client.delete_points(delete_request).await {
+                warn!("Dual delete from {} failed for {}: {}", dual.collection, id, e);
+            }
+        }
 
         debug!("Deleted memory with ID: {}", id);
         Ok(())
@@ -1178,7 +1627,7 @@ impl VectorStore for QdrantVectorStore {
             .client
             .get_points(get_request)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         if let Some(point) = response.result.first() {
             // Convert RetrievedPoint to ScoredPoint for parsing
@@ -1221,7 +1670,7 @@ impl VectorStore for QdrantVectorStore {
             .client
             .scroll(scroll_points)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         let mut results = Vec::new();
         for point in response.result {
@@ -1275,7 +1724,7 @@ impl VectorStore for QdrantVectorStore {
             .client
             .scroll(scroll_points)
             .await
-            .map_err(|e| Error::VectorStore(e))?;
+            .map_err(Error::VectorStore)?;
 
         let ids: Vec<String> = response
             .result
@@ -6,6 +6,7 @@
 use crate::embedding::EmbeddingClient;
 use crate::filesystem::{CortexFilesystem, FilesystemOperations};
 use crate::memory_events::ChangeType;
+use crate::metrics::METRICS;
 use crate::types::{Memory, MemoryMetadata};
 use crate::vector_store::{QdrantVectorStore, VectorStore, uri_to_vector_id};
 use crate::{ContextLayer, Result};
@@ -28,6 +29,17 @@ impl VectorSyncStats {
     }
 }
 
+/// Mirror a single sync operation into the pipeline metrics
+///
+/// Called where the leaf operation happens, not where sub-stats are merged,
+/// so recursive syncs are counted once.
+fn record_op(op: &str, n: usize) {
+    METRICS
+        .vector_sync_operations
+        .with_label_values(&[op])
+        .inc_by(n as u64);
+}
+
 /// Vector Sync Manager
 ///
 /// Manages synchronization between the file system and vector database.
@@ -108,6 +120,7 @@ impl VectorSyncManager {
         if self.vector_store.get(&l2_id).await?.is_some() {
             debug!("File {} already indexed, skipping", file_uri);
             stats.skipped += 1;
+            record_op("skipped", 1);
             return Ok(());
         }
         
@@ -117,6 +130,7 @@ impl VectorSyncManager {
             Err(e) => {
                 warn!("❌ VectorSync: failed to read file {}: {}", file_uri, e);
                 stats.errors += 1;
+                record_op("errors", 1);
                 return Ok(());
             }
         };
@@ -127,6 +141,7 @@ impl VectorSyncManager {
             Err(e) => {
                 warn!("❌ VectorSync: failed to generate embedding for {}: {}", file_uri, e);
                 stats.errors += 1;
+                record_op("errors", 1);
                 return Ok(());
             }
         };
@@ -148,6 +163,7 @@ impl VectorSyncManager {
         // Insert into vector store
         self.vector_store.insert(&memory).await?;
         stats.indexed += 1;
+        record_op("indexed", 1);
         
         debug!("Indexed L2 for {}", file_uri);
         
@@ -166,6 +182,7 @@ impl VectorSyncManager {
         self.index_file(file_uri, stats).await?;
         
         stats.updated += 1;
+        record_op("updated", 1);
         stats.indexed = stats.indexed.saturating_sub(1); // Adjust for the re-index
         
         Ok(())
@@ -175,6 +192,7 @@ impl VectorSyncManager {
     async fn delete_file(&self, file_uri: &str, stats: &mut VectorSyncStats) -> Result<()> {
         self.delete_vectors_for_uri(file_uri).await?;
         stats.deleted += 3; // L0, L1, L2
+        record_op("deleted", 3);
         
         Ok(())
     }
@@ -228,6 +246,7 @@ impl VectorSyncManager {
                         
                         self.vector_store.insert(&memory).await?;
                         stats.indexed += 1;
+                        record_op("indexed", 1);
                         debug!("Indexed L0 for {}", dir_uri);
                     }
                 }
@@ -257,6 +276,7 @@ impl VectorSyncManager {
                         
                         self.vector_store.insert(&memory).await?;
                         stats.indexed += 1;
+                        record_op("indexed", 1);
                         debug!("Indexed L1 for {}", dir_uri);
                     }
                 }
@@ -308,6 +328,7 @@ impl VectorSyncManager {
                             // Compare with stored hash (simplified - would need metadata comparison)
                             // For now, skip if already indexed
                             stats.skipped += 1;
+                            record_op("skipped", 1);
                         }
                     } else {
                         // Index new file
@@ -345,6 +366,7 @@ impl VectorSyncManager {
                         
                         self.vector_store.insert(&memory).await?;
                         stats.indexed += 1;
+                        record_op("indexed", 1);
                     }
                 }
             }
@@ -368,6 +390,7 @@ impl VectorSyncManager {
                         
                         self.vector_store.insert(&memory).await?;
                         stats.indexed += 1;
+                        record_op("indexed", 1);
                     }
                 }
             }
@@ -457,6 +480,7 @@ impl VectorSyncManager {
                 }
                 Err(_) => {
                     stats.errors += 1;
+                    record_op("errors", 1);
                 }
             }
         }
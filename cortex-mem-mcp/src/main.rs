@@ -1,16 +1,39 @@
 use anyhow::{anyhow, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use cortex_mem_config::Config;
 use cortex_mem_core::llm::LLMClientImpl;
-use cortex_mem_tools::MemoryOperations;
-use rmcp::{transport::stdio, ServiceExt};
+use cortex_mem_tools::ToolPolicy;
+use rmcp::{
+    transport::{
+        stdio,
+        streamable_http_server::{
+            session::local::LocalSessionManager, StreamableHttpServerConfig,
+            StreamableHttpService,
+        },
+    },
+    ServiceExt,
+};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::{error, info};
 
+mod pool;
+mod prompts;
+mod resources;
 mod service;
+use pool::{Identity, OperationsPool};
 use service::{AutoTriggerConfig, MemoryMcpService};
 
+/// How MCP clients connect to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Transport {
+    /// One client over stdin/stdout (spawned by the client)
+    Stdio,
+    /// Many clients over streamable HTTP with SSE, served at /mcp
+    Http,
+}
+
 #[derive(Parser)]
 #[command(name = "cortex-mem-mcp")]
 #[command(about = "MCP server for Cortex Memory to enhance agent's memory layer")]
@@ -21,7 +44,21 @@ struct Cli {
     #[arg(short, long, default_value = "config.toml")]
     config: PathBuf,
 
-    /// Tenant identifier for memory operations
+    /// Transport to serve MCP over
+    #[arg(long, value_enum, default_value = "stdio")]
+    transport: Transport,
+
+    /// Bind address for the HTTP transport
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port for the HTTP transport
+    #[arg(long, default_value_t = 8086)]
+    port: u16,
+
+    /// Tenant identifier for memory operations.
+    /// With the HTTP transport this is the default for connections that do not
+    /// send an `x-cortex-tenant` header (or `?tenant=` query parameter).
     #[arg(long, default_value = "default")]
     tenant: String,
 
@@ -30,9 +67,30 @@ struct Cli {
     /// Note: do NOT use the same value as --tenant. The tenant is an infrastructure
     /// isolation key (e.g. "local-XeStation_zed_agent") while user is the identity
     /// under which memories are stored (cortex://user/{user_id}/...).
+    /// With the HTTP transport this is the default for connections that do not
+    /// send an `x-cortex-user` header (or `?user=` query parameter).
     #[arg(long)]
     user: Option<String>,
 
+    /// Agent identifier (defaults to the tenant). Agents sharing a tenant keep
+    /// their own cortex://agent/{agent_id} memories and meet in the shared
+    /// spaces granted by the `[access]` config section.
+    /// With the HTTP transport this is the default for connections that do not
+    /// send an `x-cortex-agent` header (or `?agent=` query parameter).
+    #[arg(long)]
+    agent: Option<String>,
+
+    /// Tenants HTTP connections may name (comma separated). Identity headers
+    /// are not authenticated; when set, other tenants are refused. Defaults
+    /// to any tenant.
+    #[arg(long, value_delimiter = ',')]
+    allowed_tenants: Vec<String>,
+
+    /// Maximum number of identities (tenant/user/agent) the HTTP transport
+    /// keeps a memory runtime for; the least recently used one is dropped
+    #[arg(long, default_value_t = pool::DEFAULT_MAX_IDENTITIES)]
+    max_identities: usize,
+
     /// Message count threshold for auto-trigger (default: 10)
     #[arg(long, default_value = "10")]
     auto_trigger_threshold: usize,
@@ -84,6 +142,9 @@ async fn main() -> Result<()> {
     if let Some(ref uid) = cli.user {
         info!("User ID: {}", uid);
     }
+    if let Some(ref aid) = cli.agent {
+        info!("Agent ID: {}", aid);
+    }
 
     // Load configuration
     let config = Config::load(&cli.config)?;
@@ -95,32 +156,36 @@ async fn main() -> Result<()> {
     // Initialize LLM client
     let model_name = config.llm.model_efficient.clone();
     let llm_config = cortex_mem_core::llm::LLMConfig {
-        api_base_url: config.llm.api_base_url,
-        api_key: config.llm.api_key,
-        model_efficient: config.llm.model_efficient,
+        provider: config.llm.provider,
+        api_base_url: config.llm.api_base_url.clone(),
+        api_key: config.llm.api_key.clone(),
+        model_efficient: config.llm.model_efficient.clone(),
         temperature: config.llm.temperature,
         max_tokens: config.llm.max_tokens as usize,
+        api_version: config.llm.api_version.clone(),
+        models: config.llm.models.clone(),
     };
     let llm_client = Arc::new(LLMClientImpl::new(llm_config)?);
     info!("LLM client initialized with model: {}", model_name);
 
-    // Initialize MemoryOperations with vector search
-    let operations = MemoryOperations::new(
-        &data_dir,
-        &cli.tenant,
-        llm_client,
-        &config.qdrant.url,
-        &config.qdrant.collection_name,
-        config.qdrant.api_key.as_deref(),
-        &config.embedding.api_base_url,
-        &config.embedding.api_key,
-        &config.embedding.model_name,
-        config.qdrant.embedding_dim,
-        cli.user,  // explicit user_id; None → "default" (see MemoryOperations::new)
-        config.cortex.enable_intent_analysis,
-    ).await?;
-    
-    let operations = Arc::new(operations);
+    // Initialize MemoryOperations with vector search for the default identity.
+    // The HTTP transport builds further identities on demand through the pool.
+    let identity = Identity {
+        tenant: cli.tenant.clone(),
+        user: cli.user.clone(), // explicit user_id; None → "default" (see MemoryOperationsBuilder::with_user_id)
+        agent: cli.agent.clone(), // None → tenant ID
+    };
+    let tool_policy = ToolPolicy::new(config.tools.clone());
+    let mut allowed_tenants = cli.allowed_tenants.clone();
+    if !allowed_tenants.is_empty() {
+        allowed_tenants.push(cli.tenant.clone());
+    }
+    let pool = Arc::new(
+        OperationsPool::new(config, llm_client)
+            .with_allowed_tenants(allowed_tenants)
+            .with_max_identities(cli.max_identities),
+    );
+    let operations = pool.get(&identity).await?;
     info!("MemoryOperations initialized successfully");
 
     // Build auto-trigger configuration from CLI args
@@ -139,13 +204,19 @@ async fn main() -> Result<()> {
     );
 
     // Create the MCP service with auto-trigger support
-    let service = MemoryMcpService::with_config(operations, auto_trigger_config);
+    let service = MemoryMcpService::with_config(identity, operations, auto_trigger_config)
+        .with_tool_policy(&tool_policy);
 
     // Start the inactivity checker for auto-triggering
     if auto_trigger_config.enable_auto_trigger {
         service.start_inactivity_checker();
     }
 
+    if cli.transport == Transport::Http {
+        let addr: SocketAddr = format!("{}:{}", cli.host, cli.port).parse()?;
+        return serve_http(service.with_pool(pool), addr).await;
+    }
+
     // Serve the MCP service
     let running_service = service
         .serve(stdio())
@@ -162,3 +233,25 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Serve MCP over streamable HTTP until the process exits
+///
+/// Every MCP session gets its own service instance, bound to the identity
+/// from its `initialize` request headers; session states and the memory
+/// runtimes of each identity are shared.
+async fn serve_http(service: MemoryMcpService, addr: SocketAddr) -> Result<()> {
+    let mcp = StreamableHttpService::new(
+        move || Ok(service.for_connection()),
+        LocalSessionManager::default().into(),
+        StreamableHttpServerConfig::default(),
+    );
+    let app = axum::Router::new().nest_service("/mcp", mcp);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("MCP server listening on http://{}/mcp", addr);
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| anyhow!("MCP HTTP server error: {}", e))?;
+
+    Ok(())
+}
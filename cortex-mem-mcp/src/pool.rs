@@ -0,0 +1,375 @@
+//! Per-identity `MemoryOperations`
+//!
+//! With the stdio transport a process serves exactly one client, so tenant,
+//! user and agent come from `--tenant` / `--user` / `--agent`. The HTTP
+//! transport serves many clients from one process; each connection names its
+//! own identity, and this pool builds (once) and shares the `MemoryOperations`
+//! for every identity, with the `[access]` policy bound to it.
+//!
+//! Identities come from unauthenticated headers, so the pool only admits the
+//! tenants of `--allowed-tenants` (when given) and keeps at most
+//! `--max-identities` runtimes alive, dropping the least recently used one.
+//! A dropped runtime stops its background tasks once the last connection
+//! using it closes; the service also forgets its session states then (see
+//! [`OperationsPool::evictions`]).
+
+use anyhow::{Result, bail};
+use cortex_mem_config::Config;
+use cortex_mem_core::{AccessPolicy, llm::LLMClientImpl};
+use cortex_mem_tools::MemoryOperations;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OnceCell, watch};
+use tracing::info;
+
+/// HTTP header naming the tenant of a connection
+pub const TENANT_HEADER: &str = "x-cortex-tenant";
+/// HTTP header naming the user of a connection
+pub const USER_HEADER: &str = "x-cortex-user";
+/// HTTP header naming the agent of a connection
+pub const AGENT_HEADER: &str = "x-cortex-agent";
+
+/// Tenant, user and agent a client acts as
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Identity {
+    pub tenant: String,
+    /// `None` → "default" (see `MemoryOperationsBuilder::with_user_id`)
+    pub user: Option<String>,
+    /// `None` → the tenant ID (see `MemoryOperations::with_agent_id`)
+    pub agent: Option<String>,
+}
+
+impl Identity {
+    /// Resolve the identity of an HTTP connection
+    ///
+    /// Headers win over query parameters (`?tenant=..&user=..&agent=..`, for clients
+    /// that cannot set headers); anything missing falls back to `default`.
+    pub fn from_http(parts: &http::request::Parts, default: &Identity) -> Self {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let query = |name: &str| {
+            parts.uri.query().and_then(|q| {
+                form_urlencoded::parse(q.as_bytes())
+                    .find(|(k, _)| k == name)
+                    .map(|(_, v)| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            })
+        };
+
+        Self {
+            tenant: header(TENANT_HEADER)
+                .or_else(|| query("tenant"))
+                .unwrap_or_else(|| default.tenant.clone()),
+            user: header(USER_HEADER)
+                .or_else(|| query("user"))
+                .or_else(|| default.user.clone()),
+            agent: header(AGENT_HEADER)
+                .or_else(|| query("agent"))
+                .or_else(|| default.agent.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.tenant,
+            self.user.as_deref().unwrap_or("default")
+        )?;
+        if let Some(agent) = &self.agent {
+            write!(f, " (agent {})", agent)?;
+        }
+        Ok(())
+    }
+}
+
+/// Default for `--max-identities`
+pub const DEFAULT_MAX_IDENTITIES: usize = 32;
+
+/// A pooled runtime (built on first use) and when it was last handed out
+struct Entry {
+    operations: Arc<OnceCell<Arc<MemoryOperations>>>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<Identity, Entry>,
+    /// Monotonic use counter, orders entries for LRU eviction
+    clock: u64,
+}
+
+/// Lazily built `MemoryOperations`, one per identity
+pub struct OperationsPool {
+    config: Config,
+    llm_client: Arc<LLMClientImpl>,
+    access: Arc<AccessPolicy>,
+    /// `None` admits every tenant
+    allowed_tenants: Option<HashSet<String>>,
+    max_identities: usize,
+    operations: Mutex<Entries>,
+    /// Number of evictions so far
+    evictions: watch::Sender<u64>,
+}
+
+impl OperationsPool {
+    pub fn new(config: Config, llm_client: Arc<LLMClientImpl>) -> Self {
+        Self {
+            access: Arc::new(AccessPolicy::new(config.access.clone())),
+            config,
+            llm_client,
+            allowed_tenants: None,
+            max_identities: DEFAULT_MAX_IDENTITIES,
+            operations: Mutex::new(Entries::default()),
+            evictions: watch::Sender::new(0),
+        }
+    }
+
+    /// Only build runtimes for these tenants (empty admits every tenant)
+    pub fn with_allowed_tenants(mut self, tenants: impl IntoIterator<Item = String>) -> Self {
+        let tenants: HashSet<String> = tenants.into_iter().collect();
+        self.allowed_tenants = (!tenants.is_empty()).then_some(tenants);
+        self
+    }
+
+    /// Keep at most `max` runtimes alive (at least one)
+    pub fn with_max_identities(mut self, max: usize) -> Self {
+        self.max_identities = max.max(1);
+        self
+    }
+
+    /// Get the operations for an identity, building them on first use
+    ///
+    /// The pool lock only reserves the identity's slot; the runtime is built
+    /// outside of it, and concurrent connections with the same identity wait
+    /// for that one build instead of starting a second runtime. A failed build
+    /// leaves the slot empty for the next attempt. When the pool is full the
+    /// least recently used identity is dropped; connections still holding its
+    /// operations keep them until they close.
+    pub async fn get(&self, identity: &Identity) -> Result<Arc<MemoryOperations>> {
+        let cell = {
+            let mut entries = self.operations.lock().await;
+            entries.clock += 1;
+            let now = entries.clock;
+            if let Some(entry) = entries.map.get_mut(identity) {
+                entry.last_used = now;
+                entry.operations.clone()
+            } else {
+                if let Some(allowed) = &self.allowed_tenants
+                    && !allowed.contains(&identity.tenant)
+                {
+                    bail!("Tenant '{}' is not allowed on this server", identity.tenant);
+                }
+                self.evict(&mut entries);
+                let cell = Arc::new(OnceCell::new());
+                entries.map.insert(
+                    identity.clone(),
+                    Entry {
+                        operations: cell.clone(),
+                        last_used: now,
+                    },
+                );
+                cell
+            }
+        };
+
+        let ops = cell
+            .get_or_try_init(|| async {
+                let mut builder = MemoryOperations::from_config(
+                    &self.config,
+                    &identity.tenant,
+                    self.llm_client.clone(),
+                )
+                .with_access(self.access.clone());
+                if let Some(user) = &identity.user {
+                    builder = builder.with_user_id(user.clone());
+                }
+                if let Some(agent) = &identity.agent {
+                    builder = builder.with_agent_id(agent.clone());
+                }
+                let ops = Arc::new(builder.build().await?);
+                info!("MemoryOperations initialized for {}", identity);
+                anyhow::Ok(ops)
+            })
+            .await?;
+        Ok(ops.clone())
+    }
+
+    /// Pooled runtimes that finished building
+    pub async fn runtimes(&self) -> HashMap<Identity, Arc<MemoryOperations>> {
+        self.operations
+            .lock()
+            .await
+            .map
+            .iter()
+            .filter_map(|(id, entry)| Some((id.clone(), entry.operations.get()?.clone())))
+            .collect()
+    }
+
+    /// Changes after every eviction, so holders of per-identity state can
+    /// drop what refers to runtimes no longer in [`runtimes`](Self::runtimes)
+    pub fn evictions(&self) -> watch::Receiver<u64> {
+        self.evictions.subscribe()
+    }
+
+    /// Make room for one more identity
+    fn evict(&self, entries: &mut Entries) {
+        let mut evicted = 0;
+        while entries.map.len() >= self.max_identities {
+            let Some(oldest) = entries
+                .map
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            entries.map.remove(&oldest);
+            evicted += 1;
+            info!("Dropped least recently used MemoryOperations of {}", oldest);
+        }
+        if evicted > 0 {
+            self.evictions.send_modify(|count| *count += evicted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_mem_config::{
+        CortexConfig, EmbeddingConfig, LLMConfig, LoggingConfig, QdrantConfig, ServerConfig,
+    };
+
+    /// Pool on a temp dir; Qdrant, the embedding API and the LLM point at a
+    /// closed port, so runtimes build but never reach a backend
+    fn pool(data_dir: &std::path::Path, max_identities: usize) -> OperationsPool {
+        let config = Config {
+            qdrant: QdrantConfig {
+                url: "http://127.0.0.1:1".to_string(),
+                collection_name: "cortex-mem-test".to_string(),
+                embedding_dim: None,
+                timeout_secs: 1,
+                api_key: None,
+            },
+            embedding: EmbeddingConfig {
+                api_base_url: "http://127.0.0.1:1".to_string(),
+                api_key: String::new(),
+                model_name: "test-embedding".to_string(),
+                ..Default::default()
+            },
+            llm: LLMConfig {
+                provider: Default::default(),
+                api_base_url: "http://127.0.0.1:1".to_string(),
+                api_key: "test".to_string(),
+                model_efficient: "test-model".to_string(),
+                temperature: 0.0,
+                max_tokens: 16,
+                api_version: None,
+                models: Default::default(),
+            },
+            server: ServerConfig {
+                host: "127.0.0.1".to_string(),
+                port: 0,
+                cors_origins: Vec::new(),
+            },
+            logging: LoggingConfig::default(),
+            cortex: CortexConfig {
+                data_dir: Some(data_dir.to_string_lossy().into_owned()),
+                enable_intent_analysis: false,
+            },
+            rate_limit: Default::default(),
+            tools: Default::default(),
+            access: Default::default(),
+            llm_cache: Default::default(),
+            embedding_cache: Default::default(),
+            usage: Default::default(),
+            fixtures: Default::default(),
+            layers: Default::default(),
+        };
+        let llm_client = LLMClientImpl::new(cortex_mem_core::llm::LLMConfig {
+            provider: config.llm.provider,
+            api_base_url: config.llm.api_base_url.clone(),
+            api_key: config.llm.api_key.clone(),
+            model_efficient: config.llm.model_efficient.clone(),
+            temperature: config.llm.temperature,
+            max_tokens: config.llm.max_tokens as usize,
+            api_version: None,
+            models: Default::default(),
+        })
+        .unwrap();
+        let llm_client = Arc::new(llm_client);
+        OperationsPool::new(config, llm_client).with_max_identities(max_identities)
+    }
+
+    fn identity(tenant: &str) -> Identity {
+        Identity {
+            tenant: tenant.to_string(),
+            user: Some("alice".to_string()),
+            agent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shares_runtime_per_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = pool(dir.path(), 4);
+
+        let a = identity("a");
+        let (first, second) = tokio::join!(pool.get(&a), pool.get(&a));
+        assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+        let other = pool.get(&identity("b")).await.unwrap();
+        assert!(!Arc::ptr_eq(
+            &other,
+            &pool.get(&identity("a")).await.unwrap()
+        ));
+        assert_eq!(pool.runtimes().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = pool(dir.path(), 2);
+        let mut evictions = pool.evictions();
+
+        let a = Arc::downgrade(&pool.get(&identity("a")).await.unwrap());
+        pool.get(&identity("b")).await.unwrap();
+        pool.get(&identity("a")).await.unwrap();
+        let b = Arc::downgrade(&pool.get(&identity("b")).await.unwrap());
+        assert!(!evictions.has_changed().unwrap());
+
+        // "a" was used after "b" was built, but "b" was used last
+        pool.get(&identity("c")).await.unwrap();
+        assert!(evictions.has_changed().unwrap());
+        assert_eq!(*evictions.borrow_and_update(), 1);
+        assert!(a.upgrade().is_none(), "evicted runtime is still alive");
+        assert!(b.upgrade().is_some());
+
+        let runtimes = pool.runtimes().await;
+        assert!(runtimes.contains_key(&identity("b")));
+        assert!(runtimes.contains_key(&identity("c")));
+        assert!(!runtimes.contains_key(&identity("a")));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unlisted_tenants() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = pool(dir.path(), 4).with_allowed_tenants(["a".to_string()]);
+
+        assert!(pool.get(&identity("a")).await.is_ok());
+        let Err(err) = pool.get(&identity("b")).await else {
+            panic!("tenant b was admitted");
+        };
+        assert!(err.to_string().contains("not allowed"), "{}", err);
+        assert_eq!(pool.runtimes().await.len(), 1);
+    }
+}
@@ -0,0 +1,539 @@
+//! MCP prompts for memory-aware workflows
+//!
+//! Each prompt gathers memories of the connection's user and agent through
+//! `MemoryOperations` and returns them, followed by instructions, as a single
+//! user message the client can send to its model.
+
+use cortex_mem_core::FilesystemOperations;
+use cortex_mem_tools::{GuidanceArgs, MemoryOperations, SearchArgs};
+use rmcp::{ErrorData as McpError, model::*};
+use std::fmt::Write;
+use tracing::debug;
+
+/// Upper bound of memory files pulled into one prompt
+const MAX_FILES: usize = 50;
+const DEFAULT_LIMIT: usize = 5;
+
+pub fn list() -> Vec<Prompt> {
+    let argument = |name: &str, description: &str, required: bool| PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        required: Some(required),
+    };
+
+    vec![
+        Prompt::new(
+            "about_me",
+            Some("Summarize what is known about the user from their stored memories"),
+            None,
+        ),
+        Prompt::new(
+            "prepare_context",
+            Some("Gather relevant user memories, agent cases, skills and instructions, and recent sessions for a task"),
+            Some(vec![
+                argument("task", "The task to prepare context for", true),
+                argument("limit", "Maximum memories per source (default: 5)", false),
+            ]),
+        ),
+        Prompt::new(
+            "review_preferences",
+            Some("Review stored preferences for outdated, conflicting or duplicate entries"),
+            None,
+        ),
+        Prompt::new(
+            "recent_sessions",
+            Some("Recap the most recent conversation sessions"),
+            Some(vec![argument(
+                "limit",
+                "Number of sessions (default: 5)",
+                false,
+            )]),
+        ),
+    ]
+}
+
+pub async fn get(
+    operations: &MemoryOperations,
+    name: &str,
+    arguments: Option<JsonObject>,
+) -> Result<GetPromptResult, McpError> {
+    debug!("get_prompt: {} {:?}", name, arguments);
+    let arguments = arguments.unwrap_or_default();
+
+    let (description, text) = match name {
+        "about_me" => ("What is known about the user", about_me(operations).await?),
+        "prepare_context" => {
+            let task = string_arg(&arguments, "task")
+                .ok_or_else(|| McpError::invalid_params("Missing required argument: task", None))?;
+            let limit = limit_arg(&arguments)?;
+            (
+                "Memory context for the task",
+                prepare_context(operations, &task, limit).await?,
+            )
+        }
+        "review_preferences" => (
+            "Review of stored preferences",
+            review_preferences(operations).await?,
+        ),
+        "recent_sessions" => {
+            let limit = limit_arg(&arguments)?;
+            (
+                "Recap of recent sessions",
+                recent_sessions(operations, limit).await?,
+            )
+        }
+        other => {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", other),
+                None,
+            ));
+        }
+    };
+
+    Ok(GetPromptResult {
+        description: Some(description.to_string()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+// ==================== Prompts ====================
+
+async fn about_me(operations: &MemoryOperations) -> Result<String, McpError> {
+    let user_root = format!("cortex://user/{}", operations.default_user_id());
+    let mut text = String::from("# What you know about me\n\n");
+
+    if let Ok(overview) = operations.get_overview(&user_root).await {
+        let _ = writeln!(text, "## Overview\n\n{}\n", overview.overview_text.trim());
+    }
+
+    let mut found = false;
+    for category in list_dir(operations, &user_root, true).await? {
+        let files = list_dir(operations, &category, false).await?;
+        if files.is_empty() {
+            continue;
+        }
+        found = true;
+        let _ = writeln!(text, "## {}\n", name_of(&category));
+        for uri in files {
+            let summary = abstract_of(operations, &uri).await;
+            let _ = writeln!(text, "- {} ({})", summary, uri);
+        }
+        text.push('\n');
+    }
+
+    if !found {
+        text.push_str("_No memories are stored about me yet._\n\n");
+    }
+    text.push_str(
+        "Summarize what you know about me from the memories above: who I am, my preferences, \
+         the people and things I care about, and my goals. Group related facts, keep it concise, \
+         and point out anything that looks uncertain or contradictory.",
+    );
+    Ok(text)
+}
+
+async fn prepare_context(
+    operations: &MemoryOperations,
+    task: &str,
+    limit: usize,
+) -> Result<String, McpError> {
+    let mut text = format!("# Context for: {}\n\n", task);
+
+    let sources = [
+        (
+            "Relevant memories about me",
+            format!("cortex://user/{}", operations.default_user_id()),
+        ),
+        (
+            "Relevant past cases",
+            format!("cortex://agent/{}/cases", operations.default_agent_id()),
+        ),
+    ];
+    for (title, scope) in sources {
+        let response = operations
+            .search(SearchArgs {
+                query: task.to_string(),
+                recursive: Some(true),
+                return_layers: Some(vec!["L0".to_string(), "L1".to_string()]),
+                scope: Some(scope),
+                limit: Some(limit),
+            })
+            .await
+            .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))?;
+
+        let _ = writeln!(text, "## {}\n", title);
+        if response.results.is_empty() {
+            text.push_str("_Nothing relevant found._\n\n");
+            continue;
+        }
+        for result in response.results {
+            let body = result
+                .overview_text
+                .or(result.abstract_text)
+                .unwrap_or_default();
+            let _ = writeln!(
+                text,
+                "### {} (score {:.2})\n\n{}\n",
+                result.uri,
+                result.score,
+                body.trim()
+            );
+        }
+    }
+
+    let guidance = operations
+        .guidance(GuidanceArgs {
+            task: task.to_string(),
+            limit: Some(limit),
+        })
+        .await
+        .map_err(|e| McpError::internal_error(format!("Guidance failed: {}", e), None))?;
+    for (title, items) in [
+        ("Standing instructions", guidance.instructions),
+        ("Applicable skills", guidance.skills),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        let _ = writeln!(text, "## {}\n", title);
+        for item in items {
+            let _ = writeln!(text, "{}\n", item.content.trim());
+        }
+    }
+
+    text.push_str("## Recent sessions\n\n");
+    text.push_str(&session_list(operations, limit.min(DEFAULT_LIMIT)).await?);
+
+    text.push_str(
+        "\nUse the memories above as background for the task. Follow my preferences and the \
+         standing instructions, reuse the skills and what worked in past cases, and say which \
+         memories you relied on. Ignore anything that is not relevant.",
+    );
+    Ok(text)
+}
+
+async fn review_preferences(operations: &MemoryOperations) -> Result<String, McpError> {
+    let dir = format!("cortex://user/{}/preferences", operations.default_user_id());
+    let mut text = String::from("# My stored preferences\n\n");
+
+    let files = list_dir(operations, &dir, false).await?;
+    if files.is_empty() {
+        text.push_str("_No preferences are stored yet._\n\n");
+    }
+    for uri in files {
+        let content = operations.read_file(&uri).await.unwrap_or_default();
+        let _ = writeln!(text, "## {}\n\n{}\n", uri, content.trim());
+    }
+
+    text.push_str(
+        "Review these preferences with me. List any that look outdated, contradict each other or \
+         duplicate another one, cite their URIs, and propose a corrected version. Ask me to \
+         confirm before changing anything; then correct it with `update_memory` and remove \
+         duplicates with `forget`.",
+    );
+    Ok(text)
+}
+
+async fn recent_sessions(operations: &MemoryOperations, limit: usize) -> Result<String, McpError> {
+    let mut text = String::from("# Recent sessions\n\n");
+    text.push_str(&session_list(operations, limit).await?);
+    text.push_str(
+        "\nRecap these sessions: what we worked on, what was decided and what is still open. \
+         Put the most recent first.",
+    );
+    Ok(text)
+}
+
+// ==================== Helpers ====================
+
+/// Most recently updated sessions with their L0 abstracts, as a Markdown list
+async fn session_list(operations: &MemoryOperations, limit: usize) -> Result<String, McpError> {
+    let mut sessions = operations
+        .list_sessions()
+        .await
+        .map_err(|e| McpError::internal_error(format!("Failed to list sessions: {}", e), None))?;
+    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+    if sessions.is_empty() {
+        return Ok("_No sessions yet._\n".to_string());
+    }
+
+    let mut text = String::new();
+    for session in sessions.into_iter().take(limit) {
+        let uri = format!("cortex://session/{}", session.thread_id);
+        let summary = abstract_of(operations, &uri).await;
+        let _ = writeln!(
+            text,
+            "- **{}** ({}, {} messages, updated {}): {}",
+            session.thread_id,
+            session.status,
+            session.message_count,
+            session.updated_at.format("%Y-%m-%d %H:%M"),
+            summary
+        );
+    }
+    Ok(text)
+}
+
+/// Directories (or Markdown files) directly under `uri`, sorted; missing → empty
+async fn list_dir(
+    operations: &MemoryOperations,
+    uri: &str,
+    directories: bool,
+) -> Result<Vec<String>, McpError> {
+    let mut entries = match operations.filesystem().list(uri).await {
+        Ok(entries) => entries,
+        Err(cortex_mem_core::Error::NotFound { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(McpError::internal_error(e.to_string(), None)),
+    };
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(entries
+        .into_iter()
+        .filter(|e| !e.name.starts_with('.'))
+        .filter(|e| e.is_directory == directories && (directories || e.name.ends_with(".md")))
+        .take(MAX_FILES)
+        .map(|e| e.uri)
+        .collect())
+}
+
+async fn abstract_of(operations: &MemoryOperations, uri: &str) -> String {
+    operations
+        .get_abstract(uri)
+        .await
+        .map(|r| r.abstract_text.trim().replace('\n', " "))
+        .unwrap_or_else(|_| "(no abstract yet)".to_string())
+}
+
+fn name_of(uri: &str) -> &str {
+    uri.rsplit('/').next().unwrap_or(uri)
+}
+
+fn string_arg(arguments: &JsonObject, name: &str) -> Option<String> {
+    arguments
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// `limit` may arrive as a string (prompt arguments are strings) or a number;
+/// it must be positive and is capped at `MAX_FILES`
+fn limit_arg(arguments: &JsonObject) -> Result<usize, McpError> {
+    let invalid = || McpError::invalid_params("limit must be a positive integer", None);
+    let limit = match arguments.get("limit") {
+        None | Some(serde_json::Value::Null) => return Ok(DEFAULT_LIMIT),
+        Some(serde_json::Value::Number(n)) => n.as_u64().ok_or_else(invalid)?,
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => return Ok(DEFAULT_LIMIT),
+        Some(serde_json::Value::String(s)) => s.trim().parse().map_err(|_| invalid())?,
+        Some(_) => return Err(invalid()),
+    };
+    match limit {
+        0 => Err(invalid()),
+        n => Ok(usize::try_from(n).unwrap_or(MAX_FILES).min(MAX_FILES)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_mem_core::llm::MockLLMClient;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// Operations on a temp dir; Qdrant and the embedding API point at a
+    /// closed port, so only filesystem-backed prompts can be built
+    async fn operations(data_dir: &std::path::Path) -> Arc<MemoryOperations> {
+        let qdrant = cortex_mem_config::QdrantConfig {
+            url: "http://127.0.0.1:1".to_string(),
+            collection_name: "cortex-mem-test".to_string(),
+            embedding_dim: None,
+            timeout_secs: 1,
+            api_key: None,
+        };
+        let embedding = cortex_mem_config::EmbeddingConfig {
+            api_base_url: "http://127.0.0.1:1".to_string(),
+            api_key: String::new(),
+            model_name: "test-embedding".to_string(),
+            ..Default::default()
+        };
+        let operations = MemoryOperations::builder(
+            data_dir.to_string_lossy(),
+            "test",
+            Arc::new(MockLLMClient::new()),
+            &qdrant,
+            &embedding,
+        )
+        .with_user_id("alice")
+        .with_intent_analysis(false)
+        .build()
+        .await
+        .unwrap();
+        Arc::new(operations)
+    }
+
+    fn arguments(value: serde_json::Value) -> JsonObject {
+        value.as_object().unwrap().clone()
+    }
+
+    fn text_of(result: &GetPromptResult) -> &str {
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].role, PromptMessageRole::User);
+        match &result.messages[0].content {
+            PromptMessageContent::Text { text } => text,
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_limit_arg() {
+        assert_eq!(limit_arg(&JsonObject::new()).unwrap(), DEFAULT_LIMIT);
+        assert_eq!(
+            limit_arg(&arguments(json!({ "limit": null }))).unwrap(),
+            DEFAULT_LIMIT
+        );
+        assert_eq!(
+            limit_arg(&arguments(json!({ "limit": " " }))).unwrap(),
+            DEFAULT_LIMIT
+        );
+        assert_eq!(limit_arg(&arguments(json!({ "limit": 1 }))).unwrap(), 1);
+        assert_eq!(limit_arg(&arguments(json!({ "limit": " 7 " }))).unwrap(), 7);
+        assert_eq!(
+            limit_arg(&arguments(json!({ "limit": 1000 }))).unwrap(),
+            MAX_FILES
+        );
+        assert_eq!(
+            limit_arg(&arguments(json!({ "limit": u64::MAX.to_string() }))).unwrap(),
+            MAX_FILES
+        );
+
+        for limit in [
+            json!(0),
+            json!("0"),
+            json!(-1),
+            json!(2.5),
+            json!("ten"),
+            json!(true),
+        ] {
+            assert!(
+                limit_arg(&arguments(json!({ "limit": limit }))).is_err(),
+                "{}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn test_list() {
+        let prompts = list();
+        let names: Vec<_> = prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "about_me",
+                "prepare_context",
+                "review_preferences",
+                "recent_sessions"
+            ]
+        );
+
+        let task = prompts[1]
+            .arguments
+            .as_ref()
+            .and_then(|args| args.iter().find(|a| a.name == "task"))
+            .unwrap();
+        assert_eq!(task.required, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_get_rejects_bad_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = operations(dir.path()).await;
+
+        for args in [None, Some(arguments(json!({ "task": "  " })))] {
+            let err = get(&operations, "prepare_context", args).await.unwrap_err();
+            assert!(err.message.contains("task"), "{}", err.message);
+        }
+
+        let err = get(
+            &operations,
+            "recent_sessions",
+            Some(arguments(json!({ "limit": 0 }))),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.message.contains("limit"));
+
+        let err = get(&operations, "summarize_everything", None)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("Unknown prompt: summarize_everything"));
+    }
+
+    #[tokio::test]
+    async fn test_about_me_and_preferences() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = operations(dir.path()).await;
+
+        let result = get(&operations, "about_me", None).await.unwrap();
+        assert!(text_of(&result).contains("_No memories are stored about me yet._"));
+        let result = get(&operations, "review_preferences", None).await.unwrap();
+        assert!(text_of(&result).contains("_No preferences are stored yet._"));
+
+        let uri = "cortex://user/alice/preferences/editor.md";
+        operations
+            .filesystem()
+            .write(uri, "Prefers Helix over Vim\n")
+            .await
+            .unwrap();
+
+        let result = get(&operations, "about_me", None).await.unwrap();
+        assert_eq!(
+            result.description.as_deref(),
+            Some("What is known about the user")
+        );
+        let text = text_of(&result);
+        assert!(text.starts_with("# What you know about me"));
+        assert!(text.contains(&format!("## preferences\n\n- (no abstract yet) ({})", uri)));
+        assert!(text.ends_with("anything that looks uncertain or contradictory."));
+
+        let result = get(&operations, "review_preferences", None).await.unwrap();
+        let text = text_of(&result);
+        assert!(text.contains(&format!("## {}\n\nPrefers Helix over Vim\n", uri)));
+        assert!(text.contains("`update_memory`"));
+    }
+
+    #[tokio::test]
+    async fn test_recent_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = operations(dir.path()).await;
+
+        let result = get(&operations, "recent_sessions", None).await.unwrap();
+        assert!(text_of(&result).contains("_No sessions yet._"));
+
+        operations
+            .add_message("older", "user", "hello")
+            .await
+            .unwrap();
+        operations.add_message("newer", "user", "hi").await.unwrap();
+        operations
+            .add_message("newer", "assistant", "hey")
+            .await
+            .unwrap();
+
+        let result = get(
+            &operations,
+            "recent_sessions",
+            Some(arguments(json!({ "limit": "1" }))),
+        )
+        .await
+        .unwrap();
+        let text = text_of(&result);
+        assert!(text.starts_with("# Recent sessions\n\n- **newer** ("));
+        assert!(text.contains("2 messages"));
+        assert!(!text.contains("**older**"));
+        assert!(text.ends_with("Put the most recent first."));
+    }
+}
@@ -0,0 +1,402 @@
+//! MCP resources over the `cortex://` filesystem
+//!
+//! Every memory file is a resource under its own `cortex://` URI, which reads
+//! the L2 full content. Layers are addressed with a `layer` query parameter:
+//! `cortex://user/alice/preferences?layer=L1` is the L1 overview of that
+//! directory and `?layer=L0` its L0 abstract. Directories are listed through
+//! their L1 overview.
+//!
+//! Subscriptions cover a URI and everything beneath it, so subscribing to a
+//! session directory reports changes to any of its files and layers.
+
+use cortex_mem_core::{CortexFilesystem, FileEntry, FilesystemOperations};
+use cortex_mem_core::memory_events::MemoryEvent;
+use cortex_mem_tools::{MemoryOperations, ToolsError};
+use rmcp::{ErrorData as McpError, model::*};
+
+/// Resources returned per `resources/list` page
+pub const PAGE_SIZE: usize = 100;
+
+const MIME_TYPE: &str = "text/markdown";
+const DIMENSIONS: [&str; 5] = ["user", "agent", "session", "resources", "spaces"];
+
+/// Context layer addressed by a resource URI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Abstract (~100 tokens)
+    L0,
+    /// Overview (~2000 tokens)
+    L1,
+    /// Full content
+    L2,
+}
+
+/// A parsed resource URI: `cortex://{path}[?layer=L0|L1|L2]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceUri {
+    /// The `cortex://` URI without query
+    pub base: String,
+    pub layer: Layer,
+}
+
+impl ResourceUri {
+    pub fn parse(uri: &str) -> Result<Self, McpError> {
+        if !uri.starts_with("cortex://") {
+            return Err(McpError::invalid_params(
+                format!("Not a cortex:// resource: {}", uri),
+                None,
+            ));
+        }
+
+        let (base, query) = match uri.split_once('?') {
+            Some((base, query)) => (base, Some(query)),
+            None => (uri, None),
+        };
+        let layer = query
+            .and_then(|q| q.split('&').find_map(|pair| pair.strip_prefix("layer=")))
+            .unwrap_or("L2");
+        let layer = match layer {
+            "L0" => Layer::L0,
+            "L1" => Layer::L1,
+            "L2" => Layer::L2,
+            other => {
+                return Err(McpError::invalid_params(
+                    format!("Unknown layer '{}', expected L0, L1 or L2", other),
+                    None,
+                ));
+            }
+        };
+
+        Ok(Self {
+            base: base.trim_end_matches('/').to_string(),
+            layer,
+        })
+    }
+
+    /// Whether a change to `changed` (a file or directory URI) affects this resource
+    pub fn affected_by(&self, changed: &str) -> bool {
+        let changed = changed.trim_end_matches('/');
+        changed == self.base
+            || changed
+                .strip_prefix(self.base.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// Events that change resource contents
+pub fn changed_uri(event: &MemoryEvent) -> Option<&str> {
+    match event {
+        MemoryEvent::MemoryCreated { .. }
+        | MemoryEvent::MemoryUpdated { .. }
+        | MemoryEvent::MemoryDeleted { .. }
+        | MemoryEvent::LayersUpdated { .. } => event.uri(),
+        _ => None,
+    }
+}
+
+/// Events that add or remove resources
+pub fn changes_list(event: &MemoryEvent) -> bool {
+    matches!(
+        event,
+        MemoryEvent::MemoryCreated { .. } | MemoryEvent::MemoryDeleted { .. }
+    )
+}
+
+pub fn templates() -> Vec<ResourceTemplate> {
+    let template = |uri_template: &str, name: &str, description: &str| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(MIME_TYPE.to_string()),
+            icons: None,
+        }
+        .no_annotation()
+    };
+
+    vec![
+        template(
+            "cortex://{+path}",
+            "content",
+            "L2 full content of a memory file",
+        ),
+        template(
+            "cortex://{+path}?layer=L1",
+            "overview",
+            "L1 overview (~2000 tokens) of a memory file or directory",
+        ),
+        template(
+            "cortex://{+path}?layer=L0",
+            "abstract",
+            "L0 abstract (~100 tokens) of a memory file or directory",
+        ),
+    ]
+}
+
+/// One page of the memory tree's resources: files (L2) and directories (L1)
+///
+/// The tree is walked depth first with entries sorted by name, so the order
+/// is stable and the cursor is simply the URI of the previous page's last
+/// resource. A page lists the directories along the cursor path and walks on
+/// from there, never the part of the tree before it. Returns the resources
+/// and the cursor of the next page (`None` on the last page).
+pub async fn list(
+    filesystem: &CortexFilesystem,
+    cursor: Option<&str>,
+) -> Result<(Vec<Resource>, Option<String>), McpError> {
+    let after: Option<Vec<&str>> = match cursor {
+        Some(cursor) => {
+            let path = cursor
+                .strip_prefix("cortex://")
+                .filter(|path| DIMENSIONS.iter().any(|d| path.split('/').next() == Some(d)))
+                .ok_or_else(|| {
+                    McpError::invalid_params(format!("Invalid cursor: {}", cursor), None)
+                })?;
+            Some(path.split('/').filter(|c| !c.is_empty()).collect())
+        }
+        None => None,
+    };
+
+    let mut resources = Vec::new();
+    let mut last_uri = None;
+    let first_dimension = after
+        .as_ref()
+        .and_then(|after| DIMENSIONS.iter().position(|d| *d == after[0]))
+        .unwrap_or(0);
+
+    for (index, dimension) in DIMENSIONS.iter().enumerate().skip(first_dimension) {
+        // Only the cursor's own dimension resumes mid-way
+        let after = after.as_deref().filter(|_| index == first_dimension);
+
+        // Stack of sorted directory listings and the next entry of each;
+        // rebuilt along the cursor path, so the walk continues right after it
+        let mut stack = vec![(list_sorted(filesystem, &format!("cortex://{}", dimension)).await?, 0)];
+        for component in after.map(|after| &after[1..]).unwrap_or_default() {
+            let (entries, next) = stack.last_mut().expect("stack is not empty");
+            *next = entries.partition_point(|entry| entry.name.as_str() <= *component);
+            match entries[..*next].last() {
+                Some(entry) if entry.name == *component && entry.is_directory => {
+                    let entries = list_sorted(filesystem, &entry.uri).await?;
+                    stack.push((entries, 0));
+                }
+                _ => break,
+            }
+        }
+
+        while let Some((entries, next)) = stack.last_mut() {
+            let Some(entry) = entries.get(*next).cloned() else {
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            // Layer files are served through `?layer=`
+            if entry.name.starts_with('.') {
+                continue;
+            }
+            if resources.len() == PAGE_SIZE {
+                return Ok((resources, last_uri));
+            }
+
+            let resource = if entry.is_directory {
+                stack.push((list_sorted(filesystem, &entry.uri).await?, 0));
+                RawResource {
+                    description: Some(format!("L1 overview of {}", entry.uri)),
+                    mime_type: Some(MIME_TYPE.to_string()),
+                    ..RawResource::new(
+                        format!("{}?layer=L1", entry.uri),
+                        format!("{}/", entry.name),
+                    )
+                }
+            } else {
+                RawResource {
+                    mime_type: Some(MIME_TYPE.to_string()),
+                    size: u32::try_from(entry.size).ok(),
+                    ..RawResource::new(entry.uri.clone(), entry.name)
+                }
+            };
+            last_uri = Some(entry.uri);
+            resources.push(resource.no_annotation());
+        }
+    }
+
+    Ok((resources, None))
+}
+
+/// Entries of a directory sorted by name (missing directories are empty)
+async fn list_sorted(
+    filesystem: &CortexFilesystem,
+    uri: &str,
+) -> Result<Vec<FileEntry>, McpError> {
+    let mut entries = match filesystem.list(uri).await {
+        Ok(entries) => entries,
+        Err(cortex_mem_core::Error::NotFound { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(McpError::internal_error(e.to_string(), None)),
+    };
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Read one resource in the requested layer
+pub async fn read(operations: &MemoryOperations, uri: &str) -> Result<ResourceContents, McpError> {
+    let resource = ResourceUri::parse(uri)?;
+
+    let text = match resource.layer {
+        Layer::L0 => operations
+            .get_abstract(&resource.base)
+            .await
+            .map(|r| r.abstract_text),
+        Layer::L1 => operations
+            .get_overview(&resource.base)
+            .await
+            .map(|r| r.overview_text),
+        Layer::L2 => operations.read_file(&resource.base).await,
+    }
+    .map_err(|e| match e {
+        ToolsError::NotFound(_) | ToolsError::Core(cortex_mem_core::Error::NotFound { .. }) => {
+            McpError::resource_not_found(format!("Resource not found: {}", uri), None)
+        }
+        ToolsError::Core(e @ cortex_mem_core::Error::PermissionDenied(_)) => {
+            McpError::invalid_request(e.to_string(), None)
+        }
+        e => McpError::internal_error(format!("Failed to read {}: {}", uri, e), None),
+    })?;
+
+    Ok(ResourceContents::TextResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(MIME_TYPE.to_string()),
+        text,
+        meta: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 150 notes under user/alice plus one agent file: 154 resources, two pages
+    async fn filesystem() -> (tempfile::TempDir, CortexFilesystem) {
+        let dir = tempfile::tempdir().unwrap();
+        let filesystem = CortexFilesystem::new(dir.path());
+        for i in 0..150 {
+            let uri = format!("cortex://user/alice/notes/note-{:03}.md", i);
+            filesystem.write(&uri, "note").await.unwrap();
+        }
+        filesystem
+            .write("cortex://user/alice/notes/.abstract.md", "abstract")
+            .await
+            .unwrap();
+        filesystem
+            .write("cortex://agent/bot/cases.md", "case")
+            .await
+            .unwrap();
+        (dir, filesystem)
+    }
+
+    fn uris(resources: &[Resource]) -> Vec<&str> {
+        resources.iter().map(|r| r.uri.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_across_dimensions() {
+        let (_dir, filesystem) = filesystem().await;
+
+        let (first, cursor) = list(&filesystem, None).await.unwrap();
+        assert_eq!(first.len(), PAGE_SIZE);
+        assert_eq!(
+            &uris(&first)[..3],
+            &[
+                "cortex://user/alice?layer=L1",
+                "cortex://user/alice/notes?layer=L1",
+                "cortex://user/alice/notes/note-000.md",
+            ]
+        );
+        let cursor = cursor.expect("second page");
+        assert_eq!(cursor, "cortex://user/alice/notes/note-097.md");
+
+        let (second, cursor) = list(&filesystem, Some(&cursor)).await.unwrap();
+        assert!(cursor.is_none());
+        assert_eq!(second.len(), 54);
+        assert_eq!(uris(&second)[0], "cortex://user/alice/notes/note-098.md");
+        assert_eq!(
+            &uris(&second)[52..],
+            &["cortex://agent/bot?layer=L1", "cortex://agent/bot/cases.md"]
+        );
+        assert!(
+            first
+                .iter()
+                .chain(&second)
+                .all(|r| !r.uri.contains(".abstract.md"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_stale_cursor_resumes_after_it() {
+        let (_dir, filesystem) = filesystem().await;
+
+        // A file deleted since the previous page
+        let (page, _) = list(&filesystem, Some("cortex://user/alice/notes/note-050x.md"))
+            .await
+            .unwrap();
+        assert_eq!(uris(&page)[0], "cortex://user/alice/notes/note-051.md");
+
+        // A directory deleted since the previous page
+        let (page, _) = list(&filesystem, Some("cortex://user/alice/archive/old.md"))
+            .await
+            .unwrap();
+        assert_eq!(uris(&page)[0], "cortex://user/alice/notes?layer=L1");
+
+        // The last resource of a dimension moves on to the next one
+        let (page, cursor) = list(&filesystem, Some("cortex://user/alice/notes/note-149.md"))
+            .await
+            .unwrap();
+        assert_eq!(
+            uris(&page),
+            &["cortex://agent/bot?layer=L1", "cortex://agent/bot/cases.md"]
+        );
+        assert!(cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_invalid_cursor() {
+        let (_dir, filesystem) = filesystem().await;
+        for cursor in [
+            "file:///etc/passwd",
+            "cortex://unknown/x.md",
+            "cortex://",
+            "garbage",
+        ] {
+            assert!(list(&filesystem, Some(cursor)).await.is_err(), "{}", cursor);
+        }
+    }
+
+    #[test]
+    fn test_parse_layers() {
+        let uri = ResourceUri::parse("cortex://user/alice/preferences/").unwrap();
+        assert_eq!(uri.base, "cortex://user/alice/preferences");
+        assert_eq!(uri.layer, Layer::L2);
+
+        let uri = ResourceUri::parse("cortex://user/alice?layer=L0").unwrap();
+        assert_eq!(uri.base, "cortex://user/alice");
+        assert_eq!(uri.layer, Layer::L0);
+
+        let uri = ResourceUri::parse("cortex://user/alice?x=1&layer=L1").unwrap();
+        assert_eq!(uri.layer, Layer::L1);
+
+        assert!(ResourceUri::parse("cortex://user/alice?layer=L3").is_err());
+        assert!(ResourceUri::parse("https://example.com/alice").is_err());
+    }
+
+    #[test]
+    fn test_affected_by() {
+        let uri = ResourceUri::parse("cortex://session/abc?layer=L1").unwrap();
+        assert!(uri.affected_by("cortex://session/abc"));
+        assert!(uri.affected_by("cortex://session/abc/"));
+        assert!(uri.affected_by("cortex://session/abc/timeline/2026-01/01/x.md"));
+        assert!(uri.affected_by("cortex://session/abc/.overview.md"));
+        assert!(!uri.affected_by("cortex://session/abcdef/x.md"));
+        assert!(!uri.affected_by("cortex://session"));
+        assert!(!uri.affected_by("cortex://user/abc/x.md"));
+    }
+}
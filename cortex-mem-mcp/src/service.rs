@@ -1,17 +1,26 @@
-use cortex_mem_tools::MemoryOperations;
-use cortex_mem_tools::types::{ExploreArgs, LsArgs, SearchArgs};
+use cortex_mem_tools::{MemoryOperations, ToolPolicy};
+use cortex_mem_tools::types::{
+    BatchMessage, ExploreArgs, GuidanceArgs, GuidanceItem, LsArgs, MemoryEditResponse,
+    RememberArgs, SearchArgs, StoreBatchArgs, UpdateMemoryArgs,
+};
 use rmcp::{
     handler::server::tool::ToolRouter, handler::server::wrapper::Parameters, model::*,
-    tool, tool_handler, tool_router, Json, ServerHandler,
+    service::RequestContext, tool, tool_handler, tool_router, ErrorData as McpError, Json, Peer,
+    RoleServer, ServerHandler,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use crate::pool::{Identity, OperationsPool};
+use crate::prompts;
+use crate::resources::{self, ResourceUri};
+
 // ==================== Auto-Trigger Configuration ====================
 
 /// Configuration for automatic processing triggers
@@ -43,7 +52,6 @@ impl Default for AutoTriggerConfig {
 }
 
 /// Session state for auto-trigger tracking
-#[derive(Debug)]
 struct SessionState {
     /// Number of messages since last processing
     message_count: usize,
@@ -51,18 +59,43 @@ struct SessionState {
     last_processed: Option<Instant>,
     /// Time of last message
     last_message: Instant,
+    /// Operations of the identity that owns the session; the inactivity
+    /// checker is shared by all connections and sends events through these
+    operations: Arc<MemoryOperations>,
 }
 
-impl Default for SessionState {
-    fn default() -> Self {
+impl SessionState {
+    fn new(operations: Arc<MemoryOperations>) -> Self {
         Self {
             message_count: 0,
             last_processed: None,
             last_message: Instant::now(),
+            operations,
         }
     }
 }
 
+/// Identity and operations a connection is currently bound to
+struct Binding {
+    identity: Identity,
+    operations: Arc<MemoryOperations>,
+}
+
+/// Send a `SessionClosed` event so the coordinator extracts, layers and indexes the session
+fn send_session_closed(operations: &MemoryOperations, thread_id: &str) -> bool {
+    use cortex_mem_core::memory_events::MemoryEvent;
+
+    let Some(tx) = operations.memory_event_tx() else {
+        return false;
+    };
+    let _ = tx.send(MemoryEvent::SessionClosed {
+        session_id: thread_id.to_string(),
+        user_id: operations.default_user_id().to_string(),
+        agent_id: operations.default_agent_id().to_string(),
+    });
+    true
+}
+
 // ==================== Tool Arguments & Results ====================
 
 // Store Tool
@@ -74,6 +107,9 @@ pub struct StoreArgs {
     pub thread_id: Option<String>,
     /// Message role: "user", "assistant", or "system"
     pub role: Option<String>,
+    /// Shared space to store the content in (e.g. "project-apollo") instead of
+    /// the session; requires write access to the space
+    pub space: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -83,6 +119,41 @@ pub struct StoreResult {
     pub message_id: String,
 }
 
+// Store Batch Tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StoreBatchMessage {
+    /// Target thread/session ID (created if missing)
+    pub thread_id: String,
+    /// Message role: "user", "assistant", or "system"
+    pub role: String,
+    /// Message content
+    pub content: String,
+    /// Original message time in RFC 3339 (optional, defaults to import time)
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StoreBatchArgsMcp {
+    /// Messages to import
+    pub messages: Vec<StoreBatchMessage>,
+    /// Commit each touched session afterwards to trigger memory extraction (default: false)
+    pub extract: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StoreBatchThread {
+    pub thread_id: String,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StoreBatchResult {
+    pub success: bool,
+    pub total_messages: usize,
+    pub threads: Vec<StoreBatchThread>,
+    pub extraction_triggered: Vec<String>,
+}
+
 // Search Tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SearchArgsMcp {
@@ -127,6 +198,41 @@ pub struct RecallArgs {
     pub limit: Option<usize>,
 }
 
+// Guidance Tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GuidanceArgsMcp {
+    /// The task you are about to work on
+    pub task: String,
+    /// Maximum number of skills (default: 5)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GuidanceItemMcp {
+    pub uri: String,
+    pub content: String,
+    /// Relevance to the task (skills only)
+    pub score: Option<f32>,
+}
+
+impl From<GuidanceItem> for GuidanceItemMcp {
+    fn from(item: GuidanceItem) -> Self {
+        Self {
+            uri: item.uri,
+            content: item.content,
+            score: item.score,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GuidanceResult {
+    pub success: bool,
+    pub task: String,
+    pub instructions: Vec<GuidanceItemMcp>,
+    pub skills: Vec<GuidanceItemMcp>,
+}
+
 // Ls Tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LsArgsMcp {
@@ -136,6 +242,16 @@ pub struct LsArgsMcp {
     pub recursive: Option<bool>,
     /// Include abstracts in results
     pub include_abstracts: Option<bool>,
+    /// Filter entry names by glob (e.g., "*.md") or substring
+    pub pattern: Option<String>,
+    /// Sort by "name" (default), "modified" or "size"
+    pub sort: Option<String>,
+    /// Reverse the sort order (e.g., newest first with sort="modified")
+    pub descending: Option<bool>,
+    /// Maximum entries per page (default 100, 0 for all)
+    pub limit: Option<usize>,
+    /// Cursor from the previous page's next_cursor
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -145,6 +261,10 @@ pub struct LsEntryMcp {
     pub is_directory: bool,
     pub size: Option<usize>,
     pub abstract_text: Option<String>,
+    /// File size in bytes
+    pub bytes: u64,
+    /// Last modification time (RFC 3339)
+    pub modified: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -152,7 +272,12 @@ pub struct LsResult {
     pub success: bool,
     pub uri: String,
     pub entries: Vec<LsEntryMcp>,
-    pub total: usize,
+    /// Entries across all pages; absent when a recursive listing stopped at this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 // Explore Tool
@@ -164,6 +289,16 @@ pub struct ExploreArgsMcp {
     pub start_uri: Option<String>,
     /// Which layers to return in matches
     pub return_layers: Option<Vec<String>>,
+    /// Filter matched file names by glob (e.g., "*.md") or substring
+    pub pattern: Option<String>,
+    /// Sort matches by "name" (default), "modified" or "size"
+    pub sort: Option<String>,
+    /// Reverse the sort order
+    pub descending: Option<bool>,
+    /// Maximum matches per page
+    pub limit: Option<usize>,
+    /// Cursor from the previous page's next_cursor
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -181,6 +316,9 @@ pub struct ExploreResult {
     pub matches: Vec<SearchResultMcp>,
     pub total_explored: usize,
     pub total_matches: usize,
+    /// Pass as `cursor` to fetch the next page of matches; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 // Tiered Access Tools
@@ -242,6 +380,62 @@ pub struct DeleteResult {
     pub uri: String,
 }
 
+// Remember Tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RememberArgsMcp {
+    /// Memory type: "preference", "entity", "event", "case", "personal_info",
+    /// "work_history", "relationship", "goal", "skill" or "instruction"
+    pub memory_type: String,
+    /// Identifies the memory within its type (topic, name, title, ...); an existing key is updated
+    pub key: String,
+    /// The fact to remember, in Markdown
+    pub content: String,
+    /// Confidence 0-1 (default: 0.9)
+    pub confidence: Option<f32>,
+}
+
+// Update Memory Tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateMemoryArgsMcp {
+    /// URI of the memory file, e.g. cortex://user/{id}/preferences/pref_xxx.md
+    pub uri: String,
+    /// New content, in Markdown
+    pub content: String,
+    /// New confidence 0-1 (default: keep the stored one)
+    pub confidence: Option<f32>,
+}
+
+// Forget Tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ForgetArgs {
+    /// URI of the memory file to forget
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryEditResult {
+    pub success: bool,
+    pub uri: String,
+    pub memory_id: String,
+    pub memory_type: String,
+    pub key: String,
+    /// "created", "updated", "unchanged" or "deleted"
+    pub action: String,
+}
+
+impl From<MemoryEditResponse> for MemoryEditResult {
+    fn from(response: MemoryEditResponse) -> Self {
+        Self {
+            success: true,
+            uri: response.uri,
+            memory_id: response.memory_id,
+            memory_type: response.memory_type,
+            key: response.key,
+            action: response.action,
+        }
+    }
+}
+
 // Commit Tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CommitArgs {
@@ -307,40 +501,272 @@ pub struct IndexResult {
 /// - Memory extraction (session → user/agent memories)
 /// - L0/L1 layer generation
 /// - Vector indexing
+///
+/// ## Connections
+///
+/// With stdio there is a single connection bound to `--tenant` / `--user`.
+/// Over HTTP every connection gets its own copy via [`Self::for_connection`]
+/// and is rebound during `initialize` to the identity named by the client
+/// (see [`Identity::from_http`]); session states and the operations pool are
+/// shared across connections.
+///
+/// ## Resources
+///
+/// The `cortex://` tree is exposed as MCP resources (see [`resources`]).
+/// Once a connection subscribes, a watcher forwards the coordinator's memory
+/// events as `notifications/resources/updated` for matching subscriptions.
 #[derive(Clone)]
 pub struct MemoryMcpService {
-    operations: Arc<MemoryOperations>,
+    /// Per-connection binding (replaced, not shared, by `for_connection`)
+    binding: Arc<std::sync::RwLock<Binding>>,
+    /// Resource URIs this connection subscribed to (per connection)
+    subscriptions: Arc<std::sync::Mutex<HashSet<String>>>,
+    /// Whether the resource watcher of this connection is running (per connection)
+    watching: Arc<AtomicBool>,
+    /// Operations per identity; `None` for stdio (fixed identity)
+    pool: Option<Arc<OperationsPool>>,
     tool_router: ToolRouter<Self>,
     /// Auto-trigger configuration
     auto_trigger_config: AutoTriggerConfig,
     /// Session states for tracking auto-trigger conditions
-    /// Key: thread_id, Value: session state
-    session_states: Arc<RwLock<std::collections::HashMap<String, SessionState>>>,
+    /// Key: (identity, thread_id), Value: session state
+    session_states: Arc<RwLock<HashMap<(Identity, String), SessionState>>>,
     /// Last global processing time (to prevent too frequent processing)
     last_global_process: Arc<AtomicU64>,
 }
 
+/// One-line tool summaries for the server instructions, in display order
+const TOOL_SUMMARIES: &[(&str, &str)] = &[
+    ("search", "Layered semantic search with return_layers support"),
+    ("recall", "Quick recall with L0+L2 content"),
+    ("guidance", "Instructions and learned skills that apply to a task"),
+    ("store", "Add a message to memory"),
+    ("store_batch", "Import many messages across sessions with original timestamps"),
+    ("commit", "Commit session and trigger processing"),
+    ("ls", "Browse memory filesystem"),
+    ("explore", "Smart exploration of memory space"),
+    ("abstract", "Get L0 abstract (~100 tokens)"),
+    ("overview", "Get L1 overview (~2000 tokens)"),
+    ("content", "Get L2 full content"),
+    ("remember", "Remember a structured fact (preference, entity, case, skill, ...)"),
+    ("update_memory", "Correct an existing memory"),
+    ("forget", "Forget a memory"),
+    ("delete", "Delete a memory"),
+    ("layers", "Generate L0/L1 layer files"),
+    ("index", "Index memories to vector database"),
+];
+
 #[tool_router]
 impl MemoryMcpService {
     /// Create a new MCP service with auto-trigger configuration
-    pub fn with_config(operations: Arc<MemoryOperations>, config: AutoTriggerConfig) -> Self {
+    pub fn with_config(
+        identity: Identity,
+        operations: Arc<MemoryOperations>,
+        config: AutoTriggerConfig,
+    ) -> Self {
         Self {
-            operations,
-            tool_router: Self::tool_router(),
+            binding: Arc::new(std::sync::RwLock::new(Binding {
+                identity,
+                operations,
+            })),
+            subscriptions: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            watching: Arc::new(AtomicBool::new(false)),
+            pool: None,
+            tool_router: Self::configured_router(&ToolPolicy::default()),
             auto_trigger_config: config,
-            session_states: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            session_states: Arc::new(RwLock::new(HashMap::new())),
             last_global_process: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Resolve per-connection identities through `pool` (HTTP transport)
+    ///
+    /// Session states that refer to a runtime the pool evicted are dropped
+    /// with it, so they do not keep that runtime alive. Must be called within a
+    /// tokio runtime.
+    pub fn with_pool(mut self, pool: Arc<OperationsPool>) -> Self {
+        let mut evictions = pool.evictions();
+        let session_states = self.session_states.clone();
+        let pooled = pool.clone();
+        tokio::spawn(async move {
+            while evictions.changed().await.is_ok() {
+                let live = pooled.runtimes().await;
+                session_states.write().await.retain(|(identity, _), state| {
+                    live.get(identity)
+                        .is_some_and(|ops| Arc::ptr_eq(ops, &state.operations))
+                });
+            }
+        });
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Restrict and re-describe the tool surface according to the `[tools]` config
+    pub fn with_tool_policy(mut self, policy: &ToolPolicy) -> Self {
+        self.tool_router = Self::configured_router(policy);
+        self
+    }
+
+    /// Server instructions listing only the tools this server exposes
+    fn instructions(router: &ToolRouter<Self>) -> String {
+        let tools: String = TOOL_SUMMARIES
+            .iter()
+            .filter(|(name, _)| router.has_route(name))
+            .map(|(name, summary)| format!("- {}: {}\n", name, summary))
+            .collect();
+        [
+            "Cortex Memory MCP Server - Unified memory management tools.\n\n**Tool Naming Convention:** Simple verb style (search, store, ls, etc.)\n\n**Layer System:**\n- L0: Abstract (~100 tokens) - for quick relevance checking\n- L1: Overview (~2000 tokens) - for understanding core information\n- L2: Full content - complete original content\n\n**Automatic Processing:**\nThe server automatically triggers memory extraction and layer generation when:\n- Message count reaches threshold (default: 10 messages)\n- Session becomes inactive (default: 2 minutes without new messages)\n\n**Available tools:**\n",
+            &tools,
+            "\n**Resources:**\nEvery memory file is a resource under its cortex:// URI (L2 full content); append ?layer=L0 or ?layer=L1 for the abstract or overview (directories too). Subscribe to a URI to be notified when it or anything beneath it changes.\n\n**Prompts:**\n- about_me: Summarize what is known about the user\n- prepare_context: Gather relevant memories, cases and recent sessions for a task\n- review_preferences: Review stored preferences for outdated or conflicting entries\n- recent_sessions: Recap the most recent sessions\n",
+        ]
+        .concat()
+    }
+
+    /// Tool router with disabled tools removed, description overrides applied
+    /// and the read-only hint set on every tool
+    fn configured_router(policy: &ToolPolicy) -> ToolRouter<Self> {
+        let mut router = Self::tool_router();
+        router.map.retain(|name, _| policy.is_enabled(name));
+        for (name, route) in router.map.iter_mut() {
+            if let Some(description) = policy.description(name) {
+                route.attr.description = Some(description.to_string().into());
+            }
+            route
+                .attr
+                .annotations
+                .get_or_insert_with(ToolAnnotations::default)
+                .read_only_hint = Some(policy.is_read_only(name));
+        }
+        router
+    }
+
+    /// Service for a new connection, bound to the default identity until
+    /// the client's `initialize` request says otherwise
+    pub fn for_connection(&self) -> Self {
+        let binding = self.binding.read().expect("binding lock poisoned");
+        Self {
+            binding: Arc::new(std::sync::RwLock::new(Binding {
+                identity: binding.identity.clone(),
+                operations: binding.operations.clone(),
+            })),
+            subscriptions: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            watching: Arc::new(AtomicBool::new(false)),
+            ..self.clone()
+        }
+    }
+
+    /// Operations of the identity this connection is bound to
+    fn operations(&self) -> Arc<MemoryOperations> {
+        self.binding
+            .read()
+            .expect("binding lock poisoned")
+            .operations
+            .clone()
+    }
+
+    fn identity(&self) -> Identity {
+        self.binding
+            .read()
+            .expect("binding lock poisoned")
+            .identity
+            .clone()
+    }
+
+    /// Bind this connection to the identity named in its HTTP request
+    async fn bind_connection(&self, parts: &http::request::Parts) -> Result<(), McpError> {
+        let Some(pool) = &self.pool else {
+            return Ok(());
+        };
+
+        let identity = Identity::from_http(parts, &self.identity());
+        let operations = pool.get(&identity).await.map_err(|e| {
+            error!("Failed to initialize memory for {}: {}", identity, e);
+            McpError::internal_error(
+                format!("Failed to initialize memory for {}: {}", identity, e),
+                None,
+            )
+        })?;
+
+        info!("Connection bound to {}", identity);
+        *self.binding.write().expect("binding lock poisoned") = Binding {
+            identity,
+            operations,
+        };
+        Ok(())
+    }
+
+    /// Forward memory events to this connection as resource notifications
+    ///
+    /// Started once per connection; stops when the client disconnects or the
+    /// event coordinator shuts down.
+    fn start_resource_watcher(&self, peer: Peer<RoleServer>) {
+        if self.watching.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Some(mut events) = self.operations().subscribe_memory_events() else {
+            warn!("Memory events not available, resource notifications disabled");
+            return;
+        };
+        let subscriptions = self.subscriptions.clone();
+
+        tokio::spawn(async move {
+            use tokio::sync::broadcast::error::RecvError;
+
+            let idle = std::time::Duration::from_secs(30);
+            loop {
+                let event = match tokio::time::timeout(idle, events.recv()).await {
+                    Ok(Ok(event)) => event,
+                    Ok(Err(RecvError::Lagged(skipped))) => {
+                        warn!("Resource watcher skipped {} memory events", skipped);
+                        continue;
+                    }
+                    Ok(Err(RecvError::Closed)) => break,
+                    Err(_) if peer.is_transport_closed() => break,
+                    Err(_) => continue,
+                };
+
+                if resources::changes_list(&event)
+                    && peer.notify_resource_list_changed().await.is_err()
+                {
+                    break;
+                }
+
+                let Some(changed) = resources::changed_uri(&event) else {
+                    continue;
+                };
+                let updated: Vec<String> = subscriptions
+                    .lock()
+                    .expect("subscriptions lock poisoned")
+                    .iter()
+                    .filter(|uri| ResourceUri::parse(uri).is_ok_and(|r| r.affected_by(changed)))
+                    .cloned()
+                    .collect();
+                for uri in updated {
+                    debug!("Resource updated: {} ({})", uri, event.kind());
+                    if peer
+                        .notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            }
+            debug!("Resource watcher stopped");
+        });
+    }
+
     /// Check if auto-trigger conditions are met and send SessionClosed event
     async fn check_and_trigger_processing(&self, thread_id: &str) -> bool {
         if !self.auto_trigger_config.enable_auto_trigger {
             return false;
         }
 
+        let operations = self.operations();
         let mut states = self.session_states.write().await;
-        let state = states.entry(thread_id.to_string()).or_default();
+        let state = states
+            .entry((self.identity(), thread_id.to_string()))
+            .or_insert_with(|| SessionState::new(operations));
 
         // Update last message time
         state.last_message = Instant::now();
@@ -361,18 +787,7 @@ impl MemoryMcpService {
             self.last_global_process.store(now_ts, Ordering::Relaxed);
 
             // Send SessionClosed event to MemoryEventCoordinator
-            if let Some(tx) = self.operations.memory_event_tx() {
-                use cortex_mem_core::memory_events::MemoryEvent;
-
-                let user_id = self.operations.default_user_id().to_string();
-                let agent_id = self.operations.default_agent_id().to_string();
-
-                let _ = tx.send(MemoryEvent::SessionClosed {
-                    session_id: thread_id.to_string(),
-                    user_id,
-                    agent_id,
-                });
-
+            if send_session_closed(&state.operations, thread_id) {
                 info!(
                     "Auto-triggered SessionClosed event for session {} (will process in background)",
                     thread_id
@@ -417,7 +832,6 @@ impl MemoryMcpService {
     /// Start a background task to check for inactive sessions
     pub fn start_inactivity_checker(&self) {
         let session_states = self.session_states.clone();
-        let operations = self.operations.clone();
         let config = self.auto_trigger_config;
 
         tokio::spawn(async move {
@@ -434,7 +848,7 @@ impl MemoryMcpService {
                 let mut states = session_states.write().await;
                 let mut to_process = Vec::new();
 
-                for (thread_id, state) in states.iter_mut() {
+                for ((identity, thread_id), state) in states.iter_mut() {
                     let inactive_duration = state.last_message.elapsed().as_secs();
 
                     if inactive_duration >= config.inactivity_timeout_secs && state.message_count > 0
@@ -447,31 +861,19 @@ impl MemoryMcpService {
 
                         if can_process {
                             info!(
-                                "Session {} inactive for {}s, triggering processing",
-                                thread_id, inactive_duration
+                                "Session {} ({}) inactive for {}s, triggering processing",
+                                thread_id, identity, inactive_duration
                             );
-                            to_process.push(thread_id.clone());
+                            to_process.push((identity.clone(), thread_id.clone()));
                         }
                     }
                 }
 
-                for thread_id in to_process {
-                    if let Some(state) = states.get_mut(&thread_id) {
+                for key in to_process {
+                    if let Some(state) = states.get_mut(&key) {
                         state.message_count = 0;
                         state.last_processed = Some(Instant::now());
-
-                        if let Some(tx) = operations.memory_event_tx() {
-                            use cortex_mem_core::memory_events::MemoryEvent;
-
-                            let user_id = operations.default_user_id().to_string();
-                            let agent_id = operations.default_agent_id().to_string();
-
-                            let _ = tx.send(MemoryEvent::SessionClosed {
-                                session_id: thread_id.clone(),
-                                user_id,
-                                agent_id,
-                            });
-                        }
+                        send_session_closed(&state.operations, &key.1);
                     }
                 }
             }
@@ -501,7 +903,7 @@ impl MemoryMcpService {
             limit: Some(limit),
         };
 
-        match self.operations.search(search_args).await {
+        match self.operations().search(search_args).await {
             Ok(response) => {
                 let results: Vec<SearchResultMcp> = response
                     .results
@@ -541,7 +943,7 @@ impl MemoryMcpService {
         debug!("recall called with args: {:?}", params.0);
 
         match self
-            .operations
+            .operations()
             .recall(&params.0.query, params.0.scope.as_deref(), params.0.limit)
             .await
         {
@@ -576,6 +978,43 @@ impl MemoryMcpService {
         }
     }
 
+    #[tool(description = "Get the standing instructions and the learned skills that apply to a task")]
+    async fn guidance(
+        &self,
+        params: Parameters<GuidanceArgsMcp>,
+    ) -> std::result::Result<Json<GuidanceResult>, String> {
+        debug!("guidance called with args: {:?}", params.0);
+        let args = params.0;
+
+        match self
+            .operations()
+            .guidance(GuidanceArgs {
+                task: args.task,
+                limit: args.limit,
+            })
+            .await
+        {
+            Ok(response) => {
+                info!(
+                    "Guidance for '{}': {} instructions, {} skills",
+                    response.task,
+                    response.instructions.len(),
+                    response.skills.len()
+                );
+                Ok(Json(GuidanceResult {
+                    success: true,
+                    task: response.task,
+                    instructions: response.instructions.into_iter().map(Into::into).collect(),
+                    skills: response.skills.into_iter().map(Into::into).collect(),
+                }))
+            }
+            Err(e) => {
+                error!("Guidance failed: {}", e);
+                Err(format!("Guidance failed: {}", e))
+            }
+        }
+    }
+
     // ==================== Storage Tools ====================
 
     #[tool(description = "Add a message to memory for a specific session")]
@@ -585,11 +1024,45 @@ impl MemoryMcpService {
     ) -> std::result::Result<Json<StoreResult>, String> {
         debug!("store called with args: {:?}", params.0);
 
+        if let Some(space) = params.0.space {
+            let args = cortex_mem_tools::StoreArgs {
+                content: params.0.content,
+                thread_id: String::new(),
+                metadata: None,
+                auto_generate_layers: Some(true),
+                scope: "space".to_string(),
+                user_id: None,
+                agent_id: None,
+                space: Some(space),
+            };
+            return match self.operations().store(args).await {
+                Ok(response) => {
+                    info!("Memory stored at: {}", response.uri);
+                    let message_id = response
+                        .uri
+                        .rsplit('/')
+                        .next()
+                        .and_then(|s| s.strip_suffix(".md"))
+                        .unwrap_or("unknown")
+                        .to_string();
+                    Ok(Json(StoreResult {
+                        success: true,
+                        uri: response.uri,
+                        message_id,
+                    }))
+                }
+                Err(e) => {
+                    error!("Failed to store memory: {}", e);
+                    Err(format!("Failed to store memory: {}", e))
+                }
+            };
+        }
+
         let thread_id = params.0.thread_id.unwrap_or_else(|| "default".to_string());
         let role = params.0.role.as_deref().unwrap_or("user");
 
         match self
-            .operations
+            .operations()
             .add_message(&thread_id, role, &params.0.content)
             .await
         {
@@ -621,6 +1094,66 @@ impl MemoryMcpService {
         }
     }
 
+    #[tool(description = "Import many messages across sessions at once, keeping original timestamps")]
+    async fn store_batch(
+        &self,
+        params: Parameters<StoreBatchArgsMcp>,
+    ) -> std::result::Result<Json<StoreBatchResult>, String> {
+        debug!("store_batch called with {} messages", params.0.messages.len());
+
+        let mut messages = Vec::with_capacity(params.0.messages.len());
+        for (i, m) in params.0.messages.into_iter().enumerate() {
+            let timestamp = match m.timestamp.as_deref() {
+                Some(ts) => Some(
+                    chrono::DateTime::parse_from_rfc3339(ts)
+                        .map_err(|e| format!("messages[{}]: invalid timestamp '{}': {}", i, ts, e))?
+                        .with_timezone(&chrono::Utc),
+                ),
+                None => None,
+            };
+            messages.push(BatchMessage {
+                thread_id: m.thread_id,
+                role: m.role,
+                content: m.content,
+                timestamp,
+                metadata: None,
+            });
+        }
+
+        let args = StoreBatchArgs {
+            messages,
+            extract: params.0.extract.unwrap_or(false),
+        };
+
+        match self.operations().store_batch(args).await {
+            Ok(response) => {
+                info!(
+                    "Imported {} messages into {} sessions",
+                    response.total_messages,
+                    response.threads.len()
+                );
+
+                Ok(Json(StoreBatchResult {
+                    success: true,
+                    total_messages: response.total_messages,
+                    threads: response
+                        .threads
+                        .into_iter()
+                        .map(|t| StoreBatchThread {
+                            thread_id: t.thread_id,
+                            message_count: t.message_count,
+                        })
+                        .collect(),
+                    extraction_triggered: response.extraction_triggered,
+                }))
+            }
+            Err(e) => {
+                error!("Failed to import messages: {}", e);
+                Err(format!("Failed to import messages: {}", e))
+            }
+        }
+    }
+
     #[tool(description = "Commit accumulated conversation content and trigger memory extraction")]
     async fn commit(
         &self,
@@ -630,7 +1163,7 @@ impl MemoryMcpService {
 
         let thread_id = params.0.thread_id.unwrap_or_else(|| "default".to_string());
 
-        match self.operations.close_session_sync(&thread_id).await {
+        match self.operations().close_session_sync(&thread_id).await {
             Ok(_) => {
                 info!("Session {} closed and fully processed (sync)", thread_id);
 
@@ -663,9 +1196,14 @@ impl MemoryMcpService {
             uri: uri.to_string(),
             recursive: params.0.recursive,
             include_abstracts: Some(include_abstracts),
+            pattern: params.0.pattern.clone(),
+            sort: params.0.sort.clone(),
+            descending: params.0.descending,
+            limit: params.0.limit,
+            cursor: params.0.cursor.clone(),
         };
 
-        match self.operations.ls(ls_args).await {
+        match self.operations().ls(ls_args).await {
             Ok(response) => {
                 let entries: Vec<LsEntryMcp> = response
                     .entries
@@ -676,17 +1214,19 @@ impl MemoryMcpService {
                         is_directory: e.is_directory,
                         size: e.child_count.map(|c| c as usize),
                         abstract_text: e.abstract_text,
+                        bytes: e.size,
+                        modified: e.modified.to_rfc3339(),
                     })
                     .collect();
 
-                let total = entries.len();
-                info!("Listed {} items at {}", total, uri);
+                info!("Listed {} items at {}", entries.len(), uri);
 
                 Ok(Json(LsResult {
                     success: true,
                     uri: uri.to_string(),
                     entries,
-                    total,
+                    total: response.total,
+                    next_cursor: response.next_cursor,
                 }))
             }
             Err(e) => {
@@ -710,9 +1250,14 @@ impl MemoryMcpService {
             start_uri: params.0.start_uri.clone(),
             max_depth: Some(3),
             return_layers: params.0.return_layers.clone(),
+            pattern: params.0.pattern.clone(),
+            sort: params.0.sort.clone(),
+            descending: params.0.descending,
+            limit: params.0.limit,
+            cursor: params.0.cursor.clone(),
         };
 
-        match self.operations.explore(explore_args).await {
+        match self.operations().explore(explore_args).await {
             Ok(response) => {
                 let exploration_path: Vec<ExplorationPathItemMcp> = response
                     .exploration_path
@@ -749,6 +1294,7 @@ impl MemoryMcpService {
                     matches,
                     total_explored: response.total_explored,
                     total_matches: response.total_matches,
+                    next_cursor: response.next_cursor,
                 }))
             }
             Err(e) => {
@@ -760,14 +1306,17 @@ impl MemoryMcpService {
 
     // ==================== Tiered Access Tools ====================
 
-    #[tool(description = "Get L0 abstract layer (~100 tokens) for quick relevance checking")]
+    #[tool(
+        name = "abstract",
+        description = "Get L0 abstract layer (~100 tokens) for quick relevance checking"
+    )]
     async fn r#abstract(
         &self,
         params: Parameters<AbstractArgs>,
     ) -> std::result::Result<Json<AbstractResult>, String> {
         debug!("abstract called with args: {:?}", params.0);
 
-        match self.operations.get_abstract(&params.0.uri).await {
+        match self.operations().get_abstract(&params.0.uri).await {
             Ok(abstract_result) => {
                 info!("Abstract retrieved for: {}", params.0.uri);
                 Ok(Json(AbstractResult {
@@ -792,7 +1341,7 @@ impl MemoryMcpService {
     ) -> std::result::Result<Json<OverviewResult>, String> {
         debug!("overview called with args: {:?}", params.0);
 
-        match self.operations.get_overview(&params.0.uri).await {
+        match self.operations().get_overview(&params.0.uri).await {
             Ok(overview_result) => {
                 info!("Overview retrieved for: {}", params.0.uri);
                 Ok(Json(OverviewResult {
@@ -817,7 +1366,7 @@ impl MemoryMcpService {
     ) -> std::result::Result<Json<ContentResult>, String> {
         debug!("content called with args: {:?}", params.0);
 
-        match self.operations.read_file(&params.0.uri).await {
+        match self.operations().read_file(&params.0.uri).await {
             Ok(content) => {
                 let token_count = content.split_whitespace().count();
                 info!("Content retrieved from: {}", params.0.uri);
@@ -836,6 +1385,84 @@ impl MemoryMcpService {
         }
     }
 
+    // ==================== Memory Editing Tools ====================
+
+    #[tool(description = "Remember a structured fact (preference, entity, event, case, ...); an existing key is updated instead of duplicated")]
+    async fn remember(
+        &self,
+        params: Parameters<RememberArgsMcp>,
+    ) -> std::result::Result<Json<MemoryEditResult>, String> {
+        debug!("remember called with args: {:?}", params.0);
+        let args = params.0;
+
+        match self
+            .operations()
+            .remember(RememberArgs {
+                memory_type: args.memory_type,
+                key: args.key,
+                content: args.content,
+                confidence: args.confidence,
+            })
+            .await
+        {
+            Ok(response) => {
+                info!("Memory {}: {}", response.action, response.uri);
+                Ok(Json(response.into()))
+            }
+            Err(e) => {
+                error!("Failed to remember: {}", e);
+                Err(format!("Failed to remember: {}", e))
+            }
+        }
+    }
+
+    #[tool(description = "Correct the content of an existing memory, keeping its type and key")]
+    async fn update_memory(
+        &self,
+        params: Parameters<UpdateMemoryArgsMcp>,
+    ) -> std::result::Result<Json<MemoryEditResult>, String> {
+        debug!("update_memory called with args: {:?}", params.0);
+        let args = params.0;
+
+        match self
+            .operations()
+            .update_memory(UpdateMemoryArgs {
+                uri: args.uri,
+                content: args.content,
+                confidence: args.confidence,
+            })
+            .await
+        {
+            Ok(response) => {
+                info!("Memory updated: {}", response.uri);
+                Ok(Json(response.into()))
+            }
+            Err(e) => {
+                error!("Failed to update memory: {}", e);
+                Err(format!("Failed to update memory: {}", e))
+            }
+        }
+    }
+
+    #[tool(description = "Forget a memory: remove it from the memory index, filesystem and vector database")]
+    async fn forget(
+        &self,
+        params: Parameters<ForgetArgs>,
+    ) -> std::result::Result<Json<MemoryEditResult>, String> {
+        debug!("forget called with args: {:?}", params.0);
+
+        match self.operations().forget(&params.0.uri).await {
+            Ok(response) => {
+                info!("Memory forgotten: {}", response.uri);
+                Ok(Json(response.into()))
+            }
+            Err(e) => {
+                error!("Failed to forget memory: {}", e);
+                Err(format!("Failed to forget memory: {}", e))
+            }
+        }
+    }
+
     // ==================== Management Tools ====================
 
     #[tool(description = "Delete a memory by its URI")]
@@ -845,7 +1472,7 @@ impl MemoryMcpService {
     ) -> std::result::Result<Json<DeleteResult>, String> {
         debug!("delete called with args: {:?}", params.0);
 
-        match self.operations.delete(&params.0.uri).await {
+        match self.operations().delete(&params.0.uri).await {
             Ok(_) => {
                 info!("Memory deleted: {}", params.0.uri);
                 Ok(Json(DeleteResult {
@@ -868,7 +1495,7 @@ impl MemoryMcpService {
         debug!("layers called with args: {:?}", params.0);
 
         let (stats, message) = if let Some(ref thread_id) = params.0.thread_id {
-            match self.operations.ensure_session_layers(thread_id).await {
+            match self.operations().ensure_session_layers(thread_id).await {
                 Ok(stats) => {
                     let msg = format!("Generated layers for session {}", thread_id);
                     (stats, msg)
@@ -879,7 +1506,7 @@ impl MemoryMcpService {
                 }
             }
         } else {
-            match self.operations.ensure_all_layers().await {
+            match self.operations().ensure_all_layers().await {
                 Ok(stats) => {
                     let msg = "Generated layers for all sessions".to_string();
                     (stats, msg)
@@ -913,7 +1540,7 @@ impl MemoryMcpService {
         debug!("index called with args: {:?}", params.0);
 
         let (stats, message) = if let Some(ref thread_id) = params.0.thread_id {
-            match self.operations.index_session_files(thread_id).await {
+            match self.operations().index_session_files(thread_id).await {
                 Ok(stats) => {
                     let msg = format!("Indexed memories for session {}", thread_id);
                     (stats, msg)
@@ -924,7 +1551,7 @@ impl MemoryMcpService {
                 }
             }
         } else {
-            match self.operations.index_all_files().await {
+            match self.operations().index_all_files().await {
                 Ok(stats) => {
                     let msg = "Indexed all memory files".to_string();
                     (stats, msg)
@@ -954,18 +1581,209 @@ impl MemoryMcpService {
 
 #[tool_handler]
 impl ServerHandler for MemoryMcpService {
+    async fn initialize(
+        &self,
+        request: InitializeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        // Request parts are only present for the HTTP transport
+        if let Some(parts) = context.extensions.get::<http::request::Parts>() {
+            self.bind_connection(parts).await?;
+        }
+
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        Ok(self.get_info())
+    }
+
+    // ==================== Prompts ====================
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult::with_all_items(prompts::list()))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        prompts::get(&self.operations(), &request.name, request.arguments).await
+    }
+
+    // ==================== Resources ====================
+
+    async fn list_resources(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        self.start_resource_watcher(context.peer);
+
+        let cursor = request.and_then(|r| r.cursor);
+        let (resources, next_cursor) =
+            resources::list(self.operations().filesystem(), cursor.as_deref()).await?;
+
+        Ok(ListResourcesResult {
+            meta: None,
+            next_cursor,
+            resources,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        debug!("read_resource: {}", request.uri);
+        let contents = resources::read(&self.operations(), &request.uri).await?;
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        ResourceUri::parse(&request.uri)?;
+        info!("Resource subscribed: {}", request.uri);
+        self.subscriptions
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .insert(request.uri);
+        self.start_resource_watcher(context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        info!("Resource unsubscribed: {}", request.uri);
+        self.subscriptions
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .remove(&request.uri);
+        Ok(())
+    }
+
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            instructions: Some(
-                "Cortex Memory MCP Server - Unified memory management tools.\n\n**Tool Naming Convention:** Simple verb style (search, store, ls, etc.)\n\n**Layer System:**\n- L0: Abstract (~100 tokens) - for quick relevance checking\n- L1: Overview (~2000 tokens) - for understanding core information\n- L2: Full content - complete original content\n\n**Automatic Processing:**\nThe server automatically triggers memory extraction and layer generation when:\n- Message count reaches threshold (default: 10 messages)\n- Session becomes inactive (default: 2 minutes without new messages)\n\n**Available tools:**\n- search: Layered semantic search with return_layers support\n- recall: Quick recall with L0+L2 content\n- store: Add a message to memory\n- commit: Commit session and trigger processing\n- ls: Browse memory filesystem\n- explore: Smart exploration of memory space\n- abstract: Get L0 abstract (~100 tokens)\n- overview: Get L1 overview (~2000 tokens)\n- content: Get L2 full content\n- delete: Delete a memory\n- layers: Generate L0/L1 layer files\n- index: Index memories to vector database\n".to_string(),
-            ),
+            instructions: Some(Self::instructions(&self.tool_router)),
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability {
                     list_changed: Some(false),
                 }),
+                prompts: Some(PromptsCapability {
+                    list_changed: Some(false),
+                }),
+                resources: Some(ResourcesCapability {
+                    subscribe: Some(true),
+                    list_changed: Some(true),
+                }),
                 ..Default::default()
             },
             ..Default::default()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_mem_tools::{ToolAccess, ToolOverride, ToolsConfig, tool_access};
+
+    fn router(config: ToolsConfig) -> ToolRouter<MemoryMcpService> {
+        MemoryMcpService::configured_router(&ToolPolicy::new(config))
+    }
+
+    #[test]
+    fn test_default_router_exposes_every_tool() {
+        let router = router(ToolsConfig::default());
+        for (name, _) in TOOL_SUMMARIES {
+            assert!(router.has_route(name), "missing tool {}", name);
+        }
+    }
+
+    #[test]
+    fn test_disabled_tools_removed() {
+        let router = router(ToolsConfig {
+            disabled: vec!["forget".to_string(), "delete".to_string()],
+            ..Default::default()
+        });
+        assert!(!router.has_route("forget"));
+        assert!(!router.has_route("delete"));
+        assert!(router.has_route("search"));
+
+        let instructions = MemoryMcpService::instructions(&router);
+        assert!(!instructions.contains("- forget:"));
+        assert!(!instructions.contains("- delete:"));
+        assert!(instructions.contains("- search:"));
+    }
+
+    #[test]
+    fn test_read_only_drops_write_tools() {
+        let router = router(ToolsConfig {
+            read_only: true,
+            ..Default::default()
+        });
+        for tool in router.list_all() {
+            let annotations = tool.annotations.expect("annotations set");
+            assert_eq!(annotations.read_only_hint, Some(true), "{}", tool.name);
+        }
+        for (name, _) in TOOL_SUMMARIES {
+            let exposed = router.has_route(name);
+            assert_eq!(exposed, tool_access(name) == ToolAccess::Read, "{}", name);
+        }
+
+        let instructions = MemoryMcpService::instructions(&router);
+        assert!(!instructions.contains("- store:"));
+        assert!(instructions.contains("- ls:"));
+    }
+
+    #[test]
+    fn test_overrides_reach_definitions() {
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "search".to_string(),
+            ToolOverride {
+                description: Some("Search the team handbook".to_string()),
+                enabled: None,
+            },
+        );
+        let router = router(ToolsConfig {
+            overrides,
+            ..Default::default()
+        });
+        let tools = router.list_all();
+        let search = tools.iter().find(|t| t.name == "search").unwrap();
+        assert_eq!(
+            search.description.as_deref(),
+            Some("Search the team handbook")
+        );
+        let store = tools.iter().find(|t| t.name == "store").unwrap();
+        assert_ne!(store.description, search.description);
+        assert_eq!(
+            store.annotations.as_ref().and_then(|a| a.read_only_hint),
+            Some(false)
+        );
+    }
+}
@@ -1,19 +1,41 @@
+pub mod memory_agent;
 pub mod tools;
 
+pub use cortex_mem_config::Config;
+pub use cortex_mem_core::{
+    EmbeddingCacheConfig, FixturesConfig, LayersConfig, LlmCacheConfig, Provider, UsageConfig,
+};
 pub use cortex_mem_core::llm::LLMClient;
-pub use cortex_mem_tools::MemoryOperations;
+pub use cortex_mem_tools::{MemoryOperations, MemoryOperationsBuilder, ToolPolicy, ToolsConfig};
+pub use memory_agent::{MemoryAgent, MemoryAgentConfig};
 pub use tools::*;
 
+use rig::{
+    agent::{Agent, AgentBuilder, PromptHook, WithBuilderTools},
+    completion::CompletionModel,
+    tool::{Tool, ToolDyn},
+};
 use std::sync::Arc;
 
 /// Memory tools collection for Rig agents
 pub struct MemoryTools {
     operations: Arc<MemoryOperations>,
+    policy: ToolPolicy,
 }
 
 impl MemoryTools {
     pub fn new(operations: Arc<MemoryOperations>) -> Self {
-        Self { operations }
+        Self {
+            operations,
+            policy: ToolPolicy::default(),
+        }
+    }
+
+    /// Restrict and re-describe the tools registered by [`MemoryTools::register`]
+    /// (e.g. `ToolPolicy::new(config.tools.clone())`)
+    pub fn with_policy(mut self, policy: ToolPolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     /// Get the underlying MemoryOperations
@@ -21,6 +43,11 @@ impl MemoryTools {
         &self.operations
     }
 
+    /// Get the tool policy
+    pub fn policy(&self) -> &ToolPolicy {
+        &self.policy
+    }
+
     // ==================== Tiered Access Tools ====================
 
     pub fn abstract_tool(&self) -> AbstractTool {
@@ -45,6 +72,14 @@ impl MemoryTools {
         FindTool::new(self.operations.clone())
     }
 
+    pub fn recall_tool(&self) -> RecallTool {
+        RecallTool::new(self.operations.clone())
+    }
+
+    pub fn guidance_tool(&self) -> GuidanceTool {
+        GuidanceTool::new(self.operations.clone())
+    }
+
     // ==================== Filesystem Tools ====================
 
     pub fn ls_tool(&self) -> LsTool {
@@ -60,6 +95,90 @@ impl MemoryTools {
     pub fn store_tool(&self) -> StoreTool {
         StoreTool::new(self.operations.clone())
     }
+
+    pub fn commit_tool(&self) -> CommitTool {
+        CommitTool::new(self.operations.clone())
+    }
+
+    // ==================== Memory Editing Tools ====================
+
+    pub fn remember_tool(&self) -> RememberTool {
+        RememberTool::new(self.operations.clone())
+    }
+
+    pub fn update_memory_tool(&self) -> UpdateMemoryTool {
+        UpdateMemoryTool::new(self.operations.clone())
+    }
+
+    pub fn forget_tool(&self) -> ForgetTool {
+        ForgetTool::new(self.operations.clone())
+    }
+
+    // ==================== Management Tools ====================
+
+    pub fn layers_tool(&self) -> LayersTool {
+        LayersTool::new(self.operations.clone())
+    }
+
+    pub fn index_tool(&self) -> IndexTool {
+        IndexTool::new(self.operations.clone())
+    }
+
+    // ==================== Agent Registration ====================
+
+    /// Register every memory tool on a rig agent builder in one call
+    ///
+    /// The agent gets the same tool surface as the MCP server: tiered access,
+    /// search, recall and guidance, browsing, storage and commit, memory editing, and
+    /// layer / index maintenance. Tools disabled by the policy are skipped and
+    /// description overrides are applied. Register tools one by one with the
+    /// `*_tool()` getters to expose a subset instead.
+    pub fn register<M: CompletionModel, P: PromptHook<M>>(
+        &self,
+        builder: AgentBuilder<M, P>,
+    ) -> AgentBuilder<M, P, WithBuilderTools> {
+        builder.tools(self.enabled_tools())
+    }
+
+    /// The tools [`MemoryTools::register`] adds, for builders that already have tools
+    pub fn enabled_tools(&self) -> Vec<Box<dyn ToolDyn>> {
+        let mut tools = Vec::new();
+        self.push_tool(&mut tools, self.abstract_tool());
+        self.push_tool(&mut tools, self.overview_tool());
+        self.push_tool(&mut tools, self.read_tool());
+        self.push_tool(&mut tools, self.search_tool());
+        self.push_tool(&mut tools, self.find_tool());
+        self.push_tool(&mut tools, self.recall_tool());
+        self.push_tool(&mut tools, self.guidance_tool());
+        self.push_tool(&mut tools, self.ls_tool());
+        self.push_tool(&mut tools, self.explore_tool());
+        self.push_tool(&mut tools, self.store_tool());
+        self.push_tool(&mut tools, self.commit_tool());
+        self.push_tool(&mut tools, self.remember_tool());
+        self.push_tool(&mut tools, self.update_memory_tool());
+        self.push_tool(&mut tools, self.forget_tool());
+        self.push_tool(&mut tools, self.layers_tool());
+        self.push_tool(&mut tools, self.index_tool());
+        tools
+    }
+
+    /// Add one tool if the policy enables it, applying its description override
+    fn push_tool<T: Tool + 'static>(&self, tools: &mut Vec<Box<dyn ToolDyn>>, tool: T) {
+        if !self.policy.is_enabled(T::NAME) {
+            return;
+        }
+        let description = self.policy.description(T::NAME).map(str::to_string);
+        tools.push(Box::new(ConfiguredTool::new(tool, description)));
+    }
+
+    /// Wrap a built agent with automatic recall, turn persistence and session commit
+    pub fn memory_agent<M: CompletionModel>(
+        &self,
+        agent: Agent<M>,
+        config: MemoryAgentConfig,
+    ) -> MemoryAgent<M> {
+        MemoryAgent::new(agent, self.operations.clone(), config)
+    }
 }
 
 /// Create memory tools for Rig agents
@@ -83,53 +202,49 @@ pub async fn create_memory_tools_with_tenant_and_vector(
     embedding_dim: Option<usize>,
     user_id: Option<String>,
 ) -> Result<MemoryTools, Box<dyn std::error::Error>> {
-    create_memory_tools_with_config(
-        data_dir,
+    let qdrant = cortex_mem_config::QdrantConfig {
+        url: qdrant_url.to_string(),
+        collection_name: qdrant_collection.to_string(),
+        embedding_dim,
+        timeout_secs: 30,
+        api_key: qdrant_api_key.map(str::to_string),
+    };
+    let embedding = cortex_mem_config::EmbeddingConfig {
+        api_base_url: embedding_api_base_url.to_string(),
+        api_key: embedding_api_key.to_string(),
+        model_name: embedding_model_name.to_string(),
+        ..cortex_mem_config::EmbeddingConfig::default()
+    };
+    let mut builder = MemoryOperations::builder(
+        data_dir.as_ref().to_string_lossy(),
         tenant_id,
         llm_client,
-        qdrant_url,
-        qdrant_collection,
-        qdrant_api_key,
-        embedding_api_base_url,
-        embedding_api_key,
-        embedding_model_name,
-        embedding_dim,
-        user_id,
-        true, // enable_intent_analysis default
-    ).await
+        &qdrant,
+        &embedding,
+    );
+    if let Some(user_id) = user_id {
+        builder = builder.with_user_id(user_id);
+    }
+    let operations = builder.build().await?;
+    Ok(MemoryTools::new(Arc::new(operations)))
 }
 
-/// Create memory tools with full features (LLM + Vector Search) and explicit config
+/// Create memory tools with full features (LLM + Vector Search) from a config file
 ///
-/// Use this when you want to control intent analysis behaviour from config.
+/// Applies the data directory, Qdrant and embedding settings, intent
+/// analysis, the LLM result cache, the persistent embedding cache, token
+/// usage accounting, fixture record / replay, the L0/L1 generation mode and
+/// the `[access]` policy (the tenant ID is the agent ID).
 pub async fn create_memory_tools_with_config(
-    data_dir: impl AsRef<std::path::Path>,
+    config: &Config,
     tenant_id: impl Into<String>,
     llm_client: Arc<dyn LLMClient>,
-    qdrant_url: &str,
-    qdrant_collection: &str,
-    qdrant_api_key: Option<&str>,
-    embedding_api_base_url: &str,
-    embedding_api_key: &str,
-    embedding_model_name: &str,
-    embedding_dim: Option<usize>,
     user_id: Option<String>,
-    enable_intent_analysis: bool,
 ) -> Result<MemoryTools, Box<dyn std::error::Error>> {
-    let operations = MemoryOperations::new(
-        data_dir.as_ref().to_str().unwrap(),
-        tenant_id,
-        llm_client,
-        qdrant_url,
-        qdrant_collection,
-        qdrant_api_key,
-        embedding_api_base_url,
-        embedding_api_key,
-        embedding_model_name,
-        embedding_dim,
-        user_id,
-        enable_intent_analysis,
-    )
-    .await?;
+    let mut builder = MemoryOperations::from_config(config, tenant_id, llm_client);
+    if let Some(user_id) = user_id {
+        builder = builder.with_user_id(user_id);
+    }
+    let operations = builder.build().await?;
     Ok(MemoryTools::new(Arc::new(operations)))
 }
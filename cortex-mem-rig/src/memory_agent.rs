@@ -0,0 +1,514 @@
+// Automatic memory middleware for Rig agents
+
+use cortex_mem_core::layers::generator::AbstractGenerator;
+use cortex_mem_tools::{MemoryOperations, SearchArgs, SearchResult, ToolsError};
+use rig::{
+    agent::Agent,
+    completion::{Chat, CompletionModel, Message},
+};
+use std::collections::HashSet;
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Configuration for [`MemoryAgent`]
+#[derive(Debug, Clone)]
+pub struct MemoryAgentConfig {
+    /// Session thread the turns are written to (a new UUID when `None`)
+    pub thread_id: Option<String>,
+    /// Scopes searched before each completion
+    /// (defaults to the current user's and agent's memories)
+    pub recall_scopes: Option<Vec<String>>,
+    /// Maximum number of memories recalled per scope
+    pub recall_limit: usize,
+    /// Token budget for the injected memory context
+    pub context_token_budget: usize,
+    /// Commit the session after this much idle time (`None` disables the watcher)
+    pub inactivity_timeout: Option<Duration>,
+    /// Commit any uncommitted turns when the agent is dropped
+    pub commit_on_drop: bool,
+}
+
+impl Default for MemoryAgentConfig {
+    fn default() -> Self {
+        Self {
+            thread_id: None,
+            recall_scopes: None,
+            recall_limit: 5,
+            context_token_budget: 1500,
+            inactivity_timeout: Some(Duration::from_secs(300)),
+            commit_on_drop: true,
+        }
+    }
+}
+
+/// Shared between the agent and its inactivity watcher
+struct SessionTracker {
+    last_turn: Mutex<Instant>,
+    pending: AtomicBool,
+}
+
+impl SessionTracker {
+    fn touch(&self) {
+        *self.last_turn.lock().unwrap() = Instant::now();
+        self.pending.store(true, Ordering::SeqCst);
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_turn.lock().unwrap().elapsed()
+    }
+}
+
+/// A Rig agent wrapped with automatic memory
+///
+/// Before each completion the most relevant memories (L0 abstracts) are
+/// recalled and prepended to the prompt within `context_token_budget`. Every
+/// user / assistant turn is persisted to the session thread, and the session
+/// is committed (memory extraction + layer generation) after
+/// `inactivity_timeout` of idle time, on [`MemoryAgent::commit`], or on drop.
+///
+/// Must be created inside a Tokio runtime. Commits triggered by the watcher
+/// or by drop are fire-and-forget; call [`MemoryAgent::commit`] before
+/// shutting down to wait for the full pipeline.
+pub struct MemoryAgent<M: CompletionModel> {
+    agent: Agent<M>,
+    operations: Arc<MemoryOperations>,
+    config: MemoryAgentConfig,
+    thread_id: String,
+    history: Vec<Message>,
+    tracker: Arc<SessionTracker>,
+    watcher: Option<JoinHandle<()>>,
+}
+
+impl<M: CompletionModel> MemoryAgent<M> {
+    pub fn new(
+        agent: Agent<M>,
+        operations: Arc<MemoryOperations>,
+        config: MemoryAgentConfig,
+    ) -> Self {
+        let thread_id = config
+            .thread_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let tracker = Arc::new(SessionTracker {
+            last_turn: Mutex::new(Instant::now()),
+            pending: AtomicBool::new(false),
+        });
+
+        let watcher = config.inactivity_timeout.map(|timeout| {
+            Self::spawn_watcher(
+                operations.clone(),
+                tracker.clone(),
+                thread_id.clone(),
+                timeout,
+            )
+        });
+
+        Self {
+            agent,
+            operations,
+            config,
+            thread_id,
+            history: Vec::new(),
+            tracker,
+            watcher,
+        }
+    }
+
+    /// Session thread the conversation is persisted to
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// Conversation history (raw turns, without injected memory context)
+    pub fn history(&self) -> &[Message] {
+        &self.history
+    }
+
+    /// The wrapped Rig agent
+    pub fn agent(&self) -> &Agent<M> {
+        &self.agent
+    }
+
+    /// Send a message and get the reply, with recall and persistence handled automatically
+    pub async fn chat(&mut self, input: &str) -> Result<String, ToolsError> {
+        let context = self.recall_context(input).await;
+        let prompt = prompt_with_context(context.as_deref(), input);
+
+        let reply = self
+            .agent
+            .chat(prompt, self.history.clone())
+            .await
+            .map_err(|e| ToolsError::Runtime(format!("Agent completion failed: {}", e)))?;
+
+        self.history.push(Message::user(input));
+        self.history.push(Message::assistant(&reply));
+
+        for (role, content) in [("user", input), ("assistant", reply.as_str())] {
+            if let Err(e) = self
+                .operations
+                .add_message(&self.thread_id, role, content)
+                .await
+            {
+                warn!(
+                    "Failed to persist {} turn to session {}: {}",
+                    role, self.thread_id, e
+                );
+            }
+        }
+        self.tracker.touch();
+
+        Ok(reply)
+    }
+
+    /// Commit the session and wait for memory extraction to finish
+    ///
+    /// No-op when there are no uncommitted turns.
+    pub async fn commit(&self) -> Result<(), ToolsError> {
+        if !self.tracker.pending.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        if let Err(e) = self.operations.close_session_sync(&self.thread_id).await {
+            self.tracker.pending.store(true, Ordering::SeqCst);
+            return Err(e);
+        }
+        info!("Committed memory session {}", self.thread_id);
+        Ok(())
+    }
+
+    /// Build the memory context block for a prompt, or `None` if nothing relevant was found
+    async fn recall_context(&self, query: &str) -> Option<String> {
+        if self.config.context_token_budget == 0 || self.config.recall_limit == 0 {
+            return None;
+        }
+
+        let scopes = self.config.recall_scopes.clone().unwrap_or_else(|| {
+            vec![
+                format!("cortex://user/{}", self.operations.default_user_id()),
+                format!("cortex://agent/{}", self.operations.default_agent_id()),
+            ]
+        });
+
+        let mut results = Vec::new();
+        for scope in scopes {
+            let args = SearchArgs {
+                query: query.to_string(),
+                recursive: Some(true),
+                return_layers: Some(vec!["L0".to_string()]),
+                scope: Some(scope.clone()),
+                limit: Some(self.config.recall_limit),
+            };
+            match self.operations.search(args).await {
+                Ok(response) => results.extend(response.results),
+                Err(e) => debug!("Memory recall in {} failed: {}", scope, e),
+            }
+        }
+        memory_context(results, self.config.context_token_budget)
+    }
+
+    fn spawn_watcher(
+        operations: Arc<MemoryOperations>,
+        tracker: Arc<SessionTracker>,
+        thread_id: String,
+        timeout: Duration,
+    ) -> JoinHandle<()> {
+        let tick = (timeout / 4).clamp(Duration::from_secs(1), Duration::from_secs(30));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                if tracker.idle_for() < timeout || !tracker.pending.swap(false, Ordering::SeqCst) {
+                    continue;
+                }
+                info!("Session {} idle for {:?}, committing", thread_id, timeout);
+                if let Err(e) = operations.close_session(&thread_id).await {
+                    warn!("Failed to commit idle session {}: {}", thread_id, e);
+                    tracker.pending.store(true, Ordering::SeqCst);
+                }
+            }
+        })
+    }
+}
+
+/// Memory block of the best-scored abstracts that fit in `token_budget`
+fn memory_context(mut results: Vec<SearchResult>, token_budget: usize) -> Option<String> {
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut seen = HashSet::new();
+    let mut used_tokens = 0;
+    let mut lines = Vec::new();
+    for result in results {
+        let Some(text) = result.abstract_text.as_deref().map(str::trim) else {
+            continue;
+        };
+        if text.is_empty() || !seen.insert(result.uri.clone()) {
+            continue;
+        }
+        let line = format!("- [{}] {}", result.uri, text);
+        let tokens = AbstractGenerator::estimate_tokens(&line);
+        if used_tokens + tokens > token_budget {
+            break;
+        }
+        used_tokens += tokens;
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+    debug!(
+        "Injecting {} recalled memories (~{} tokens)",
+        lines.len(),
+        used_tokens
+    );
+    Some(format!(
+        "<memory>\nRelevant memories about this user (use them only if they help):\n{}\n</memory>",
+        lines.join("\n")
+    ))
+}
+
+/// The prompt sent to the model: the memory block, if any, before the input
+fn prompt_with_context(context: Option<&str>, input: &str) -> String {
+    match context {
+        Some(context) => format!("{}\n\n{}", context, input),
+        None => input.to_string(),
+    }
+}
+
+impl<M: CompletionModel> Drop for MemoryAgent<M> {
+    fn drop(&mut self) {
+        if let Some(watcher) = self.watcher.take() {
+            watcher.abort();
+        }
+        if !self.config.commit_on_drop || !self.tracker.pending.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let operations = self.operations.clone();
+                let thread_id = self.thread_id.clone();
+                handle.spawn(async move {
+                    if let Err(e) = operations.close_session(&thread_id).await {
+                        warn!("Failed to commit session {} on drop: {}", thread_id, e);
+                    }
+                });
+            }
+            Err(_) => warn!(
+                "No Tokio runtime on drop, session {} left uncommitted",
+                self.thread_id
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::{
+        OneOrMany,
+        agent::AgentBuilder,
+        completion::{
+            AssistantContent, CompletionError, CompletionRequest, CompletionResponse, Usage,
+            message::UserContent,
+        },
+        streaming::StreamingCompletionResponse,
+    };
+
+    /// Completion model that records the prompts it receives and echoes a fixed reply
+    #[derive(Clone, Default)]
+    struct RecordingModel {
+        prompts: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CompletionModel for RecordingModel {
+        type Response = ();
+        type StreamingResponse = ();
+        type Client = ();
+
+        fn make(_client: &Self::Client, _model: impl Into<String>) -> Self {
+            Self::default()
+        }
+
+        async fn completion(
+            &self,
+            request: CompletionRequest,
+        ) -> Result<CompletionResponse<()>, CompletionError> {
+            if let Message::User { content } = request.chat_history.last() {
+                for part in content.iter() {
+                    if let UserContent::Text(text) = part {
+                        self.prompts.lock().unwrap().push(text.text.clone());
+                    }
+                }
+            }
+            Ok(CompletionResponse {
+                choice: OneOrMany::one(AssistantContent::text("Noted.")),
+                usage: Usage::new(),
+                raw_response: (),
+                message_id: None,
+            })
+        }
+
+        async fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Result<StreamingCompletionResponse<()>, CompletionError> {
+            Err(CompletionError::ProviderError(
+                "streaming not supported".to_string(),
+            ))
+        }
+    }
+
+    fn result(uri: &str, score: f32, abstract_text: &str) -> SearchResult {
+        SearchResult {
+            uri: uri.to_string(),
+            score,
+            abstract_text: Some(abstract_text.to_string()),
+            overview_text: None,
+            content: None,
+        }
+    }
+
+    #[test]
+    fn test_memory_context_budget() {
+        let results = vec![
+            result("cortex://user/u/preferences/editor.md", 0.4, "Uses Helix"),
+            result("cortex://user/u/preferences/lang.md", 0.9, "Writes Rust"),
+            result("cortex://user/u/preferences/lang.md", 0.8, "Writes Rust"),
+            result("cortex://user/u/entities/empty.md", 0.7, "  "),
+        ];
+
+        let context = memory_context(results.clone(), 1500).unwrap();
+        assert!(context.starts_with("<memory>\n"));
+        assert!(context.ends_with("\n</memory>"));
+        // Best score first, duplicates and empty abstracts dropped
+        let lines: Vec<_> = context.lines().filter(|l| l.starts_with("- [")).collect();
+        assert_eq!(
+            lines,
+            [
+                "- [cortex://user/u/preferences/lang.md] Writes Rust",
+                "- [cortex://user/u/preferences/editor.md] Uses Helix",
+            ]
+        );
+
+        // Room for the first line only: the rest is cut off
+        let first = AbstractGenerator::estimate_tokens(lines[0]);
+        let truncated = memory_context(results.clone(), first).unwrap();
+        assert!(truncated.contains("Writes Rust"));
+        assert!(!truncated.contains("Uses Helix"));
+
+        assert_eq!(memory_context(results, first - 1), None);
+        assert_eq!(memory_context(Vec::new(), 1500), None);
+    }
+
+    #[test]
+    fn test_prompt_with_context() {
+        assert_eq!(prompt_with_context(None, "hi"), "hi");
+        assert_eq!(
+            prompt_with_context(Some("<memory>\n- m\n</memory>"), "hi"),
+            "<memory>\n- m\n</memory>\n\nhi"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_persists_turns() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = crate::tools::tests::operations(dir.path()).await;
+        let model = RecordingModel::default();
+        let agent = AgentBuilder::new(model.clone()).build();
+
+        let mut memory_agent = MemoryAgent::new(
+            agent,
+            operations.clone(),
+            MemoryAgentConfig {
+                thread_id: Some("t1".to_string()),
+                inactivity_timeout: None,
+                commit_on_drop: false,
+                ..Default::default()
+            },
+        );
+        // Nothing to commit before the first turn
+        memory_agent.commit().await.unwrap();
+
+        let reply = memory_agent.chat("I prefer tabs").await.unwrap();
+        assert_eq!(reply, "Noted.");
+
+        // No memory could be recalled: the model gets the raw input
+        assert_eq!(*model.prompts.lock().unwrap(), ["I prefer tabs"]);
+        assert_eq!(memory_agent.history().len(), 2);
+        assert!(memory_agent.tracker.pending.load(Ordering::SeqCst));
+
+        let session_manager = operations.session_manager().read().await;
+        let messages = session_manager
+            .message_storage()
+            .list_messages("t1")
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+    }
+
+    /// Wait until the session is closed (commits by drop and the watcher are detached)
+    async fn wait_closed(operations: &MemoryOperations, thread_id: &str) -> bool {
+        for _ in 0..50 {
+            if operations.get_session(thread_id).await.unwrap().status == "closed" {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_commit_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = crate::tools::tests::operations(dir.path()).await;
+        let agent = AgentBuilder::new(RecordingModel::default()).build();
+
+        let mut memory_agent = MemoryAgent::new(
+            agent,
+            operations.clone(),
+            MemoryAgentConfig {
+                thread_id: Some("t1".to_string()),
+                recall_limit: 0,
+                inactivity_timeout: None,
+                ..Default::default()
+            },
+        );
+        memory_agent.chat("I prefer tabs").await.unwrap();
+        assert_eq!(operations.get_session("t1").await.unwrap().status, "active");
+
+        drop(memory_agent);
+        assert!(wait_closed(&operations, "t1").await);
+    }
+
+    #[tokio::test]
+    async fn test_commit_on_inactivity() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = crate::tools::tests::operations(dir.path()).await;
+        let agent = AgentBuilder::new(RecordingModel::default()).build();
+
+        let mut memory_agent = MemoryAgent::new(
+            agent,
+            operations.clone(),
+            MemoryAgentConfig {
+                thread_id: Some("t1".to_string()),
+                recall_limit: 0,
+                inactivity_timeout: Some(Duration::from_millis(200)),
+                commit_on_drop: false,
+                ..Default::default()
+            },
+        );
+        memory_agent.chat("I prefer tabs").await.unwrap();
+
+        assert!(wait_closed(&operations, "t1").await);
+        assert!(!memory_agent.tracker.pending.load(Ordering::SeqCst));
+    }
+}
@@ -1,9 +1,10 @@
 // Rig Tool Implementations
 
 use cortex_mem_tools::{
-    AbstractResponse, ExploreArgs, ExploreResponse, FindArgs, FindResponse, LsArgs, LsResponse,
-    MemoryOperations, OverviewResponse, ReadResponse, SearchArgs, SearchResponse, StoreArgs,
-    StoreResponse, ToolsError,
+    AbstractResponse, ExploreArgs, ExploreResponse, FindArgs, FindResponse, GenerationStats,
+    GuidanceArgs, GuidanceResponse, LsArgs, LsResponse, MemoryEditResponse, MemoryOperations, OverviewResponse, ReadResponse,
+    RememberArgs, SearchArgs, SearchResponse, StoreArgs, StoreResponse, ToolsError,
+    UpdateMemoryArgs,
 };
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
@@ -288,6 +289,120 @@ impl Tool for FindTool {
     }
 }
 
+/// Recall Tool - Search returning L0 snippets with full L2 content
+pub struct RecallTool {
+    operations: Arc<MemoryOperations>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecallArgs {
+    pub query: String,
+    pub scope: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl RecallTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for RecallTool {
+    const NAME: &'static str = "recall";
+
+    type Error = ToolsError;
+    type Args = RecallArgs;
+    type Output = SearchResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "回忆相关记忆，同时返回 L0 摘要和 L2 完整内容".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "回忆查询"
+                        },
+                        "scope": {
+                            "type": "string",
+                            "description": "搜索范围 URI（默认 cortex://session）"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "最大结果数",
+                            "default": 10
+                        }
+                    },
+                    "required": ["query"]
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self
+            .operations
+            .recall(&args.query, args.scope.as_deref(), args.limit)
+            .await?)
+    }
+}
+
+/// Guidance Tool - Instructions and learned skills that apply to a task
+pub struct GuidanceTool {
+    operations: Arc<MemoryOperations>,
+}
+
+impl GuidanceTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for GuidanceTool {
+    const NAME: &'static str = "guidance";
+
+    type Error = ToolsError;
+    type Args = GuidanceArgs;
+    type Output = GuidanceResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "获取适用于当前任务的长期指令和从过往会话中学到的技能".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "task": {
+                            "type": "string",
+                            "description": "即将执行的任务"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "最多返回的技能数",
+                            "default": 5
+                        }
+                    },
+                    "required": ["task"]
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self.operations.guidance(args).await?)
+    }
+}
+
 // ==================== Filesystem Tools ====================
 
 /// Ls Tool - List directory contents
@@ -315,7 +430,7 @@ impl Tool for LsTool {
         async {
             ToolDefinition {
                 name: Self::NAME.to_string(),
-                description: "列出目录内容，浏览文件系统结构".to_string(),
+                description: "列出目录内容，浏览文件系统结构，支持过滤、排序和分页".to_string(),
                 parameters: json!({
                     "type": "object",
                     "properties": {
@@ -332,6 +447,29 @@ impl Tool for LsTool {
                             "type": "boolean",
                             "description": "是否包含文件的 L0 摘要",
                             "default": false
+                        },
+                        "pattern": {
+                            "type": "string",
+                            "description": "按名称过滤条目，支持通配符（如 \"*.md\"）或子串"
+                        },
+                        "sort": {
+                            "type": "string",
+                            "enum": ["name", "modified", "size"],
+                            "description": "排序字段",
+                            "default": "name"
+                        },
+                        "descending": {
+                            "type": "boolean",
+                            "description": "是否倒序（如最新优先）",
+                            "default": false
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "每页最多返回的条目数"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "上一页返回的 next_cursor"
                         }
                     },
                     "required": []
@@ -396,6 +534,29 @@ impl Tool for ExploreTool {
                             },
                             "description": "返回哪些层级",
                             "default": ["L0"]
+                        },
+                        "pattern": {
+                            "type": "string",
+                            "description": "按名称过滤匹配文件，支持通配符（如 \"*.md\"）或子串"
+                        },
+                        "sort": {
+                            "type": "string",
+                            "enum": ["name", "modified", "size"],
+                            "description": "排序字段",
+                            "default": "name"
+                        },
+                        "descending": {
+                            "type": "boolean",
+                            "description": "是否倒序（如最新优先）",
+                            "default": false
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "每页最多返回的匹配数"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "上一页返回的 next_cursor"
                         }
                     },
                     "required": ["query"]
@@ -450,10 +611,14 @@ impl Tool for StoreTool {
                         },
                         "scope": {
                             "type": "string",
-                            "description": "存储范围：'session'（会话，默认）、'user'（用户长期记忆）、'agent'（Agent 记忆）",
-                            "enum": ["session", "user", "agent"],
+                            "description": "存储范围：'session'（会话，默认）、'user'（用户长期记忆）、'agent'（Agent 记忆）、'space'（共享空间）",
+                            "enum": ["session", "user", "agent", "space"],
                             "default": "session"
                         },
+                        "space": {
+                            "type": "string",
+                            "description": "共享空间名称（scope 为 'space' 时必填，需要该空间的写权限）"
+                        },
                         "metadata": {
                             "type": "object",
                             "description": "元数据（标签、重要性等）"
@@ -474,3 +639,509 @@ impl Tool for StoreTool {
         Ok(self.operations.store(args).await?)
     }
 }
+
+/// Commit Tool - Close a session and run memory extraction
+pub struct CommitTool {
+    operations: Arc<MemoryOperations>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitArgs {
+    pub thread_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitResponse {
+    pub thread_id: String,
+    pub success: bool,
+}
+
+impl CommitTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for CommitTool {
+    const NAME: &'static str = "commit";
+
+    type Error = ToolsError;
+    type Args = CommitArgs;
+    type Output = CommitResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "提交会话：关闭会话并同步完成记忆提取、L0/L1 生成和向量同步"
+                    .to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "thread_id": {
+                            "type": "string",
+                            "description": "要提交的会话 ID（默认 \"default\"）"
+                        }
+                    }
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let thread_id = args.thread_id.unwrap_or_else(|| "default".to_string());
+        self.operations.close_session_sync(&thread_id).await?;
+        Ok(CommitResponse {
+            thread_id,
+            success: true,
+        })
+    }
+}
+
+// ==================== Memory Editing Tools ====================
+
+/// Remember Tool - Store a structured memory through the incremental updater
+pub struct RememberTool {
+    operations: Arc<MemoryOperations>,
+}
+
+impl RememberTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for RememberTool {
+    const NAME: &'static str = "remember";
+
+    type Error = ToolsError;
+    type Args = RememberArgs;
+    type Output = MemoryEditResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "记住一条结构化记忆（偏好、实体、事件、案例等）；相同类型和 key 的记忆会被更新而不是重复创建"
+                    .to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "memory_type": {
+                            "type": "string",
+                            "description": "记忆类型；case 存入 Agent 记忆，其余存入用户记忆",
+                            "enum": ["preference", "entity", "event", "case", "personal_info", "work_history", "relationship", "goal", "skill", "instruction"]
+                        },
+                        "key": {
+                            "type": "string",
+                            "description": "记忆在该类型下的标识（主题、名称、标题等）"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "要记住的内容（Markdown）"
+                        },
+                        "confidence": {
+                            "type": "number",
+                            "description": "置信度 0-1（默认 0.9）"
+                        }
+                    },
+                    "required": ["memory_type", "key", "content"]
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self.operations.remember(args).await?)
+    }
+}
+
+/// Update Memory Tool - Correct an existing memory
+pub struct UpdateMemoryTool {
+    operations: Arc<MemoryOperations>,
+}
+
+impl UpdateMemoryTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for UpdateMemoryTool {
+    const NAME: &'static str = "update_memory";
+
+    type Error = ToolsError;
+    type Args = UpdateMemoryArgs;
+    type Output = MemoryEditResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "修正一条已有记忆的内容，保留其类型和 key".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "uri": {
+                            "type": "string",
+                            "description": "记忆文件的 URI，如 cortex://user/{id}/preferences/pref_xxx.md"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "新的内容（Markdown）"
+                        },
+                        "confidence": {
+                            "type": "number",
+                            "description": "新的置信度 0-1（默认保持不变）"
+                        }
+                    },
+                    "required": ["uri", "content"]
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self.operations.update_memory(args).await?)
+    }
+}
+
+/// Forget Tool - Remove a memory and its index entry
+pub struct ForgetTool {
+    operations: Arc<MemoryOperations>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForgetArgs {
+    pub uri: String,
+}
+
+impl ForgetTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for ForgetTool {
+    const NAME: &'static str = "forget";
+
+    type Error = ToolsError;
+    type Args = ForgetArgs;
+    type Output = MemoryEditResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "忘记一条记忆：从记忆索引、文件系统和向量库中删除".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "uri": {
+                            "type": "string",
+                            "description": "要忘记的记忆文件 URI"
+                        }
+                    },
+                    "required": ["uri"]
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self.operations.forget(&args.uri).await?)
+    }
+}
+
+// ==================== Management Tools ====================
+
+/// Layers Tool - Generate missing L0/L1 layer files
+pub struct LayersTool {
+    operations: Arc<MemoryOperations>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LayersArgs {
+    pub thread_id: Option<String>,
+}
+
+impl LayersTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for LayersTool {
+    const NAME: &'static str = "layers";
+
+    type Error = ToolsError;
+    type Args = LayersArgs;
+    type Output = GenerationStats;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "为缺少 L0/L1 层级文件的目录生成 .abstract.md 和 .overview.md"
+                    .to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "thread_id": {
+                            "type": "string",
+                            "description": "会话 ID（可选，不提供时为所有会话生成）"
+                        }
+                    }
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        match args.thread_id {
+            Some(thread_id) => Ok(self.operations.ensure_session_layers(&thread_id).await?),
+            None => Ok(self.operations.ensure_all_layers().await?),
+        }
+    }
+}
+
+/// Index Tool - Sync memory files to the vector database
+pub struct IndexTool {
+    operations: Arc<MemoryOperations>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexArgs {
+    pub thread_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexResponse {
+    pub total_files: usize,
+    pub indexed_files: usize,
+    pub skipped_files: usize,
+    pub error_files: usize,
+}
+
+impl IndexTool {
+    pub fn new(operations: Arc<MemoryOperations>) -> Self {
+        Self { operations }
+    }
+}
+
+impl Tool for IndexTool {
+    const NAME: &'static str = "index";
+
+    type Error = ToolsError;
+    type Args = IndexArgs;
+    type Output = IndexResponse;
+
+    fn definition(
+        &self,
+        _prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        async {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "将记忆文件同步到向量数据库，用于语义搜索".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "thread_id": {
+                            "type": "string",
+                            "description": "会话 ID（可选，不提供时索引所有文件）"
+                        }
+                    }
+                }),
+            }
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let stats = match args.thread_id {
+            Some(thread_id) => self.operations.index_session_files(&thread_id).await?,
+            None => self.operations.index_all_files().await?,
+        };
+        Ok(IndexResponse {
+            total_files: stats.total_files,
+            indexed_files: stats.indexed_files,
+            skipped_files: stats.skipped_files,
+            error_files: stats.error_files,
+        })
+    }
+}
+
+// ==================== Configured Tool ====================
+
+/// Wraps a tool to replace its description (see `ToolPolicy`)
+pub struct ConfiguredTool<T: Tool> {
+    inner: T,
+    description: Option<String>,
+}
+
+impl<T: Tool> ConfiguredTool<T> {
+    pub fn new(inner: T, description: Option<String>) -> Self {
+        Self { inner, description }
+    }
+}
+
+impl<T: Tool> Tool for ConfiguredTool<T> {
+    const NAME: &'static str = T::NAME;
+
+    type Error = T::Error;
+    type Args = T::Args;
+    type Output = T::Output;
+
+    fn definition(
+        &self,
+        prompt: String,
+    ) -> impl std::future::Future<Output = ToolDefinition> + Send + Sync {
+        let description = self.description.clone();
+        let definition = self.inner.definition(prompt);
+        async move {
+            let mut definition = definition.await;
+            if let Some(description) = description {
+                definition.description = description;
+            }
+            definition
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        self.inner.call(args).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use cortex_mem_core::llm::MockLLMClient;
+
+    /// Operations on a temp dir; Qdrant and the embedding API point at a
+    /// closed port, so only filesystem-backed calls succeed
+    pub(crate) async fn operations(data_dir: &std::path::Path) -> Arc<MemoryOperations> {
+        let qdrant = cortex_mem_config::QdrantConfig {
+            url: "http://127.0.0.1:1".to_string(),
+            collection_name: "cortex-mem-test".to_string(),
+            embedding_dim: None,
+            timeout_secs: 1,
+            api_key: None,
+        };
+        let embedding = cortex_mem_config::EmbeddingConfig {
+            api_base_url: "http://127.0.0.1:1".to_string(),
+            api_key: String::new(),
+            model_name: "test-embedding".to_string(),
+            ..Default::default()
+        };
+        let operations = MemoryOperations::builder(
+            data_dir.to_string_lossy(),
+            "test",
+            Arc::new(MockLLMClient::new()),
+            &qdrant,
+            &embedding,
+        )
+        .with_intent_analysis(false)
+        .build()
+        .await
+        .unwrap();
+        Arc::new(operations)
+    }
+
+    #[test]
+    fn test_recall_args() {
+        let args: RecallArgs = serde_json::from_value(json!({ "query": "editor" })).unwrap();
+        assert_eq!(args.query, "editor");
+        assert_eq!(args.scope, None);
+        assert_eq!(args.limit, None);
+
+        let args: RecallArgs = serde_json::from_value(json!({
+            "query": "editor",
+            "scope": "cortex://user",
+            "limit": 3
+        }))
+        .unwrap();
+        assert_eq!(args.scope.as_deref(), Some("cortex://user"));
+        assert_eq!(args.limit, Some(3));
+
+        assert!(serde_json::from_value::<RecallArgs>(json!({ "limit": 3 })).is_err());
+        assert!(
+            serde_json::from_value::<RecallArgs>(json!({ "query": "x", "limit": "3" })).is_err()
+        );
+    }
+
+    #[test]
+    fn test_optional_thread_args() {
+        let commit: CommitArgs = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(commit.thread_id, None);
+        let layers: LayersArgs = serde_json::from_value(json!({ "thread_id": "t1" })).unwrap();
+        assert_eq!(layers.thread_id.as_deref(), Some("t1"));
+        let index: IndexArgs = serde_json::from_value(json!({ "thread_id": null })).unwrap();
+        assert_eq!(index.thread_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_tool_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = operations(dir.path()).await;
+
+        let recall = RecallTool::new(operations.clone())
+            .definition(String::new())
+            .await;
+        assert_eq!(recall.name, "recall");
+        assert_eq!(recall.parameters["required"], json!(["query"]));
+        assert_eq!(recall.parameters["properties"]["limit"]["default"], 10);
+
+        for definition in [
+            CommitTool::new(operations.clone())
+                .definition(String::new())
+                .await,
+            LayersTool::new(operations.clone())
+                .definition(String::new())
+                .await,
+            IndexTool::new(operations.clone())
+                .definition(String::new())
+                .await,
+        ] {
+            // The thread is optional: no required parameters
+            assert!(definition.parameters.get("required").is_none());
+            assert_eq!(
+                definition.parameters["properties"]["thread_id"]["type"],
+                "string"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_configured_tool_description() {
+        let dir = tempfile::tempdir().unwrap();
+        let operations = operations(dir.path()).await;
+
+        let tool = ConfiguredTool::new(
+            CommitTool::new(operations.clone()),
+            Some("Save the conversation".to_string()),
+        );
+        let definition = tool.definition(String::new()).await;
+        assert_eq!(definition.name, "commit");
+        assert_eq!(definition.description, "Save the conversation");
+
+        let plain = ConfiguredTool::new(CommitTool::new(operations), None)
+            .definition(String::new())
+            .await;
+        assert!(plain.description.starts_with("提交会话"));
+    }
+}
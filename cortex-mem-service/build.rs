@@ -0,0 +1,12 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc so building does not require a system install
+    let mut config = prost_build::Config::new();
+    config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+
+    tonic_build::configure()
+        .build_client(true)
+        .compile_protos_with_config(config, &["proto/cortex_mem.proto"], &["proto"])?;
+
+    println!("cargo:rerun-if-changed=proto/cortex_mem.proto");
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Identity of REST and gRPC callers for shared space access control
+//!
+//! Requests name the agent and user they act as with the `x-cortex-agent` and
+//! `x-cortex-user` headers (gRPC metadata), like cortex-mem-mcp connections.
+//! When `[access]` is enabled, `AppState::access_guard` binds the policy to
+//! them and the filesystem, layer, search and memory endpoints enforce it.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{HeaderMap, request::Parts},
+};
+use std::convert::Infallible;
+use tonic::metadata::MetadataMap;
+
+/// Header naming the agent a request acts as
+pub const AGENT_HEADER: &str = "x-cortex-agent";
+/// Header naming the user a request acts as
+pub const USER_HEADER: &str = "x-cortex-user";
+
+/// Agent and user a request acts as; missing IDs fall back to `default`
+#[derive(Debug, Clone, Default)]
+pub struct Caller {
+    pub agent: Option<String>,
+    pub user: Option<String>,
+}
+
+impl Caller {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self::from_lookup(|name| headers.get(name).and_then(|v| v.to_str().ok()))
+    }
+
+    pub fn from_metadata(metadata: &MetadataMap) -> Self {
+        Self::from_lookup(|name| metadata.get(name).and_then(|v| v.to_str().ok()))
+    }
+
+    fn from_lookup<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let value = |name: &str| {
+            get(name)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            agent: value(AGENT_HEADER),
+            user: value(USER_HEADER),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Caller {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caller_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AGENT_HEADER, " support-agent ".parse().unwrap());
+        headers.insert(USER_HEADER, "".parse().unwrap());
+
+        let caller = Caller::from_headers(&headers);
+        assert_eq!(caller.agent.as_deref(), Some("support-agent"));
+        assert_eq!(caller.user, None);
+    }
+}
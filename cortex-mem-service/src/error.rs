@@ -1,9 +1,10 @@
 use axum::{
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+
+use crate::models::ErrorResponse;
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
@@ -13,6 +14,7 @@ pub enum AppError {
     Internal(String),
     NotFound(String),
     BadRequest(String),
+    TooManyRequests { message: String, retry_after_secs: u64 },
     Core(cortex_mem_core::Error),
 }
 
@@ -36,23 +38,38 @@ impl From<serde_json::Error> for AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let mut retry_after = None;
         let (status, error_message) = match self {
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::TooManyRequests {
+                message,
+                retry_after_secs,
+            } => {
+                retry_after = Some(retry_after_secs);
+                (StatusCode::TOO_MANY_REQUESTS, message)
+            }
             AppError::Core(err) => match err {
                 cortex_mem_core::Error::NotFound { uri } => {
                     (StatusCode::NOT_FOUND, format!("Not found: {}", uri))
                 }
+                cortex_mem_core::Error::PermissionDenied(msg) => (StatusCode::FORBIDDEN, msg),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
             },
         };
 
-        let body = Json(json!({
-            "error": error_message,
-            "status": status.as_u16(),
-        }));
+        let body = Json(ErrorResponse {
+            error: error_message,
+            status: status.as_u16(),
+        });
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
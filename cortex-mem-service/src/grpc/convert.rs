@@ -0,0 +1,292 @@
+//! Conversions between protobuf messages and the REST models.
+
+use chrono::{DateTime, Utc};
+use cortex_mem_core::memory_events::MemoryEvent;
+use tonic::Status;
+
+use super::proto;
+use crate::models;
+
+pub fn timestamp(dt: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: dt.timestamp(),
+        nanos: dt.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub fn datetime(ts: prost_types::Timestamp) -> Result<DateTime<Utc>, Status> {
+    DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("timestamp out of range"))
+}
+
+pub fn metadata(json: Option<String>) -> Result<Option<serde_json::Value>, Status> {
+    json.map(|s| {
+        serde_json::from_str(&s)
+            .map_err(|e| Status::invalid_argument(format!("metadata_json is not valid JSON: {}", e)))
+    })
+    .transpose()
+}
+
+fn or_default_layers(layers: Vec<String>) -> Vec<String> {
+    if layers.is_empty() {
+        vec!["L0".to_string()]
+    } else {
+        layers
+    }
+}
+
+fn or_default_uri(uri: String) -> String {
+    if uri.is_empty() {
+        "cortex://session".to_string()
+    } else {
+        uri
+    }
+}
+
+// ==================== Sessions ====================
+
+impl From<models::SessionResponse> for proto::Session {
+    fn from(s: models::SessionResponse) -> Self {
+        Self {
+            thread_id: s.thread_id,
+            status: s.status,
+            message_count: s.message_count as u64,
+            created_at: Some(timestamp(s.created_at)),
+            updated_at: Some(timestamp(s.updated_at)),
+        }
+    }
+}
+
+impl From<proto::CreateSessionRequest> for models::CreateSessionRequest {
+    fn from(r: proto::CreateSessionRequest) -> Self {
+        Self {
+            thread_id: r.thread_id,
+            title: r.title,
+            user_id: r.user_id,
+            agent_id: r.agent_id,
+        }
+    }
+}
+
+impl TryFrom<proto::BatchMessage> for models::BatchMessageRequest {
+    type Error = Status;
+
+    fn try_from(m: proto::BatchMessage) -> Result<Self, Status> {
+        Ok(Self {
+            thread_id: m.thread_id,
+            role: m.role,
+            content: m.content,
+            timestamp: m.timestamp.map(datetime).transpose()?,
+            metadata: metadata(m.metadata_json)?,
+        })
+    }
+}
+
+impl From<models::BatchAddMessagesResponse> for proto::BatchAddMessagesResponse {
+    fn from(r: models::BatchAddMessagesResponse) -> Self {
+        Self {
+            total_messages: r.total_messages as u64,
+            threads: r
+                .threads
+                .into_iter()
+                .map(|t| proto::BatchThreadResult {
+                    thread_id: t.thread_id,
+                    message_count: t.message_count as u64,
+                    uris: t.uris,
+                })
+                .collect(),
+            extraction_triggered: r.extraction_triggered,
+        }
+    }
+}
+
+impl From<models::CloseAndWaitResponse> for proto::CloseSessionAndWaitResponse {
+    fn from(r: models::CloseAndWaitResponse) -> Self {
+        Self {
+            thread_id: r.thread_id,
+            status: r.status,
+            user_id: r.user_id,
+            agent_id: r.agent_id,
+            waited_ms: r.waited_ms,
+            user_index_exists: r.user_index_exists,
+            user_memory_count: r.user_memory_count as u64,
+            session_summary_exists: r.session_summary_exists,
+            session_summary_memory_count: r.session_summary_memory_count as u64,
+            vector_sync_confirmed: r.vector_sync_confirmed,
+            timeline_abstract_exists: r.timeline_abstract_exists,
+            timeline_overview_exists: r.timeline_overview_exists,
+        }
+    }
+}
+
+// ==================== Memories ====================
+
+impl From<proto::RememberRequest> for models::RememberRequest {
+    fn from(r: proto::RememberRequest) -> Self {
+        Self {
+            memory_type: r.memory_type,
+            key: r.key,
+            content: r.content,
+            confidence: r.confidence,
+            user_id: r.user_id,
+            agent_id: r.agent_id,
+        }
+    }
+}
+
+impl From<proto::UpdateMemoryRequest> for models::UpdateMemoryRequest {
+    fn from(r: proto::UpdateMemoryRequest) -> Self {
+        Self {
+            uri: r.uri,
+            content: r.content,
+            confidence: r.confidence,
+        }
+    }
+}
+
+impl From<models::MemoryEditResponse> for proto::MemoryEditResponse {
+    fn from(r: models::MemoryEditResponse) -> Self {
+        Self {
+            uri: r.uri,
+            memory_id: r.memory_id,
+            memory_type: r.memory_type,
+            key: r.key,
+            action: r.action,
+        }
+    }
+}
+
+// ==================== Search ====================
+
+impl From<proto::SearchRequest> for models::SearchRequest {
+    fn from(r: proto::SearchRequest) -> Self {
+        Self {
+            query: r.query,
+            thread: r.thread,
+            limit: r.limit.map(|l| l as usize),
+            min_score: r.min_score,
+            return_layers: or_default_layers(r.return_layers),
+        }
+    }
+}
+
+impl From<models::SearchResultResponse> for proto::SearchResult {
+    fn from(r: models::SearchResultResponse) -> Self {
+        Self {
+            uri: r.uri,
+            score: r.score,
+            snippet: r.snippet,
+            overview: r.overview,
+            content: r.content,
+            source: r.source,
+            layers: r.layers,
+        }
+    }
+}
+
+// ==================== Filesystem ====================
+
+impl From<proto::ListRequest> for models::LsRequest {
+    fn from(r: proto::ListRequest) -> Self {
+        Self {
+            uri: or_default_uri(r.uri),
+            recursive: r.recursive,
+            include_abstracts: r.include_abstracts,
+            include_layers: r.include_layers,
+            pattern: r.pattern,
+            sort: r.sort,
+            descending: r.descending,
+            limit: r.limit.map(|l| l as usize),
+            cursor: r.cursor,
+        }
+    }
+}
+
+impl From<models::LsResponse> for proto::ListResponse {
+    fn from(r: models::LsResponse) -> Self {
+        Self {
+            uri: r.uri,
+            total: r.total as u64,
+            entries: r
+                .entries
+                .into_iter()
+                .map(|e| proto::FileEntry {
+                    uri: e.uri,
+                    name: e.name,
+                    is_directory: e.is_directory,
+                    size: e.size,
+                    modified: Some(timestamp(e.modified)),
+                    abstract_text: e.abstract_text,
+                })
+                .collect(),
+            next_cursor: r.next_cursor,
+        }
+    }
+}
+
+impl From<proto::ExploreRequest> for models::ExploreRequest {
+    fn from(r: proto::ExploreRequest) -> Self {
+        Self {
+            query: r.query,
+            start_uri: or_default_uri(r.start_uri),
+            return_layers: or_default_layers(r.return_layers),
+        }
+    }
+}
+
+impl From<models::ExploreResponse> for proto::ExploreResponse {
+    fn from(r: models::ExploreResponse) -> Self {
+        Self {
+            query: r.query,
+            exploration_path: r
+                .exploration_path
+                .into_iter()
+                .map(|p| proto::ExplorationPathItem {
+                    uri: p.uri,
+                    relevance_score: p.relevance_score,
+                    abstract_text: p.abstract_text,
+                })
+                .collect(),
+            matches: r.matches.into_iter().map(Into::into).collect(),
+            total_explored: r.total_explored as u64,
+            total_matches: r.total_matches as u64,
+        }
+    }
+}
+
+impl From<models::LayerResponse> for proto::LayerResponse {
+    fn from(r: models::LayerResponse) -> Self {
+        Self {
+            uri: r.uri,
+            content: r.content,
+            layer: r.layer,
+            token_count: r.token_count as u64,
+        }
+    }
+}
+
+// ==================== Automation ====================
+
+impl From<models::AutomationResponse> for proto::AutomationResponse {
+    fn from(r: models::AutomationResponse) -> Self {
+        Self {
+            thread_id: r.thread_id,
+            status: r.status,
+            message: r.message,
+        }
+    }
+}
+
+// ==================== Events ====================
+
+pub fn memory_event(event: &MemoryEvent, skipped: u64) -> proto::MemoryEvent {
+    proto::MemoryEvent {
+        kind: event.kind().to_string(),
+        scope: event.scope().map(|s| s.to_string()),
+        owner_id: event.owner_id().map(str::to_string),
+        uri: event.uri().map(str::to_string),
+        description: event.to_string(),
+        observed_at: Some(timestamp(Utc::now())),
+        skipped,
+    }
+}
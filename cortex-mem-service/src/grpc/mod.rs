@@ -0,0 +1,131 @@
+//! gRPC API served alongside the REST API on a separate port.
+//!
+//! The services are defined in `proto/cortex_mem.proto` and mirror the REST
+//! routes under `/api/v2`. Unary RPCs call the REST handlers directly, so both
+//! transports share validation, tenant resolution and rate limits; this module
+//! only converts between protobuf messages and the JSON models.
+//!
+//! Streaming RPCs:
+//! - `SessionService.StreamMessages`: client-streaming bulk ingestion, written
+//!   through the batch import handler in chunks of [`STREAM_CHUNK_SIZE`].
+//!   Every chunk is charged against the write limit; when it is exceeded the
+//!   stream fails and the chunks written before stay written.
+//! - `EventService.Subscribe`: server stream of the memory events handled by
+//!   the current tenant's event coordinator.
+
+mod convert;
+mod service;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tonic::{Status, metadata::{MetadataMap, MetadataValue}};
+
+use crate::{
+    error::AppError,
+    models::ApiResponse,
+    rate_limit::{API_KEY_HEADER, Category},
+    state::AppState,
+};
+
+pub mod proto {
+    tonic::include_proto!("cortex.mem.v2");
+}
+
+/// Messages buffered by `StreamMessages` before they are written as one batch
+pub const STREAM_CHUNK_SIZE: usize = 500;
+
+/// Serve all gRPC services until the process exits
+pub async fn serve(state: Arc<AppState>, addr: SocketAddr) -> anyhow::Result<()> {
+    use proto::{
+        automation_service_server::AutomationServiceServer, event_service_server::EventServiceServer,
+        filesystem_service_server::FilesystemServiceServer, layer_service_server::LayerServiceServer,
+        memory_service_server::MemoryServiceServer, search_service_server::SearchServiceServer,
+        session_service_server::SessionServiceServer,
+        tenant_service_server::TenantServiceServer,
+    };
+
+    let api = service::GrpcApi::new(state);
+
+    tracing::info!("gRPC server listening on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(SessionServiceServer::new(api.clone()))
+        .add_service(MemoryServiceServer::new(api.clone()))
+        .add_service(SearchServiceServer::new(api.clone()))
+        .add_service(FilesystemServiceServer::new(api.clone()))
+        .add_service(LayerServiceServer::new(api.clone()))
+        .add_service(AutomationServiceServer::new(api.clone()))
+        .add_service(TenantServiceServer::new(api.clone()))
+        .add_service(EventServiceServer::new(api))
+        .serve(addr)
+        .await?;
+
+    Ok(())
+}
+
+impl From<AppError> for Status {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::Internal(msg) => Status::internal(msg),
+            AppError::NotFound(msg) => Status::not_found(msg),
+            AppError::BadRequest(msg) => Status::invalid_argument(msg),
+            AppError::TooManyRequests {
+                message,
+                retry_after_secs,
+            } => {
+                let mut status = Status::resource_exhausted(message);
+                status
+                    .metadata_mut()
+                    .insert("retry-after", MetadataValue::from(retry_after_secs));
+                status
+            }
+            AppError::Core(err) => match err {
+                cortex_mem_core::Error::NotFound { uri } => {
+                    Status::not_found(format!("Not found: {}", uri))
+                }
+                cortex_mem_core::Error::PermissionDenied(msg) => Status::permission_denied(msg),
+                _ => Status::internal(err.to_string()),
+            },
+        }
+    }
+}
+
+/// Unwrap the data of a REST handler response
+fn into_data<T>(result: crate::error::Result<axum::Json<ApiResponse<T>>>) -> Result<T, Status> {
+    let axum::Json(response) = result?;
+    response
+        .data
+        .ok_or_else(|| Status::internal(response.error.unwrap_or_else(|| "empty response".to_string())))
+}
+
+/// Apply the same per-tenant / per-key limits as the REST middleware
+///
+/// The API key is read from the `x-api-key` request metadata. Takes the
+/// metadata rather than the request: streaming request bodies are not `Sync`.
+async fn enforce_limit(
+    state: &AppState,
+    metadata: &MetadataMap,
+    category: Category,
+) -> Result<(), Status> {
+    let tenant = state
+        .current_tenant_id
+        .read()
+        .await
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let api_key = metadata
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    state
+        .rate_limiter
+        .check(&tenant, api_key, category)
+        .map_err(|limited| {
+            tracing::warn!("🚦 {} (retry after {}s)", limited.message, limited.retry_after_secs);
+            AppError::TooManyRequests {
+                message: limited.message,
+                retry_after_secs: limited.retry_after_secs,
+            }
+            .into()
+        })
+}
@@ -0,0 +1,471 @@
+//! Service implementations: thin adapters over the REST handlers.
+
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use futures::Stream;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status, Streaming, metadata::MetadataMap};
+
+use super::{STREAM_CHUNK_SIZE, convert, enforce_limit, into_data, proto};
+use crate::{access::Caller, handlers, models, rate_limit::Category, state::AppState};
+
+type RpcResult<T> = Result<Response<T>, Status>;
+
+#[derive(Clone)]
+pub struct GrpcApi {
+    state: Arc<AppState>,
+}
+
+impl GrpcApi {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    fn state(&self) -> State<Arc<AppState>> {
+        State(self.state.clone())
+    }
+
+    /// Write one chunk of streamed messages and merge it into the running summary
+    ///
+    /// Each chunk is charged as one write, like a REST batch request of the
+    /// same size, so a single stream cannot ingest past the write quota.
+    async fn flush_chunk(
+        &self,
+        metadata: &MetadataMap,
+        chunk: &mut Vec<models::BatchMessageRequest>,
+        summary: &mut models::BatchAddMessagesResponse,
+    ) -> Result<(), Status> {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        enforce_limit(&self.state, metadata, Category::Write).await?;
+
+        let request = models::BatchAddMessagesRequest {
+            messages: std::mem::take(chunk),
+            trigger_extraction: false,
+        };
+        let result = into_data(
+            handlers::sessions::batch_add_messages(self.state(), Json(request)).await,
+        )?;
+
+        summary.total_messages += result.total_messages;
+        for thread in result.threads {
+            match summary
+                .threads
+                .iter_mut()
+                .find(|t| t.thread_id == thread.thread_id)
+            {
+                Some(existing) => {
+                    existing.message_count += thread.message_count;
+                    existing.uris.extend(thread.uris);
+                }
+                None => summary.threads.push(thread),
+            }
+        }
+        Ok(())
+    }
+}
+
+// ==================== Sessions ====================
+
+#[tonic::async_trait]
+impl proto::session_service_server::SessionService for GrpcApi {
+    async fn list_sessions(
+        &self,
+        _request: Request<proto::ListSessionsRequest>,
+    ) -> RpcResult<proto::ListSessionsResponse> {
+        let sessions = into_data(handlers::sessions::list_sessions(self.state()).await)?;
+        Ok(Response::new(proto::ListSessionsResponse {
+            sessions: sessions.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn create_session(
+        &self,
+        request: Request<proto::CreateSessionRequest>,
+    ) -> RpcResult<proto::Session> {
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let payload = models::CreateSessionRequest::from(request.into_inner());
+        let session =
+            into_data(handlers::sessions::create_session(self.state(), Json(payload)).await)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn add_message(
+        &self,
+        request: Request<proto::AddMessageRequest>,
+    ) -> RpcResult<proto::AddMessageResponse> {
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let req = request.into_inner();
+        let payload = models::AddMessageRequest {
+            role: req.role,
+            content: req.content,
+            metadata: convert::metadata(req.metadata_json)?,
+        };
+        let message = into_data(
+            handlers::sessions::add_message(self.state(), Path(req.thread_id), Json(payload))
+                .await,
+        )?;
+        Ok(Response::new(proto::AddMessageResponse { message }))
+    }
+
+    async fn batch_add_messages(
+        &self,
+        request: Request<proto::BatchAddMessagesRequest>,
+    ) -> RpcResult<proto::BatchAddMessagesResponse> {
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let req = request.into_inner();
+        let payload = models::BatchAddMessagesRequest {
+            messages: req
+                .messages
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            trigger_extraction: req.trigger_extraction,
+        };
+        let result = into_data(
+            handlers::sessions::batch_add_messages(self.state(), Json(payload)).await,
+        )?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn stream_messages(
+        &self,
+        request: Request<Streaming<proto::BatchMessage>>,
+    ) -> RpcResult<proto::BatchAddMessagesResponse> {
+        let metadata = request.metadata().clone();
+        let mut stream = request.into_inner();
+
+        let mut summary = models::BatchAddMessagesResponse {
+            total_messages: 0,
+            threads: Vec::new(),
+            extraction_triggered: Vec::new(),
+        };
+        let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE);
+
+        while let Some(message) = stream.message().await? {
+            chunk.push(message.try_into()?);
+            if chunk.len() >= STREAM_CHUNK_SIZE {
+                self.flush_chunk(&metadata, &mut chunk, &mut summary).await?;
+            }
+        }
+        self.flush_chunk(&metadata, &mut chunk, &mut summary).await?;
+
+        tracing::info!(
+            "📥 Streamed {} messages into {} sessions",
+            summary.total_messages,
+            summary.threads.len()
+        );
+        Ok(Response::new(summary.into()))
+    }
+
+    async fn close_session(
+        &self,
+        request: Request<proto::CloseSessionRequest>,
+    ) -> RpcResult<proto::Session> {
+        enforce_limit(&self.state, request.metadata(), Category::Extraction).await?;
+        let thread_id = request.into_inner().thread_id;
+        let session =
+            into_data(handlers::sessions::close_session(self.state(), Path(thread_id)).await)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn close_session_and_wait(
+        &self,
+        request: Request<proto::CloseSessionAndWaitRequest>,
+    ) -> RpcResult<proto::CloseSessionAndWaitResponse> {
+        enforce_limit(&self.state, request.metadata(), Category::Extraction).await?;
+        let req = request.into_inner();
+        let defaults = models::CloseAndWaitRequest::default();
+        let payload = models::CloseAndWaitRequest {
+            timeout_secs: req.timeout_secs.unwrap_or(defaults.timeout_secs),
+            poll_interval_ms: req.poll_interval_ms.unwrap_or(defaults.poll_interval_ms),
+        };
+        let status = into_data(
+            handlers::sessions::close_session_and_wait(
+                self.state(),
+                Path(req.thread_id),
+                Some(Json(payload)),
+            )
+            .await,
+        )?;
+        Ok(Response::new(status.into()))
+    }
+}
+
+// ==================== Memories ====================
+
+#[tonic::async_trait]
+impl proto::memory_service_server::MemoryService for GrpcApi {
+    async fn remember(
+        &self,
+        request: Request<proto::RememberRequest>,
+    ) -> RpcResult<proto::MemoryEditResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let payload = models::RememberRequest::from(request.into_inner());
+        let result =
+            into_data(handlers::memories::remember(self.state(), caller, Json(payload)).await)?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn update_memory(
+        &self,
+        request: Request<proto::UpdateMemoryRequest>,
+    ) -> RpcResult<proto::MemoryEditResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let payload = models::UpdateMemoryRequest::from(request.into_inner());
+        let result = into_data(
+            handlers::memories::update_memory(self.state(), caller, Json(payload)).await,
+        )?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn forget(
+        &self,
+        request: Request<proto::ForgetRequest>,
+    ) -> RpcResult<proto::MemoryEditResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let query = Query(models::MemoryQuery {
+            uri: request.into_inner().uri,
+        });
+        let result = into_data(handlers::memories::forget(self.state(), caller, query).await)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+// ==================== Search ====================
+
+#[tonic::async_trait]
+impl proto::search_service_server::SearchService for GrpcApi {
+    async fn search(
+        &self,
+        request: Request<proto::SearchRequest>,
+    ) -> RpcResult<proto::SearchResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        enforce_limit(&self.state, request.metadata(), Category::Search).await?;
+        let payload = models::SearchRequest::from(request.into_inner());
+        let results =
+            into_data(handlers::search::search(self.state(), caller, Json(payload)).await)?;
+        Ok(Response::new(proto::SearchResponse {
+            results: results.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+// ==================== Filesystem ====================
+
+#[tonic::async_trait]
+impl proto::filesystem_service_server::FilesystemService for GrpcApi {
+    async fn list(&self, request: Request<proto::ListRequest>) -> RpcResult<proto::ListResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let params = models::LsRequest::from(request.into_inner());
+        let listing = into_data(
+            handlers::filesystem::list_directory(self.state(), caller, Query(params)).await,
+        )?;
+        Ok(Response::new(listing.into()))
+    }
+
+    async fn read(&self, request: Request<proto::ReadRequest>) -> RpcResult<proto::ReadResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let uri = request.into_inner().uri;
+        let content =
+            into_data(handlers::filesystem::read_file(self.state(), caller, Path(uri)).await)?;
+        Ok(Response::new(proto::ReadResponse { content }))
+    }
+
+    async fn write(
+        &self,
+        request: Request<proto::WriteRequest>,
+    ) -> RpcResult<proto::WriteResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        enforce_limit(&self.state, request.metadata(), Category::Write).await?;
+        let req = request.into_inner();
+        let payload = models::WriteFileRequest {
+            path: req.uri,
+            content: req.content,
+        };
+        let path =
+            into_data(handlers::filesystem::write_file(self.state(), caller, Json(payload)).await)?;
+        Ok(Response::new(proto::WriteResponse { path }))
+    }
+
+    async fn stats(
+        &self,
+        request: Request<proto::StatsRequest>,
+    ) -> RpcResult<proto::StatsResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let params = models::StatsQuery {
+            uri: request.into_inner().uri,
+        };
+        let stats = into_data(
+            handlers::filesystem::get_directory_stats(self.state(), caller, Query(params)).await,
+        )?;
+        Ok(Response::new(proto::StatsResponse {
+            file_count: stats.file_count,
+            total_size: stats.total_size,
+        }))
+    }
+
+    async fn explore(
+        &self,
+        request: Request<proto::ExploreRequest>,
+    ) -> RpcResult<proto::ExploreResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let payload = models::ExploreRequest::from(request.into_inner());
+        let result =
+            into_data(handlers::filesystem::explore(self.state(), caller, Json(payload)).await)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+// ==================== Layers ====================
+
+#[tonic::async_trait]
+impl proto::layer_service_server::LayerService for GrpcApi {
+    async fn get_abstract(
+        &self,
+        request: Request<proto::LayerRequest>,
+    ) -> RpcResult<proto::LayerResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let query = Query(models::LayerQuery {
+            uri: request.into_inner().uri,
+        });
+        let layer =
+            into_data(handlers::filesystem::get_abstract(self.state(), caller, query).await)?;
+        Ok(Response::new(layer.into()))
+    }
+
+    async fn get_overview(
+        &self,
+        request: Request<proto::LayerRequest>,
+    ) -> RpcResult<proto::LayerResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let query = Query(models::LayerQuery {
+            uri: request.into_inner().uri,
+        });
+        let layer =
+            into_data(handlers::filesystem::get_overview(self.state(), caller, query).await)?;
+        Ok(Response::new(layer.into()))
+    }
+
+    async fn get_content(
+        &self,
+        request: Request<proto::LayerRequest>,
+    ) -> RpcResult<proto::LayerResponse> {
+        let caller = Caller::from_metadata(request.metadata());
+        let query = Query(models::LayerQuery {
+            uri: request.into_inner().uri,
+        });
+        let layer =
+            into_data(handlers::filesystem::get_content(self.state(), caller, query).await)?;
+        Ok(Response::new(layer.into()))
+    }
+}
+
+// ==================== Automation ====================
+
+#[tonic::async_trait]
+impl proto::automation_service_server::AutomationService for GrpcApi {
+    async fn trigger_extraction(
+        &self,
+        request: Request<proto::TriggerExtractionRequest>,
+    ) -> RpcResult<proto::AutomationResponse> {
+        enforce_limit(&self.state, request.metadata(), Category::Extraction).await?;
+        let thread_id = request.into_inner().thread_id;
+        let result = into_data(
+            handlers::automation::trigger_extraction(self.state(), Path(thread_id)).await,
+        )?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn trigger_reindex(
+        &self,
+        request: Request<proto::TriggerReindexRequest>,
+    ) -> RpcResult<proto::AutomationResponse> {
+        enforce_limit(&self.state, request.metadata(), Category::Extraction).await?;
+        let result = into_data(handlers::automation::trigger_reindex(self.state()).await)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+// ==================== Tenants ====================
+
+#[tonic::async_trait]
+impl proto::tenant_service_server::TenantService for GrpcApi {
+    async fn list_tenants(
+        &self,
+        _request: Request<proto::ListTenantsRequest>,
+    ) -> RpcResult<proto::ListTenantsResponse> {
+        let tenant_ids = into_data(handlers::tenants::list_tenants(self.state()).await)?;
+        Ok(Response::new(proto::ListTenantsResponse { tenant_ids }))
+    }
+
+    async fn switch_tenant(
+        &self,
+        request: Request<proto::SwitchTenantRequest>,
+    ) -> RpcResult<proto::SwitchTenantResponse> {
+        let payload = models::TenantSwitchRequest {
+            tenant_id: request.into_inner().tenant_id,
+        };
+        let tenant_id =
+            into_data(handlers::tenants::switch_tenant(self.state(), Json(payload)).await)?;
+        Ok(Response::new(proto::SwitchTenantResponse { tenant_id }))
+    }
+}
+
+// ==================== Events ====================
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::MemoryEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl proto::event_service_server::EventService for GrpcApi {
+    type SubscribeStream = EventStream;
+
+    async fn subscribe(
+        &self,
+        request: Request<proto::SubscribeRequest>,
+    ) -> RpcResult<Self::SubscribeStream> {
+        let kinds: Arc<HashSet<String>> = Arc::new(request.into_inner().kinds.into_iter().collect());
+
+        let cortex = self.state.cortex.read().await.clone();
+        let rx = cortex.subscribe_memory_events().ok_or_else(|| {
+            Status::unavailable(
+                "memory event coordinator is not running (requires LLM, embedding and Qdrant)",
+            )
+        })?;
+
+        // `skipped` accumulates events dropped because this subscriber lagged behind
+        let stream = futures::stream::unfold((rx, 0u64), move |(mut rx, mut skipped)| {
+            let kinds = kinds.clone();
+            async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(event) => {
+                            if !kinds.is_empty() && !kinds.contains(event.kind()) {
+                                continue;
+                            }
+                            let message = convert::memory_event(&event, skipped);
+                            return Some((Ok(message), (rx, 0)));
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            tracing::warn!("Event subscriber lagged, skipped {} events", n);
+                            skipped += n;
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use crate::{
     error::{AppError, Result},
-    models::ApiResponse,
+    models::{ApiResponse, AutomationResponse, ErrorResponse},
     state::AppState,
 };
 
@@ -20,10 +20,21 @@ use crate::{
 /// reference directly (it uses `CortexMem` which wires up the coordinator internally).
 /// For now, this endpoint delegates to `SessionManager::close_session` which sends a
 /// `SessionClosed` event that the coordinator handles asynchronously.
+#[utoipa::path(
+    post,
+    path = "/api/v2/automation/extract/{thread_id}",
+    tag = "automation",
+    params(("thread_id" = String, Path, description = "Session to close and extract")),
+    responses(
+        (status = 200, description = "Extraction queued", body = ApiResponse<AutomationResponse>),
+        (status = 400, description = "LLM client not configured", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn trigger_extraction(
     State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
-) -> Result<Json<ApiResponse<serde_json::Value>>> {
+) -> Result<Json<ApiResponse<AutomationResponse>>> {
     // Ensure LLM is available (coordinator needs it)
     if state.llm_client.is_none() {
         return Err(AppError::BadRequest(
@@ -39,12 +50,13 @@ pub async fn trigger_extraction(
     let mut session_mgr = session_mgr.write().await;
     session_mgr.close_session(&thread_id).await?;
 
-    let response = serde_json::json!({
-        "thread_id": thread_id,
-        "status": "extraction_triggered",
-        "message": "Session closed. Memory extraction and L0/L1 generation are being processed \
-                    asynchronously by MemoryEventCoordinator.",
-    });
+    let response = AutomationResponse {
+        thread_id: Some(thread_id),
+        status: "extraction_triggered".to_string(),
+        message: "Session closed. Memory extraction and L0/L1 generation are being processed \
+                  asynchronously by MemoryEventCoordinator."
+            .to_string(),
+    };
 
     Ok(Json(ApiResponse::success(response)))
 }
@@ -53,9 +65,19 @@ pub async fn trigger_extraction(
 ///
 /// This scans all files in user/agent/session scopes and indexes any that are missing
 /// from the vector store. Useful after 429 rate limit failures during initial ingest.
+#[utoipa::path(
+    post,
+    path = "/api/v2/automation/reindex",
+    tag = "automation",
+    responses(
+        (status = 200, description = "Reindex started in background", body = ApiResponse<AutomationResponse>),
+        (status = 500, description = "Vector store or embedding client unavailable", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn trigger_reindex(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<ApiResponse<serde_json::Value>>> {
+) -> Result<Json<ApiResponse<AutomationResponse>>> {
     let cortex = state.cortex.read().await.clone();
 
     let Some(qdrant_store) = cortex.qdrant_store() else {
@@ -90,10 +112,12 @@ pub async fn trigger_reindex(
         }
     });
 
-    let response = serde_json::json!({
-        "status": "reindex_triggered",
-        "message": "Full reindex started in background. Check service logs for progress.",
-    });
+    let response = AutomationResponse {
+        thread_id: None,
+        status: "reindex_triggered".to_string(),
+        message: "Full reindex started in background. Check service logs for progress."
+            .to_string(),
+    };
 
     Ok(Json(ApiResponse::success(response)))
 }
@@ -2,23 +2,42 @@ use axum::{
     extract::{Path, Query, State, Json},
     Json as JsonExtractor,
 };
-use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
+    access::Caller,
     error::{Result, AppError},
-    models::{ApiResponse, FileEntryResponse, LsRequest, LsResponse, ExploreRequest, ExploreResponse, ExplorationPathItem, SearchResultResponse},
+    models::{
+        ApiResponse, DirectoryStats, ErrorResponse, ExplorationPathItem, ExploreRequest,
+        ExploreResponse, FileEntryResponse, LayerQuery, LayerResponse, LsRequest, LsResponse,
+        SearchResultResponse, StatsQuery, WriteFileRequest,
+    },
     state::AppState,
 };
 use chrono::{DateTime, Utc};
+use cortex_mem_core::{FileEntry, ListOptions, ListSort};
 
 // ==================== List Directory ====================
 
-/// List directory contents with optional recursive and abstracts
+/// List directory contents with optional recursion, abstracts, name filter, sorting and pagination
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/list",
+    tag = "filesystem",
+    params(LsRequest),
+    responses(
+        (status = 200, description = "Directory entries", body = ApiResponse<LsResponse>),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn list_directory(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Query(params): Query<LsRequest>,
 ) -> Result<Json<ApiResponse<LsResponse>>> {
+    state.check_read(&caller, &params.uri)?;
+    let guard = state.access_guard(&caller);
+
     // Get tenant root if set
     let tenant_root = state.current_tenant_root.read().await.clone();
     
@@ -35,11 +54,31 @@ pub async fn list_directory(
     tracing::debug!("Listing directory: {:?} (recursive={}, include_abstracts={})", 
         base_path, params.recursive, params.include_abstracts);
     
+    let sort = match params.sort.as_deref() {
+        Some(s) => ListSort::parse(s).ok_or_else(|| {
+            AppError::BadRequest(format!("Unknown sort '{}', expected name, modified or size", s))
+        })?,
+        None => ListSort::default(),
+    };
+    let offset = match params.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(c) => ListOptions::decode_cursor(c)
+            .ok_or_else(|| AppError::BadRequest(format!("Invalid cursor: {}", c)))?,
+        None => 0,
+    };
+    let options = ListOptions {
+        pattern: params.pattern.clone(),
+        sort,
+        descending: params.descending,
+        offset,
+        limit: params.limit.filter(|&l| l > 0),
+    };
+
     if !base_path.exists() {
         return Ok(Json(ApiResponse::success(LsResponse {
             uri: params.uri,
             total: 0,
             entries: vec![],
+            next_cursor: None,
         })));
     }
 
@@ -49,20 +88,52 @@ pub async fn list_directory(
     let include_layers = params.include_layers;
     
     // Use spawn_blocking to avoid blocking the async runtime
-    let entries = tokio::task::spawn_blocking(move || {
+    let page = tokio::task::spawn_blocking(move || {
         let mut entries = Vec::new();
-        list_directory_recursive(&base_path, &uri, recursive, include_abstracts, include_layers, &mut entries);
-        entries
+        list_directory_recursive(&base_path, &uri, recursive, include_layers, &mut entries);
+        if let Some(guard) = &guard {
+            entries.retain(|entry| guard.can_read(&entry.uri));
+        }
+        let page = options.apply(entries);
+
+        // Abstracts are only loaded for the returned page.
+        // All files in the same directory share the directory-level abstract
+        let base_uri = uri.trim_end_matches('/');
+        let entries: Vec<FileEntryResponse> = page
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let abstract_text = if include_abstracts && !entry.is_directory {
+                    let relative = entry.uri[base_uri.len()..].trim_start_matches('/');
+                    base_path
+                        .join(relative)
+                        .parent()
+                        .and_then(|dir| std::fs::read_to_string(dir.join(".abstract.md")).ok())
+                } else {
+                    None
+                };
+                FileEntryResponse {
+                    uri: entry.uri,
+                    name: entry.name,
+                    is_directory: entry.is_directory,
+                    size: entry.size,
+                    modified: entry.modified,
+                    abstract_text,
+                }
+            })
+            .collect();
+
+        LsResponse {
+            uri,
+            total: page.total,
+            entries,
+            next_cursor: page.next_cursor,
+        }
     })
     .await
     .map_err(|e| AppError::Internal(format!("Failed to list directory: {}", e)))?;
     
-    let total = entries.len();
-    Ok(Json(ApiResponse::success(LsResponse {
-        uri: params.uri,
-        total,
-        entries,
-    })))
+    Ok(Json(ApiResponse::success(page)))
 }
 
 /// Recursively list directory contents (synchronous, runs in spawn_blocking)
@@ -70,9 +141,8 @@ fn list_directory_recursive(
     base_path: &std::path::Path,
     base_uri: &str,
     recursive: bool,
-    include_abstracts: bool,
     include_layers: bool,
-    entries: &mut Vec<FileEntryResponse>,
+    entries: &mut Vec<FileEntry>,
 ) {
     if !base_path.exists() || !base_path.is_dir() {
         return;
@@ -102,28 +172,18 @@ fn list_directory_recursive(
                 
                 let entry_uri = format!("{}/{}", base_uri.trim_end_matches('/'), name);
                 
-                // Load abstract if requested and this is a file
-                // All files in the same directory share the directory-level abstract
-                let abstract_text = if include_abstracts && !is_dir {
-                    let abstract_path = base_path.join(".abstract.md");
-                    std::fs::read_to_string(&abstract_path).ok()
-                } else {
-                    None
-                };
-                
-                entries.push(FileEntryResponse {
+                entries.push(FileEntry {
                     uri: entry_uri.clone(),
                     name: name.clone(),
                     is_directory: is_dir,
                     size,
                     modified,
-                    abstract_text,
                 });
                 
                 // Recurse into subdirectories if requested
                 if recursive && is_dir {
                     let sub_path = base_path.join(&name);
-                    list_directory_recursive(&sub_path, &entry_uri, true, include_abstracts, include_layers, entries);
+                    list_directory_recursive(&sub_path, &entry_uri, true, include_layers, entries);
                 }
             }
         }
@@ -133,10 +193,24 @@ fn list_directory_recursive(
 // ==================== Read/Write File ====================
 
 /// Read file content
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/read/{path}",
+    tag = "filesystem",
+    params(("path" = String, Path, description = "URI path without the cortex:// prefix, e.g. user/default/preferences/pref_1a2b3c4d.md")),
+    responses(
+        (status = 200, description = "Raw file content", body = ApiResponse<String>),
+        (status = 500, description = "File could not be read", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn read_file(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Path(path): Path<String>,
 ) -> Result<Json<ApiResponse<String>>> {
+    state.check_read(&caller, &path)?;
+
     // Get tenant root if set
     let tenant_root = state.current_tenant_root.read().await.clone();
     
@@ -160,10 +234,24 @@ pub async fn read_file(
 }
 
 /// Write file content
+#[utoipa::path(
+    post,
+    path = "/api/v2/filesystem/write",
+    tag = "filesystem",
+    request_body = WriteFileRequest,
+    responses(
+        (status = 200, description = "Path of the written file", body = ApiResponse<String>),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn write_file(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     JsonExtractor(req): JsonExtractor<WriteFileRequest>,
 ) -> Result<Json<ApiResponse<String>>> {
+    state.check_write(&caller, &req.path)?;
+
     // Get tenant root if set
     let tenant_root = state.current_tenant_root.read().await.clone();
     
@@ -196,10 +284,23 @@ pub async fn write_file(
 // ==================== Directory Stats ====================
 
 /// Get directory stats (recursive)
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/stats",
+    tag = "filesystem",
+    params(StatsQuery),
+    responses(
+        (status = 200, description = "Recursive file count and size", body = ApiResponse<DirectoryStats>),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn get_directory_stats(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Query(params): Query<StatsQuery>,
 ) -> Result<Json<ApiResponse<DirectoryStats>>> {
+    state.check_read(&caller, &params.uri)?;
+
     // Get tenant root if set
     let tenant_root = state.current_tenant_root.read().await.clone();
     
@@ -274,45 +375,26 @@ fn count_files_recursive(path: &std::path::Path) -> std::io::Result<(u64, u64)>
     Ok((file_count, total_size))
 }
 
-#[derive(serde::Deserialize)]
-pub struct WriteFileRequest {
-    path: String,
-    content: String,
-}
-
-#[derive(serde::Deserialize)]
-pub struct StatsQuery {
-    uri: String,
-}
-
-#[derive(serde::Serialize)]
-pub struct DirectoryStats {
-    file_count: u64,
-    total_size: u64,
-}
-
 // ==================== Layered Access ====================
 
-/// Query for layered access endpoints
-#[derive(Debug, Deserialize)]
-pub struct LayerQuery {
-    uri: String,
-}
-
-/// Response for layered access endpoints
-#[derive(Debug, Serialize)]
-pub struct LayerResponse {
-    pub uri: String,
-    pub content: String,
-    pub layer: String,
-    pub token_count: usize,
-}
-
 /// Get L0 abstract layer (~100 tokens) for quick relevance checking
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/abstract",
+    tag = "filesystem",
+    params(LayerQuery),
+    responses(
+        (status = 200, description = "L0 abstract", body = ApiResponse<LayerResponse>),
+        (status = 500, description = "Abstract not generated yet", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn get_abstract(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Query(params): Query<LayerQuery>,
 ) -> Result<Json<ApiResponse<LayerResponse>>> {
+    state.check_read(&caller, &params.uri)?;
     let (_base_path, layer_path) = resolve_layer_path(&state, &params.uri, "abstract").await?;
     
     tracing::debug!("Reading abstract layer: {:?}", layer_path);
@@ -332,10 +414,23 @@ pub async fn get_abstract(
 }
 
 /// Get L1 overview layer (~2000 tokens) for understanding core information
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/overview",
+    tag = "filesystem",
+    params(LayerQuery),
+    responses(
+        (status = 200, description = "L1 overview", body = ApiResponse<LayerResponse>),
+        (status = 500, description = "Overview not generated yet", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn get_overview(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Query(params): Query<LayerQuery>,
 ) -> Result<Json<ApiResponse<LayerResponse>>> {
+    state.check_read(&caller, &params.uri)?;
     let (_base_path, layer_path) = resolve_layer_path(&state, &params.uri, "overview").await?;
     
     tracing::debug!("Reading overview layer: {:?}", layer_path);
@@ -355,10 +450,23 @@ pub async fn get_overview(
 }
 
 /// Get L2 full content layer - complete original content
+#[utoipa::path(
+    get,
+    path = "/api/v2/filesystem/content",
+    tag = "filesystem",
+    params(LayerQuery),
+    responses(
+        (status = 200, description = "L2 full content", body = ApiResponse<LayerResponse>),
+        (status = 500, description = "Content not found", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn get_content(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Query(params): Query<LayerQuery>,
 ) -> Result<Json<ApiResponse<LayerResponse>>> {
+    state.check_read(&caller, &params.uri)?;
     // Get tenant root if set
     let tenant_root = state.current_tenant_root.read().await.clone();
     
@@ -492,17 +600,35 @@ pub async fn load_abstract_for_uri(base_dir: &std::path::Path, uri: &str) -> Opt
 // ==================== Explore ====================
 
 /// Smart exploration of memory space, combining search and browsing
+#[utoipa::path(
+    post,
+    path = "/api/v2/filesystem/explore",
+    tag = "filesystem",
+    request_body = ExploreRequest,
+    responses(
+        (status = 200, description = "Exploration path and matches", body = ApiResponse<ExploreResponse>),
+        (status = 400, description = "Vector search not configured", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
 pub async fn explore(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     JsonExtractor(req): JsonExtractor<ExploreRequest>,
 ) -> Result<Json<ApiResponse<ExploreResponse>>> {
     use cortex_mem_core::SearchOptions;
 
+    state.check_read(&caller, &req.start_uri)?;
+
     // Check if vector engine is available
-    let vector_engine_lock = state.vector_engine.read().await;
-    let vector_engine = vector_engine_lock.as_ref().ok_or_else(|| {
+    let vector_engine = state.vector_engine.read().await.clone().ok_or_else(|| {
         AppError::BadRequest("Vector search not available. Qdrant and Embedding service must be configured.".to_string())
     })?;
+    // Results outside the caller's spaces are filtered by the engine
+    let vector_engine = match state.access_guard(&caller) {
+        Some(guard) => Arc::new((*vector_engine).clone().with_access(guard)),
+        None => vector_engine,
+    };
 
     // Get tenant root
     let tenant_root = state.current_tenant_root.read().await.clone();
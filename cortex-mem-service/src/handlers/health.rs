@@ -2,22 +2,27 @@ use axum::{
     extract::State,
     Json,
 };
-use serde_json::json;
 use std::sync::Arc;
 
-use crate::{error::Result, state::AppState};
+use crate::{error::Result, models::HealthResponse, state::AppState};
 
 /// Health check endpoint
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "Service is up", body = HealthResponse))
+)]
 pub async fn health_check(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<serde_json::Value>> {
+) -> Result<Json<HealthResponse>> {
     let has_llm = state.llm_client.is_some();
-    
-    Ok(Json(json!({
-        "status": "healthy",
-        "service": "cortex-mem-service",
-        "version": env!("CARGO_PKG_VERSION"),
-        "llm_available": has_llm,
-        "timestamp": chrono::Utc::now(),
-    })))
+
+    Ok(Json(HealthResponse {
+        status: "healthy".to_string(),
+        service: "cortex-mem-service".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        llm_available: has_llm,
+        timestamp: chrono::Utc::now(),
+    }))
 }
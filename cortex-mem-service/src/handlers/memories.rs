@@ -0,0 +1,211 @@
+//! Explicit memory editing: remember, update and forget.
+//!
+//! All three go through the coordinator's `IncrementalMemoryUpdater`, so they
+//! share deduplication, the memory index and `MemoryEvent`s (layer cascades,
+//! vector sync) with memories extracted from sessions.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use std::sync::Arc;
+
+use crate::{
+    access::Caller,
+    error::{AppError, Result},
+    models::{
+        ApiResponse, ErrorResponse, MemoryEditResponse, MemoryQuery, RememberRequest,
+        UpdateMemoryRequest,
+    },
+    state::AppState,
+};
+use cortex_mem_core::{
+    DeleteReason, IncrementalMemoryUpdater, MANUAL_SOURCE, ManualMemory, MemoryMetadata,
+    MemoryScope, MemoryType,
+};
+
+const DEFAULT_CONFIDENCE: f32 = 0.9;
+
+/// Remember a structured memory
+///
+/// Cases are stored under `cortex://agent/{agent_id}/cases`, every other type
+/// under `cortex://user/{user_id}`. A memory with the same type and key is
+/// updated instead of duplicated.
+#[utoipa::path(
+    post,
+    path = "/api/v2/memories",
+    tag = "memories",
+    request_body = RememberRequest,
+    responses(
+        (status = 200, description = "Memory created, updated or unchanged", body = ApiResponse<MemoryEditResponse>),
+        (status = 400, description = "Unknown memory type or empty key/content", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
+pub async fn remember(
+    State(state): State<Arc<AppState>>,
+    caller: Caller,
+    Json(payload): Json<RememberRequest>,
+) -> Result<Json<ApiResponse<MemoryEditResponse>>> {
+    let memory_type: MemoryType = payload
+        .memory_type
+        .trim()
+        .parse()
+        .map_err(AppError::BadRequest)?;
+    if payload.content.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "content must not be empty".to_string(),
+        ));
+    }
+
+    let item = ManualMemory::new(
+        memory_type.clone(),
+        payload.key,
+        payload.content,
+        payload.confidence.unwrap_or(DEFAULT_CONFIDENCE),
+    )
+    .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let scope = ManualMemory::scope_of(&memory_type);
+    let owner_id = match scope {
+        MemoryScope::Agent => payload.agent_id,
+        _ => payload.user_id,
+    }
+    .unwrap_or_else(|| "default".to_string());
+    state.check_write(&caller, &format!("cortex://{}/{}", scope, owner_id))?;
+
+    let (result, metadata) = memory_updater(&state)
+        .await?
+        .remember(&scope, &owner_id, MANUAL_SOURCE, &item)
+        .await?;
+
+    let action = if result.created > 0 {
+        "created"
+    } else if result.updated > 0 {
+        "updated"
+    } else {
+        "unchanged"
+    };
+    Ok(Json(ApiResponse::success(edit_response(
+        &scope, &owner_id, metadata, action,
+    ))))
+}
+
+/// Replace the content of an existing memory, keeping its type and key
+#[utoipa::path(
+    put,
+    path = "/api/v2/memories",
+    tag = "memories",
+    request_body = UpdateMemoryRequest,
+    responses(
+        (status = 200, description = "Memory updated", body = ApiResponse<MemoryEditResponse>),
+        (status = 400, description = "Not a memory URI or empty content", body = ErrorResponse),
+        (status = 404, description = "Memory not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
+pub async fn update_memory(
+    State(state): State<Arc<AppState>>,
+    caller: Caller,
+    Json(payload): Json<UpdateMemoryRequest>,
+) -> Result<Json<ApiResponse<MemoryEditResponse>>> {
+    if payload.content.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "content must not be empty".to_string(),
+        ));
+    }
+    let (scope, owner_id, memory_id) = parse_uri(&payload.uri)?;
+    state.check_write(&caller, &payload.uri)?;
+
+    let metadata = memory_updater(&state)
+        .await?
+        .update_memory(
+            &scope,
+            &owner_id,
+            &memory_id,
+            &payload.content,
+            payload.confidence,
+            MANUAL_SOURCE,
+        )
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Memory not found: {}", payload.uri)))?;
+
+    Ok(Json(ApiResponse::success(edit_response(
+        &scope, &owner_id, metadata, "updated",
+    ))))
+}
+
+/// Forget a memory: remove its file, index entry and vectors
+#[utoipa::path(
+    delete,
+    path = "/api/v2/memories",
+    tag = "memories",
+    params(MemoryQuery),
+    responses(
+        (status = 200, description = "Memory deleted", body = ApiResponse<MemoryEditResponse>),
+        (status = 400, description = "Not a memory URI", body = ErrorResponse),
+        (status = 404, description = "Memory not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+        (status = 403, description = "Denied by the [access] policy", body = ErrorResponse),
+    )
+)]
+pub async fn forget(
+    State(state): State<Arc<AppState>>,
+    caller: Caller,
+    Query(params): Query<MemoryQuery>,
+) -> Result<Json<ApiResponse<MemoryEditResponse>>> {
+    let (scope, owner_id, memory_id) = parse_uri(&params.uri)?;
+    state.check_write(&caller, &params.uri)?;
+    let updater = memory_updater(&state).await?;
+
+    let not_found = || AppError::NotFound(format!("Memory not found: {}", params.uri));
+    let metadata = updater
+        .get_memory(&scope, &owner_id, &memory_id)
+        .await?
+        .ok_or_else(not_found)?;
+    if !updater
+        .delete_memory(&scope, &owner_id, &memory_id, DeleteReason::UserRequest)
+        .await?
+    {
+        return Err(not_found());
+    }
+
+    Ok(Json(ApiResponse::success(edit_response(
+        &scope, &owner_id, metadata, "deleted",
+    ))))
+}
+
+async fn memory_updater(state: &AppState) -> Result<Arc<IncrementalMemoryUpdater>> {
+    state.cortex.read().await.memory_updater().ok_or_else(|| {
+        AppError::Internal(
+            "Memory event coordinator not running (LLM, embedding and vector store are required)"
+                .to_string(),
+        )
+    })
+}
+
+fn parse_uri(uri: &str) -> Result<(MemoryScope, String, String)> {
+    IncrementalMemoryUpdater::parse_memory_uri(uri).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Not a memory URI (expected cortex://user|agent/{{id}}/{{dir}}/{{memory_id}}.md): {}",
+            uri
+        ))
+    })
+}
+
+fn edit_response(
+    scope: &MemoryScope,
+    owner_id: &str,
+    metadata: MemoryMetadata,
+    action: &str,
+) -> MemoryEditResponse {
+    MemoryEditResponse {
+        uri: format!("cortex://{}/{}/{}", scope, owner_id, metadata.file),
+        memory_id: metadata.id,
+        memory_type: metadata.memory_type.to_string(),
+        key: metadata.key,
+        action: action.to_string(),
+    }
+}
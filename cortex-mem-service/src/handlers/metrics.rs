@@ -0,0 +1,75 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+use cortex_mem_core::metrics::{self, METRICS};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::state::AppState;
+
+/// Scopes whose `.memory_index.json` files are counted for the memory gauges
+const SCOPES: [&str; 4] = ["user", "agent", "session", "resources"];
+
+/// Prometheus metrics endpoint
+///
+/// Pipeline counters and histograms are recorded by cortex-mem-core as work
+/// happens; per-tenant memory counts are refreshed here, at scrape time, from
+/// the memory index files under the data directory.
+pub async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut roots = vec![("default".to_string(), state.data_dir.clone())];
+    for tenant_id in state.list_tenants().await {
+        let root = state.data_dir.join("tenants").join(&tenant_id);
+        roots.push((tenant_id, root));
+    }
+
+    let counts = tokio::task::spawn_blocking(move || {
+        roots
+            .into_iter()
+            .map(|(tenant, root)| {
+                let per_scope = SCOPES.map(|scope| (scope, count_scope_memories(&root, scope)));
+                (tenant, per_scope)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    // Reset first so tenants removed from disk disappear from the output
+    METRICS.memories.reset();
+    for (tenant, per_scope) in counts {
+        for (scope, count) in per_scope {
+            METRICS
+                .memories
+                .with_label_values(&[tenant.as_str(), scope])
+                .set(count as i64);
+        }
+    }
+
+    ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics::encode_text())
+}
+
+/// Count memories recorded in the `.memory_index.json` files of one scope
+///
+/// `resources` has a single index at its root; the other scopes keep one
+/// index per owner directory.
+fn count_scope_memories(root: &Path, scope: &str) -> usize {
+    let scope_dir = root.join(scope);
+    if scope == "resources" {
+        return count_index(&scope_dir.join(".memory_index.json"));
+    }
+
+    let Ok(entries) = std::fs::read_dir(&scope_dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| count_index(&entry.path().join(".memory_index.json")))
+        .sum()
+}
+
+fn count_index(path: &Path) -> usize {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|index| index.get("memories").and_then(|m| m.as_object()).map(|m| m.len()))
+        .unwrap_or(0)
+}
@@ -1,6 +1,9 @@
 pub mod automation;
 pub mod filesystem;
 pub mod health;
+pub mod memories;
+pub mod metrics;
 pub mod search;
 pub mod sessions;
 pub mod tenants;
+pub mod usage;
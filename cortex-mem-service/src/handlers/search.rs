@@ -4,18 +4,32 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
+use cortex_mem_core::AccessGuard;
 use tracing::info;
 
 use crate::handlers::filesystem::load_layers_for_uri;
 use crate::{
+    access::Caller,
     error::{AppError, Result},
-    models::{ApiResponse, SearchRequest, SearchResultResponse},
+    models::{ApiResponse, ErrorResponse, SearchRequest, SearchResultResponse},
     state::AppState,
 };
 
 /// Search endpoint using layered vector search (L0/L1/L2)
+#[utoipa::path(
+    post,
+    path = "/api/v2/search",
+    tag = "search",
+    request_body = SearchRequest,
+    responses(
+        (status = 200, description = "Ranked results with the requested layers", body = ApiResponse<Vec<SearchResultResponse>>),
+        (status = 500, description = "Search pipeline failure", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn search(
     State(state): State<Arc<AppState>>,
+    caller: Caller,
     Json(req): Json<SearchRequest>,
 ) -> Result<Json<ApiResponse<Vec<SearchResultResponse>>>> {
     let limit = req.limit.unwrap_or(10);
@@ -24,6 +38,7 @@ pub async fn search(
 
     let results = search_layered(
         &state,
+        state.access_guard(&caller),
         &req.query,
         req.thread.as_deref(),
         limit,
@@ -38,6 +53,7 @@ pub async fn search(
 /// Layered semantic search using L0/L1/L2 tiered retrieval
 async fn search_layered(
     state: &AppState,
+    guard: Option<AccessGuard>,
     query: &str,
     thread: Option<&str>,
     limit: usize,
@@ -46,13 +62,16 @@ async fn search_layered(
 ) -> Result<Vec<SearchResultResponse>> {
     use cortex_mem_core::SearchOptions;
 
-    let vector_engine_lock = state.vector_engine.read().await;
-    let vector_engine = vector_engine_lock.as_ref().ok_or_else(|| {
+    let vector_engine = state.vector_engine.read().await.clone().ok_or_else(|| {
         AppError::BadRequest(
             "Vector search not available. Qdrant and Embedding service must be configured."
                 .to_string(),
         )
     })?;
+    let vector_engine = match &guard {
+        Some(guard) => Arc::new((*vector_engine).clone().with_access(guard.clone())),
+        None => vector_engine,
+    };
 
     let mut options = SearchOptions {
         limit,
@@ -187,6 +206,10 @@ async fn search_layered(
         merged = merge_merged_results(merged, lexical_results);
         rerank_results(&profile, &mut merged);
     }
+    // The lexical fallback scans files directly, bypassing the engine's filter
+    if let Some(guard) = &guard {
+        merged.retain(|result| guard.can_read(&result.uri));
+    }
 
     let mut results = Vec::new();
     for result in merged.drain(..).take(limit) {
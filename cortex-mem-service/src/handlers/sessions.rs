@@ -7,8 +7,9 @@ use std::{path::PathBuf, sync::Arc, time::Duration};
 use crate::{
     error::{AppError, Result},
     models::{
-        AddMessageRequest, ApiResponse, CloseAndWaitRequest, CloseAndWaitResponse,
-        SessionResponse,
+        AddMessageRequest, ApiResponse, BatchAddMessagesRequest, BatchAddMessagesResponse,
+        BatchThreadResult, CloseAndWaitRequest, CloseAndWaitResponse, CreateSessionRequest,
+        ErrorResponse, SessionResponse,
     },
     state::AppState,
 };
@@ -23,26 +24,27 @@ use cortex_mem_core::{
 };
 
 /// Create a new session
+#[utoipa::path(
+    post,
+    path = "/api/v2/sessions",
+    tag = "sessions",
+    request_body = CreateSessionRequest,
+    responses(
+        (status = 200, description = "Created session", body = ApiResponse<SessionResponse>),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn create_session(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<serde_json::Value>,
+    Json(payload): Json<CreateSessionRequest>,
 ) -> Result<Json<ApiResponse<SessionResponse>>> {
-    let thread_id = payload.get("thread_id")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-    let title = payload.get("title")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let user_id = payload.get("user_id")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let agent_id = payload.get("agent_id")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let CreateSessionRequest {
+        thread_id,
+        title,
+        user_id,
+        agent_id,
+    } = payload;
+    let thread_id = thread_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
     let session_mgr = state.current_session_manager().await;
     let session_mgr = session_mgr.write().await;
@@ -66,6 +68,12 @@ pub async fn create_session(
 }
 
 /// List all sessions
+#[utoipa::path(
+    get,
+    path = "/api/v2/sessions",
+    tag = "sessions",
+    responses((status = 200, description = "Sessions of the current tenant", body = ApiResponse<Vec<SessionResponse>>))
+)]
 pub async fn list_sessions(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ApiResponse<Vec<SessionResponse>>>> {
@@ -120,6 +128,18 @@ pub async fn list_sessions(
 }
 
 /// Add message to session
+#[utoipa::path(
+    post,
+    path = "/api/v2/sessions/{thread_id}/messages",
+    tag = "sessions",
+    params(("thread_id" = String, Path, description = "Session ID (auto-created if missing)")),
+    request_body = AddMessageRequest,
+    responses(
+        (status = 200, description = "Confirmation containing the message URI", body = ApiResponse<String>),
+        (status = 400, description = "Invalid role", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn add_message(
     State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
@@ -127,13 +147,11 @@ pub async fn add_message(
 ) -> Result<Json<ApiResponse<String>>> {
     use cortex_mem_core::MessageRole;
 
-    let role = match payload.role.to_lowercase().as_str() {
-        "user" => MessageRole::User,
-        "assistant" => MessageRole::Assistant,
-        "system" => MessageRole::System,
-        _ => return Err(crate::error::AppError::BadRequest(
-            format!("Invalid role: {}", payload.role)
-        )),
+    let Some(role) = MessageRole::parse(&payload.role) else {
+        return Err(crate::error::AppError::BadRequest(format!(
+            "Invalid role: {}",
+            payload.role
+        )));
     };
 
     // Ensure the session exists before adding a message (auto-create if missing)
@@ -200,7 +218,98 @@ pub async fn add_message(
     Ok(Json(ApiResponse::success(format!("Message saved to {}", message_uri))))
 }
 
+/// Import messages in bulk across sessions
+///
+/// The batch is validated, then written with `SessionManager::import_messages`:
+/// grouped per thread, in timestamp order, with one `MessageAdded` per thread and
+/// one `LayerUpdateNeeded` per timeline day directory.
+#[utoipa::path(
+    post,
+    path = "/api/v2/sessions/batch",
+    tag = "sessions",
+    request_body = BatchAddMessagesRequest,
+    responses(
+        (status = 200, description = "Per-thread import result", body = ApiResponse<BatchAddMessagesResponse>),
+        (status = 400, description = "Empty batch or invalid role; nothing is written", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
+pub async fn batch_add_messages(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<BatchAddMessagesRequest>,
+) -> Result<Json<ApiResponse<BatchAddMessagesResponse>>> {
+    use cortex_mem_core::{Message, MessageRole};
+
+    if payload.messages.is_empty() {
+        return Err(AppError::BadRequest("messages must not be empty".to_string()));
+    }
+
+    // Validate the whole batch before writing anything
+    let mut messages = Vec::with_capacity(payload.messages.len());
+    for (i, item) in payload.messages.into_iter().enumerate() {
+        let role = MessageRole::parse(&item.role).ok_or_else(|| {
+            AppError::BadRequest(format!("messages[{}]: invalid role: {}", i, item.role))
+        })?;
+        if item.thread_id.is_empty() {
+            return Err(AppError::BadRequest(format!("messages[{}]: thread_id is required", i)));
+        }
+
+        let mut message = Message::new(role, item.content);
+        if let Some(ts) = item.timestamp {
+            message = message.with_timestamp(ts);
+        }
+        if let Some(metadata) = item.metadata {
+            message = message.with_metadata(metadata);
+        }
+        messages.push((item.thread_id, message));
+    }
+
+    let session_mgr = state.current_session_manager().await;
+    let imported = session_mgr.read().await.import_messages(messages).await?;
+    let total_messages = imported.iter().map(|thread| thread.uris.len()).sum();
+    let threads: Vec<BatchThreadResult> = imported
+        .into_iter()
+        .map(|thread| BatchThreadResult {
+            thread_id: thread.thread_id,
+            message_count: thread.uris.len(),
+            uris: thread.uris,
+        })
+        .collect();
+
+    let mut extraction_triggered = Vec::new();
+    if payload.trigger_extraction {
+        for thread in &threads {
+            let mut sm = session_mgr.write().await;
+            match sm.close_session(&thread.thread_id).await {
+                Ok(_) => extraction_triggered.push(thread.thread_id.clone()),
+                Err(e) => tracing::warn!(
+                    "Failed to close session {} for extraction: {}",
+                    thread.thread_id,
+                    e
+                ),
+            }
+        }
+    }
+
+    Ok(Json(ApiResponse::success(BatchAddMessagesResponse {
+        total_messages,
+        threads,
+        extraction_triggered,
+    })))
+}
+
 /// Close session
+#[utoipa::path(
+    post,
+    path = "/api/v2/sessions/{thread_id}/close",
+    tag = "sessions",
+    params(("thread_id" = String, Path, description = "Session ID")),
+    responses(
+        (status = 200, description = "Closed session; extraction runs asynchronously", body = ApiResponse<SessionResponse>),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn close_session(
     State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
@@ -222,17 +331,25 @@ pub async fn close_session(
 }
 
 /// Close session and wait until extracted memories are ready for retrieval.
+#[utoipa::path(
+    post,
+    path = "/api/v2/sessions/{thread_id}/close-and-wait",
+    tag = "sessions",
+    params(("thread_id" = String, Path, description = "Session ID")),
+    request_body(content = CloseAndWaitRequest, description = "Optional wait settings; defaults apply when omitted"),
+    responses(
+        (status = 200, description = "Memories extracted and indexed", body = ApiResponse<CloseAndWaitResponse>),
+        (status = 400, description = "Invalid timeout or poll interval", body = ErrorResponse),
+        (status = 500, description = "Timed out waiting for readiness", body = ErrorResponse),
+        (status = 429, description = "Rate limit or daily quota exceeded; see Retry-After", body = ErrorResponse),
+    )
+)]
 pub async fn close_session_and_wait(
     State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     payload: Option<Json<CloseAndWaitRequest>>,
 ) -> Result<Json<ApiResponse<CloseAndWaitResponse>>> {
-    let request = payload
-        .map(|Json(v)| v)
-        .unwrap_or(CloseAndWaitRequest {
-            timeout_secs: 120,
-            poll_interval_ms: 500,
-        });
+    let request = payload.map(|Json(v)| v).unwrap_or_default();
 
     if request.timeout_secs == 0 {
         return Err(AppError::BadRequest("timeout_secs must be greater than 0".to_string()));
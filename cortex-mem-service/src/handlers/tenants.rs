@@ -3,11 +3,17 @@ use std::sync::Arc;
 
 use crate::{
     error::Result,
-    models::ApiResponse,
+    models::{ApiResponse, ErrorResponse, TenantSwitchRequest},
     state::AppState,
 };
 
 /// List all available tenants
+#[utoipa::path(
+    get,
+    path = "/api/v2/tenants",
+    tag = "tenants",
+    responses((status = 200, description = "Tenant IDs found under the data directory", body = ApiResponse<Vec<String>>))
+)]
 pub async fn list_tenants(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ApiResponse<Vec<String>>>> {
@@ -16,6 +22,16 @@ pub async fn list_tenants(
 }
 
 /// Switch to a different tenant
+#[utoipa::path(
+    post,
+    path = "/api/v2/tenants/switch",
+    tag = "tenants",
+    request_body = TenantSwitchRequest,
+    responses(
+        (status = 200, description = "Active tenant ID", body = ApiResponse<String>),
+        (status = 500, description = "Tenant runtime could not be built", body = ErrorResponse),
+    )
+)]
 pub async fn switch_tenant(
     State(state): State<Arc<AppState>>,
     Json(tenant_id): Json<TenantSwitchRequest>,
@@ -23,8 +39,3 @@ pub async fn switch_tenant(
     state.switch_tenant(&tenant_id.tenant_id).await?;
     Ok(Json(ApiResponse::success(tenant_id.tenant_id)))
 }
-
-#[derive(serde::Deserialize)]
-pub struct TenantSwitchRequest {
-    pub tenant_id: String,
-}
@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use cortex_mem_core::UsageTotals;
+use std::sync::Arc;
+
+use crate::{
+    error::{AppError, Result},
+    models::{
+        ApiResponse, ErrorResponse, TokenUsageBucket, TokenUsageQuery, TokenUsageResponse,
+        TokenUsageTotals, UsageResponse,
+    },
+    state::AppState,
+};
+
+/// Longest range `GET /api/v2/usage/tokens` summarizes
+const MAX_TOKEN_USAGE_DAYS: u32 = 366;
+
+/// Rate limit usage counters for the current UTC day
+#[utoipa::path(
+    get,
+    path = "/api/v2/usage",
+    tag = "usage",
+    responses((status = 200, description = "Per-tenant and per-key counters with their effective limits", body = ApiResponse<UsageResponse>))
+)]
+pub async fn get_usage(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ApiResponse<UsageResponse>>> {
+    Ok(Json(ApiResponse::success(state.rate_limiter.usage())))
+}
+
+/// LLM and embedding token usage of the current tenant
+#[utoipa::path(
+    get,
+    path = "/api/v2/usage/tokens",
+    tag = "usage",
+    params(TokenUsageQuery),
+    responses(
+        (status = 200, description = "Usage per day, operation and model with budget status", body = ApiResponse<TokenUsageResponse>),
+        (status = 400, description = "Invalid number of days", body = ErrorResponse),
+    )
+)]
+pub async fn get_token_usage(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TokenUsageQuery>,
+) -> Result<Json<ApiResponse<TokenUsageResponse>>> {
+    if params.days == 0 || params.days > MAX_TOKEN_USAGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "days must be between 1 and {}",
+            MAX_TOKEN_USAGE_DAYS
+        )));
+    }
+
+    let to = chrono::Utc::now().date_naive();
+    let from = to - chrono::Duration::days(i64::from(params.days) - 1);
+    let cortex = state.cortex.read().await.clone();
+    let Some(tracker) = cortex.usage() else {
+        return Ok(Json(ApiResponse::success(TokenUsageResponse {
+            enabled: false,
+            from: from.to_string(),
+            to: to.to_string(),
+            total: TokenUsageTotals::default(),
+            by_day: Vec::new(),
+            by_operation: Vec::new(),
+            by_model: Vec::new(),
+            daily_token_budget: None,
+            daily_cost_budget_usd: None,
+            over_budget: false,
+        })));
+    };
+
+    let summary = tracker.summary(from, to);
+    let bucket = |key: String, totals: &UsageTotals| TokenUsageBucket {
+        key,
+        usage: to_wire(totals),
+    };
+    Ok(Json(ApiResponse::success(TokenUsageResponse {
+        enabled: true,
+        from: from.to_string(),
+        to: to.to_string(),
+        total: to_wire(&summary.total),
+        by_day: summary
+            .by_day
+            .iter()
+            .map(|(day, totals)| bucket(day.to_string(), totals))
+            .collect(),
+        by_operation: summary
+            .by_operation
+            .iter()
+            .map(|(operation, totals)| bucket(operation.as_str().to_string(), totals))
+            .collect(),
+        by_model: summary
+            .by_model
+            .iter()
+            .map(|(model, totals)| bucket(model.clone(), totals))
+            .collect(),
+        daily_token_budget: tracker.config().daily_token_budget,
+        daily_cost_budget_usd: tracker.config().daily_cost_budget_usd,
+        over_budget: tracker.over_budget(),
+    })))
+}
+
+fn to_wire(totals: &UsageTotals) -> TokenUsageTotals {
+    TokenUsageTotals {
+        calls: totals.calls,
+        prompt_tokens: totals.prompt_tokens,
+        completion_tokens: totals.completion_tokens,
+        estimated_calls: totals.estimated_calls,
+        cost_usd: totals.cost_usd,
+    }
+}
@@ -9,9 +9,13 @@ use tower_http::trace::TraceLayer;
 use tracing::{info, level_filters::LevelFilter};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod access;
 mod error;
+mod grpc;
 mod handlers;
 mod models;
+mod openapi;
+mod rate_limit;
 mod routes;
 mod state;
 
@@ -39,6 +43,10 @@ struct Cli {
     #[arg(short, long, default_value_t = 8085)]
     port: u16,
 
+    /// gRPC server port. When specified, the gRPC API is served on this port alongside REST
+    #[arg(long, value_name = "PORT")]
+    grpc_port: Option<u16>,
+
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
@@ -130,10 +138,26 @@ async fn main() -> anyhow::Result<()> {
     let state = AppState::new(&cli.data_dir, &config_path).await?;
     let state = Arc::new(state);
 
+    // Start gRPC server on its own port
+    if let Some(grpc_port) = cli.grpc_port {
+        let grpc_addr = SocketAddr::from(([127, 0, 0, 1], grpc_port));
+        let grpc_state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = grpc::serve(grpc_state, grpc_addr).await {
+                tracing::error!("gRPC server failed: {}", e);
+            }
+        });
+    }
+
     // Build router
     let app = Router::new()
         .route("/health", get(handlers::health::health_check))
+        .route("/metrics", get(handlers::metrics::metrics))
         .nest("/api/v2", routes::api_routes())
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::enforce,
+        ))
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
         .with_state(state);
@@ -0,0 +1,70 @@
+//! OpenAPI 3 document for the REST API, served at `GET /api/v2/openapi.json`.
+//!
+//! Schemas come from `cortex_mem_client::models` (re-exported as `crate::models`),
+//! so the document, the handlers and the Rust client cannot drift apart.
+
+use axum::Json;
+use utoipa::OpenApi;
+
+use crate::handlers;
+use crate::models::*;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Cortex Memory Service API",
+        description = "HTTP REST API for Cortex Memory: sessions, layered (L0/L1/L2) search, \
+                       the cortex:// filesystem, automation triggers and tenants."
+    ),
+    paths(
+        handlers::health::health_check,
+        // Sessions
+        handlers::sessions::list_sessions,
+        handlers::sessions::create_session,
+        handlers::sessions::add_message,
+        handlers::sessions::batch_add_messages,
+        handlers::sessions::close_session,
+        handlers::sessions::close_session_and_wait,
+        // Memories
+        handlers::memories::remember,
+        handlers::memories::update_memory,
+        handlers::memories::forget,
+        // Search
+        handlers::search::search,
+        // Filesystem
+        handlers::filesystem::list_directory,
+        handlers::filesystem::read_file,
+        handlers::filesystem::write_file,
+        handlers::filesystem::get_directory_stats,
+        handlers::filesystem::get_abstract,
+        handlers::filesystem::get_overview,
+        handlers::filesystem::get_content,
+        handlers::filesystem::explore,
+        // Automation
+        handlers::automation::trigger_extraction,
+        handlers::automation::trigger_reindex,
+        // Tenants
+        handlers::tenants::list_tenants,
+        handlers::tenants::switch_tenant,
+        // Usage
+        handlers::usage::get_usage,
+        handlers::usage::get_token_usage,
+    ),
+    components(schemas(ErrorResponse, FileEntryResponse, SearchResultResponse, ExplorationPathItem)),
+    tags(
+        (name = "health", description = "Liveness"),
+        (name = "sessions", description = "Session lifecycle and message ingestion"),
+        (name = "memories", description = "Explicit remember / update / forget of structured memories"),
+        (name = "search", description = "Layered semantic search"),
+        (name = "filesystem", description = "cortex:// filesystem and L0/L1/L2 layer access"),
+        (name = "automation", description = "Manual extraction and reindex triggers"),
+        (name = "tenants", description = "Tenant discovery and switching"),
+        (name = "usage", description = "Rate limit counters and LLM token usage"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Serve the OpenAPI document
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -0,0 +1,487 @@
+//! Per-tenant / per-key rate limiting and daily quotas.
+//!
+//! Requests are classified into three cost categories (search, write, extraction).
+//! Each category is limited by a token bucket (`requests_per_minute`) and a
+//! daily counter (`daily_quota`, reset at UTC midnight). Limits come from the
+//! `[rate_limit]` section of config.toml; usage is counted even when
+//! enforcement is disabled so `GET /api/v2/usage` always has data.
+
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{NaiveDate, Utc};
+use cortex_mem_config::{LimitRule, RateLimitConfig, RateLimitRules};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::{
+    error::AppError,
+    models::{UsageEntry, UsageResponse},
+    state::AppState,
+};
+
+/// Header carrying the caller's API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Endpoint cost category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Category {
+    Search,
+    Write,
+    Extraction,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::Search => "search",
+            Category::Write => "write",
+            Category::Extraction => "extraction",
+        }
+    }
+
+    /// Map a request onto a category; `None` means the route is not limited
+    pub fn classify(method: &Method, path: &str) -> Option<Self> {
+        let path = path.strip_prefix("/api/v2")?.trim_end_matches('/');
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        // Memory edits and deletions cost as much as creating one
+        if method == Method::PUT || method == Method::DELETE {
+            return matches!(segments.as_slice(), ["memories"]).then_some(Category::Write);
+        }
+        if method != Method::POST {
+            return None;
+        }
+
+        match segments.as_slice() {
+            ["search"] => Some(Category::Search),
+            ["sessions"] | ["sessions", "batch"] | ["sessions", _, "messages"] => {
+                Some(Category::Write)
+            }
+            ["filesystem", "write"] | ["memories"] => Some(Category::Write),
+            ["sessions", _, "close"] | ["sessions", _, "close-and-wait"] => {
+                Some(Category::Extraction)
+            }
+            ["automation", ..] => Some(Category::Extraction),
+            _ => None,
+        }
+    }
+
+    fn pick(self, rules: &RateLimitRules) -> Option<LimitRule> {
+        match self {
+            Category::Search => rules.search,
+            Category::Write => rules.write,
+            Category::Extraction => rules.extraction,
+        }
+    }
+}
+
+/// Who a counter belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Subject {
+    Tenant(String),
+    Key(String),
+}
+
+/// A rejected request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limited {
+    pub message: String,
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug)]
+struct Counter {
+    tokens: f64,
+    last_refill: Instant,
+    day: NaiveDate,
+    used_today: u64,
+    rejected_today: u64,
+}
+
+impl Counter {
+    fn new(rule: &LimitRule, now: Instant, today: NaiveDate) -> Self {
+        Self {
+            tokens: rule.requests_per_minute.unwrap_or(0) as f64,
+            last_refill: now,
+            day: today,
+            used_today: 0,
+            rejected_today: 0,
+        }
+    }
+
+    /// Refill the bucket and roll the daily counters over if the day changed
+    fn advance(&mut self, rule: &LimitRule, now: Instant, today: NaiveDate) {
+        if let Some(rpm) = rule.requests_per_minute {
+            let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * rpm as f64 / 60.0).min(rpm as f64);
+        }
+        self.last_refill = now;
+
+        if self.day != today {
+            self.day = today;
+            self.used_today = 0;
+            self.rejected_today = 0;
+        }
+    }
+
+    /// Seconds until this counter would admit a request, or `None` if it does now
+    fn wait_secs(&self, rule: &LimitRule, secs_to_midnight: u64) -> Option<u64> {
+        if let Some(quota) = rule.daily_quota {
+            if self.used_today >= quota {
+                return Some(secs_to_midnight.max(1));
+            }
+        }
+        if let Some(rpm) = rule.requests_per_minute {
+            if rpm == 0 {
+                return Some(secs_to_midnight.max(1));
+            }
+            if self.tokens < 1.0 {
+                let per_sec = rpm as f64 / 60.0;
+                return Some(((1.0 - self.tokens) / per_sec).ceil().max(1.0) as u64);
+            }
+        }
+        None
+    }
+}
+
+/// In-memory limiter shared by all requests
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    counters: Mutex<HashMap<(Subject, Category), Counter>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            counters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count one request and decide whether it may proceed
+    pub fn check(&self, tenant: &str, api_key: Option<&str>, category: Category) -> Result<(), Limited> {
+        let now_utc = Utc::now();
+        let today = now_utc.date_naive();
+        let secs_to_midnight = today
+            .succ_opt()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|midnight| (midnight - now_utc.naive_utc()).num_seconds().max(0) as u64)
+            .unwrap_or(0);
+        self.check_at(tenant, api_key, category, Instant::now(), today, secs_to_midnight)
+    }
+
+    fn check_at(
+        &self,
+        tenant: &str,
+        api_key: Option<&str>,
+        category: Category,
+        now: Instant,
+        today: NaiveDate,
+        secs_to_midnight: u64,
+    ) -> Result<(), Limited> {
+        let mut subjects = vec![(Subject::Tenant(tenant.to_string()), self.rule_for_tenant(tenant, category))];
+        // Only configured keys get their own counters; any other key is just
+        // counted against the tenant, so unknown keys can't grow the map
+        if let Some(key) = api_key.filter(|key| self.config.keys.contains_key(*key)) {
+            subjects.push((Subject::Key(key.to_string()), self.rule_for_key(key, category)));
+        }
+
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Check every subject before consuming anything, so a rejection by the
+        // key limit doesn't eat into the tenant budget (and vice versa)
+        let mut rejection: Option<Limited> = None;
+        for (subject, rule) in &subjects {
+            let counter = counters
+                .entry((subject.clone(), category))
+                .or_insert_with(|| Counter::new(rule, now, today));
+            counter.advance(rule, now, today);
+
+            if let Some(wait) = counter.wait_secs(rule, secs_to_midnight) {
+                let longest = rejection.as_ref().map(|r| r.retry_after_secs).unwrap_or(0);
+                if wait > longest {
+                    let who = match subject {
+                        Subject::Tenant(id) => format!("tenant '{}'", id),
+                        Subject::Key(key) => format!("API key '{}'", mask_key(key)),
+                    };
+                    rejection = Some(Limited {
+                        message: format!(
+                            "Rate limit exceeded for {} ({} requests)",
+                            who,
+                            category.as_str()
+                        ),
+                        retry_after_secs: wait,
+                    });
+                }
+            }
+        }
+
+        for (subject, rule) in &subjects {
+            if let Some(counter) = counters.get_mut(&(subject.clone(), category)) {
+                if rejection.is_some() {
+                    counter.rejected_today += 1;
+                } else {
+                    counter.used_today += 1;
+                    if rule.requests_per_minute.is_some() {
+                        counter.tokens -= 1.0;
+                    }
+                }
+            }
+        }
+
+        match rejection {
+            Some(limited) => Err(limited),
+            None => Ok(()),
+        }
+    }
+
+    /// Snapshot of today's counters
+    pub fn usage(&self) -> UsageResponse {
+        let today = Utc::now().date_naive();
+        let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut keys: Vec<&(Subject, Category)> = counters.keys().collect();
+        keys.sort();
+
+        let entries = keys
+            .into_iter()
+            .map(|key| {
+                let (subject, category) = key;
+                let counter = &counters[key];
+                let (requests_today, rejected_today) = if counter.day == today {
+                    (counter.used_today, counter.rejected_today)
+                } else {
+                    (0, 0)
+                };
+                let (kind, id, rule) = match subject {
+                    Subject::Tenant(id) => ("tenant", id.clone(), self.rule_for_tenant(id, *category)),
+                    Subject::Key(k) => ("key", mask_key(k), self.rule_for_key(k, *category)),
+                };
+                UsageEntry {
+                    subject: kind.to_string(),
+                    id,
+                    category: category.as_str().to_string(),
+                    requests_today,
+                    rejected_today,
+                    requests_per_minute: rule.requests_per_minute,
+                    daily_quota: rule.daily_quota,
+                }
+            })
+            .collect();
+
+        UsageResponse {
+            enabled: self.config.enabled,
+            date: today.format("%Y-%m-%d").to_string(),
+            entries,
+        }
+    }
+
+    fn rule_for_tenant(&self, tenant: &str, category: Category) -> LimitRule {
+        if !self.config.enabled {
+            return LimitRule::default();
+        }
+        self.config
+            .tenants
+            .get(tenant)
+            .and_then(|rules| category.pick(rules))
+            .or_else(|| category.pick(&self.config.defaults))
+            .unwrap_or_default()
+    }
+
+    fn rule_for_key(&self, key: &str, category: Category) -> LimitRule {
+        if !self.config.enabled {
+            return LimitRule::default();
+        }
+        self.config
+            .keys
+            .get(key)
+            .and_then(|rules| category.pick(rules))
+            .unwrap_or_default()
+    }
+}
+
+/// Keep only the first 4 characters of an API key for display
+fn mask_key(key: &str) -> String {
+    let prefix: String = key.chars().take(4).collect();
+    format!("{}…", prefix)
+}
+
+/// Axum middleware enforcing [`RateLimiter`] on search, write and extraction routes
+pub async fn enforce(State(state): State<Arc<AppState>>, req: Request, next: Next) -> Response {
+    let Some(category) = Category::classify(req.method(), req.uri().path()) else {
+        return next.run(req).await;
+    };
+
+    let tenant = state
+        .current_tenant_id
+        .read()
+        .await
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let api_key = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    match state.rate_limiter.check(&tenant, api_key.as_deref(), category) {
+        Ok(()) => next.run(req).await,
+        Err(limited) => {
+            tracing::warn!("🚦 {} (retry after {}s)", limited.message, limited.retry_after_secs);
+            AppError::TooManyRequests {
+                message: limited.message,
+                retry_after_secs: limited.retry_after_secs,
+            }
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+    }
+
+    fn limiter(rule: LimitRule) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            defaults: RateLimitRules {
+                search: Some(rule),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(Category::classify(&Method::POST, "/api/v2/search"), Some(Category::Search));
+        assert_eq!(
+            Category::classify(&Method::POST, "/api/v2/sessions/t1/messages"),
+            Some(Category::Write)
+        );
+        assert_eq!(Category::classify(&Method::POST, "/api/v2/sessions/batch"), Some(Category::Write));
+        assert_eq!(Category::classify(&Method::POST, "/api/v2/memories"), Some(Category::Write));
+        assert_eq!(
+            Category::classify(&Method::POST, "/api/v2/sessions/t1/close-and-wait"),
+            Some(Category::Extraction)
+        );
+        assert_eq!(
+            Category::classify(&Method::POST, "/api/v2/automation/reindex"),
+            Some(Category::Extraction)
+        );
+        assert_eq!(Category::classify(&Method::PUT, "/api/v2/memories"), Some(Category::Write));
+        assert_eq!(Category::classify(&Method::DELETE, "/api/v2/memories"), Some(Category::Write));
+        assert_eq!(Category::classify(&Method::DELETE, "/api/v2/sessions/t1"), None);
+        assert_eq!(Category::classify(&Method::GET, "/api/v2/memories"), None);
+        assert_eq!(Category::classify(&Method::GET, "/api/v2/sessions"), None);
+        assert_eq!(Category::classify(&Method::POST, "/api/v2/tenants/switch"), None);
+    }
+
+    #[test]
+    fn test_token_bucket_refills() {
+        let limiter = limiter(LimitRule {
+            requests_per_minute: Some(2),
+            daily_quota: None,
+        });
+        let t0 = Instant::now();
+
+        assert!(limiter.check_at("t", None, Category::Search, t0, day(), 100).is_ok());
+        assert!(limiter.check_at("t", None, Category::Search, t0, day(), 100).is_ok());
+        let limited = limiter
+            .check_at("t", None, Category::Search, t0, day(), 100)
+            .unwrap_err();
+        assert_eq!(limited.retry_after_secs, 30);
+
+        // Other categories are unaffected
+        assert!(limiter.check_at("t", None, Category::Write, t0, day(), 100).is_ok());
+
+        let t1 = t0 + Duration::from_secs(30);
+        assert!(limiter.check_at("t", None, Category::Search, t1, day(), 100).is_ok());
+    }
+
+    #[test]
+    fn test_daily_quota_resets_next_day() {
+        let limiter = limiter(LimitRule {
+            requests_per_minute: None,
+            daily_quota: Some(1),
+        });
+        let now = Instant::now();
+
+        assert!(limiter.check_at("t", None, Category::Search, now, day(), 600).is_ok());
+        let limited = limiter
+            .check_at("t", None, Category::Search, now, day(), 600)
+            .unwrap_err();
+        assert_eq!(limited.retry_after_secs, 600);
+
+        let tomorrow = day().succ_opt().unwrap();
+        assert!(limiter.check_at("t", None, Category::Search, now, tomorrow, 600).is_ok());
+    }
+
+    #[test]
+    fn test_key_limit_does_not_consume_tenant_budget() {
+        let mut config = RateLimitConfig {
+            enabled: true,
+            defaults: RateLimitRules {
+                search: Some(LimitRule {
+                    requests_per_minute: None,
+                    daily_quota: Some(2),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config.keys.insert(
+            "key-a".to_string(),
+            RateLimitRules {
+                search: Some(LimitRule {
+                    requests_per_minute: None,
+                    daily_quota: Some(1),
+                }),
+                ..Default::default()
+            },
+        );
+        let limiter = RateLimiter::new(config);
+        let now = Instant::now();
+
+        assert!(limiter.check_at("t", Some("key-a"), Category::Search, now, day(), 60).is_ok());
+        assert!(limiter.check_at("t", Some("key-a"), Category::Search, now, day(), 60).is_err());
+        // Rejected key request did not use the tenant's second slot
+        assert!(limiter.check_at("t", Some("key-b"), Category::Search, now, day(), 60).is_ok());
+        assert!(limiter.check_at("t", None, Category::Search, now, day(), 60).is_err());
+    }
+
+    #[test]
+    fn test_disabled_counts_without_limiting() {
+        let limiter = RateLimiter::new(RateLimitConfig::default());
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(limiter.check_at("t", None, Category::Search, now, day(), 60).is_ok());
+        }
+        let counters = limiter.counters.lock().unwrap();
+        let counter = &counters[&(Subject::Tenant("t".to_string()), Category::Search)];
+        assert_eq!(counter.used_today, 5);
+    }
+
+    #[test]
+    fn test_unknown_keys_use_tenant_counter() {
+        let limiter = limiter(LimitRule::default());
+        let now = Instant::now();
+        for i in 0..100 {
+            let key = format!("random-{}", i);
+            assert!(limiter.check_at("t", Some(&key), Category::Search, now, day(), 60).is_ok());
+        }
+        let counters = limiter.counters.lock().unwrap();
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters[&(Subject::Tenant("t".to_string()), Category::Search)].used_today, 100);
+    }
+}
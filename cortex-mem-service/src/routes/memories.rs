@@ -0,0 +1,12 @@
+use axum::{Router, routing::post};
+use crate::state::AppState;
+use std::sync::Arc;
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/",
+        post(crate::handlers::memories::remember)
+            .put(crate::handlers::memories::update_memory)
+            .delete(crate::handlers::memories::forget),
+    )
+}
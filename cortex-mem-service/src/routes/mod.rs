@@ -1,7 +1,8 @@
-use axum::Router;
+use axum::{Router, routing::get};
 use crate::state::AppState;
 
 mod filesystem;
+mod memories;
 mod sessions;
 mod search;
 mod automation;
@@ -13,10 +14,17 @@ pub fn api_routes() -> Router<std::sync::Arc<AppState>> {
         .nest("/filesystem", filesystem::routes())
         // Session routes
         .nest("/sessions", sessions::routes())
+        // Explicit memory editing routes
+        .nest("/memories", memories::routes())
         // Search routes
         .nest("/search", search::routes())
         // Automation routes
         .nest("/automation", automation::routes())
         // Tenant routes
         .nest("/tenants", tenants::routes())
+        // Rate limit usage counters and LLM token usage
+        .route("/usage", get(crate::handlers::usage::get_usage))
+        .route("/usage/tokens", get(crate::handlers::usage::get_token_usage))
+        // OpenAPI document
+        .route("/openapi.json", get(crate::openapi::openapi_json))
 }
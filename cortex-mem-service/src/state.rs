@@ -1,9 +1,13 @@
 use cortex_mem_core::{
-    CortexMem, CortexMemBuilder, EmbeddingClient, EmbeddingConfig, FilesystemOperations, LLMClient,
-    MemoryIndexManager, QdrantConfig, SessionManager, VectorSearchEngine,
+    AccessGuard, AccessPolicy, CacheConfig, CoordinatorConfig, CortexMem, CortexMemBuilder,
+    EmbeddingClient, EmbeddingConfig, FilesystemOperations, FixturesConfig, LLMClient,
+    LayersConfig, LlmResultCache, MemoryIndexManager, QdrantConfig, SessionManager, UsageConfig,
+    VectorSearchEngine,
     automation::{SyncConfig, SyncManager},
     memory_events::MemoryEvent,
 };
+use crate::access::Caller;
+use crate::rate_limit::RateLimiter;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -33,6 +37,20 @@ pub struct AppState {
     pub memory_event_tx: Arc<RwLock<Option<tokio::sync::mpsc::UnboundedSender<MemoryEvent>>>>,
     /// Whether to use LLM intent analysis before each search (from config.toml [cortex] section).
     pub enable_intent_analysis: bool,
+    /// Per-tenant / per-key rate limits and usage counters (from config.toml [rate_limit] section).
+    pub rate_limiter: Arc<RateLimiter>,
+    /// LLM result cache shared by every tenant runtime (from config.toml [llm_cache] section).
+    /// Keys cover prompt version, model and content, so tenants can safely share it.
+    pub llm_cache: Option<Arc<LlmResultCache>>,
+    /// Token usage accounting and budgets (from config.toml [usage] section).
+    /// Each tenant runtime records into its own `usage/` directory.
+    pub usage_config: UsageConfig,
+    /// Record / replay of LLM and embedding calls (from config.toml [fixtures] section).
+    pub fixtures_config: FixturesConfig,
+    /// L0/L1 generation mode (from config.toml [layers] section).
+    pub layers_config: LayersConfig,
+    /// Shared memory spaces and their ACLs (from config.toml [access] section).
+    pub access: Arc<AccessPolicy>,
     /// Set of tenant IDs that have already had their bootstrap vector sync executed.
     /// Prevents duplicate bootstrap runs when the same tenant is switched multiple times.
     bootstrapped_tenants: Arc<RwLock<HashSet<String>>>,
@@ -47,11 +65,60 @@ impl AppState {
 
         tracing::info!("Initializing Cortex Memory with unified automation...");
 
-        let (llm_client, embedding_config, qdrant_config) = Self::load_configs(config_path)?;
+        let (llm_client, embedding_config, qdrant_config) =
+            Self::load_configs(config_path, &data_dir)?;
 
-        let enable_intent_analysis = cortex_mem_config::Config::load(config_path)
+        let service_config = cortex_mem_config::Config::load(config_path).ok();
+        let enable_intent_analysis = service_config
+            .as_ref()
             .map(|c| c.cortex.enable_intent_analysis)
             .unwrap_or(true);
+        let llm_cache_config = service_config
+            .as_ref()
+            .map(|c| c.llm_cache.clone())
+            .unwrap_or_default();
+        let usage_config = service_config
+            .as_ref()
+            .map(|c| c.usage.clone())
+            .unwrap_or_default();
+        // Tenant runtimes are rooted in their tenant directory; resolve the
+        // default fixture directory here so all tenants share one, as the CLI does
+        let mut fixtures_config = service_config
+            .as_ref()
+            .map(|c| c.fixtures.clone())
+            .unwrap_or_default();
+        fixtures_config.dir = Some(
+            fixtures_config
+                .dir_or_default(&data_dir)
+                .to_string_lossy()
+                .into_owned(),
+        );
+        let layers_config = service_config
+            .as_ref()
+            .map(|c| c.layers.clone())
+            .unwrap_or_default();
+        let access = Arc::new(AccessPolicy::new(
+            service_config
+                .as_ref()
+                .map(|c| c.access.clone())
+                .unwrap_or_default(),
+        ));
+        if access.is_enabled() {
+            tracing::info!("🔒 Access control enabled");
+        }
+        let rate_limit_config = service_config.map(|c| c.rate_limit).unwrap_or_default();
+        if rate_limit_config.enabled {
+            tracing::info!("🚦 Rate limiting enabled");
+        }
+        let llm_cache = llm_cache_config.enabled.then(|| {
+            Arc::new(LlmResultCache::new(CacheConfig::from_config(
+                &llm_cache_config,
+                &data_dir,
+            )))
+        });
+        if llm_cache.as_ref().is_some_and(|c| c.is_persistent()) {
+            tracing::info!("💾 Persistent LLM result cache enabled");
+        }
 
         let cortex = Arc::new(
             Self::build_runtime(
@@ -60,6 +127,10 @@ impl AppState {
                 llm_client.clone(),
                 embedding_config,
                 qdrant_config,
+                llm_cache.clone(),
+                &usage_config,
+                &fixtures_config,
+                &layers_config,
             )
             .await?,
         );
@@ -72,7 +143,8 @@ impl AppState {
         let embedding_client = cortex.embedding();
         let vector_store = cortex.vector_store();
         let memory_event_tx = cortex.memory_event_tx();
-        let vector_engine = Self::build_vector_engine(&cortex, enable_intent_analysis);
+        let vector_engine =
+            Self::build_vector_engine(&cortex, enable_intent_analysis, llm_cache.clone());
 
         Ok(Self {
             cortex: Arc::new(RwLock::new(cortex)),
@@ -86,11 +158,47 @@ impl AppState {
             current_tenant_id: Arc::new(RwLock::new(None)),
             memory_event_tx: Arc::new(RwLock::new(memory_event_tx)),
             enable_intent_analysis,
+            rate_limiter: Arc::new(RateLimiter::new(rate_limit_config)),
+            llm_cache,
+            usage_config,
+            fixtures_config,
+            layers_config,
+            access,
             bootstrapped_tenants: Arc::new(RwLock::new(HashSet::new())),
             config_path: config_path.to_path_buf(),
         })
     }
 
+    /// Bind the access policy to a caller (None when access control is off)
+    pub fn access_guard(&self, caller: &Caller) -> Option<AccessGuard> {
+        self.access.is_enabled().then(|| {
+            self.access.guard(
+                caller.agent.clone().unwrap_or_else(|| "default".to_string()),
+                caller.user.clone().unwrap_or_else(|| "default".to_string()),
+            )
+        })
+    }
+
+    /// Fail with `PermissionDenied` (403) unless the caller may read the URI
+    pub fn check_read(&self, caller: &Caller, uri: &str) -> crate::error::Result<()> {
+        match self.access_guard(caller) {
+            Some(guard) => Ok(guard
+                .check_read(&to_uri(uri))
+                .map_err(cortex_mem_core::Error::from)?),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail with `PermissionDenied` (403) unless the caller may write the URI
+    pub fn check_write(&self, caller: &Caller, uri: &str) -> crate::error::Result<()> {
+        match self.access_guard(caller) {
+            Some(guard) => Ok(guard
+                .check_write(&to_uri(uri))
+                .map_err(cortex_mem_core::Error::from)?),
+            None => Ok(()),
+        }
+    }
+
     /// Get current SessionManager handle
     pub async fn current_session_manager(&self) -> Arc<RwLock<SessionManager>> {
         self.session_manager.read().await.clone()
@@ -99,6 +207,7 @@ impl AppState {
     fn build_vector_engine(
         cortex: &Arc<CortexMem>,
         enable_intent_analysis: bool,
+        llm_cache: Option<Arc<LlmResultCache>>,
     ) -> Option<Arc<VectorSearchEngine>> {
         let filesystem = cortex.filesystem();
         let embedding_client = cortex.embedding();
@@ -119,6 +228,9 @@ impl AppState {
             }
             engine = engine.with_index_manager(index_manager);
             engine = engine.with_intent_analysis(enable_intent_analysis);
+            if let Some(cache) = llm_cache {
+                engine = engine.with_llm_cache(cache);
+            }
             Some(Arc::new(engine))
         } else {
             None
@@ -131,12 +243,25 @@ impl AppState {
         llm_client: Option<Arc<dyn LLMClient>>,
         embedding_config: Option<EmbeddingConfig>,
         qdrant_config: Option<QdrantConfig>,
+        llm_cache: Option<Arc<LlmResultCache>>,
+        usage_config: &UsageConfig,
+        fixtures_config: &FixturesConfig,
+        layers_config: &LayersConfig,
     ) -> anyhow::Result<CortexMem> {
         let expected_vector = qdrant_config.is_some() && embedding_config.is_some();
         let mut last_error: Option<anyhow::Error> = None;
 
         for attempt in 1..=3 {
-            let mut builder = CortexMemBuilder::new(runtime_root);
+            let mut builder = CortexMemBuilder::new(runtime_root).with_coordinator_config(
+                CoordinatorConfig {
+                    enable_cache: llm_cache.is_some(),
+                    llm_cache: llm_cache.clone(),
+                    layer_mode: layers_config.mode,
+                    ..CoordinatorConfig::default()
+                },
+            )
+            .with_usage(usage_config.clone())
+            .with_fixtures(fixtures_config.clone());
 
             if let Some(llm) = llm_client.clone() {
                 builder = builder.with_llm(llm);
@@ -286,8 +411,12 @@ impl AppState {
     }
 
     /// Load configurations from config file or environment variables
+    ///
+    /// The persistent embedding cache lives under the base data directory, so
+    /// every tenant runtime reuses the same vectors.
     fn load_configs(
         config_path: &Path,
+        data_dir: &Path,
     ) -> anyhow::Result<(
         Option<Arc<dyn LLMClient>>,
         Option<EmbeddingConfig>,
@@ -300,11 +429,14 @@ impl AppState {
             // LLM client
             let llm_client = {
                 let llm_config = cortex_mem_core::llm::client::LLMConfig {
+                    provider: config.llm.provider,
                     api_base_url: config.llm.api_base_url.clone(),
                     api_key: config.llm.api_key.clone(),
                     model_efficient: config.llm.model_efficient.clone(),
                     temperature: 0.1,
                     max_tokens: 4096,
+                    api_version: config.llm.api_version.clone(),
+                    models: config.llm.models.clone(),
                 };
                 match cortex_mem_core::llm::LLMClientImpl::new(llm_config) {
                     Ok(client) => {
@@ -320,13 +452,17 @@ impl AppState {
 
             // Embedding config
             let embedding_config = EmbeddingConfig {
+                provider: config.embedding.provider,
                 api_base_url: config.embedding.api_base_url,
                 api_key: config.embedding.api_key,
                 model_name: config.embedding.model_name,
                 batch_size: config.embedding.batch_size,
                 timeout_secs: config.embedding.timeout_secs,
+                api_version: config.embedding.api_version,
+                local_model_dir: config.embedding.local_model_dir.map(Into::into),
                 ..EmbeddingConfig::default()
-            };
+            }
+            .with_persistent_cache(&config.embedding_cache, data_dir);
 
             // Qdrant config
             let qdrant_config = QdrantConfig {
@@ -354,6 +490,7 @@ impl AppState {
                     model_efficient: model,
                     temperature: 0.1,
                     max_tokens: 4096,
+                    ..Default::default()
                 };
                 match cortex_mem_core::llm::LLMClientImpl::new(config) {
                     Ok(client) => {
@@ -426,7 +563,8 @@ impl AppState {
         std::fs::create_dir_all(tenant_root.join("session"))?;
         std::fs::create_dir_all(tenant_root.join("user"))?;
 
-        let (llm_client, embedding_config, qdrant_config) = Self::load_configs(&self.config_path)?;
+        let (llm_client, embedding_config, qdrant_config) =
+            Self::load_configs(&self.config_path, &self.data_dir)?;
         let new_cortex = Arc::new(
             Self::build_runtime(
                 &tenant_root,
@@ -434,6 +572,10 @@ impl AppState {
                 llm_client,
                 embedding_config,
                 qdrant_config,
+                self.llm_cache.clone(),
+                &self.usage_config,
+                &self.fixtures_config,
+                &self.layers_config,
             )
             .await?,
         );
@@ -465,7 +607,11 @@ impl AppState {
         let new_session_manager = new_cortex.session_manager();
         let new_vector_store = new_cortex.vector_store();
         let new_memory_event_tx = new_cortex.memory_event_tx();
-        let new_vector_engine = Self::build_vector_engine(&new_cortex, self.enable_intent_analysis);
+        let new_vector_engine = Self::build_vector_engine(
+            &new_cortex,
+            self.enable_intent_analysis,
+            self.llm_cache.clone(),
+        );
 
         {
             let mut cortex_guard = self.cortex.write().await;
@@ -522,3 +668,8 @@ impl AppState {
         tenants
     }
 }
+
+/// Accept both `cortex://user/..` and the bare `user/..` form of path parameters
+fn to_uri(path: &str) -> String {
+    format!("cortex://{}", path.trim_start_matches("cortex://"))
+}
@@ -1,12 +1,14 @@
 pub mod errors;
 pub mod mcp;
 pub mod operations;
+pub mod policy;
 pub mod tools;
 pub mod types;
 
 pub use errors::{Result, ToolsError};
 pub use mcp::{ToolDefinition, get_mcp_tool_definition, get_mcp_tool_definitions};
-pub use operations::MemoryOperations;
+pub use operations::{MemoryOperations, MemoryOperationsBuilder};
+pub use policy::{TOOL_CATALOG, ToolAccess, ToolOverride, ToolPolicy, ToolsConfig, tool_access};
 pub use types::*;
 
 // 重新导出长期运行服务 API 相关类型
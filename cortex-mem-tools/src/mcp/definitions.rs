@@ -84,6 +84,25 @@ pub fn get_mcp_tool_definitions() -> Vec<ToolDefinition> {
                 "required": ["query"]
             }),
         },
+        ToolDefinition {
+            name: "guidance".to_string(),
+            description: include_str!("../docs/guidance.md").to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "task": {
+                        "type": "string",
+                        "description": "The task you are about to work on"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of skills (default: 5)",
+                        "default": 5
+                    }
+                },
+                "required": ["task"]
+            }),
+        },
         // ==================== Storage Tools ====================
         ToolDefinition {
             name: "store".to_string(),
@@ -104,6 +123,10 @@ pub fn get_mcp_tool_definitions() -> Vec<ToolDefinition> {
                         "enum": ["user", "assistant", "system"],
                         "description": "Role of the message sender (default: user)",
                         "default": "user"
+                    },
+                    "space": {
+                        "type": "string",
+                        "description": "Shared space to store the content in instead of the session (requires write access)"
                     }
                 },
                 "required": ["content"]
@@ -122,6 +145,50 @@ pub fn get_mcp_tool_definitions() -> Vec<ToolDefinition> {
                 }
             }),
         },
+        ToolDefinition {
+            name: "store_batch".to_string(),
+            description: include_str!("../docs/store_batch.md").to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "messages": {
+                        "type": "array",
+                        "description": "Messages to import",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "thread_id": {
+                                    "type": "string",
+                                    "description": "Target session ID (created if missing)"
+                                },
+                                "role": {
+                                    "type": "string",
+                                    "enum": ["user", "assistant", "system"]
+                                },
+                                "content": {
+                                    "type": "string"
+                                },
+                                "timestamp": {
+                                    "type": "string",
+                                    "format": "date-time",
+                                    "description": "Original message time (RFC 3339)"
+                                },
+                                "metadata": {
+                                    "type": "object"
+                                }
+                            },
+                            "required": ["thread_id", "role", "content"]
+                        }
+                    },
+                    "extract": {
+                        "type": "boolean",
+                        "description": "Commit each touched session afterwards to trigger memory extraction",
+                        "default": false
+                    }
+                },
+                "required": ["messages"]
+            }),
+        },
         // ==================== Filesystem Tools ====================
         ToolDefinition {
             name: "ls".to_string(),
@@ -143,6 +210,29 @@ pub fn get_mcp_tool_definitions() -> Vec<ToolDefinition> {
                         "type": "boolean",
                         "description": "Whether to include L0 abstracts for each file",
                         "default": false
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Glob (e.g. \"*.md\") or substring filter on entry names"
+                    },
+                    "sort": {
+                        "type": "string",
+                        "enum": ["name", "modified", "size"],
+                        "description": "Sort key",
+                        "default": "name"
+                    },
+                    "descending": {
+                        "type": "boolean",
+                        "description": "Reverse the sort order (newest / largest first)",
+                        "default": false
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum entries per page (default 100, 0 for all)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page"
                     }
                 }
             }),
@@ -214,11 +304,99 @@ pub fn get_mcp_tool_definitions() -> Vec<ToolDefinition> {
                         },
                         "description": "Which layers to return in matches",
                         "default": ["L0"]
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Glob (e.g. \"*.md\") or substring filter on matched file names"
+                    },
+                    "sort": {
+                        "type": "string",
+                        "enum": ["name", "modified", "size"],
+                        "description": "Sort key",
+                        "default": "name"
+                    },
+                    "descending": {
+                        "type": "boolean",
+                        "description": "Reverse the sort order (newest / largest first)",
+                        "default": false
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum matches per page (all when omitted)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page"
                     }
                 },
                 "required": ["query"]
             }),
         },
+        // ==================== Memory Editing Tools ====================
+        ToolDefinition {
+            name: "remember".to_string(),
+            description: include_str!("../docs/remember.md").to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "memory_type": {
+                        "type": "string",
+                        "enum": ["preference", "entity", "event", "case", "personal_info", "work_history", "relationship", "goal", "skill", "instruction"],
+                        "description": "Type of memory to store"
+                    },
+                    "key": {
+                        "type": "string",
+                        "description": "Identifies the memory within its type (topic, name, title, ...); an existing key is updated"
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "The fact to remember, in Markdown"
+                    },
+                    "confidence": {
+                        "type": "number",
+                        "description": "Confidence 0-1 (default: 0.9)",
+                        "default": 0.9
+                    }
+                },
+                "required": ["memory_type", "key", "content"]
+            }),
+        },
+        ToolDefinition {
+            name: "update_memory".to_string(),
+            description: "Correct an existing memory.\n\nReplaces the content of the memory at `uri` (e.g. cortex://user/{id}/preferences/pref_xxx.md), keeping its type and key. Layers and vectors are refreshed in the background.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "uri": {
+                        "type": "string",
+                        "description": "URI of the memory file"
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "New content, in Markdown"
+                    },
+                    "confidence": {
+                        "type": "number",
+                        "description": "New confidence 0-1 (default: keep the stored one)"
+                    }
+                },
+                "required": ["uri", "content"]
+            }),
+        },
+        ToolDefinition {
+            name: "forget".to_string(),
+            description: "Forget a memory.\n\nRemoves the memory at `uri` from the memory index, the filesystem and the vector database, and refreshes the layers of its directory.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "uri": {
+                        "type": "string",
+                        "description": "URI of the memory file to forget"
+                    }
+                },
+                "required": ["uri"]
+            }),
+        },
         // ==================== Management Tools ====================
         ToolDefinition {
             name: "delete".to_string(),
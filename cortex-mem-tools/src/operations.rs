@@ -1,10 +1,24 @@
 use crate::{errors::*, types::*};
 use cortex_mem_core::{
+    AccessGuard,
+    AccessPolicy,
+    CacheConfig,
+    CoordinatorConfig,
     CortexFilesystem,
+    EmbeddingCacheConfig,
     FilesystemOperations,
+    FixtureStore,
+    FixturesConfig,
+    LayerMode,
+    LayersConfig,
+    LlmCacheConfig,
+    LlmResultCache,
     MemoryIndexManager,
+    MeteredLLMClient,
     SessionConfig,
     SessionManager,
+    UsageConfig,
+    UsageTracker,
     automation::{
         AbstractConfig, AutoIndexer, AutomationConfig, AutomationManager, IndexerConfig,
         LayerGenerationConfig, LayerGenerator, OverviewConfig, SyncConfig, SyncManager,
@@ -14,7 +28,7 @@ use cortex_mem_core::{
     layers::manager::LayerManager,
     llm::LLMClient,
     search::VectorSearchEngine,
-    vector_store::{QdrantVectorStore, VectorStore},
+    vector_store::{QdrantVectorStore, VectorMigration, VectorStore},
 };
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -38,16 +52,126 @@ pub struct MemoryOperations {
     pub(crate) embedding_client: Arc<EmbeddingClient>,
     pub(crate) vector_store: Arc<QdrantVectorStore>,
     pub(crate) llm_client: Arc<dyn LLMClient>,
+    /// LLM 结果缓存（L0/L1 生成与意图分析共享），未启用时为 None
+    pub(crate) llm_cache: Option<Arc<LlmResultCache>>,
+    /// Token 用量统计，`[usage]` 未启用时为 None
+    pub(crate) usage: Option<Arc<UsageTracker>>,
+    /// L0/L1 生成方式（`[layers] mode`）
+    pub(crate) layer_mode: LayerMode,
 
     pub(crate) default_user_id: String,
     pub(crate) default_agent_id: String,
 
+    /// 访问控制（共享空间 ACL），未启用时为 None
+    pub(crate) access: Option<AccessGuard>,
+
     /// 事件发送器，用于异步触发层级生成
     pub(crate) memory_event_tx:
         Option<tokio::sync::mpsc::UnboundedSender<cortex_mem_core::memory_events::MemoryEvent>>,
 
     /// 事件协调器引用，用于同步等待后台处理完成
     pub(crate) event_coordinator: Option<Arc<cortex_mem_core::MemoryEventCoordinator>>,
+
+    /// 后台任务（事件协调器、AutomationManager、启动同步、遗忘曲线清理），实例释放时终止
+    pub(crate) background_tasks: Vec<tokio::task::AbortHandle>,
+}
+
+impl Drop for MemoryOperations {
+    fn drop(&mut self) {
+        // The coordinator loop never sees its channel close (the cascade
+        // updater keeps a sender), so it has to be stopped explicitly
+        for task in self.background_tasks.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+/// Builder of [`MemoryOperations`] (see [`MemoryOperations::builder`])
+pub struct MemoryOperationsBuilder {
+    data_dir: String,
+    tenant_id: String,
+    llm_client: Arc<dyn LLMClient>,
+    qdrant: cortex_mem_config::QdrantConfig,
+    embedding: cortex_mem_config::EmbeddingConfig,
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    access: Option<Arc<AccessPolicy>>,
+    enable_intent_analysis: bool,
+    llm_cache: LlmCacheConfig,
+    embedding_cache: EmbeddingCacheConfig,
+    usage: UsageConfig,
+    fixtures: FixturesConfig,
+    layers: LayersConfig,
+}
+
+impl MemoryOperationsBuilder {
+    /// Act for a specific user (defaults to `default`, never the tenant ID)
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Act as a specific agent (see [`MemoryOperations::with_agent_id`])
+    pub fn with_agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    /// Enforce an access policy (see [`MemoryOperations::with_access`])
+    pub fn with_access(mut self, policy: Arc<AccessPolicy>) -> Self {
+        self.access = Some(policy);
+        self
+    }
+
+    /// Enable or disable LLM intent analysis of search queries (default: on)
+    pub fn with_intent_analysis(mut self, enabled: bool) -> Self {
+        self.enable_intent_analysis = enabled;
+        self
+    }
+
+    /// Cache LLM results for L0/L1 generation and intent analysis (`[llm_cache]`)
+    pub fn with_llm_cache(mut self, config: &LlmCacheConfig) -> Self {
+        self.llm_cache = config.clone();
+        self
+    }
+
+    /// Persist embedding vectors across restarts (`[embedding_cache]`)
+    pub fn with_embedding_cache(mut self, config: &EmbeddingCacheConfig) -> Self {
+        self.embedding_cache = config.clone();
+        self
+    }
+
+    /// Account token usage and enforce budgets (`[usage]`)
+    pub fn with_usage(mut self, config: &UsageConfig) -> Self {
+        self.usage = config.clone();
+        self
+    }
+
+    /// Record or replay LLM and embedding calls (`[fixtures]`)
+    pub fn with_fixtures(mut self, config: &FixturesConfig) -> Self {
+        self.fixtures = config.clone();
+        self
+    }
+
+    /// Choose how L0/L1 layers are generated (`[layers]`)
+    pub fn with_layers(mut self, config: &LayersConfig) -> Self {
+        self.layers = config.clone();
+        self
+    }
+
+    /// Connect to Qdrant and the embedding service and start the background pipelines
+    pub async fn build(mut self) -> Result<MemoryOperations> {
+        let agent_id = self.agent_id.take();
+        let access = self.access.take();
+        let mut operations = MemoryOperations::open(self).await?;
+        if let Some(agent_id) = agent_id {
+            operations = operations.with_agent_id(agent_id);
+        }
+        if let Some(policy) = access {
+            operations = operations.with_access(policy);
+        }
+        Ok(operations)
+    }
 }
 
 impl MemoryOperations {
@@ -94,48 +218,154 @@ impl MemoryOperations {
         self.memory_event_tx.as_ref()
     }
 
+    /// Subscribe to the memory events handled by the event coordinator
+    /// (None when the coordinator is not running)
+    pub fn subscribe_memory_events(
+        &self,
+    ) -> Option<tokio::sync::broadcast::Receiver<cortex_mem_core::memory_events::MemoryEvent>>
+    {
+        self.event_coordinator
+            .as_ref()
+            .map(|coordinator| coordinator.observer().subscribe())
+    }
+
     /// Get the vector store (for admin operations like prune, reindex)
     pub fn vector_store(&self) -> &Arc<QdrantVectorStore> {
         &self.vector_store
     }
 
-    /// Create from data directory with tenant isolation, LLM support, and vector search
+    /// Get the shared LLM result cache (None when `[llm_cache]` is disabled)
+    pub fn llm_cache(&self) -> Option<&Arc<LlmResultCache>> {
+        self.llm_cache.as_ref()
+    }
+
+    /// Get the token usage tracker (None when `[usage]` is disabled)
+    pub fn usage(&self) -> Option<&Arc<UsageTracker>> {
+        self.usage.as_ref()
+    }
+
+    /// Get the L0/L1 generation mode (`[layers] mode`)
+    pub fn layer_mode(&self) -> LayerMode {
+        self.layer_mode
+    }
+
+    /// Start building operations from the `cortex_mem_config` sections
+    ///
+    /// Every other section keeps its default until set on the builder; see
+    /// [`MemoryOperations::from_config`] to apply a whole config file.
+    pub fn builder(
+        data_dir: impl Into<String>,
+        tenant_id: impl Into<String>,
+        llm_client: Arc<dyn LLMClient>,
+        qdrant: &cortex_mem_config::QdrantConfig,
+        embedding: &cortex_mem_config::EmbeddingConfig,
+    ) -> MemoryOperationsBuilder {
+        MemoryOperationsBuilder {
+            data_dir: data_dir.into(),
+            tenant_id: tenant_id.into(),
+            llm_client,
+            qdrant: qdrant.clone(),
+            embedding: embedding.clone(),
+            user_id: None,
+            agent_id: None,
+            access: None,
+            enable_intent_analysis: true,
+            llm_cache: LlmCacheConfig::default(),
+            embedding_cache: EmbeddingCacheConfig::default(),
+            usage: UsageConfig::default(),
+            fixtures: FixturesConfig::default(),
+            layers: LayersConfig::default(),
+        }
+    }
+
+    /// Start building operations with every section of a config file applied
     ///
-    /// This is the primary constructor that requires all dependencies.
-    pub async fn new(
-        data_dir: &str,
+    /// The data directory comes from `[cortex]`; the `[access]` policy is
+    /// bound to the agent and user set on the builder.
+    pub fn from_config(
+        config: &cortex_mem_config::Config,
         tenant_id: impl Into<String>,
         llm_client: Arc<dyn LLMClient>,
-        qdrant_url: &str,
-        qdrant_collection: &str,
-        qdrant_api_key: Option<&str>,
-        embedding_api_base_url: &str,
-        embedding_api_key: &str,
-        embedding_model_name: &str,
-        embedding_dim: Option<usize>,
-        user_id: Option<String>,
-        enable_intent_analysis: bool,
-    ) -> Result<Self> {
-        let tenant_id = tenant_id.into();
+    ) -> MemoryOperationsBuilder {
+        Self::builder(
+            config.cortex.data_dir(),
+            tenant_id,
+            llm_client,
+            &config.qdrant,
+            &config.embedding,
+        )
+        .with_intent_analysis(config.cortex.enable_intent_analysis)
+        .with_llm_cache(&config.llm_cache)
+        .with_embedding_cache(&config.embedding_cache)
+        .with_usage(&config.usage)
+        .with_fixtures(&config.fixtures)
+        .with_layers(&config.layers)
+        .with_access(Arc::new(AccessPolicy::new(config.access.clone())))
+    }
+
+    /// Create from data directory with tenant isolation, LLM support, and vector search
+    async fn open(options: MemoryOperationsBuilder) -> Result<Self> {
+        let MemoryOperationsBuilder {
+            data_dir,
+            tenant_id,
+            llm_client,
+            qdrant,
+            embedding,
+            user_id,
+            agent_id: _,
+            access: _,
+            enable_intent_analysis,
+            llm_cache: llm_cache_config,
+            embedding_cache: embedding_cache_config,
+            usage: usage_config,
+            fixtures: fixtures_config,
+            layers: layers_config,
+        } = options;
+        let data_dir = data_dir.as_str();
+        let embedding_dim = qdrant.embedding_dim;
+        let embedding_model_name = embedding.model_name.as_str();
+        let layer_mode = layers_config.mode;
         let filesystem = Arc::new(CortexFilesystem::with_tenant(data_dir, &tenant_id));
         filesystem.initialize().await?;
 
+        // Record / replay of LLM and embedding calls (offline tests); fixtures
+        // are keyed by request content, so tenants share one directory
+        let fixtures = FixtureStore::from_config(&fixtures_config, std::path::Path::new(data_dir))?;
+        let llm_client = match &fixtures {
+            Some(store) => store.wrap_llm(llm_client),
+            None => llm_client,
+        };
+
+        // Token usage accounting: every LLM call below goes through the
+        // metered client, so budgets apply to all of them
+        let usage = usage_config.enabled.then(|| {
+            Arc::new(UsageTracker::new(
+                filesystem.base_path().join("usage"),
+                &usage_config,
+            ))
+        });
+        let llm_client: Arc<dyn LLMClient> = match &usage {
+            Some(tracker) => Arc::new(MeteredLLMClient::new(llm_client, tracker.clone())),
+            None => llm_client,
+        };
+
         // 创建EventBus用于自动化
         let (event_bus, event_rx_main) = EventBus::new();
 
         // Initialize Qdrant first (needed for MemoryEventCoordinator)
-        tracing::info!("Initializing Qdrant vector store: {}", qdrant_url);
+        tracing::info!("Initializing Qdrant vector store: {}", qdrant.url);
         let qdrant_config = cortex_mem_core::QdrantConfig {
-            url: qdrant_url.to_string(),
-            collection_name: qdrant_collection.to_string(),
+            url: qdrant.url.clone(),
+            collection_name: qdrant.collection_name.clone(),
             embedding_dim,
             timeout_secs: 30,
-            api_key: qdrant_api_key
-                .map(|s| s.to_string())
+            api_key: qdrant
+                .api_key
+                .clone()
                 .or_else(|| std::env::var("QDRANT_API_KEY").ok()),
             tenant_id: Some(tenant_id.clone()), // 设置租户ID
         };
-        let vector_store = Arc::new(QdrantVectorStore::new(&qdrant_config).await?);
+        let vector_store = QdrantVectorStore::new(&qdrant_config).await?;
         tracing::info!(
             "Qdrant connected successfully, collection: {}",
             qdrant_config.get_collection_name()
@@ -147,16 +377,84 @@ impl MemoryOperations {
             embedding_model_name
         );
         let embedding_config = EmbeddingConfig {
-            api_base_url: embedding_api_base_url.to_string(),
-            api_key: embedding_api_key.to_string(),
-            model_name: embedding_model_name.to_string(),
+            provider: embedding.provider,
+            api_base_url: embedding.api_base_url.clone(),
+            api_key: embedding.api_key.clone(),
+            model_name: embedding.model_name.clone(),
             batch_size: 10,
             timeout_secs: 30,
+            api_version: embedding.api_version.clone(),
+            local_model_dir: embedding.local_model_dir.as_deref().map(Into::into),
             ..EmbeddingConfig::default()
+        }
+        .with_persistent_cache(&embedding_cache_config, data_dir);
+        let embedding_client = EmbeddingClient::new(embedding_config)?;
+        let embedding_client = match &fixtures {
+            Some(store) => embedding_client.with_fixtures(store.clone()),
+            None => embedding_client,
         };
-        let embedding_client = Arc::new(EmbeddingClient::new(embedding_config)?);
+        let embedding_client = Arc::new(match &usage {
+            Some(tracker) => embedding_client.with_usage(tracker.clone()),
+            None => embedding_client,
+        });
         tracing::info!("Embedding client initialized");
 
+        // An embedding model migration in progress: also write new vectors
+        // to its target collection so the backfill never falls behind
+        let vector_store = match VectorMigration::load(&filesystem.base_path()) {
+            Ok(Some(migration))
+                if migration.source_collection == vector_store.collection_name() =>
+            {
+                if migration.phase.is_dual_write() {
+                    let target_config = migration
+                        .target
+                        .embedding_config(&embedding.api_key)
+                        .with_persistent_cache(&embedding_cache_config, data_dir);
+                    match EmbeddingClient::new(target_config) {
+                        Ok(target_client) => {
+                            let target_client = match &fixtures {
+                                Some(store) => target_client.with_fixtures(store.clone()),
+                                None => target_client,
+                            };
+                            tracing::info!(
+                                "Embedding migration to {} in progress, dual-writing to {}",
+                                migration.target.model_name,
+                                migration.target_collection
+                            );
+                            vector_store.with_dual_write(
+                                migration.target_collection.clone(),
+                                Arc::new(target_client),
+                            )
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                "Embedding migration to {} in progress but its client failed: {}",
+                                migration.target.model_name,
+                                e
+                            );
+                            vector_store
+                        }
+                    }
+                } else {
+                    if migration.target.model_name != embedding_model_name {
+                        tracing::warn!(
+                            "{} was migrated to embedding model {}, but {} is configured",
+                            migration.source_collection,
+                            migration.target.model_name,
+                            embedding_model_name
+                        );
+                    }
+                    vector_store
+                }
+            }
+            Ok(_) => vector_store,
+            Err(e) => {
+                tracing::warn!("Failed to read embedding migration state: {}", e);
+                vector_store
+            }
+        };
+        let vector_store = Arc::new(vector_store);
+
         // 🔧 Fix: ensure Qdrant collection exists even when embedding_dim is not in config.
         // When embedding_dim is None, QdrantVectorStore::new skips ensure_collection.
         // We probe the real dimension by running a test embedding and create the collection.
@@ -180,19 +478,34 @@ impl MemoryOperations {
             }
         }
 
+        // LLM result cache shared by the coordinator, LayerGenerator and intent analysis
+        let llm_cache = llm_cache_config.enabled.then(|| {
+            Arc::new(LlmResultCache::new(CacheConfig::from_config(
+                &llm_cache_config,
+                data_dir,
+            )))
+        });
+
         // Create MemoryEventCoordinator BEFORE SessionManager
-        let (coordinator, memory_event_tx, event_rx) = cortex_mem_core::MemoryEventCoordinator::new(
-            filesystem.clone(),
-            llm_client.clone(),
-            embedding_client.clone(),
-            vector_store.clone(),
-        );
+        let (coordinator, memory_event_tx, event_rx) =
+            cortex_mem_core::MemoryEventCoordinator::new_with_config(
+                filesystem.clone(),
+                llm_client.clone(),
+                embedding_client.clone(),
+                vector_store.clone(),
+                CoordinatorConfig {
+                    enable_cache: llm_cache.is_some(),
+                    llm_cache: llm_cache.clone(),
+                    layer_mode,
+                    ..CoordinatorConfig::default()
+                },
+            );
 
         // 保存 coordinator 克隆用于后台任务等待
         let coordinator_clone = coordinator.clone();
 
         // Start the coordinator event loop in background
-        tokio::spawn(coordinator.start(event_rx));
+        let coordinator_task = tokio::spawn(coordinator.start(event_rx)).abort_handle();
         tracing::info!("MemoryEventCoordinator started for incremental updates");
 
         let config = SessionConfig::default();
@@ -207,7 +520,9 @@ impl MemoryOperations {
         let session_manager = Arc::new(RwLock::new(session_manager));
 
         // LLM-enabled LayerManager for high-quality L0/L1 generation
-        let layer_manager = Arc::new(LayerManager::new(filesystem.clone(), llm_client.clone()));
+        let layer_manager = Arc::new(
+            LayerManager::new(filesystem.clone(), llm_client.clone()).with_mode(layer_mode),
+        );
 
         // Create shared MemoryIndexManager (used by VectorSearchEngine for archived filtering
         // and by MemoryCleanupService for forgetting curve evictions)
@@ -217,17 +532,19 @@ impl MemoryOperations {
         // Wire up:
         //   - memory_event_tx  → search hits emit MemoryAccessed events (forgetting mechanism)
         //   - index_manager    → archived memories are filtered from search results
-        let vector_engine = Arc::new(
-            VectorSearchEngine::with_llm(
-                vector_store.clone(),
-                embedding_client.clone(),
-                filesystem.clone(),
-                llm_client.clone(),
-            )
-            .with_memory_event_tx(memory_event_tx.clone())
-            .with_index_manager(index_manager.clone())
-            .with_intent_analysis(enable_intent_analysis),
-        );
+        let mut vector_engine = VectorSearchEngine::with_llm(
+            vector_store.clone(),
+            embedding_client.clone(),
+            filesystem.clone(),
+            llm_client.clone(),
+        )
+        .with_memory_event_tx(memory_event_tx.clone())
+        .with_index_manager(index_manager.clone())
+        .with_intent_analysis(enable_intent_analysis);
+        if let Some(cache) = &llm_cache {
+            vector_engine = vector_engine.with_llm_cache(cache.clone());
+        }
+        let vector_engine = Arc::new(vector_engine);
         tracing::info!("Vector search engine created with LLM, event tracking, and archived filter");
 
         // 使用传入的user_id。
@@ -268,7 +585,7 @@ impl MemoryOperations {
 
         // 启动 AutomationManager（直接消费 EventBus 事件，无需分裂转发）
         let tenant_id_for_automation = tenant_id.clone();
-        tokio::spawn(async move {
+        let automation_task = tokio::spawn(async move {
             tracing::info!(
                 "AutomationManager started for tenant {} (L2 message indexing)",
                 tenant_id_for_automation
@@ -276,27 +593,15 @@ impl MemoryOperations {
             if let Err(e) = automation_manager.start(event_rx_main).await {
                 tracing::error!("AutomationManager stopped with error: {}", e);
             }
-        });
+        })
+        .abort_handle();
 
         // 创建 LayerGenerator（供 ensure_all_layers / ensure_session_layers 手动调用）
-        let layer_gen_config = LayerGenerationConfig {
-            batch_size: 10,
-            delay_ms: 1000,
-            auto_generate_on_startup: false,
-            abstract_config: AbstractConfig {
-                max_tokens: 400,
-                max_chars: 2000,
-                target_sentences: 2,
-            },
-            overview_config: OverviewConfig {
-                max_tokens: 1500,
-                max_chars: 6000,
-            },
-        };
-        let layer_generator = Arc::new(LayerGenerator::new(
+        let layer_generator = Arc::new(Self::build_layer_generator(
             filesystem.clone(),
             llm_client.clone(),
-            layer_gen_config,
+            llm_cache.clone(),
+            layer_mode,
         ));
 
         // Auto-sync existing content to vector database (in background)
@@ -309,8 +614,7 @@ impl MemoryOperations {
         );
 
         // Spawn background sync task
-        let _fs_clone = filesystem.clone();
-        tokio::spawn(async move {
+        let sync_task = tokio::spawn(async move {
             tracing::info!("Starting background sync to vector database...");
             match sync_manager.sync_all().await {
                 Ok(stats) => {
@@ -324,7 +628,8 @@ impl MemoryOperations {
                     tracing::warn!("Auto-sync failed: {}", e);
                 }
             }
-        });
+        })
+        .abort_handle();
 
         // Build VectorSyncManager for MemoryCleanupService.
         // The embedding client is required by the constructor but only used for Add/Update
@@ -340,7 +645,7 @@ impl MemoryOperations {
         // Launch background MemoryCleanupService (Ebbinghaus forgetting curve eviction).
         // Runs every 24 hours; removes archived memories whose strength has decayed below
         // the delete threshold and syncs deletions to Qdrant.
-        {
+        let cleanup_task = {
             use cortex_mem_core::{
                 memory_cleanup::{MemoryCleanupConfig, MemoryCleanupService},
                 memory_index::MemoryScope,
@@ -385,8 +690,9 @@ impl MemoryOperations {
                     }
                     tokio::time::sleep(interval).await;
                 }
-            });
-        }
+            })
+            .abort_handle()
+        };
 
         Ok(Self {
             filesystem,
@@ -399,23 +705,119 @@ impl MemoryOperations {
             embedding_client,
             vector_store,
             llm_client,
+            llm_cache,
+            usage,
+            layer_mode,
 
             default_user_id: actual_user_id,
             default_agent_id: tenant_id.clone(),
+            access: None,
 
             memory_event_tx: Some(memory_event_tx),
             event_coordinator: Some(coordinator_clone),
+            background_tasks: vec![coordinator_task, automation_task, sync_task, cleanup_task],
         })
     }
 
-    /// Add a message to a session
-    pub async fn add_message(&self, thread_id: &str, role: &str, content: &str) -> Result<String> {
-        let thread_id = if thread_id.is_empty() {
-            "default"
-        } else {
-            thread_id
-        };
+    /// Act as a specific agent (defaults to the tenant ID)
+    ///
+    /// Agents sharing a tenant keep their own `cortex://agent/{agent_id}`
+    /// memories and meet in shared spaces. Re-binds the access guard when
+    /// one is set.
+    pub fn with_agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.default_agent_id = agent_id.into();
+        match self.access.take() {
+            Some(guard) => self.with_access(guard.policy().clone()),
+            None => self,
+        }
+    }
 
+    /// Enforce an access policy for the current agent and user
+    ///
+    /// Every tool operation then goes through a guarded filesystem and search
+    /// engine: other agents' and users' private memories are invisible and
+    /// shared spaces follow their ACLs. Background pipelines (extraction,
+    /// layer cascades, cleanup) keep unrestricted access. No-op when the
+    /// policy is disabled.
+    pub fn with_access(mut self, policy: Arc<AccessPolicy>) -> Self {
+        if !policy.is_enabled() {
+            return self;
+        }
+        let guard = policy.guard(self.default_agent_id.clone(), self.default_user_id.clone());
+
+        // Replaces any previous guard (see `with_agent_id`)
+        let filesystem = Arc::new((*self.filesystem).clone().with_access(guard.clone()));
+
+        self.layer_manager = Arc::new(
+            LayerManager::new(filesystem.clone(), self.llm_client.clone())
+                .with_mode(self.layer_mode),
+        );
+        if self.layer_generator.is_some() {
+            self.layer_generator = Some(Arc::new(Self::build_layer_generator(
+                filesystem.clone(),
+                self.llm_client.clone(),
+                self.llm_cache.clone(),
+                self.layer_mode,
+            )));
+        }
+        self.vector_engine = Arc::new((*self.vector_engine).clone().with_access(guard.clone()));
+        self.filesystem = filesystem;
+        self.access = Some(guard);
+        self
+    }
+
+    /// Access guard of this instance (None when access control is off)
+    pub fn access(&self) -> Option<&AccessGuard> {
+        self.access.as_ref()
+    }
+
+    /// Fail with `PermissionDenied` unless the URI may be written
+    pub(crate) fn check_write(&self, uri: &str) -> Result<()> {
+        match &self.access {
+            Some(guard) => Ok(guard
+                .check_write(uri)
+                .map_err(cortex_mem_core::Error::from)?),
+            None => Ok(()),
+        }
+    }
+
+    fn build_layer_generator(
+        filesystem: Arc<CortexFilesystem>,
+        llm_client: Arc<dyn LLMClient>,
+        llm_cache: Option<Arc<LlmResultCache>>,
+        layer_mode: LayerMode,
+    ) -> LayerGenerator {
+        let generator = LayerGenerator::new(
+            filesystem,
+            llm_client,
+            Self::layer_generation_config(layer_mode),
+        );
+        match llm_cache {
+            Some(cache) => generator.with_cache(cache),
+            None => generator,
+        }
+    }
+
+    fn layer_generation_config(mode: LayerMode) -> LayerGenerationConfig {
+        LayerGenerationConfig {
+            batch_size: 10,
+            delay_ms: 1000,
+            auto_generate_on_startup: false,
+            abstract_config: AbstractConfig {
+                max_tokens: 400,
+                max_chars: 2000,
+                target_sentences: 2,
+            },
+            overview_config: OverviewConfig {
+                max_tokens: 1500,
+                max_chars: 6000,
+            },
+            mode,
+        }
+    }
+
+    /// Ensure a session exists and carries user_id/agent_id (auto-create with defaults)
+    pub(crate) async fn ensure_session(&self, thread_id: &str) -> Result<()> {
         let sm = self.session_manager.read().await;
 
         if !sm.session_exists(thread_id).await? {
@@ -454,6 +856,19 @@ impl MemoryOperations {
             }
         }
 
+        Ok(())
+    }
+
+    /// Add a message to a session
+    pub async fn add_message(&self, thread_id: &str, role: &str, content: &str) -> Result<String> {
+        let thread_id = if thread_id.is_empty() {
+            "default"
+        } else {
+            thread_id
+        };
+
+        self.ensure_session(thread_id).await?;
+
         let sm = self.session_manager.read().await;
 
         // 🔧 使用SessionManager::add_message()替代message_storage().save_message()
@@ -509,7 +924,7 @@ impl MemoryOperations {
                     session_infos.push(SessionInfo {
                         thread_id: metadata.thread_id,
                         status: status_str.to_string(),
-                        message_count: 0,
+                        message_count: metadata.message_count,
                         created_at: metadata.created_at,
                         updated_at: metadata.updated_at,
                     });
@@ -534,7 +949,7 @@ impl MemoryOperations {
         Ok(SessionInfo {
             thread_id: metadata.thread_id,
             status: status_str.to_string(),
-            message_count: 0,
+            message_count: metadata.message_count,
             created_at: metadata.created_at,
             updated_at: metadata.updated_at,
         })
@@ -614,6 +1029,8 @@ impl MemoryOperations {
 
     /// Delete file or directory
     pub async fn delete(&self, uri: &str) -> Result<()> {
+        self.check_write(uri)?;
+
         // First delete from vector database
         // We need to delete all 3 layers: L0, L1, L2
         let l0_id =
@@ -841,7 +1258,7 @@ impl MemoryOperations {
     /// 而不需要依赖 SessionClosed 事件。
     ///
     /// # Arguments
-    /// * `scope` - 处理范围：User, Agent, Session, 或 Resources
+    /// * `scope` - 处理范围：User, Agent, Session, Resources 或 Space
     /// * `owner_id` - 所有者 ID（如 user_id, agent_id, session_id）
     ///
     /// # Returns
@@ -856,13 +1273,15 @@ impl MemoryOperations {
             "agent" => cortex_mem_core::MemoryScope::Agent,
             "session" => cortex_mem_core::MemoryScope::Session,
             "resources" => cortex_mem_core::MemoryScope::Resources,
+            "space" | "spaces" => cortex_mem_core::MemoryScope::Space,
             _ => {
                 return Err(crate::ToolsError::ValidationError(format!(
-                    "Invalid scope: {}. Valid values: user, agent, session, resources",
+                    "Invalid scope: {}. Valid values: user, agent, session, resources, space",
                     scope
                 )));
             }
         };
+        self.check_write(&format!("cortex://{}/{}", memory_scope, owner_id))?;
 
         tracing::info!("Manual trigger processing for {:?}/{}", memory_scope, owner_id);
 
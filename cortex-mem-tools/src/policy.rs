@@ -0,0 +1,211 @@
+// Tool Policy
+//
+// Decides which tools are exposed to agents and how they are described,
+// based on the `[tools]` config section. Shared by cortex-mem-mcp and
+// cortex-mem-rig so both present the same tool surface.
+
+use crate::mcp::ToolDefinition;
+pub use cortex_mem_config::{ToolOverride, ToolsConfig};
+
+/// Whether a tool only reads memory or also modifies it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolAccess {
+    Read,
+    Write,
+}
+
+/// Every tool name exposed by cortex-mem-mcp or cortex-mem-rig and its access mode
+pub const TOOL_CATALOG: &[(&str, ToolAccess)] = &[
+    ("search", ToolAccess::Read),
+    ("find", ToolAccess::Read),
+    ("recall", ToolAccess::Read),
+    ("guidance", ToolAccess::Read),
+    ("ls", ToolAccess::Read),
+    ("explore", ToolAccess::Read),
+    ("abstract", ToolAccess::Read),
+    ("overview", ToolAccess::Read),
+    ("content", ToolAccess::Read),
+    ("read", ToolAccess::Read),
+    ("store", ToolAccess::Write),
+    ("store_batch", ToolAccess::Write),
+    ("commit", ToolAccess::Write),
+    ("remember", ToolAccess::Write),
+    ("update_memory", ToolAccess::Write),
+    ("forget", ToolAccess::Write),
+    ("delete", ToolAccess::Write),
+    ("layers", ToolAccess::Write),
+    ("index", ToolAccess::Write),
+];
+
+/// Access mode of a tool (unknown tools are treated as writes)
+pub fn tool_access(name: &str) -> ToolAccess {
+    TOOL_CATALOG
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, access)| *access)
+        .unwrap_or(ToolAccess::Write)
+}
+
+/// Applies the `[tools]` config to a tool surface
+///
+/// The default policy exposes every tool with its built-in description.
+#[derive(Debug, Clone, Default)]
+pub struct ToolPolicy {
+    config: ToolsConfig,
+}
+
+impl ToolPolicy {
+    pub fn new(config: ToolsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether the tool is exposed to agents
+    pub fn is_enabled(&self, name: &str) -> bool {
+        if self.config.read_only && tool_access(name) == ToolAccess::Write {
+            return false;
+        }
+        if let Some(enabled) = self.config.overrides.get(name).and_then(|o| o.enabled) {
+            return enabled;
+        }
+        let allowed = self
+            .config
+            .enabled
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|n| n == name));
+        allowed && !self.config.disabled.iter().any(|n| n == name)
+    }
+
+    /// Configured description override, if any
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.config
+            .overrides
+            .get(name)
+            .and_then(|o| o.description.as_deref())
+    }
+
+    /// Whether the tool only reads memory
+    pub fn is_read_only(&self, name: &str) -> bool {
+        tool_access(name) == ToolAccess::Read
+    }
+
+    /// Drop disabled tools and apply description overrides
+    pub fn apply(&self, definitions: Vec<ToolDefinition>) -> Vec<ToolDefinition> {
+        definitions
+            .into_iter()
+            .filter(|def| self.is_enabled(&def.name))
+            .map(|mut def| {
+                if let Some(description) = self.description(&def.name) {
+                    def.description = description.to_string();
+                }
+                def
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: format!("built-in {}", name),
+            input_schema: json!({}),
+        }
+    }
+
+    fn override_enabled(enabled: bool) -> ToolOverride {
+        ToolOverride {
+            description: None,
+            enabled: Some(enabled),
+        }
+    }
+
+    #[test]
+    fn test_default_enables_everything() {
+        let policy = ToolPolicy::default();
+        for (name, _) in TOOL_CATALOG {
+            assert!(policy.is_enabled(name));
+            assert!(policy.description(name).is_none());
+        }
+    }
+
+    #[test]
+    fn test_enabled_and_disabled_lists() {
+        let policy = ToolPolicy::new(ToolsConfig {
+            enabled: Some(vec!["search".to_string(), "store".to_string()]),
+            disabled: vec!["store".to_string()],
+            ..Default::default()
+        });
+        assert!(policy.is_enabled("search"));
+        assert!(!policy.is_enabled("store"));
+        assert!(!policy.is_enabled("ls"));
+    }
+
+    #[test]
+    fn test_read_only_wins_over_overrides() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("forget".to_string(), override_enabled(true));
+        overrides.insert("unknown_tool".to_string(), override_enabled(true));
+        let policy = ToolPolicy::new(ToolsConfig {
+            read_only: true,
+            overrides,
+            ..Default::default()
+        });
+        assert!(policy.is_enabled("search"));
+        assert!(!policy.is_enabled("store"));
+        assert!(!policy.is_enabled("forget"));
+        // Unknown tools are treated as writes
+        assert!(!policy.is_enabled("unknown_tool"));
+    }
+
+    #[test]
+    fn test_override_enabled_takes_precedence() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("ls".to_string(), override_enabled(true));
+        overrides.insert("search".to_string(), override_enabled(false));
+        let policy = ToolPolicy::new(ToolsConfig {
+            disabled: vec!["ls".to_string()],
+            overrides,
+            ..Default::default()
+        });
+        assert!(policy.is_enabled("ls"));
+        assert!(!policy.is_enabled("search"));
+    }
+
+    #[test]
+    fn test_apply_filters_and_describes() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert(
+            "recall".to_string(),
+            ToolOverride {
+                description: Some("Recall project notes".to_string()),
+                enabled: None,
+            },
+        );
+        let policy = ToolPolicy::new(ToolsConfig {
+            disabled: vec!["delete".to_string()],
+            overrides,
+            ..Default::default()
+        });
+        let applied = policy.apply(vec![
+            definition("search"),
+            definition("recall"),
+            definition("delete"),
+        ]);
+        let names: Vec<_> = applied.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["search", "recall"]);
+        assert_eq!(applied[0].description, "built-in search");
+        assert_eq!(applied[1].description, "Recall project notes");
+    }
+
+    #[test]
+    fn test_access_modes() {
+        let policy = ToolPolicy::default();
+        assert!(policy.is_read_only("search"));
+        assert!(!policy.is_read_only("store_batch"));
+        assert_eq!(tool_access("missing"), ToolAccess::Write);
+    }
+}
@@ -1,10 +1,18 @@
 // Filesystem Tools
 
-use crate::{MemoryOperations, Result, types::*};
-use cortex_mem_core::{ContextLayer, FilesystemOperations};
+use crate::{MemoryOperations, Result, ToolsError, types::*};
+use cortex_mem_core::{
+    ContextLayer, FileEntry, FilesystemOperations, ListOptions, ListSort,
+    filesystem::listing::name_matches,
+};
 
 impl MemoryOperations {
     /// List directory contents
+    ///
+    /// Entries are filtered by `pattern`, sorted, and paginated with `limit`
+    /// (default [`DEFAULT_LS_LIMIT`]) / `cursor`; child counts and abstracts
+    /// are only loaded for the returned page. Recursive listings in name order
+    /// stop walking the tree once the page is complete.
     pub async fn ls(&self, args: LsArgs) -> Result<LsResponse> {
         // Use default URI if empty
         let uri = if args.uri.is_empty() {
@@ -12,10 +20,51 @@ impl MemoryOperations {
         } else {
             args.uri
         };
-        let entries = self.filesystem.list(&uri).await?;
+        let options = Self::list_options(
+            args.pattern,
+            args.sort.as_deref(),
+            args.descending,
+            Some(args.limit.unwrap_or(DEFAULT_LS_LIMIT)),
+            args.cursor.as_deref(),
+        )?;
+
+        let recursive = args.recursive.unwrap_or(false);
+        let (page, total) = match options.limit {
+            // The walk yields path order, so it can stop one entry past the page
+            Some(limit) if recursive && options.sort == ListSort::Name && !options.descending => {
+                let needed = options.offset.saturating_add(limit).saturating_add(1);
+                let (entries, complete) = self
+                    .walk_in_path_order(&uri, options.pattern.as_deref(), needed)
+                    .await?;
+                let page = options.apply(entries);
+                let total = complete.then_some(page.total);
+                (page, total)
+            }
+            _ => {
+                let mut entries = self.filesystem.list(&uri).await?;
+                if recursive {
+                    let mut pending: Vec<String> = entries
+                        .iter()
+                        .filter(|e| e.is_directory)
+                        .map(|e| e.uri.clone())
+                        .collect();
+                    while let Some(dir) = pending.pop() {
+                        for entry in self.filesystem.list(&dir).await? {
+                            if entry.is_directory {
+                                pending.push(entry.uri.clone());
+                            }
+                            entries.push(entry);
+                        }
+                    }
+                }
+                let page = options.apply(entries);
+                let total = Some(page.total);
+                (page, total)
+            }
+        };
 
         let mut result_entries = Vec::new();
-        for entry in entries {
+        for entry in page.entries {
             let child_count = if entry.is_directory {
                 Some(self.filesystem.list(&entry.uri).await?.len())
             } else {
@@ -28,6 +77,8 @@ impl MemoryOperations {
                 is_directory: entry.is_directory,
                 child_count,
                 abstract_text: None,
+                size: entry.size,
+                modified: entry.modified,
             };
 
             // Include abstracts if requested and entry is a file
@@ -44,8 +95,9 @@ impl MemoryOperations {
 
         Ok(LsResponse {
             uri,
-            total: result_entries.len(),
+            total,
             entries: result_entries,
+            next_cursor: page.next_cursor,
         })
     }
 
@@ -55,6 +107,14 @@ impl MemoryOperations {
         let max_depth = args.max_depth.unwrap_or(3);
         let return_layers = args.return_layers.unwrap_or(vec!["L0".to_string()]);
 
+        let options = Self::list_options(
+            args.pattern,
+            args.sort.as_deref(),
+            args.descending,
+            args.limit,
+            args.cursor.as_deref(),
+        )?;
+
         let mut exploration_path = Vec::new();
         let mut all_matches = Vec::new();
         let mut total_explored = 0;
@@ -65,24 +125,136 @@ impl MemoryOperations {
             &start_uri,
             0,
             max_depth,
-            &return_layers,
+            options.pattern.as_deref(),
             &mut exploration_path,
             &mut all_matches,
             &mut total_explored,
         )
         .await?;
 
+        // Page over the matched files, then load layers for the page only
+        let scores: std::collections::HashMap<String, f32> = all_matches
+            .iter()
+            .map(|(entry, score)| (entry.uri.clone(), *score))
+            .collect();
+        let page = options.apply(all_matches.into_iter().map(|(entry, _)| entry).collect());
+
+        let mut matches = Vec::new();
+        for entry in page.entries {
+            let mut result = SearchResult {
+                uri: entry.uri.clone(),
+                score: scores.get(&entry.uri).copied().unwrap_or_default(),
+                abstract_text: None,
+                overview_text: None,
+                content: None,
+            };
+
+            if return_layers.contains(&"L0".to_string()) {
+                result.abstract_text = self
+                    .layer_manager
+                    .load(&entry.uri, ContextLayer::L0Abstract)
+                    .await
+                    .ok();
+            }
+            if return_layers.contains(&"L1".to_string()) {
+                result.overview_text = self
+                    .layer_manager
+                    .load(&entry.uri, ContextLayer::L1Overview)
+                    .await
+                    .ok();
+            }
+            if return_layers.contains(&"L2".to_string()) {
+                result.content = self.filesystem.read(&entry.uri).await.ok();
+            }
+
+            matches.push(result);
+        }
+
         Ok(ExploreResponse {
             query: args.query,
             exploration_path,
             total_explored,
-            total_matches: all_matches.len(),
-            matches: all_matches,
+            total_matches: page.total,
+            matches,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// Build listing options from tool arguments
+    fn list_options(
+        pattern: Option<String>,
+        sort: Option<&str>,
+        descending: Option<bool>,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+    ) -> Result<ListOptions> {
+        let sort = match sort {
+            Some(s) => ListSort::parse(s).ok_or_else(|| {
+                ToolsError::InvalidInput(format!(
+                    "Unknown sort '{}', expected name, modified or size",
+                    s
+                ))
+            })?,
+            None => ListSort::default(),
+        };
+        let offset = match cursor.filter(|c| !c.is_empty()) {
+            Some(c) => ListOptions::decode_cursor(c)
+                .ok_or_else(|| ToolsError::InvalidInput(format!("Invalid cursor: {}", c)))?,
+            None => 0,
+        };
+
+        Ok(ListOptions {
+            pattern,
+            sort,
+            descending: descending.unwrap_or(false),
+            offset,
+            limit: limit.filter(|&l| l > 0),
         })
     }
 
     // ==================== Internal Methods ====================
 
+    /// Recursive listing in `ListSort::Name` order, stopping after `needed` matches
+    ///
+    /// Directories are read sorted by name and walked depth first, so the
+    /// part of the tree past the page is never read. Returns the matching
+    /// entries and whether the whole tree was walked.
+    async fn walk_in_path_order(
+        &self,
+        uri: &str,
+        pattern: Option<&str>,
+        needed: usize,
+    ) -> Result<(Vec<FileEntry>, bool)> {
+        let pattern = pattern.filter(|p| !p.is_empty());
+        let mut found = Vec::new();
+        let mut stack = vec![self.sorted_children(uri).await?.into_iter()];
+
+        while let Some(children) = stack.last_mut() {
+            let Some(entry) = children.next() else {
+                stack.pop();
+                continue;
+            };
+            let subdirectory = entry.is_directory.then(|| entry.uri.clone());
+            if pattern.is_none_or(|p| name_matches(p, &entry.name)) {
+                if found.len() == needed {
+                    return Ok((found, false));
+                }
+                found.push(entry);
+            }
+            if let Some(dir) = subdirectory {
+                stack.push(self.sorted_children(&dir).await?.into_iter());
+            }
+        }
+
+        Ok((found, true))
+    }
+
+    async fn sorted_children(&self, uri: &str) -> Result<Vec<FileEntry>> {
+        let mut entries = self.filesystem.list(uri).await?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn explore_recursive<'a>(
         &'a self,
@@ -90,9 +262,9 @@ impl MemoryOperations {
         uri: &'a str,
         depth: usize,
         max_depth: usize,
-        return_layers: &'a [String],
+        pattern: Option<&'a str>,
         path: &'a mut Vec<ExplorationPathItem>,
-        matches: &'a mut Vec<SearchResult>,
+        matches: &'a mut Vec<(FileEntry, f32)>,
         total_explored: &'a mut usize,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(async move {
@@ -140,44 +312,20 @@ impl MemoryOperations {
                         &entry.uri,
                         depth + 1,
                         max_depth,
-                        return_layers,
+                        pattern,
                         path,
                         matches,
                         total_explored,
                     )
                     .await?;
                 } else {
+                    if pattern.is_some_and(|p| !name_matches(p, &entry.name)) {
+                        continue;
+                    }
                     // Check if file matches
                     if let Ok(content) = self.filesystem.read(&entry.uri).await {
                         if content.to_lowercase().contains(&query.to_lowercase()) {
-                            // Enrich with requested layers
-                            let mut result = SearchResult {
-                                uri: entry.uri.clone(),
-                                score: relevance_score,
-                                abstract_text: None,
-                                overview_text: None,
-                                content: None,
-                            };
-
-                            if return_layers.contains(&"L0".to_string()) {
-                                result.abstract_text = self
-                                    .layer_manager
-                                    .load(&entry.uri, ContextLayer::L0Abstract)
-                                    .await
-                                    .ok();
-                            }
-                            if return_layers.contains(&"L1".to_string()) {
-                                result.overview_text = self
-                                    .layer_manager
-                                    .load(&entry.uri, ContextLayer::L1Overview)
-                                    .await
-                                    .ok();
-                            }
-                            if return_layers.contains(&"L2".to_string()) {
-                                result.content = Some(content);
-                            }
-
-                            matches.push(result);
+                            matches.push((entry, relevance_score));
                         }
                     }
                 }
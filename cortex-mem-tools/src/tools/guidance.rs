@@ -0,0 +1,89 @@
+// Guidance Tool - Skills and instructions the agent learned from past sessions
+
+use crate::{MemoryOperations, Result, ToolsError, types::*};
+use cortex_mem_core::{FilesystemOperations, SearchOptions};
+
+/// Skills returned when the caller gives no limit
+const DEFAULT_SKILL_LIMIT: usize = 5;
+
+impl MemoryOperations {
+    /// Get the skills and instructions that apply to a task
+    ///
+    /// Instructions are standing rules, so all of them are returned; skills
+    /// are ranked against the task by vector search over
+    /// `cortex://agent/{agent_id}/skills`.
+    pub async fn guidance(&self, args: GuidanceArgs) -> Result<GuidanceResponse> {
+        if args.task.trim().is_empty() {
+            return Err(ToolsError::InvalidInput(
+                "task must not be empty".to_string(),
+            ));
+        }
+        let agent_root = format!("cortex://agent/{}", self.default_agent_id);
+
+        let instructions = self
+            .read_memory_files(&format!("{}/instructions", agent_root))
+            .await?;
+
+        let options = SearchOptions {
+            limit: args.limit.unwrap_or(DEFAULT_SKILL_LIMIT),
+            threshold: 0.5,
+            root_uri: Some(format!("{}/skills", agent_root)),
+            recursive: true,
+        };
+        let mut skills = Vec::new();
+        for result in self
+            .vector_engine
+            .layered_semantic_search(&args.task, &options)
+            .await?
+        {
+            if !Self::is_memory_file(&result.uri) {
+                continue;
+            }
+            // Vectors can outlive a forgotten skill until the next sync
+            let Ok(content) = self.filesystem.read(&result.uri).await else {
+                continue;
+            };
+            skills.push(GuidanceItem {
+                uri: result.uri,
+                content,
+                score: Some(result.score),
+            });
+        }
+
+        Ok(GuidanceResponse {
+            task: args.task,
+            instructions,
+            skills,
+        })
+    }
+
+    /// Read every memory file of a directory, sorted by name
+    async fn read_memory_files(&self, dir: &str) -> Result<Vec<GuidanceItem>> {
+        let mut entries = match self.filesystem.list(dir).await {
+            Ok(entries) => entries,
+            Err(cortex_mem_core::Error::NotFound { .. }) => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut items = Vec::new();
+        for entry in entries {
+            if entry.is_directory || !Self::is_memory_file(&entry.uri) {
+                continue;
+            }
+            let content = self.filesystem.read(&entry.uri).await?;
+            items.push(GuidanceItem {
+                uri: entry.uri,
+                content,
+                score: None,
+            });
+        }
+        Ok(items)
+    }
+
+    /// Markdown memory files, excluding `.abstract.md` / `.overview.md` layers
+    fn is_memory_file(uri: &str) -> bool {
+        let name = uri.rsplit('/').next().unwrap_or_default();
+        name.ends_with(".md") && !name.starts_with('.')
+    }
+}
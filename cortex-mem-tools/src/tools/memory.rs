@@ -0,0 +1,159 @@
+// Memory Tools - Explicitly remember, update and forget structured memories
+
+use crate::{MemoryOperations, Result, ToolsError, types::*};
+use cortex_mem_core::{
+    DeleteReason, IncrementalMemoryUpdater, MANUAL_SOURCE, ManualMemory, MemoryMetadata,
+    MemoryScope, MemoryType,
+};
+use std::sync::Arc;
+
+/// Confidence of remembered memories when the caller gives none
+const DEFAULT_CONFIDENCE: f32 = 0.9;
+
+impl MemoryOperations {
+    /// Remember a structured memory (preference, entity, case, ...)
+    ///
+    /// The memory is written by `IncrementalMemoryUpdater` exactly like an
+    /// extracted one: an existing memory with the same type and key is updated
+    /// instead of duplicated, the memory index is maintained and `MemoryEvent`s
+    /// drive layer and vector updates. Cases, skills and instructions belong to
+    /// the agent, all other types to the user.
+    pub async fn remember(&self, args: RememberArgs) -> Result<MemoryEditResponse> {
+        let memory_type: MemoryType = args
+            .memory_type
+            .trim()
+            .parse()
+            .map_err(ToolsError::InvalidInput)?;
+        if args.content.trim().is_empty() {
+            return Err(ToolsError::InvalidInput(
+                "content must not be empty".to_string(),
+            ));
+        }
+
+        let item = ManualMemory::new(
+            memory_type.clone(),
+            args.key,
+            args.content,
+            args.confidence.unwrap_or(DEFAULT_CONFIDENCE),
+        )
+        .map_err(|e| ToolsError::InvalidInput(e.to_string()))?;
+
+        let scope = ManualMemory::scope_of(&memory_type);
+        let owner_id = self.owner_of(&scope).to_string();
+        let (result, metadata) = self
+            .memory_updater()?
+            .remember(&scope, &owner_id, MANUAL_SOURCE, &item)
+            .await?;
+
+        let action = if result.created > 0 {
+            "created"
+        } else if result.updated > 0 {
+            "updated"
+        } else {
+            "unchanged"
+        };
+        Ok(Self::edit_response(&scope, &owner_id, metadata, action))
+    }
+
+    /// Replace the content of an existing memory, keeping its type and key
+    pub async fn update_memory(&self, args: UpdateMemoryArgs) -> Result<MemoryEditResponse> {
+        if args.content.trim().is_empty() {
+            return Err(ToolsError::InvalidInput(
+                "content must not be empty".to_string(),
+            ));
+        }
+        let (scope, owner_id, memory_id) = self.own_memory(&args.uri)?;
+
+        let metadata = self
+            .memory_updater()?
+            .update_memory(
+                &scope,
+                &owner_id,
+                &memory_id,
+                &args.content,
+                args.confidence,
+                MANUAL_SOURCE,
+            )
+            .await?
+            .ok_or_else(|| ToolsError::NotFound(args.uri.clone()))?;
+
+        Ok(Self::edit_response(&scope, &owner_id, metadata, "updated"))
+    }
+
+    /// Forget a memory: remove its file and index entry
+    ///
+    /// Unlike `delete`, this only accepts indexed memories and emits
+    /// `MemoryDeleted`, so vectors and parent layers are cleaned up by the
+    /// event coordinator.
+    pub async fn forget(&self, uri: &str) -> Result<MemoryEditResponse> {
+        let (scope, owner_id, memory_id) = self.own_memory(uri)?;
+        let updater = self.memory_updater()?;
+
+        let metadata = updater
+            .get_memory(&scope, &owner_id, &memory_id)
+            .await?
+            .ok_or_else(|| ToolsError::NotFound(uri.to_string()))?;
+
+        if !updater
+            .delete_memory(&scope, &owner_id, &memory_id, DeleteReason::UserRequest)
+            .await?
+        {
+            return Err(ToolsError::NotFound(uri.to_string()));
+        }
+
+        Ok(Self::edit_response(&scope, &owner_id, metadata, "deleted"))
+    }
+
+    // ==================== Helpers ====================
+
+    fn memory_updater(&self) -> Result<Arc<IncrementalMemoryUpdater>> {
+        self.event_coordinator
+            .as_ref()
+            .map(|coordinator| coordinator.memory_updater())
+            .ok_or_else(|| {
+                ToolsError::Runtime("memory event coordinator is not running".to_string())
+            })
+    }
+
+    fn owner_of(&self, scope: &MemoryScope) -> &str {
+        match scope {
+            MemoryScope::Agent => &self.default_agent_id,
+            _ => &self.default_user_id,
+        }
+    }
+
+    /// Parse a memory URI and make sure it belongs to this user or agent
+    fn own_memory(&self, uri: &str) -> Result<(MemoryScope, String, String)> {
+        let (scope, owner_id, memory_id) = IncrementalMemoryUpdater::parse_memory_uri(uri)
+            .ok_or_else(|| {
+                ToolsError::InvalidInput(format!(
+                    "Not a memory URI (expected cortex://user|agent/{{id}}/{{dir}}/{{memory_id}}.md): {}",
+                    uri
+                ))
+            })?;
+        if owner_id != self.owner_of(&scope) {
+            return Err(ToolsError::ValidationError(format!(
+                "{} does not belong to {} '{}'",
+                uri,
+                scope,
+                self.owner_of(&scope)
+            )));
+        }
+        Ok((scope, owner_id, memory_id))
+    }
+
+    fn edit_response(
+        scope: &MemoryScope,
+        owner_id: &str,
+        metadata: MemoryMetadata,
+        action: &str,
+    ) -> MemoryEditResponse {
+        MemoryEditResponse {
+            uri: format!("cortex://{}/{}/{}", scope, owner_id, metadata.file),
+            memory_id: metadata.id,
+            memory_type: metadata.memory_type.to_string(),
+            key: metadata.key,
+            action: action.to_string(),
+        }
+    }
+}
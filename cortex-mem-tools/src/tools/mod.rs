@@ -1,6 +1,8 @@
 // Tools module
 
 pub mod filesystem;
+pub mod guidance;
+pub mod memory;
 pub mod recall;
 pub mod search;
 pub mod storage;
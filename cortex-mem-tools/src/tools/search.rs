@@ -88,7 +88,7 @@ impl MemoryOperations {
                         .unwrap_or("");
 
                     match dimension {
-                        "resources" | "user" | "agent" | "session" => s.to_string(),
+                        "resources" | "user" | "agent" | "session" | "spaces" => s.to_string(),
                         // Legacy aliases - map to new structure
                         "threads" | "agents" | "users" | "global" => {
                             let rest = s
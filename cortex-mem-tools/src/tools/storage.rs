@@ -1,11 +1,11 @@
 // Storage Tools - Store content with automatic layer generation
 
-use crate::{MemoryOperations, Result, types::*};
+use crate::{MemoryOperations, Result, ToolsError, types::*};
 use chrono::Utc;
-use cortex_mem_core::{FilesystemOperations, MessageRole};
+use cortex_mem_core::{FilesystemOperations, Message, MessageRole};
 use cortex_mem_core::memory_events::{MemoryEvent, ChangeType};
 use cortex_mem_core::memory_index::MemoryScope;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 impl MemoryOperations {
     /// Store content with automatic L0/L1 layer generation
@@ -14,11 +14,20 @@ impl MemoryOperations {
     /// the agent's response. For session scope, we send LayerUpdateNeeded events
     /// which are processed by MemoryEventCoordinator in the background.
     pub async fn store(&self, args: StoreArgs) -> Result<StoreResponse> {
-        // Determine storage scope: user, session, or agent
+        // Determine storage scope: user, session, agent or space
         let scope = match args.scope.as_str() {
-            "user" | "session" | "agent" => args.scope.as_str(),
+            "user" | "session" | "agent" | "space" => args.scope.as_str(),
             _ => "session", // Default to session
         };
+        let space = match (scope, args.space.as_deref().map(str::trim)) {
+            ("space", Some(space)) if !space.is_empty() && !space.contains('/') => Some(space),
+            ("space", _) => {
+                return Err(ToolsError::InvalidInput(
+                    "scope \"space\" requires a space name".to_string(),
+                ));
+            }
+            _ => None,
+        };
 
         // Build URI based on scope
         // Note: This stores raw messages to memories/ subdirectory.
@@ -75,6 +84,26 @@ impl MemoryOperations {
                     agent_id, year_month, day, filename
                 )
             }
+            "space" => {
+                // cortex://spaces/{space}/memories/YYYY-MM/DD/HH_MM_SS_id.md
+                let space = space.unwrap_or_default();
+                let now = Utc::now();
+                let year_month = now.format("%Y-%m").to_string();
+                let day = now.format("%d").to_string();
+                let filename = format!(
+                    "{}_{}.md",
+                    now.format("%H_%M_%S"),
+                    uuid::Uuid::new_v4()
+                        .to_string()
+                        .split('-')
+                        .next()
+                        .unwrap_or("unknown")
+                );
+                format!(
+                    "cortex://spaces/{}/memories/{}/{}/{}",
+                    space, year_month, day, filename
+                )
+            }
             "session" => {
                 // cortex://session/{thread_id}/timeline/YYYY-MM/DD/HH_MM_SS_id.md
                 let thread_id = if args.thread_id.is_empty() {
@@ -151,8 +180,8 @@ impl MemoryOperations {
             _ => unreachable!(),
         };
 
-        // For user and agent scope, directly write to filesystem
-        if scope == "user" || scope == "agent" {
+        // For user, agent and space scope, directly write to filesystem
+        if scope == "user" || scope == "agent" || scope == "space" {
             self.filesystem.write(&uri, &args.content).await?;
         }
 
@@ -213,6 +242,27 @@ impl MemoryOperations {
                         }
                     }
                 }
+                "space" => {
+                    if let Some(ref tx) = self.memory_event_tx {
+                        let parent_dir = uri.rsplit_once('/')
+                            .map(|(dir, _)| dir.to_string())
+                            .unwrap_or_else(|| uri.clone());
+
+                        let _ = tx.send(MemoryEvent::LayerUpdateNeeded {
+                            scope: MemoryScope::Space,
+                            owner_id: space.unwrap_or_default().to_string(),
+                            directory_uri: parent_dir,
+                            change_type: ChangeType::Add,
+                            changed_file: uri.clone(),
+                        });
+                        tracing::debug!("📤 Sent LayerUpdateNeeded event for space scope");
+                    } else {
+                        tracing::warn!("⚠️ memory_event_tx not available, falling back to sync generation");
+                        if let Err(e) = self.layer_manager.generate_all_layers(&uri, &args.content, &[]).await {
+                            tracing::warn!("Failed to generate layers for {}: {}", uri, e);
+                        }
+                    }
+                }
                 "session" => {
                     // Session scope: Send LayerUpdateNeeded for the timeline directory
                     // Layer generation is deferred to session close for efficiency
@@ -249,4 +299,85 @@ impl MemoryOperations {
             success: true,
         })
     }
+
+    /// Import many messages across sessions in one call
+    ///
+    /// The whole batch is validated first, then written with
+    /// `SessionManager::import_messages`: grouped per thread, in timestamp order, with one
+    /// `MessageAdded` per thread and one `LayerUpdateNeeded` per timeline day directory
+    /// instead of one of each per message. With `extract`, each touched session is closed
+    /// afterwards so memory extraction runs once per thread.
+    pub async fn store_batch(&self, args: StoreBatchArgs) -> Result<StoreBatchResponse> {
+        if args.messages.is_empty() {
+            return Err(ToolsError::InvalidInput("messages must not be empty".to_string()));
+        }
+
+        // Validate and build everything up front so a bad entry doesn't leave a half import
+        let mut messages = Vec::with_capacity(args.messages.len());
+        for (i, item) in args.messages.into_iter().enumerate() {
+            let role = MessageRole::parse(&item.role).ok_or_else(|| {
+                ToolsError::ValidationError(format!(
+                    "messages[{}]: invalid role '{}'",
+                    i, item.role
+                ))
+            })?;
+
+            let thread_id = if item.thread_id.is_empty() {
+                "default".to_string()
+            } else {
+                item.thread_id
+            };
+
+            let mut message = Message::new(role, item.content);
+            if let Some(ts) = item.timestamp {
+                message = message.with_timestamp(ts);
+            }
+            if let Some(metadata) = item.metadata {
+                message = message.with_metadata(metadata);
+            }
+            messages.push((thread_id, message));
+        }
+
+        // Sessions get the default user and agent, as with `store`
+        let mut ensured = HashSet::new();
+        for (thread_id, _) in &messages {
+            if ensured.insert(thread_id.as_str()) {
+                self.ensure_session(thread_id).await?;
+            }
+        }
+
+        let imported = {
+            let sm = self.session_manager.read().await;
+            sm.import_messages(messages).await?
+        };
+        let total_messages = imported.iter().map(|thread| thread.uris.len()).sum();
+        let threads: Vec<BatchThreadResult> = imported
+            .into_iter()
+            .map(|thread| BatchThreadResult {
+                thread_id: thread.thread_id,
+                message_count: thread.uris.len(),
+                uris: thread.uris,
+            })
+            .collect();
+
+        let mut extraction_triggered = Vec::new();
+        if args.extract {
+            for thread in &threads {
+                match self.close_session(&thread.thread_id).await {
+                    Ok(()) => extraction_triggered.push(thread.thread_id.clone()),
+                    Err(e) => tracing::warn!(
+                        "Failed to close session {} for extraction: {}",
+                        thread.thread_id,
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(StoreBatchResponse {
+            total_messages,
+            threads,
+            extraction_triggered,
+        })
+    }
 }
@@ -124,8 +124,26 @@ pub struct LsArgs {
     pub uri: String,
     pub recursive: Option<bool>,
     pub include_abstracts: Option<bool>,
+    /// Glob (`*.md`) or substring filter on entry names
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Sort key: "name" (default), "modified" or "size"
+    #[serde(default)]
+    pub sort: Option<String>,
+    /// Reverse the sort order
+    #[serde(default)]
+    pub descending: Option<bool>,
+    /// Page size ([`DEFAULT_LS_LIMIT`] when omitted, 0 for all entries)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
+/// Entries per `ls` page when no `limit` is given
+pub const DEFAULT_LS_LIMIT: usize = 100;
+
 /// Directory entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LsEntry {
@@ -134,6 +152,8 @@ pub struct LsEntry {
     pub is_directory: bool,
     pub child_count: Option<usize>,
     pub abstract_text: Option<String>,
+    pub size: u64,
+    pub modified: DateTime<Utc>,
 }
 
 /// List directory response
@@ -141,7 +161,13 @@ pub struct LsEntry {
 pub struct LsResponse {
     pub uri: String,
     pub entries: Vec<LsEntry>,
-    pub total: usize,
+    /// Entries matching the filter across all pages; absent when a recursive
+    /// listing stopped walking after this page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+    /// Cursor for the next page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Explore arguments
@@ -151,6 +177,21 @@ pub struct ExploreArgs {
     pub start_uri: Option<String>,
     pub max_depth: Option<usize>,
     pub return_layers: Option<Vec<String>>,
+    /// Glob (`*.md`) or substring filter on matched file names
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Sort key for matches: "name" (default), "modified" or "size"
+    #[serde(default)]
+    pub sort: Option<String>,
+    /// Reverse the sort order
+    #[serde(default)]
+    pub descending: Option<bool>,
+    /// Page size for matches (all matches when omitted)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// Exploration path item
@@ -168,7 +209,11 @@ pub struct ExploreResponse {
     pub exploration_path: Vec<ExplorationPathItem>,
     pub matches: Vec<SearchResult>,
     pub total_explored: usize,
+    /// Matches across all pages
     pub total_matches: usize,
+    /// Cursor for the next page of matches, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Store arguments
@@ -179,7 +224,7 @@ pub struct StoreArgs {
     pub thread_id: String,
     pub metadata: Option<Value>,
     pub auto_generate_layers: Option<bool>,
-    /// Storage scope: "session" (default), "user", or "agent"
+    /// Storage scope: "session" (default), "user", "agent" or "space"
     #[serde(default = "default_scope")]
     pub scope: String,
     /// User ID for user scope storage (required when scope is "user")
@@ -188,6 +233,9 @@ pub struct StoreArgs {
     /// Agent ID for agent scope storage (required when scope is "agent")
     #[serde(default)]
     pub agent_id: Option<String>,
+    /// Shared space name (required when scope is "space")
+    #[serde(default)]
+    pub space: Option<String>,
 }
 
 fn default_scope() -> String {
@@ -202,7 +250,110 @@ pub struct StoreResponse {
     pub success: bool,
 }
 
-// Internal types
+/// A single message in a batch import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchMessage {
+    /// Target session; created on first use ("default" when empty)
+    #[serde(default)]
+    pub thread_id: String,
+    /// "user", "assistant" or "system"
+    pub role: String,
+    pub content: String,
+    /// Original message time; defaults to import time
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub metadata: Option<Value>,
+}
+
+/// Batch store arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreBatchArgs {
+    pub messages: Vec<BatchMessage>,
+    /// Close each touched session afterwards to trigger memory extraction once per thread
+    #[serde(default)]
+    pub extract: bool,
+}
+
+/// Per-thread result of a batch import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchThreadResult {
+    pub thread_id: String,
+    pub message_count: usize,
+    pub uris: Vec<String>,
+}
+
+/// Batch store response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreBatchResponse {
+    pub total_messages: usize,
+    pub threads: Vec<BatchThreadResult>,
+    /// Threads closed for extraction (empty unless `extract` was set)
+    pub extraction_triggered: Vec<String>,
+}
+
+/// Remember arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RememberArgs {
+    /// preference, entity, event, case, personal_info, work_history, relationship, goal, skill
+    /// or instruction
+    pub memory_type: String,
+    /// Deduplication key (topic, name, title, ...); remembering an existing key updates it
+    pub key: String,
+    pub content: String,
+    /// 0.0–1.0, default 0.9
+    pub confidence: Option<f32>,
+}
+
+/// Update memory arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMemoryArgs {
+    /// URI of the memory file, e.g. cortex://user/{id}/preferences/pref_xxx.md
+    pub uri: String,
+    pub content: String,
+    /// Defaults to the stored confidence
+    pub confidence: Option<f32>,
+}
+
+/// Result of remember / update_memory / forget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryEditResponse {
+    pub uri: String,
+    pub memory_id: String,
+    pub memory_type: String,
+    pub key: String,
+    /// "created", "updated", "unchanged" or "deleted"
+    pub action: String,
+}
+
+/// Guidance arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidanceArgs {
+    /// The task the agent is about to work on
+    pub task: String,
+    /// Maximum number of skills (default: 5)
+    pub limit: Option<usize>,
+}
+
+/// A learned skill or instruction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidanceItem {
+    pub uri: String,
+    pub content: String,
+    /// Relevance to the task (skills only)
+    pub score: Option<f32>,
+}
+
+/// Guidance response: what the agent learned that applies to a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidanceResponse {
+    pub task: String,
+    /// All standing instructions of the agent
+    pub instructions: Vec<GuidanceItem>,
+    /// Skills relevant to the task, best match first
+    pub skills: Vec<GuidanceItem>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct RawSearchResult {
     pub uri: String,
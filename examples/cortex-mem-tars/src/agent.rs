@@ -55,7 +55,6 @@ impl ChatMessage {
 /// 创建带记忆功能的Agent（支持租户隔离）
 /// 返回 (Agent, MemoryOperations) 以便外部使用租户隔离的 operations
 pub async fn create_memory_agent(
-    data_dir: impl AsRef<std::path::Path>,
     config: &cortex_mem_config::Config,
     user_info: Option<&str>,
     bot_system_prompt: Option<&str>,
@@ -64,11 +63,14 @@ pub async fn create_memory_agent(
 ) -> Result<(RigAgent<CompletionModel>, Arc<MemoryOperations>), Box<dyn std::error::Error>> {
     // 创建 cortex LLMClient 用于 L0/L1 生成
     let llm_config = cortex_mem_core::llm::LLMConfig {
+        provider: config.llm.provider,
         api_base_url: config.llm.api_base_url.clone(),
         api_key: config.llm.api_key.clone(),
         model_efficient: config.llm.model_efficient.clone(),
         temperature: 0.1,
         max_tokens: 4096,
+        api_version: config.llm.api_version.clone(),
+        models: config.llm.models.clone(),
     };
     let cortex_llm_client: Arc<dyn cortex_mem_core::llm::LLMClient> =
         Arc::new(cortex_mem_core::llm::LLMClientImpl::new(llm_config)?);
@@ -81,18 +83,10 @@ pub async fn create_memory_agent(
         config.qdrant.embedding_dim
     );
     let memory_tools = create_memory_tools_with_config(
-        data_dir,
+        config,
         agent_id,
         cortex_llm_client,
-        &config.qdrant.url,
-        &config.qdrant.collection_name,
-        config.qdrant.api_key.as_deref(),
-        &config.embedding.api_base_url,
-        &config.embedding.api_key,
-        &config.embedding.model_name,
-        config.qdrant.embedding_dim,
         Some(user_id.to_string()),
-        config.cortex.enable_intent_analysis,
     )
     .await?;
 
@@ -871,6 +865,7 @@ impl AgentChatHandler {
                         auto_generate_layers: Some(true),
                         user_id: Some("tars_user".to_string()), // 🔧 传递user_id
                         agent_id: None, // 🔧 agent_id由tenant_id决定，这里不传
+                        space: None,
                     };
                     if let Err(e) = ops.store(user_store).await {
                         tracing::warn!("Failed to save user message: {}", e);
@@ -886,6 +881,7 @@ impl AgentChatHandler {
                         auto_generate_layers: Some(true),
                         user_id: Some("tars_user".to_string()), // 🔧 传递user_id
                         agent_id: None, // 🔧 agent_id由tenant_id决定，这里不传
+                        space: None,
                     };
                     if let Err(e) = ops.store(assistant_store).await {
                         tracing::warn!("Failed to save assistant message: {}", e);
@@ -767,7 +767,6 @@ impl App {
                 log::info!("🤖 开始初始化 AI Agent...");
                 let config = infrastructure.config();
                 match create_memory_agent(
-                    config.cortex.data_dir(),
                     config,
                     None, // user_info 稍后从租户 operations 提取
                     Some(bot.system_prompt.as_str()),
@@ -913,7 +912,6 @@ impl App {
 
                     let config = infrastructure.config();
                     match create_memory_agent(
-                        config.cortex.data_dir(),
                         config,
                         None,
                         Some(bot.system_prompt.as_str()),
@@ -100,6 +100,9 @@ impl ConfigManager {
                     model_efficient: "gpt-5-mini".to_string(),
                     temperature: 0.7,
                     max_tokens: 2000,
+                    provider: Default::default(),
+                    api_version: None,
+                    models: Default::default(),
                 },
                 server: cortex_mem_config::ServerConfig {
                     host: "localhost".to_string(),
@@ -111,6 +114,14 @@ impl ConfigManager {
                     data_dir: Some(tars_data_dir_str.clone()),
                     enable_intent_analysis: true,
                 },
+                rate_limit: cortex_mem_config::RateLimitConfig::default(),
+                tools: cortex_mem_config::ToolsConfig::default(),
+                access: cortex_mem_config::AccessConfig::default(),
+                llm_cache: cortex_mem_config::LlmCacheConfig::default(),
+                embedding_cache: cortex_mem_config::EmbeddingCacheConfig::default(),
+                usage: cortex_mem_config::UsageConfig::default(),
+                fixtures: cortex_mem_config::FixturesConfig::default(),
+                layers: cortex_mem_config::LayersConfig::default(),
             };
             let content = toml::to_string_pretty(&default_config).context("无法序列化默认配置")?;
             fs::write(&cortex_config_file, content).context("无法写入默认配置文件")?;
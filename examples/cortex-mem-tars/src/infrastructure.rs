@@ -24,30 +24,25 @@ impl Infrastructure {
 
         // Get LLM configuration
         let llm_config = cortex_mem_core::llm::LLMConfig {
+            provider: config.llm.provider,
             api_base_url: config.llm.api_base_url.clone(),
             api_key: config.llm.api_key.clone(),
             model_efficient: config.llm.model_efficient.clone(),
             temperature: 0.1,
             max_tokens: 4096,
+            api_version: config.llm.api_version.clone(),
+            models: config.llm.models.clone(),
         };
         let llm_client: Arc<dyn cortex_mem_core::llm::LLMClient> = 
             Arc::new(cortex_mem_core::llm::LLMClientImpl::new(llm_config)?);
 
         // 创建MemoryOperations（使用global租户）
-        let operations = MemoryOperations::new(
-            &data_dir,
-            "global",  // Use "global" as tenant ID for infrastructure
+        let operations = MemoryOperations::from_config(
+            &config,
+            "global", // Use "global" as tenant ID for infrastructure
             llm_client,
-            &config.qdrant.url,
-            &config.qdrant.collection_name,
-            config.qdrant.api_key.as_deref(),
-            &config.embedding.api_base_url,
-            &config.embedding.api_key,
-            &config.embedding.model_name,
-            config.qdrant.embedding_dim,
-            None,  // user_id = None，使用tenant_id作为user_id
-            config.cortex.enable_intent_analysis,
         )
+        .build()
         .await
         .context("Failed to initialize MemoryOperations")?;
 